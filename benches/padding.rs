@@ -0,0 +1,102 @@
+use ascii_ansi_table::{TableUserConfig, table, utils::slab::push_repeated};
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Counts allocations, so each benchmark can report how many one run makes.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations<T>(name: &str, run: impl FnOnce() -> T) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(run());
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!("{name}: {allocations} allocations");
+}
+
+const ROWS: usize = 100_000;
+const PADDING: [usize; 4] = [1, 6, 13, 2];
+
+// Pads the cells of 100k lines of four columns, as the renderer does, with a
+// `String` built per run of spaces against runs copied from the space slab.
+fn benchmark_padding(c: &mut Criterion) {
+    let pad_with_repeat = || {
+        let mut line = String::new();
+        for _ in 0..ROWS {
+            line.clear();
+            for padding in PADDING {
+                line.push_str(&" ".repeat(padding));
+                line.push_str("cell");
+            }
+            black_box(&line);
+        }
+    };
+    let pad_with_slab = || {
+        let mut line = String::new();
+        for _ in 0..ROWS {
+            line.clear();
+            for padding in PADDING {
+                push_repeated(&mut line, " ", padding);
+                line.push_str("cell");
+            }
+            black_box(&line);
+        }
+    };
+
+    count_allocations("pad_100k_rows/repeat", pad_with_repeat);
+    count_allocations("pad_100k_rows/slab", pad_with_slab);
+
+    let mut group = c.benchmark_group("pad_100k_rows");
+    group.bench_function("repeat", |b| b.iter(pad_with_repeat));
+    group.bench_function("slab", |b| b.iter(pad_with_slab));
+    group.finish();
+}
+
+// A whole 100k-row table, for the allocations a render makes end to end.
+fn benchmark_table(c: &mut Criterion) {
+    let data: Vec<Vec<String>> = (0..ROWS)
+        .map(|row| {
+            vec![
+                format!("host-{row}"),
+                "ok".to_string(),
+                (row % 97).to_string(),
+            ]
+        })
+        .collect();
+    let config = TableUserConfig {
+        header: Some(Box::default()),
+        ..Default::default()
+    };
+
+    count_allocations("table_100k_rows", || table(&data, Some(&config)));
+
+    let mut group = c.benchmark_group("table_100k_rows");
+    group.sample_size(10);
+    group.bench_function("render", |b| {
+        b.iter(|| black_box(table(black_box(&data), black_box(Some(&config)))))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_padding, benchmark_table);
+criterion_main!(benches);
@@ -1,5 +1,6 @@
 use ascii_ansi_table::{
-    BorderUserConfig, ColumnUserConfig, TableUserConfig, table, utils::convert_ansi_to_html,
+    Alignment, BorderUserConfig, ColumnUserConfig, RenderSession, TableUserConfig, Width, table,
+    testing::sample_rows, utils::convert_ansi_to_html,
 };
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
 
@@ -58,23 +59,17 @@ fn benchmark_table_wrapping(c: &mut Criterion) {
             join_left: Some("├".to_string()),
             join_right: Some("┤".to_string()),
             join_join: Some("┼".to_string()),
+            ..Default::default()
         }),
         columns: Some(vec![
             ColumnUserConfig {
-                width: Some(3),
+                width: Some(Width::Chars(3)),
                 wrap_word: Some(false), // Character wrapping for maximum stress
-                alignment: None,
-                vertical_alignment: None,
-                padding_left: None,
-                padding_right: None,
-                truncate: None,
+                ..Default::default()
             };
             3
         ]),
-        column_default: None,
-        single_line: None,
-        spanning_cells: None,
-        header: None,
+        ..Default::default()
     };
 
     c.bench_function("table_wrapping_10x3_width3", |b| {
@@ -109,11 +104,180 @@ fn benchmark_wrapping_components(c: &mut Criterion) {
     });
 }
 
+// 1,000 rows x 10 columns mixing plain ASCII, colored text, CJK and long
+// hyphenated words, so every kind of wrap segment shows up.
+fn generate_large_table() -> Vec<Vec<String>> {
+    let samples = [
+        "The quick brown fox jumps over the lazy dog",
+        "\u{1b}[31mError:\u{1b}[0m connection reset by \u{1b}[1mpeer\u{1b}[0m while reading",
+        "数据表格渲染性能测试用例",
+        "alpha-beta-gamma-delta-epsilon-zeta-eta-theta",
+        "short",
+    ];
+
+    (0..1_000)
+        .map(|row| {
+            (0..10)
+                .map(|col| format!("{} {row}", samples[(row + col) % samples.len()]))
+                .collect()
+        })
+        .collect()
+}
+
+fn benchmark_large_table_wrapping(c: &mut Criterion) {
+    let data = generate_large_table();
+    let cells: Vec<&str> = data.iter().flatten().map(String::as_str).collect();
+
+    for (name, word_wrap) in [
+        ("wrap_text_10k_cells_words", true),
+        ("wrap_text_10k_cells_chars", false),
+    ] {
+        c.bench_function(name, |b| {
+            b.iter(|| {
+                for cell in &cells {
+                    black_box(ascii_ansi_table::wrap_text(
+                        black_box(cell),
+                        black_box(12),
+                        word_wrap,
+                    ));
+                }
+            })
+        });
+    }
+
+    let config = TableUserConfig {
+        column_default: Some(ColumnUserConfig {
+            width: Some(Width::Chars(12)),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    c.bench_function("table_10k_cells_width_12", |b| {
+        b.iter(|| black_box(table(black_box(&data), black_box(Some(&config)))))
+    });
+}
+
+// Short cells that never wrap, so the time goes to aligning, padding and
+// joining cells rather than to wrapping.
+fn benchmark_alignment(c: &mut Criterion) {
+    let data: Vec<Vec<String>> = (0..1_000)
+        .map(|row| (0..10).map(|col| format!("r{row}c{col}")).collect())
+        .collect();
+
+    for (name, alignment) in [
+        ("align_10k_cells_left", Alignment::Left),
+        ("align_10k_cells_center", Alignment::Center),
+    ] {
+        let config = TableUserConfig {
+            column_default: Some(ColumnUserConfig {
+                width: Some(Width::Chars(12)),
+                alignment: Some(alignment),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        c.bench_function(name, |b| {
+            b.iter(|| black_box(table(black_box(&data), black_box(Some(&config)))))
+        });
+    }
+}
+
+// The kind of table CLIs print by the thousand: a header and a few rows of
+// short plain values.
+fn benchmark_small_table(c: &mut Criterion) {
+    let data: Vec<Vec<String>> = [
+        ["Name", "Status", "Age"],
+        ["web-01", "running", "3d"],
+        ["db-01", "stopped", "12h"],
+        ["cache", "running", "7m"],
+    ]
+    .iter()
+    .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+    .collect();
+    let config = TableUserConfig {
+        header: Some(Box::default()),
+        ..Default::default()
+    };
+
+    c.bench_function("small_table_4x3", |b| {
+        b.iter(|| black_box(table(black_box(&data), black_box(Some(&config)))))
+    });
+}
+
+// Realistic mixed content from the shared sample generator: colored,
+// accented, CJK and emoji cells, with multi-line products.
+fn benchmark_sample_table(c: &mut Criterion) {
+    let data = sample_rows(500, 8, 42, true, true);
+    let config = TableUserConfig {
+        header: Some(Box::default()),
+        ..Default::default()
+    };
+
+    c.bench_function("sample_table_500x8", |b| {
+        b.iter(|| black_box(table(black_box(&data), black_box(Some(&config)))))
+    });
+}
+
+// A dashboard redrawing 200 rows a frame, of which one changes: `table` lays
+// out every row again, a `RenderSession` only the changed one.
+fn benchmark_render_session(c: &mut Criterion) {
+    let mut data: Vec<Vec<String>> = (0..200)
+        .map(|row| {
+            vec![
+                format!("host-{row}"),
+                "running".to_string(),
+                row.to_string(),
+            ]
+        })
+        .collect();
+    let config = TableUserConfig {
+        header: Some(Box::default()),
+        ..Default::default()
+    };
+
+    let mut tick = 0;
+    c.bench_function("dashboard_frame_table", |b| {
+        b.iter(|| {
+            tick += 1;
+            data[100][2] = (tick % 100).to_string();
+            black_box(table(black_box(&data), black_box(Some(&config))))
+        })
+    });
+
+    let mut session = RenderSession::new();
+    c.bench_function("dashboard_frame_session", |b| {
+        b.iter(|| {
+            tick += 1;
+            data[100][2] = (tick % 100).to_string();
+            black_box(session.table(black_box(&data), black_box(Some(&config))))
+        })
+    });
+}
+
+// An export too long to read, where rows are laid out on every core with the
+// `parallel` feature.
+fn benchmark_large_export(c: &mut Criterion) {
+    let data = sample_rows(50_000, 6, 7, true, false);
+    let config = TableUserConfig {
+        header: Some(Box::default()),
+        ..Default::default()
+    };
+
+    let mut group = c.benchmark_group("export_50k");
+    group.sample_size(10);
+    group.bench_function("rows", |b| {
+        b.iter(|| black_box(table(black_box(&data), black_box(Some(&config)))))
+    });
+    group.finish();
+}
+
 fn benchmark_ansi(c: &mut Criterion) {
     let data = generate_test_data();
 
     // Benchmark just the wrapping function
-    let sample_text = ascii_ansi_table::wrap_text(&text, 3, false);
+    let sample_text = ascii_ansi_table::wrap_text(&data[0][0], 3, false);
 
     c.bench_function("convert_ansi_to_html", |b| {
         b.iter(|| {
@@ -143,23 +307,17 @@ fn benchmark_memory_usage(c: &mut Criterion) {
             join_left: Some("├".to_string()),
             join_right: Some("┤".to_string()),
             join_join: Some("┼".to_string()),
+            ..Default::default()
         }),
         columns: Some(vec![
             ColumnUserConfig {
-                width: Some(3),
+                width: Some(Width::Chars(3)),
                 wrap_word: Some(false),
-                alignment: None,
-                vertical_alignment: None,
-                padding_left: None,
-                padding_right: None,
-                truncate: None,
+                ..Default::default()
             };
             3
         ]),
-        column_default: None,
-        single_line: None,
-        spanning_cells: None,
-        header: None,
+        ..Default::default()
     };
 
     c.bench_function("memory_efficient_rendering", |b| {
@@ -177,5 +335,11 @@ criterion_group!(
     benchmark_wrapping_components,
     benchmark_memory_usage,
     benchmark_ansi,
+    benchmark_large_table_wrapping,
+    benchmark_alignment,
+    benchmark_small_table,
+    benchmark_sample_table,
+    benchmark_render_session,
+    benchmark_large_export,
 );
 criterion_main!(benches);
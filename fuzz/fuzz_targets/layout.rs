@@ -0,0 +1,132 @@
+#![no_main]
+
+use ascii_ansi_table::fixtures::check_layout_invariants;
+use ascii_ansi_table::{
+    Alignment, ColumnUserConfig, Row, TableUserConfig, Width, WordBreakPolicy, table,
+};
+use libfuzzer_sys::arbitrary::{Result, Unstructured};
+use libfuzzer_sys::fuzz_target;
+
+const FRAGMENTS: &[&str] = &[
+    "a",
+    "word",
+    " ",
+    "\n",
+    "漢字",
+    "テ",
+    "한",
+    "🚚",
+    "👍🏽",
+    "e\u{301}",
+    "✓",
+    "\u{1b}[31m",
+    "\u{1b}[1;32m",
+    "\u{1b}[38;5;196m",
+    "\u{1b}[0m",
+];
+
+const ALIGNMENTS: &[Alignment] = &[
+    Alignment::Left,
+    Alignment::Center,
+    Alignment::Right,
+    Alignment::Justify,
+];
+
+// `None` leaves the policy to `wrap_word`.
+const WORD_BREAKS: &[Option<WordBreakPolicy>] = &[
+    None,
+    Some(WordBreakPolicy::BreakAnywhere),
+    Some(WordBreakPolicy::BreakAtHyphen),
+    Some(WordBreakPolicy::NoBreakOverflow),
+    Some(WordBreakPolicy::NoBreakTruncate),
+];
+
+fn cell(u: &mut Unstructured) -> Result<String> {
+    let fragment_count = u.int_in_range(0..=8)?;
+    let mut text = String::new();
+    for _ in 0..fragment_count {
+        text.push_str(u.choose(FRAGMENTS)?);
+    }
+    // Close any open style so the input itself is well-formed.
+    text.push_str("\u{1b}[0m");
+    Ok(text)
+}
+
+fn input(u: &mut Unstructured) -> Result<(Vec<Row>, TableUserConfig)> {
+    let column_count = u.int_in_range(1..=4)?;
+    let row_count = u.int_in_range(1..=4)?;
+
+    let rows = (0..row_count)
+        .map(|_| (0..column_count).map(|_| cell(u)).collect())
+        .collect::<Result<Vec<Row>>>()?;
+
+    let columns = (0..column_count)
+        .map(|_| {
+            Ok(ColumnUserConfig {
+                width: if u.arbitrary()? {
+                    Some(Width::Chars(u.int_in_range(1..=12)?))
+                } else {
+                    None
+                },
+                wrap_word: Some(u.arbitrary()?),
+                word_break: *u.choose(WORD_BREAKS)?,
+                alignment: Some(*u.choose(ALIGNMENTS)?),
+                padding_left: Some(u.int_in_range(0..=2)?),
+                padding_right: Some(u.int_in_range(0..=2)?),
+                ..Default::default()
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let config = TableUserConfig {
+        columns: Some(columns),
+        single_line: Some(u.arbitrary()?),
+        header: if u.arbitrary()? {
+            Some(Box::default())
+        } else {
+            None
+        },
+        auto_layout: Some(true),
+        // Down to widths that leave the columns no room for their padding.
+        terminal_width: Some(u.int_in_range(1..=60)?),
+        ..Default::default()
+    };
+
+    Ok((rows, config))
+}
+
+// Whether a column keeps long words whole past its width, as
+// `NoBreakOverflow` (or `wrap_word: true` without a `word_break`) does by
+// design.
+fn overflows(config: &TableUserConfig) -> bool {
+    config
+        .columns
+        .iter()
+        .flatten()
+        .any(|column| match column.word_break {
+            Some(policy) => policy == WordBreakPolicy::NoBreakOverflow,
+            None => column.wrap_word == Some(true),
+        })
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut u = Unstructured::new(data);
+    let Ok((rows, config)) = input(&mut u) else {
+        return;
+    };
+
+    // Configs rejected by validation are fine; a rendered table must be
+    // well-formed. Where words may run past their column, only each line's
+    // styles are checked.
+    let Ok(output) = table(&rows, Some(&config)) else {
+        return;
+    };
+    let checked = if overflows(&config) {
+        output.lines().try_for_each(check_layout_invariants)
+    } else {
+        check_layout_invariants(&output)
+    };
+    if let Err(message) = checked {
+        panic!("{message}\n{output}");
+    }
+});
@@ -0,0 +1,26 @@
+/* C API of ascii_ansi_table, built with
+ * `--profile release-ffi --features ffi`. */
+#ifndef ASCII_ANSI_TABLE_H
+#define ASCII_ANSI_TABLE_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Draws a table from a JSON array of rows or of objects, with a JSON
+ * TableUserConfig (NULL or "" for the defaults). Returns a string to free
+ * with table_free_string, or NULL on error. */
+char *table_render_json(const char *data_json, const char *config_json);
+
+/* Frees a string returned by table_render_json. NULL is ignored. */
+void table_free_string(char *table);
+
+/* Why the last call on this thread returned NULL, or NULL. Owned by the
+ * library and valid until the next call on the same thread. */
+const char *table_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ASCII_ANSI_TABLE_H */
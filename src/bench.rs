@@ -0,0 +1,246 @@
+//! Render throughput measurements over `testing::sample_rows` data, for the
+//! CLI `bench` command and for regression checks in downstream builds that
+//! cannot run `cargo bench`. Each measurement warms up, then times a fixed
+//! number of runs and keeps every sample.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::testing::{render_streamed, sample_rows};
+use crate::types::{StreamUserConfig, TableResult, TableUserConfig};
+use crate::utils::wrapping::wrap_text;
+
+/// How often a measurement runs its routine, and the sample data it uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Untimed runs before sampling, to warm caches and the allocator.
+    pub warmup_iterations: usize,
+    /// Timed runs; at least one is always taken.
+    pub iterations: usize,
+    /// Seed for `sample_rows`.
+    pub seed: u64,
+    /// Generate ANSI-colored and Unicode cells instead of plain ASCII.
+    pub colors: bool,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup_iterations: 1,
+            iterations: 10,
+            seed: 42,
+            colors: false,
+        }
+    }
+}
+
+/// The timed runs of one measurement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchTimings {
+    pub name: String,
+    /// Body rows handled per run, not counting the header row.
+    pub rows: usize,
+    pub cols: usize,
+    /// Bytes of output one run produces.
+    pub output_bytes: usize,
+    /// Wall time of every timed run, in the order they ran.
+    pub samples: Vec<Duration>,
+}
+
+impl BenchTimings {
+    pub fn total(&self) -> Duration {
+        self.samples.iter().sum()
+    }
+
+    pub fn mean(&self) -> Duration {
+        self.total() / self.samples.len().max(1) as u32
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples.iter().min().copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.iter().max().copied().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Duration {
+        let mut sorted = self.samples.clone();
+        sorted.sort_unstable();
+        sorted.get(sorted.len() / 2).copied().unwrap_or_default()
+    }
+
+    /// Body rows per second at the mean run time.
+    pub fn rows_per_second(&self) -> f64 {
+        per_second(self.rows, self.mean())
+    }
+
+    /// Output bytes per second at the mean run time.
+    pub fn bytes_per_second(&self) -> f64 {
+        per_second(self.output_bytes, self.mean())
+    }
+}
+
+fn per_second(count: usize, elapsed: Duration) -> f64 {
+    if elapsed.is_zero() {
+        return 0.0;
+    }
+    count as f64 / elapsed.as_secs_f64()
+}
+
+/// Times `routine` the way criterion's `Bencher::iter` does: untimed warm-up
+/// runs, then one sample per timed run, with every result passed through
+/// `black_box` so the work cannot be optimised away.
+pub fn measure<T>(options: &BenchOptions, mut routine: impl FnMut() -> T) -> Vec<Duration> {
+    for _ in 0..options.warmup_iterations {
+        black_box(routine());
+    }
+
+    (0..options.iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(routine());
+            start.elapsed()
+        })
+        .collect()
+}
+
+/// Times `table` over a header row and `rows` rows of `cols` sample columns.
+/// Fails, without timing anything, if `config` rejects the data.
+pub fn bench_render(
+    rows: usize,
+    cols: usize,
+    config: Option<&TableUserConfig>,
+    options: &BenchOptions,
+) -> TableResult<BenchTimings> {
+    let data = sample_rows(rows, cols, options.seed, options.colors, options.colors);
+    let output = crate::table(&data, config)?;
+
+    Ok(BenchTimings {
+        name: "table".to_string(),
+        rows,
+        cols,
+        output_bytes: output.len(),
+        samples: measure(options, || crate::table(&data, config)),
+    })
+}
+
+/// Like `bench_render`, writing the rows through a `TableStream` instead.
+pub fn bench_stream(
+    rows: usize,
+    cols: usize,
+    config: Option<&StreamUserConfig>,
+    options: &BenchOptions,
+) -> TableResult<BenchTimings> {
+    let data = sample_rows(rows, cols, options.seed, options.colors, options.colors);
+    let (output, _) = render_streamed(&data, config)?;
+
+    Ok(BenchTimings {
+        name: "stream".to_string(),
+        rows,
+        cols,
+        output_bytes: output.len(),
+        samples: measure(options, || render_streamed(&data, config)),
+    })
+}
+
+/// Times wrapping `text` to `width`, as one row of one column.
+pub fn bench_wrap_text(text: &str, width: usize, options: &BenchOptions) -> BenchTimings {
+    let output_bytes = wrap_text(text, width, false).iter().map(String::len).sum();
+
+    BenchTimings {
+        name: "wrap_text".to_string(),
+        rows: 1,
+        cols: 1,
+        output_bytes,
+        samples: measure(options, || wrap_text(text, width, false)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ColumnUserConfig, Width};
+
+    fn quick() -> BenchOptions {
+        BenchOptions {
+            warmup_iterations: 0,
+            iterations: 3,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_measure_takes_one_sample_per_iteration() {
+        let mut runs = 0;
+        let options = BenchOptions {
+            warmup_iterations: 2,
+            iterations: 4,
+            ..Default::default()
+        };
+        assert_eq!(measure(&options, || runs += 1).len(), 4);
+        assert_eq!(runs, 6);
+
+        let none = BenchOptions {
+            iterations: 0,
+            ..quick()
+        };
+        assert_eq!(measure(&none, || ()).len(), 1);
+    }
+
+    #[test]
+    fn test_bench_render_reports_the_rendered_table() {
+        let timings = bench_render(20, 5, None, &quick()).unwrap();
+        let expected = crate::table(&sample_rows(20, 5, 42, false, false), None).unwrap();
+
+        assert_eq!(
+            (timings.name.as_str(), timings.rows, timings.cols),
+            ("table", 20, 5)
+        );
+        assert_eq!(timings.output_bytes, expected.len());
+        assert_eq!(timings.samples.len(), 3);
+        assert!(timings.min() <= timings.median() && timings.median() <= timings.max());
+        assert!(timings.rows_per_second() >= 0.0);
+    }
+
+    #[test]
+    fn test_bench_render_rejects_invalid_config_before_timing() {
+        let config = TableUserConfig {
+            columns: Some(vec![ColumnUserConfig {
+                width: Some(Width::Percent(150)),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+        assert!(bench_render(5, 3, Some(&config), &quick()).is_err());
+    }
+
+    #[test]
+    fn test_bench_stream_and_wrap_text() {
+        let stream = bench_stream(10, 4, None, &quick()).unwrap();
+        assert_eq!((stream.name.as_str(), stream.samples.len()), ("stream", 3));
+        assert!(stream.output_bytes > 0);
+
+        let wrap = bench_wrap_text(&"a".repeat(12), 3, &quick());
+        assert_eq!(wrap.output_bytes, 12);
+    }
+
+    #[test]
+    fn test_timings_statistics() {
+        let timings = BenchTimings {
+            name: "fixed".to_string(),
+            rows: 100,
+            cols: 1,
+            output_bytes: 1_000,
+            samples: [30, 10, 20].map(Duration::from_millis).to_vec(),
+        };
+
+        assert_eq!(timings.total(), Duration::from_millis(60));
+        assert_eq!(timings.mean(), Duration::from_millis(20));
+        assert_eq!(timings.median(), Duration::from_millis(20));
+        assert_eq!(timings.min(), Duration::from_millis(10));
+        assert_eq!(timings.max(), Duration::from_millis(30));
+        assert_eq!(timings.rows_per_second(), 5_000.0);
+        assert_eq!(timings.bytes_per_second(), 50_000.0);
+    }
+}
@@ -0,0 +1,213 @@
+#![cfg(feature = "ffi")]
+
+//! A C API for drawing tables from other languages, e.g. through cgo or from
+//! C++. Strings cross the boundary as NUL-terminated UTF-8; every string the
+//! library returns is freed with `table_free_string`. The declarations are in
+//! `include/ascii_ansi_table.h`.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+use serde_json::Value;
+
+use crate::features::json::{table_from_json, value_to_cell};
+use crate::types::{Row, TableError, TableResult, TableUserConfig};
+
+thread_local! {
+    // Why the last call on this thread returned NULL.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Draws the table for `data_json`, an array of rows (arrays of cells) or of
+/// objects, with `config_json`, a `TableUserConfig` in JSON, or the defaults
+/// when it is NULL or empty. Objects are drawn as `table_from_json` draws
+/// them, their keys as a header row.
+///
+/// Returns the table, to be freed with `table_free_string`, or NULL when the
+/// arguments are not valid UTF-8 JSON, the table cannot be drawn or drawing
+/// panicked; `table_last_error` then says why. Panics are only caught when
+/// the library is built to unwind, as the `release-ffi` profile builds it;
+/// the `release` profile aborts instead.
+///
+/// # Safety
+///
+/// `data_json` must point to a NUL-terminated string, and `config_json` to
+/// one or be NULL, both valid for the whole call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn table_render_json(
+    data_json: *const c_char,
+    config_json: *const c_char,
+) -> *mut c_char {
+    guarded(|| {
+        // SAFETY: the caller passes NUL-terminated strings or NULL.
+        let data = unsafe { str_arg(data_json, "data_json") }?.ok_or("data_json is NULL")?;
+        let config = unsafe { str_arg(config_json, "config_json") }?
+            .filter(|config| !config.trim().is_empty());
+        render_json(data, config).map_err(|e| e.to_string())
+    })
+}
+
+/// Frees a string returned by `table_render_json`. NULL is ignored.
+///
+/// # Safety
+///
+/// `table` must be NULL or a string returned by `table_render_json` that has
+/// not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn table_free_string(table: *mut c_char) {
+    if !table.is_null() {
+        // SAFETY: the string came from `CString::into_raw` in `guarded`.
+        drop(unsafe { CString::from_raw(table) });
+    }
+}
+
+/// Why the last call on this thread returned NULL, or NULL after a call that
+/// succeeded. The message belongs to the library and stays valid until the
+/// next call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn table_last_error() -> *const c_char {
+    LAST_ERROR.with_borrow(|error| error.as_ref().map_or(ptr::null(), |error| error.as_ptr()))
+}
+
+fn render_json(data: &str, config: Option<&str>) -> TableResult<String> {
+    let data: Value = serde_json::from_str(data)?;
+    let config: Option<TableUserConfig> = config.map(serde_json::from_str).transpose()?;
+
+    let Value::Array(items) = &data else {
+        return Err(TableError::InvalidData);
+    };
+    if !items.iter().all(Value::is_array) {
+        return table_from_json(&data, config.as_ref());
+    }
+    let rows: Vec<Row> = items
+        .iter()
+        .filter_map(Value::as_array)
+        .map(|cells| cells.iter().map(value_to_cell).collect())
+        .collect();
+    crate::table(&rows, config.as_ref())
+}
+
+// `ptr` as a string, or `None` for NULL.
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<Option<&'a str>, String> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    // SAFETY: the caller guarantees a NUL-terminated string.
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map(Some)
+        .map_err(|e| format!("{name} is not valid UTF-8: {e}"))
+}
+
+// Runs `render` without letting a panic unwind into the caller, and hands
+// its output over as a C string, recording the error for `table_last_error`
+// when there is none.
+fn guarded(render: impl FnOnce() -> Result<String, String>) -> *mut c_char {
+    let result = catch_unwind(AssertUnwindSafe(render))
+        .unwrap_or_else(|panic| {
+            Err(format!(
+                "drawing the table panicked: {}",
+                panic_message(&*panic)
+            ))
+        })
+        .and_then(|output| {
+            CString::new(output).map_err(|_| "the table contains a NUL character".to_string())
+        });
+
+    let (table, error) = match result {
+        Ok(table) => (table.into_raw(), None),
+        Err(error) => (
+            ptr::null_mut(),
+            // Messages come from `TableError` and panics, so a NUL is unlikely.
+            Some(CString::new(error.replace('\0', "")).unwrap_or_default()),
+        ),
+    };
+    LAST_ERROR.set(error);
+    table
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(data: &str, config: Option<&str>) -> Result<String, String> {
+        let data = CString::new(data).unwrap();
+        let config = config.map(|config| CString::new(config).unwrap());
+        let config_ptr = config
+            .as_ref()
+            .map_or(ptr::null(), |config| config.as_ptr());
+
+        unsafe {
+            let table = table_render_json(data.as_ptr(), config_ptr);
+            if table.is_null() {
+                return Err(CStr::from_ptr(table_last_error())
+                    .to_string_lossy()
+                    .into_owned());
+            }
+            assert!(table_last_error().is_null());
+            let output = CStr::from_ptr(table).to_str().unwrap().to_string();
+            table_free_string(table);
+            Ok(output)
+        }
+    }
+
+    #[test]
+    fn test_render_json_rows_objects_and_errors() {
+        let rows = vec![vec!["a".to_string(), "1".to_string()]];
+        assert_eq!(
+            render(r#"[["a", 1]]"#, None),
+            crate::table(&rows, None).map_err(|e| e.to_string())
+        );
+        assert_eq!(
+            render(r#"[["a", 1]]"#, Some("  ")),
+            render(r#"[["a", "1"]]"#, None)
+        );
+
+        let config = r#"{"border": {"body_left": "[", "body_right": "]"}}"#;
+        let objects = render(r#"[{"name": "web", "port": 443}]"#, Some(config)).unwrap();
+        assert!(objects.contains("[ name ") && objects.contains("[ web "));
+
+        assert!(render("[[1], [1, 2]]", None).unwrap_err().contains("cells"));
+        assert!(render("{", None).is_err());
+        assert!(render("[]", Some(r#"{"columns": 3}"#)).is_err());
+
+        let invalid = [0xff, b'[', b']', 0];
+        let error = unsafe {
+            assert!(table_render_json(invalid.as_ptr().cast(), ptr::null()).is_null());
+            CStr::from_ptr(table_last_error())
+                .to_str()
+                .unwrap()
+                .to_string()
+        };
+        assert!(error.starts_with("data_json is not valid UTF-8"));
+        assert!(unsafe { table_render_json(ptr::null(), ptr::null()) }.is_null());
+        unsafe { table_free_string(ptr::null_mut()) };
+    }
+
+    #[test]
+    fn test_panics_do_not_cross_the_boundary() {
+        assert!(guarded(|| panic!("bad cell")).is_null());
+        let error = unsafe { CStr::from_ptr(table_last_error()) };
+        assert_eq!(error.to_str(), Ok("drawing the table panicked: bad cell"));
+        assert!(render("[[1]]", None).is_ok());
+
+        // The profile embedders build with must unwind for this to hold.
+        let manifest = include_str!("../../Cargo.toml");
+        let profile = manifest
+            .split("\n[")
+            .find(|section| section.starts_with("profile.release-ffi]"))
+            .expect("a `release-ffi` profile");
+        assert!(profile.contains("panic = \"unwind\""), "{profile}");
+    }
+}
@@ -1,4 +1,6 @@
 pub mod cli;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 #[cfg(feature = "wasm")]
 pub mod wasm;
 
@@ -1,12 +1,17 @@
 #![cfg(feature = "wasm")]
 
+use serde::de::DeserializeOwned;
+use serde_json::Value;
 use serde_wasm_bindgen;
 use wasm_bindgen::prelude::*;
 
-use crate::types::{Row, TableUserConfig};
+use crate::features::json::{JsonColumns, json_to_rows};
+use crate::features::streaming::{TableStream, create_string_stream};
+use crate::types::{Row, StreamUserConfig, TableUserConfig, border_style_names};
 use crate::{get_border_characters, table as table_fn};
 
 #[wasm_bindgen]
+#[derive(Default)]
 pub struct WasmTable {
     data: Vec<Row>,
     config: Option<TableUserConfig>,
@@ -16,23 +21,18 @@ pub struct WasmTable {
 impl WasmTable {
     #[wasm_bindgen(constructor)]
     pub fn new() -> WasmTable {
-        WasmTable {
-            data: Vec::new(),
-            config: None,
-        }
+        WasmTable::default()
     }
 
     #[wasm_bindgen(js_name = setData)]
     pub fn set_data(&mut self, data: &JsValue) -> Result<(), JsValue> {
-        let data: Vec<Row> = serde_wasm_bindgen::from_value(data.clone())?;
-        self.data = data;
+        self.data = rows_from(data)?;
         Ok(())
     }
 
     #[wasm_bindgen(js_name = setConfig)]
     pub fn set_config(&mut self, config: &JsValue) -> Result<(), JsValue> {
-        let config: TableUserConfig = serde_wasm_bindgen::from_value(config.clone())?;
-        self.config = Some(config);
+        self.config = config_from(config)?;
         Ok(())
     }
 
@@ -48,18 +48,134 @@ impl WasmTable {
     }
 }
 
+/// Draws `data`, an array of rows or of objects, with `config`, either given
+/// as JSON text or as JS values. Config keys may be written in camelCase, as
+/// the JS `table` package spells them, and cells that are not strings are
+/// drawn as `String(cell)` would, e.g. `3` and `true`.
 #[wasm_bindgen(js_name = table)]
 pub fn wasm_table(data: &JsValue, config: &JsValue) -> Result<String, JsValue> {
-    let data: Vec<Row> = serde_wasm_bindgen::from_value(data.clone())?;
-    let config: Option<TableUserConfig> = if config.is_undefined() || config.is_null() {
-        None
-    } else {
-        Some(serde_wasm_bindgen::from_value(config.clone())?)
-    };
+    let data = rows_from(data)?;
+    let config: Option<TableUserConfig> = config_from(config)?;
 
     table_fn(&data, config.as_ref()).map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
+/// The names `getBorderCharacters` takes, built-in and registered styles.
+#[wasm_bindgen(js_name = borderStyleNames)]
+pub fn wasm_border_style_names() -> Result<JsValue, JsValue> {
+    serde_wasm_bindgen::to_value(&border_style_names())
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// `createStream` from the JS `table` package: each row written comes back
+/// as the lines to print for it, drawn at the widths the config fixes.
+#[wasm_bindgen]
+pub struct WasmStream {
+    stream: TableStream,
+}
+
+#[wasm_bindgen]
+impl WasmStream {
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: &JsValue) -> Result<WasmStream, JsValue> {
+        let config: Option<StreamUserConfig> = config_from(config)?;
+        Ok(WasmStream {
+            stream: create_string_stream(config),
+        })
+    }
+
+    #[wasm_bindgen(js_name = writeRow)]
+    pub fn write_row(&mut self, row: &JsValue) -> Result<String, JsValue> {
+        let row = row_from(row)?;
+        self.stream
+            .write_row(&row)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = writeHeader)]
+    pub fn write_header(&mut self, row: &JsValue) -> Result<String, JsValue> {
+        let row = row_from(row)?;
+        self.stream
+            .write_header(&row)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// The bottom border, once the last row is written.
+    pub fn finalize(&mut self) -> String {
+        self.stream.finalize()
+    }
+}
+
+// `value` as JSON: strings are parsed as JSON text, other values converted.
+fn json_from(value: &JsValue) -> Result<Value, JsValue> {
+    match value.as_string() {
+        Some(text) => serde_json::from_str(&text).map_err(|e| JsValue::from_str(&e.to_string())),
+        None => Ok(serde_wasm_bindgen::from_value(value.clone())?),
+    }
+}
+
+fn rows_from(data: &JsValue) -> Result<Vec<Row>, JsValue> {
+    json_to_rows(&json_from(data)?, &JsonColumns::default())
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+fn row_from(row: &JsValue) -> Result<Row, JsValue> {
+    let row = Value::Array(vec![json_from(row)?]);
+    let mut rows = json_to_rows(&row, &JsonColumns::default())
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    Ok(rows.pop().unwrap_or_default())
+}
+
+// No config is `undefined`, `null` or an empty string.
+fn config_from<T: DeserializeOwned>(config: &JsValue) -> Result<Option<T>, JsValue> {
+    if config.is_undefined()
+        || config.is_null()
+        || config
+            .as_string()
+            .is_some_and(|text| text.trim().is_empty())
+    {
+        return Ok(None);
+    }
+    let mut config = json_from(config)?;
+    snake_case_keys(&mut config);
+    serde_json::from_value(config)
+        .map(Some)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Renames `columnDefault` to `column_default` and so on, at every depth but
+// inside `placeholders`, whose keys are the caller's names.
+fn snake_case_keys(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            *map = std::mem::take(map)
+                .into_iter()
+                .map(|(key, mut value)| {
+                    if key != "placeholders" {
+                        snake_case_keys(&mut value);
+                    }
+                    (snake_case(&key), value)
+                })
+                .collect();
+        }
+        Value::Array(values) => values.iter_mut().for_each(snake_case_keys),
+        _ => {}
+    }
+}
+
+fn snake_case(key: &str) -> String {
+    let mut snake = String::with_capacity(key.len() + 4);
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            snake.push('_');
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
 #[wasm_bindgen(js_name = getBorderCharacters)]
 pub fn wasm_get_border_characters(name: &str) -> Result<JsValue, JsValue> {
     let border_config =
@@ -112,6 +228,7 @@ pub fn wasm_convert_ansi_to_html(text: &str) -> String {
 pub fn wasm_init() {}
 
 pub mod js_exports {
+    pub use super::wasm_border_style_names as borderStyleNames;
     pub use super::wasm_calculate_column_widths as calculateColumnWidths;
     pub use super::wasm_calculate_display_width as calculateDisplayWidth;
     pub use super::wasm_convert_ansi_to_html as convertAnsiToHtml;
@@ -121,6 +238,7 @@ pub mod js_exports {
     pub use super::wasm_validate_table_data as validateTableData;
     pub use super::wasm_wrap_text as wrapText;
 
+    pub use super::WasmStream as Stream;
     pub use super::WasmTable as Table;
 }
 
@@ -139,6 +257,26 @@ mod tests {
         assert!(table.config.is_none());
     }
 
+    #[test]
+    fn test_config_keys_follow_the_js_package() {
+        let mut config: Value = serde_json::from_str(
+            r#"{"columnDefault": {"paddingLeft": 2, "wrapWord": true},
+                "columns": [{"verticalAlignment": "middle"}],
+                "singleLine": true,
+                "placeholders": {"reportDate": "today"}}"#,
+        )
+        .unwrap();
+        snake_case_keys(&mut config);
+        let config: TableUserConfig = serde_json::from_value(config).unwrap();
+
+        assert_eq!(config.single_line, Some(true));
+        let column_default = config.column_default.unwrap();
+        assert_eq!(column_default.padding_left, Some(2));
+        assert_eq!(column_default.wrap_word, Some(true));
+        assert!(config.columns.unwrap()[0].vertical_alignment.is_some());
+        assert!(config.placeholders.unwrap().contains_key("reportDate"));
+    }
+
     #[test]
     fn test_wasm_display_width() {
         assert_eq!(wasm_calculate_display_width("hello"), 5);
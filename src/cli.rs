@@ -1,5 +1,5 @@
 use crate::table;
-use crate::types::{Row, TableError, TableResult, TableUserConfig};
+use crate::types::{ColorMode, Row, TableError, TableResult, TableUserConfig};
 #[cfg(feature = "cli")]
 use clap::{Parser, Subcommand};
 #[cfg(feature = "cli")]
@@ -19,6 +19,7 @@ pub struct Cli {
 
 #[cfg(feature = "cli")]
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 pub enum Commands {
     Generate {
         #[arg(short, long)]
@@ -39,17 +40,185 @@ pub enum Commands {
         #[arg(short, long)]
         config: Option<String>,
 
+        /// Reject unknown config keys instead of warning about them
+        #[arg(long)]
+        strict: bool,
+
         #[arg(long)]
         pretty: bool,
+
+        /// Also copy the table to the clipboard; needs the clipboard feature
+        #[arg(long)]
+        copy: bool,
+
+        /// What --copy puts on the clipboard: ansi, plain or markdown
+        #[arg(long, default_value = "ansi")]
+        copy_format: String,
+
+        /// Output format: table, csv, tsv or markdown
+        #[arg(long, default_value = "table")]
+        format: String,
+
+        /// Shrink and wrap long columns to keep the table within this width
+        #[arg(long)]
+        max_width: Option<usize>,
+
+        /// Shrink and wrap long columns to fit the terminal
+        #[arg(long)]
+        fit: bool,
+
+        /// Treat the first row as a header: yes, no or auto (detect)
+        #[arg(long, default_value = "no")]
+        header_row: String,
+
+        /// Draw the last row as a footer (e.g. totals) below a separator line
+        #[arg(long)]
+        footer_row: bool,
+
+        /// Sort by COLUMN[:asc|desc][:lexical|numeric|natural]; repeat for tie-breakers
+        #[arg(long)]
+        sort: Vec<String>,
+
+        /// Mark format rule matches with symbols instead of colors
+        #[arg(long)]
+        symbols_only: bool,
+
+        /// Only render rows matching COLUMN=VALUE, COLUMN~TEXT or COLUMN=~REGEX;
+        /// COLUMN is an index or a first-row name. Repeat to require several
+        #[arg(long)]
+        filter: Vec<String>,
+
+        /// Append a totals row: COLUMN:sum|avg|min|max|count, or COLUMN:LABEL for
+        /// fixed text. Repeat for several columns
+        #[arg(long)]
+        total: Vec<String>,
+
+        /// Append a column computed from another: COLUMN:share (percent of the
+        /// column total), COLUMN:rank or COLUMN:week, optionally :HEADER.
+        /// Repeat for several columns
+        #[arg(long)]
+        computed: Vec<String>,
+
+        /// Write a column's numbers as COLUMN:integer, COLUMN:float[:PRECISION],
+        /// COLUMN:percent[:PRECISION], COLUMN:currency[:SYMBOL], COLUMN:bytes
+        /// or COLUMN:date, e.g. 2:currency:€. Repeat for several columns
+        #[arg(long)]
+        number_format: Vec<String>,
+
+        /// BCP 47 locale for computed columns, number formats and text
+        /// sorting, e.g. de-DE
+        #[arg(long)]
+        locale: Option<String>,
+
+        /// Draw only these columns, in this order, e.g. 2,0,3
+        #[arg(long)]
+        columns: Option<String>,
+
+        /// List invisible characters, trailing whitespace and mixed tabs in the
+        /// input on stderr, failing if there are any unless --clean-data is given
+        #[arg(long)]
+        lint_data: bool,
+
+        /// Remove invisible characters and trailing whitespace, and expand tabs
+        /// in mixed indentation, before drawing
+        #[arg(long)]
+        clean_data: bool,
+
+        /// Constant and duplicate columns: keep, drop, or merge (drop, joining
+        /// a duplicate's header into its original's)
+        #[arg(long)]
+        redundant_columns: Option<String>,
+
+        /// Refuse to draw if validation finds an issue at or above this
+        /// severity: info, warning, or error
+        #[arg(long)]
+        fail_on: Option<String>,
+
+        /// Set a title into the top border
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Set a caption into the bottom border
+        #[arg(long)]
+        caption: Option<String>,
+
+        /// Start from a theme: compact, double, dotted, fancy or markdown;
+        /// --border and the config file override its settings
+        #[arg(long)]
+        theme: Option<String>,
+
+        /// Colors in the table: always, never, or auto (only when writing to a
+        /// terminal that takes them). Defaults to the config file's
+        /// color_mode, or auto
+        #[arg(long)]
+        color: Option<String>,
+
+        /// Measure emoji and ambiguous-width characters, and keep hyperlinks,
+        /// as this terminal does: generic, iterm2, windows-terminal, kitty,
+        /// tmux, or auto (detect from the environment)
+        #[arg(long)]
+        terminal: Option<String>,
     },
 
     Validate {
         #[arg(short, long)]
         config: String,
+
+        /// Reject unknown config keys instead of warning about them
+        #[arg(long)]
+        strict: bool,
+
+        /// Print every issue as JSON on stdout, with its code, severity and
+        /// message, instead of stopping at the first
+        #[arg(long)]
+        json: bool,
     },
 
     Borders,
 
+    /// Page through a table: arrows and PgUp/PgDn scroll, `/` searches,
+    /// `n` finds the next match, `<` and `>` resize the leftmost column and
+    /// `q` quits
+    #[cfg(feature = "tui")]
+    View {
+        /// A JSON, CSV or TSV file; JSON from stdin when omitted
+        input: Option<String>,
+
+        #[arg(short, long)]
+        config: Option<String>,
+
+        /// Treat the first row as a header: yes, no or auto (detect)
+        #[arg(long, default_value = "auto")]
+        header_row: String,
+
+        #[arg(short, long, default_value = "honeywell")]
+        border: String,
+    },
+
+    Fmt {
+        input: String,
+
+        #[arg(short, long)]
+        write: bool,
+
+        #[arg(long)]
+        check: bool,
+    },
+
+    Stats {
+        input: String,
+
+        #[arg(long)]
+        no_header: bool,
+
+        /// Treat the first row as a header: yes, no or auto (detect)
+        #[arg(long, default_value = "yes")]
+        header_row: String,
+
+        #[arg(short, long, default_value = "honeywell")]
+        border: String,
+    },
+
     StreamDemo {
         #[arg(short, long, default_value = "10")]
         rows: usize,
@@ -65,6 +234,31 @@ pub enum Commands {
 
         #[arg(long)]
         widths: Option<String>,
+
+        /// Seed for the generated rows
+        #[arg(long, default_value = "42")]
+        seed: u64,
+    },
+
+    /// Draw NDJSON records from stdin as they arrive. The first record fixes
+    /// the columns; later keys and extra values are dropped with a warning
+    Stream {
+        #[arg(long, default_value = "ndjson")]
+        format: String,
+
+        #[arg(short, long, default_value = "honeywell")]
+        border: String,
+
+        #[arg(long)]
+        widths: Option<String>,
+
+        #[arg(long)]
+        follow: bool,
+
+        /// Print each column's type, width and numeric range to stderr once
+        /// the stream ends
+        #[arg(long)]
+        stats: bool,
     },
 
     Demo {
@@ -79,6 +273,35 @@ pub enum Commands {
 
         #[arg(long)]
         widths: Option<String>,
+
+        /// Seed for the generated rows
+        #[arg(long, default_value = "42")]
+        seed: u64,
+    },
+
+    /// Measure render throughput on generated rows
+    Bench {
+        #[arg(short, long, default_value = "10000")]
+        rows: usize,
+
+        #[arg(long, default_value = "10")]
+        cols: usize,
+
+        /// Timed runs per measurement
+        #[arg(short, long, default_value = "5")]
+        iterations: usize,
+
+        /// Untimed runs before each measurement
+        #[arg(long, default_value = "1")]
+        warmup: usize,
+
+        /// Generate ANSI-colored and Unicode cells
+        #[arg(long)]
+        colors: bool,
+
+        /// Seed for the generated rows
+        #[arg(long, default_value = "42")]
+        seed: u64,
     },
 }
 
@@ -94,7 +317,32 @@ pub fn run_cli() -> TableResult<()> {
             alignment,
             single_line,
             config,
+            strict,
             pretty,
+            copy,
+            copy_format,
+            format,
+            max_width,
+            fit,
+            header_row,
+            footer_row,
+            sort,
+            symbols_only,
+            filter,
+            total,
+            computed,
+            number_format,
+            locale,
+            columns,
+            lint_data,
+            clean_data,
+            redundant_columns,
+            fail_on,
+            title,
+            caption,
+            theme,
+            color,
+            terminal,
         } => generate_table(
             input,
             output,
@@ -102,27 +350,108 @@ pub fn run_cli() -> TableResult<()> {
             alignment,
             single_line,
             config,
+            strict,
             pretty,
+            copy.then_some(copy_format),
+            format,
+            max_width,
+            fit,
+            header_row,
+            footer_row,
+            sort,
+            symbols_only,
+            filter,
+            total,
+            computed,
+            number_format,
+            locale,
+            columns,
+            lint_data,
+            clean_data,
+            redundant_columns,
+            fail_on,
+            title,
+            caption,
+            theme,
+            color,
+            terminal,
         ),
-        Commands::Validate { config } => validate_config(config),
+        Commands::Validate {
+            config,
+            strict,
+            json,
+        } => validate_config(config, strict, json),
         Commands::Borders => list_borders(),
+        #[cfg(feature = "tui")]
+        Commands::View {
+            input,
+            config,
+            header_row,
+            border,
+        } => view_table(input, config, header_row, border),
+        Commands::Fmt {
+            input,
+            write,
+            check,
+        } => format_markdown(input, write, check),
+        Commands::Stats {
+            input,
+            no_header,
+            header_row,
+            border,
+        } => column_stats(
+            input,
+            if no_header {
+                "no".to_string()
+            } else {
+                header_row
+            },
+            border,
+        ),
         Commands::StreamDemo {
             rows,
             delay,
             border,
             colors,
             widths,
-        } => stream_demo(rows, delay, border, colors, widths),
+            seed,
+        } => stream_demo(rows, delay, border, colors, widths, seed),
+        Commands::Stream {
+            format,
+            border,
+            widths,
+            follow,
+            stats,
+        } => stream_records(format, border, widths, follow, stats),
         Commands::Demo {
             rows,
             border,
             colors,
             widths,
-        } => table_demo(rows, border, colors, widths),
+            seed,
+        } => table_demo(rows, border, colors, widths, seed),
+        Commands::Bench {
+            rows,
+            cols,
+            iterations,
+            warmup,
+            colors,
+            seed,
+        } => run_bench(
+            rows,
+            cols,
+            crate::bench::BenchOptions {
+                warmup_iterations: warmup,
+                iterations,
+                seed,
+                colors,
+            },
+        ),
     }
 }
 
 #[cfg(feature = "cli")]
+#[allow(clippy::too_many_arguments)]
 fn generate_table(
     input: Option<String>,
     output: Option<String>,
@@ -130,377 +459,745 @@ fn generate_table(
     alignment: Option<String>,
     single_line: bool,
     config_path: Option<String>,
+    strict: bool,
     _pretty: bool,
+    copy_format: Option<String>,
+    format: String,
+    max_width: Option<usize>,
+    fit: bool,
+    header_row: String,
+    footer_row: bool,
+    sort: Vec<String>,
+    symbols_only: bool,
+    filter: Vec<String>,
+    total: Vec<String>,
+    computed: Vec<String>,
+    number_format: Vec<String>,
+    locale: Option<String>,
+    columns: Option<String>,
+    lint_data: bool,
+    clean_data: bool,
+    redundant_columns: Option<String>,
+    fail_on: Option<String>,
+    title: Option<String>,
+    caption: Option<String>,
+    theme: Option<String>,
+    color: Option<String>,
+    terminal: Option<String>,
 ) -> TableResult<()> {
+    if let Some(copy_format) = &copy_format {
+        check_copy_format(copy_format)?;
+    }
+    if let Some(terminal) = terminal {
+        crate::features::terminal_profile::set_terminal_profile(terminal.parse()?);
+    }
+
     let input_data = read_input_data(input)?;
-    let table_data: Vec<Row> = serde_json::from_str(&input_data)
-        .map_err(|e| TableError::InvalidConfig(format!("Invalid JSON input: {e}")))?;
+    let mut table_data: Vec<Row> = serde_json::from_str(&input_data)?;
+
+    if lint_data {
+        let issues = crate::features::hygiene::lint_rows(&table_data);
+        for issue in &issues {
+            eprintln!("warning: {issue}");
+        }
+        if let Some(&issue) = issues.first()
+            && !clean_data
+        {
+            return Err(TableError::UncleanData(issue));
+        }
+    }
+    // Cleaned here rather than through `DataHygiene::Clean` so the CSV and
+    // Markdown outputs are cleaned too.
+    if clean_data {
+        crate::features::hygiene::clean_rows(&mut table_data);
+    }
 
-    let mut config = if let Some(config_path) = config_path {
-        read_config_file(config_path)?
+    let file_config = if let Some(config_path) = config_path {
+        read_config_file(config_path, strict)?
     } else {
-        TableUserConfig {
-            border: None,
-            columns: None,
-            column_default: None,
-            single_line: None,
-            spanning_cells: None,
-            header: None,
+        TableUserConfig::default()
+    };
+
+    // Flags only override the config file when they were actually given.
+    let mut flags = TableUserConfig {
+        single_line: single_line.then_some(true),
+        max_table_width: max_width,
+        auto_layout: fit.then_some(true),
+        symbols_only: symbols_only.then_some(true),
+        theme,
+        ..Default::default()
+    };
+
+    let header_row: crate::features::stats::HeaderRow = header_row.parse()?;
+    let mut has_header = header_row.resolve(&table_data);
+
+    if !filter.is_empty() {
+        use crate::features::filtering::RowFilter;
+
+        let header_names = table_data.first().map(Vec::as_slice);
+        let mut filters = Vec::with_capacity(filter.len());
+        for spec in &filter {
+            // A filter that names a column implies the first row is a header.
+            let parsed = match spec.parse::<RowFilter>() {
+                Ok(parsed) => parsed,
+                Err(_) => {
+                    has_header = true;
+                    RowFilter::parse(spec, header_names)?
+                }
+            };
+            filters.push(parsed);
         }
+        flags.filters = Some(filters);
+    }
+
+    if has_header {
+        flags.header = Some(Box::default());
+    }
+
+    if footer_row {
+        flags.footer = Some(Box::default());
+    }
+
+    if !total.is_empty() {
+        flags.aggregations = Some(
+            total
+                .iter()
+                .map(|spec| spec.parse())
+                .collect::<TableResult<_>>()?,
+        );
+    }
+
+    if !computed.is_empty() {
+        flags.computed_columns = Some(
+            computed
+                .iter()
+                .map(|spec| spec.parse())
+                .collect::<TableResult<_>>()?,
+        );
+    }
+    flags.locale = locale;
+
+    for spec in &number_format {
+        let (column, format) = spec
+            .split_once(':')
+            .and_then(|(column, format)| Some((column.trim().parse::<usize>().ok()?, format)))
+            .ok_or_else(|| TableError::parse("number format", spec, "expected COLUMN:FORMAT"))?;
+        let columns = flags.columns.get_or_insert_with(Vec::new);
+        if columns.len() <= column {
+            columns.resize(column + 1, crate::types::ColumnUserConfig::default());
+        }
+        columns[column].formatter = Some(format.parse()?);
+    }
+
+    if !sort.is_empty() {
+        flags.sort = Some(
+            sort.iter()
+                .map(|key| key.parse())
+                .collect::<TableResult<_>>()?,
+        );
+    }
+
+    if let Some(columns) = columns {
+        flags.column_order = Some(parse_column_order(&columns)?);
+    }
+
+    if let Some(mode) = redundant_columns {
+        flags.redundant_columns = Some(mode.parse()?);
+    }
+
+    if let Some(severity) = fail_on {
+        flags.fail_on = Some(severity.parse()?);
+    }
+
+    let label = |text| crate::types::TableLabel {
+        text,
+        alignment: None,
+        placement: None,
     };
+    flags.title = title.map(label);
+    flags.caption = caption.map(label);
 
     if border != "honeywell" {
         let border_config = crate::get_border_characters(&border)?;
-        config.border = Some(crate::types::BorderUserConfig {
-            top_body: Some(border_config.top_body),
-            top_join: Some(border_config.top_join),
-            top_left: Some(border_config.top_left),
-            top_right: Some(border_config.top_right),
-            bottom_body: Some(border_config.bottom_body),
-            bottom_join: Some(border_config.bottom_join),
-            bottom_left: Some(border_config.bottom_left),
-            bottom_right: Some(border_config.bottom_right),
-            body_left: Some(border_config.body_left),
-            body_right: Some(border_config.body_right),
-            body_join: Some(border_config.body_join),
-            header_join: Some(border_config.header_join),
-            join_body: Some(border_config.join_body),
-            join_left: Some(border_config.join_left),
-            join_right: Some(border_config.join_right),
-            join_join: Some(border_config.join_join),
-        });
+        flags.border = Some(crate::types::BorderUserConfig::from(border_config));
     }
 
     if let Some(alignment) = alignment {
-        let align: crate::types::Alignment = alignment.parse()?;
-        if config.column_default.is_none() {
-            config.column_default = Some(crate::types::ColumnUserConfig {
-                alignment: Some(align),
-                vertical_alignment: None,
-                padding_left: None,
-                padding_right: None,
-                truncate: None,
-                wrap_word: None,
-                width: None,
-            });
-        } else {
-            config.column_default.as_mut().unwrap().alignment = Some(align);
-        }
+        flags.column_default = Some(crate::types::ColumnUserConfig {
+            alignment: Some(alignment.parse()?),
+            ..Default::default()
+        });
     }
 
-    config.single_line = Some(single_line);
+    if let Some(color) = color {
+        flags.color_mode = Some(color.parse()?);
+    }
+
+    let mut config = file_config.merge(flags);
+    // Auto checks standard output, so it strips for an output file here.
+    config.color_mode = match config.color_mode {
+        None | Some(ColorMode::Auto) if output.is_some() => Some(ColorMode::Strip),
+        None => Some(ColorMode::Auto),
+        mode => mode,
+    };
 
     let table_output = table(&table_data, Some(&config))?;
 
-    write_output(output, &table_output)?;
+    let formatted_output = match format.as_str() {
+        "table" => table_output.clone(),
+        "csv" => crate::utils::csv::table_to_csv(&table_data, ',', Default::default()),
+        "tsv" => crate::utils::csv::table_to_csv(&table_data, '\t', Default::default()),
+        "markdown" => crate::utils::markdown::table_to_markdown(&table_data),
+        _ => {
+            return Err(TableError::parse(
+                "output format",
+                format,
+                "expected table, csv, tsv or markdown",
+            ));
+        }
+    };
+
+    write_output(output, &formatted_output)?;
+
+    if let Some(copy_format) = copy_format {
+        let clipboard_content = match copy_format.as_str() {
+            "ansi" => table_output,
+            "plain" => crate::utils::ansi::strip_ansi_sequences(&table_output),
+            "markdown" => crate::utils::markdown::table_to_markdown(&table_data),
+            _ => unreachable!("checked by check_copy_format"),
+        };
+        copy_to_clipboard(&clipboard_content)?;
+    }
 
     Ok(())
 }
 
+// A comma-separated list of column indices, e.g. `2,0,3`.
 #[cfg(feature = "cli")]
-fn validate_config(config_path: String) -> TableResult<()> {
-    let config = read_config_file(config_path)?;
+fn parse_column_order(spec: &str) -> TableResult<Vec<usize>> {
+    spec.split(',')
+        .map(|index| {
+            index.trim().parse().map_err(|_| {
+                TableError::parse(
+                    "column order",
+                    spec,
+                    "expected column indices such as 2,0,3",
+                )
+            })
+        })
+        .collect()
+}
 
-    let full_config = config.merge_with_default(&crate::types::TableConfig::default());
-    crate::core::validator::validate_config(&full_config)?;
+#[cfg(feature = "cli")]
+fn validate_config(config_path: String, strict: bool, json: bool) -> TableResult<()> {
+    use crate::types::Severity;
+
+    let mut result = validate_config_file(&config_path, strict);
+    if json {
+        println!("{}", result.to_json()?);
+        if result.fails(Severity::Error) {
+            return Err(TableError::Validation(result));
+        }
+        return Ok(());
+    }
 
+    // Warnings are printed as they are; errors make up the failure.
+    result.issues.retain(|issue| {
+        let is_error = issue.severity == Severity::Error;
+        if !is_error {
+            eprintln!("{config_path}: {issue}");
+        }
+        is_error
+    });
+    if !result.is_empty() {
+        return Err(TableError::Validation(result));
+    }
     println!("Configuration is valid!");
     Ok(())
 }
 
+// Every issue with the config file at `path`: failing to read it, then
+// whatever `validate_user_config_verbose` finds.
 #[cfg(feature = "cli")]
-fn list_borders() -> TableResult<()> {
-    let borders = ["honeywell", "norc", "ramac", "void"];
+fn validate_config_file(path: &str, strict: bool) -> crate::core::validator::ValidationResult {
+    use crate::core::validator::{ValidationIssue, ValidationResult, validate_user_config_verbose};
+    use crate::utils::config_file::ConfigFormat;
+
+    match fs::read_to_string(path).map_err(TableError::io(format!(
+        "Failed to read config file '{path}'"
+    ))) {
+        Ok(content) => {
+            validate_user_config_verbose(&content, ConfigFormat::from_path(path), strict)
+        }
+        Err(error) => ValidationResult {
+            issues: vec![ValidationIssue::from_error(&error)],
+        },
+    }
+}
 
+#[cfg(feature = "cli")]
+fn list_borders() -> TableResult<()> {
     println!("Available border styles:");
-    for border in &borders {
-        println!("  {border}");
+    for (name, preview) in crate::features::borders::preview_border_styles() {
+        println!("  {name}");
+        println!("{preview}");
+        println!();
+    }
 
-        let border_config = crate::get_border_characters(border)?;
-        let example_data = vec![
-            vec!["A".to_string(), "B".to_string()],
-            vec!["C".to_string(), "D".to_string()],
-        ];
+    Ok(())
+}
 
-        let config = TableUserConfig {
-            border: Some(crate::types::BorderUserConfig {
-                top_body: Some(border_config.top_body),
-                top_join: Some(border_config.top_join),
-                top_left: Some(border_config.top_left),
-                top_right: Some(border_config.top_right),
-                bottom_body: Some(border_config.bottom_body),
-                bottom_join: Some(border_config.bottom_join),
-                bottom_left: Some(border_config.bottom_left),
-                bottom_right: Some(border_config.bottom_right),
-                body_left: Some(border_config.body_left),
-                body_right: Some(border_config.body_right),
-                body_join: Some(border_config.body_join),
-                header_join: Some(border_config.header_join),
-                join_body: Some(border_config.join_body),
-                join_left: Some(border_config.join_left),
-                join_right: Some(border_config.join_right),
-                join_join: Some(border_config.join_join),
-            }),
-            columns: None,
-            column_default: None,
-            single_line: None,
-            spanning_cells: None,
-            header: None,
-        };
+#[cfg(feature = "tui")]
+fn view_table(
+    input: Option<String>,
+    config_path: Option<String>,
+    header_row: String,
+    border: String,
+) -> TableResult<()> {
+    let content = read_input_data(input.clone())?;
+    let rows = parse_table_input(input.as_deref().unwrap_or(""), &content)?;
+    if rows.is_empty() {
+        println!("No data to view.");
+        return Ok(());
+    }
 
-        let example_table = table(&example_data, Some(&config))?;
-        println!("{example_table}");
-        println!();
+    let mut config = match config_path {
+        Some(config_path) => read_config_file(config_path, false)?,
+        None => TableUserConfig::default(),
+    };
+    if config.border.is_none() {
+        config.border = Some(crate::types::BorderUserConfig::from(
+            crate::get_border_characters(&border)?,
+        ));
     }
+    if header_row
+        .parse::<crate::features::stats::HeaderRow>()?
+        .resolve(&rows)
+    {
+        config.header.get_or_insert_with(Box::default);
+    }
+
+    let (rows, config) = crate::prepare_table(&rows, Some(&config))?;
+    crate::viewer::run_viewer(rows, config)
+}
+
+#[cfg(feature = "cli")]
+fn format_markdown(input: String, write: bool, check: bool) -> TableResult<()> {
+    use crate::utils::markdown::reformat_markdown_tables;
+
+    let content = read_input_data(Some(input.clone()))?;
+    let formatted = reformat_markdown_tables(&content);
 
+    if check {
+        if formatted != content {
+            return Err(TableError::InvalidConfig(format!(
+                "{input}: Markdown tables are not formatted"
+            )));
+        }
+        return Ok(());
+    }
+
+    if write {
+        write_output(Some(input), &formatted)
+    } else {
+        write_output(None, &formatted)
+    }
+}
+
+#[cfg(feature = "cli")]
+fn column_stats(input: String, header_row: String, border: String) -> TableResult<()> {
+    use crate::features::stats::{
+        ColumnRedundancy, HeaderRow, calculate_column_stats, column_stats_to_rows,
+        suggest_redundant_columns,
+    };
+
+    let content = read_input_data(Some(input.clone()))?;
+    let rows = parse_table_input(&input, &content)?;
+
+    let has_header = header_row.parse::<HeaderRow>()?.resolve(&rows);
+    let stats = calculate_column_stats(&rows, has_header);
+    if stats.is_empty() {
+        println!("No data to analyze.");
+        return Ok(());
+    }
+
+    let config = TableUserConfig {
+        border: Some(crate::types::BorderUserConfig::from(
+            crate::get_border_characters(&border)?,
+        )),
+        ..Default::default()
+    };
+
+    println!("{}", table(&column_stats_to_rows(&stats), Some(&config))?);
+
+    let suggestions = suggest_redundant_columns(&rows, has_header);
+    for suggestion in &suggestions {
+        let name = &stats[suggestion.column].name;
+        match &suggestion.redundancy {
+            ColumnRedundancy::Constant(value) if value.is_empty() => {
+                println!("Suggestion: {name} is always empty")
+            }
+            ColumnRedundancy::Constant(value) => println!("Suggestion: {name} is always `{value}`"),
+            ColumnRedundancy::DuplicateOf(original) => {
+                println!("Suggestion: {name} duplicates {}", stats[*original].name)
+            }
+        }
+    }
+    if !suggestions.is_empty() {
+        println!("Leave these out with `generate --redundant-columns drop` (or `merge`).");
+    }
     Ok(())
 }
 
+#[cfg(feature = "cli")]
+fn parse_table_input(path: &str, content: &str) -> TableResult<Vec<Row>> {
+    let extension = std::path::Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase());
+
+    match extension.as_deref() {
+        Some("csv") => crate::utils::csv::parse_csv(content, ','),
+        Some("tsv") => crate::utils::csv::parse_csv(content, '\t'),
+        _ => Ok(serde_json::from_str(content)?),
+    }
+}
+
 #[cfg(feature = "cli")]
 fn read_input_data(input: Option<String>) -> TableResult<String> {
     match input {
-        Some(path) => fs::read_to_string(path)
-            .map_err(|e| TableError::InvalidConfig(format!("Failed to read input file: {e}"))),
+        Some(path) => fs::read_to_string(&path).map_err(TableError::io(format!(
+            "Failed to read input file '{path}'"
+        ))),
         None => {
             let mut buffer = String::new();
-            io::stdin().read_to_string(&mut buffer).map_err(|e| {
-                TableError::InvalidConfig(format!("Failed to read from stdin: {e}"))
-            })?;
+            io::stdin()
+                .read_to_string(&mut buffer)
+                .map_err(TableError::io("Failed to read from stdin"))?;
             Ok(buffer)
         }
     }
 }
 
 #[cfg(feature = "cli")]
-fn read_config_file(path: String) -> TableResult<TableUserConfig> {
-    let content = fs::read_to_string(path)
-        .map_err(|e| TableError::InvalidConfig(format!("Failed to read config file: {e}")))?;
+fn read_config_file(path: String, strict: bool) -> TableResult<TableUserConfig> {
+    use crate::utils::config_file::{ConfigFormat, parse_config_lenient, parse_config_strict};
+
+    let content = fs::read_to_string(&path).map_err(TableError::io(format!(
+        "Failed to read config file '{path}'"
+    )))?;
+    let format = ConfigFormat::from_path(&path);
+    let with_path = |e: TableError| match e {
+        TableError::InvalidConfig(message) => {
+            TableError::InvalidConfig(format!("{path}:{message}"))
+        }
+        other => other,
+    };
+
+    if strict {
+        return parse_config_strict(&content, format).map_err(with_path);
+    }
 
-    serde_json::from_str(&content)
-        .map_err(|e| TableError::InvalidConfig(format!("Invalid JSON in config file: {e}")))
+    let (config, warnings) = parse_config_lenient(&content, format).map_err(with_path)?;
+    for warning in warnings {
+        eprintln!("warning: {path}: {warning}");
+    }
+    Ok(config)
 }
 
 #[cfg(feature = "cli")]
 fn write_output(output: Option<String>, content: &str) -> TableResult<()> {
     match output {
-        Some(path) => fs::write(path, content)
-            .map_err(|e| TableError::InvalidConfig(format!("Failed to write output file: {e}"))),
+        Some(path) => fs::write(&path, content).map_err(TableError::io(format!(
+            "Failed to write output file '{path}'"
+        ))),
         None => {
-            io::stdout().write_all(content.as_bytes()).map_err(|e| {
-                TableError::InvalidConfig(format!("Failed to write to stdout: {e}"))
-            })?;
+            io::stdout()
+                .write_all(content.as_bytes())
+                .map_err(TableError::io("Failed to write to stdout"))?;
             Ok(())
         }
     }
 }
 
-// Streaming demo function that displays a table row by row with a delay.
-// IMPORTANT: In streaming mode, we only overwrite the bottom border from the previous
-// iteration (1 line up), NOT the entire previous record. This allows each row to be
-// displayed progressively while replacing only the temporary bottom border with the
-// proper join border for the next row.
+// Rejects `--copy` before anything is written when the copy cannot happen:
+// an unknown format, or a build without the clipboard feature.
 #[cfg(feature = "cli")]
-fn stream_demo(
-    rows: usize,
-    delay: u64,
+fn check_copy_format(copy_format: &str) -> TableResult<()> {
+    if !["ansi", "plain", "markdown"].contains(&copy_format) {
+        return Err(TableError::parse(
+            "copy format",
+            copy_format,
+            "expected ansi, plain or markdown",
+        ));
+    }
+    if cfg!(not(feature = "clipboard")) {
+        return Err(TableError::InvalidConfig(
+            "--copy needs the clipboard feature; build with --features clipboard".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "clipboard")]
+fn copy_to_clipboard(content: &str) -> TableResult<()> {
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(io::Error::other)
+        .map_err(TableError::io("Failed to access clipboard"))?;
+    clipboard
+        .set_text(content)
+        .map_err(io::Error::other)
+        .map_err(TableError::io("Failed to copy to clipboard"))
+}
+
+#[cfg(all(feature = "cli", not(feature = "clipboard")))]
+fn copy_to_clipboard(_content: &str) -> TableResult<()> {
+    unreachable!("check_copy_format rejects --copy without the clipboard feature")
+}
+
+// Reads one JSON record per line from stdin and renders it as soon as it arrives.
+// The first record fixes the columns, as rows already drawn cannot grow: an
+// object's keys become the header row, an array's or value's length the
+// column count. See `RecordLayout` for records of another shape. With
+// `follow`, EOF is treated as "no data yet" and the reader keeps polling
+// instead of closing the table.
+#[cfg(feature = "cli")]
+fn stream_records(
+    format: String,
     border: String,
-    colors: bool,
     widths: Option<String>,
+    follow: bool,
+    stats: bool,
 ) -> TableResult<()> {
-    use std::io::{self, Write};
+    use std::io::BufRead;
     use std::thread;
     use std::time::Duration;
 
-    use crate::types::{BorderUserConfig, ColumnUserConfig, StreamUserConfig};
+    use crate::features::streaming::create_stream;
+    use crate::types::{BorderUserConfig, ColumnUserConfig, StreamUserConfig, Width};
 
-    println!("🚀 ASCII ANSI Table Streaming Demo");
-    println!("📊 Streaming {rows} rows with {delay}ms delay (1 row per second)");
-    println!("🎨 Border style: {border}");
-    if colors {
-        println!("🌈 ANSI colors: enabled");
+    if format != "ndjson" {
+        return Err(TableError::parse(
+            "stream format",
+            format,
+            "expected ndjson",
+        ));
     }
-    println!("────────────────────────────────────────");
 
     let border_config = crate::get_border_characters(&border)?;
-    let mut stream_config = StreamUserConfig {
-        border: Some(BorderUserConfig {
-            top_body: Some(border_config.top_body),
-            top_join: Some(border_config.top_join),
-            top_left: Some(border_config.top_left),
-            top_right: Some(border_config.top_right),
-            bottom_body: Some(border_config.bottom_body),
-            bottom_join: Some(border_config.bottom_join),
-            bottom_left: Some(border_config.bottom_left),
-            bottom_right: Some(border_config.bottom_right),
-            body_left: Some(border_config.body_left),
-            body_right: Some(border_config.body_right),
-            body_join: Some(border_config.body_join),
-            header_join: Some(border_config.header_join),
-            join_body: Some(border_config.join_body),
-            join_left: Some(border_config.join_left),
-            join_right: Some(border_config.join_right),
-            join_join: Some(border_config.join_join),
-        }),
-        columns: None,
+    let columns = match widths {
+        Some(widths_str) => Some(
+            widths_str
+                .split(',')
+                .map(|w| {
+                    w.trim()
+                        .parse::<usize>()
+                        .map(|width| ColumnUserConfig {
+                            width: Some(Width::Chars(width)),
+                            ..Default::default()
+                        })
+                        .map_err(|e| TableError::parse("width", w, e.to_string()))
+                })
+                .collect::<TableResult<Vec<_>>>()?,
+        ),
+        None => None,
+    };
+
+    let stream_config = StreamUserConfig {
+        border: Some(BorderUserConfig::from(border_config)),
+        columns,
         column_default: None,
         single_line: None,
     };
 
-    let products = [
-        "Gaming\nLaptop",
-        "Wireless\nMouse",
-        "Mechanical\nKeyboard",
-        "4K\nMonitor",
-        "Bluetooth\nSpeakers",
-        "HD\nWebcam",
-        "Noise-Cancelling\nHeadphones",
-        "Android\nTablet",
-        "Smartphone\nPro",
-        "Fast\nCharger",
-        "USB-C\nCable",
-        "Power\nAdapter",
-        "WiFi\nRouter",
-        "Network\nSwitch",
-        "Laser\nPrinter",
-    ];
-    let statuses = ["Active", "Sold", "Pending", "Shipped", "Delivered"];
-
-    let mut all_sample_data = vec![];
+    let mut writer = create_stream(io::stdout(), Some(stream_config));
+    if stats {
+        writer = writer.with_stats();
+    }
+    let mut layout: Option<RecordLayout> = None;
+    let stdin = io::stdin();
+    let mut reader = stdin.lock();
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        let bytes_read = reader
+            .read_line(&mut line)
+            .map_err(TableError::io("Failed to read from stdin"))?;
+
+        if bytes_read == 0 {
+            if follow {
+                writer.suspend()?;
+                thread::sleep(Duration::from_millis(100));
+                continue;
+            }
+            break;
+        }
 
-    let header = if colors {
-        vec![
-            "\u{1b}[1;36mID\u{1b}[0m".to_string(),
-            "\u{1b}[1;32mProduct\u{1b}[0m".to_string(),
-            "\u{1b}[1;33mPrice\u{1b}[0m".to_string(),
-            "\u{1b}[1;35mStatus\u{1b}[0m".to_string(),
-        ]
-    } else {
-        vec![
-            "ID".to_string(),
-            "Product".to_string(),
-            "Price".to_string(),
-            "Status".to_string(),
-        ]
-    };
-    all_sample_data.push(header.clone());
+        let record = line.trim();
+        if record.is_empty() {
+            continue;
+        }
 
-    for i in 0..rows {
-        let product = products[i % products.len()];
-        let status = statuses[i % statuses.len()];
-        let price = format!("${}", (i + 1) * 25 + 99);
+        let value: serde_json::Value = serde_json::from_str(record)?;
 
-        let row = if colors {
-            vec![
-                format!("\u{1b}[37m{}\u{1b}[0m", i + 1),
-                format!("\u{1b}[34m{}\u{1b}[0m", product),
-                format!("\u{1b}[32m{}\u{1b}[0m", price),
-                match status {
-                    "Active" => format!("\u{1b}[32m✓ {status}\u{1b}[0m"),
-                    "Sold" => format!("\u{1b}[31m✗ {status}\u{1b}[0m"),
-                    "Pending" => format!("\u{1b}[33m⚠ {status}\u{1b}[0m"),
-                    "Shipped" => format!("\u{1b}[36m🚚 {status}\u{1b}[0m"),
-                    "Delivered" => format!("\u{1b}[35m📦 {status}\u{1b}[0m"),
-                    _ => status.to_string(),
-                },
-            ]
-        } else {
-            vec![
-                (i + 1).to_string(),
-                product.to_string(),
-                price,
-                status.to_string(),
-            ]
+        let layout = match &mut layout {
+            Some(layout) => layout,
+            None => {
+                let first = RecordLayout::new(&value);
+                if let Some(keys) = &first.keys {
+                    writer.write_header(keys)?;
+                }
+                layout.insert(first)
+            }
         };
-        all_sample_data.push(row);
+        let row = layout.row(&value);
+        for warning in layout.warnings.drain(..) {
+            eprintln!("warning: {warning}");
+        }
+
+        writer.write_row(&row)?;
     }
 
-    if let Some(widths_str) = widths {
-        let widths: Result<Vec<usize>, _> = widths_str
-            .split(',')
-            .map(|w| w.trim().parse::<usize>())
-            .collect();
+    let column_stats = writer.stats().map(<[_]>::to_vec).unwrap_or_default();
+    writer.finalize()?;
+    println!();
 
-        if let Ok(widths) = widths {
-            let column_configs: Vec<ColumnUserConfig> = widths
-                .iter()
-                .map(|&width| ColumnUserConfig {
-                    width: Some(width),
-                    ..Default::default()
-                })
-                .collect();
-            stream_config.columns = Some(column_configs);
-        }
-    } else {
-        let temp_config = TableUserConfig {
-            border: stream_config.border.clone(),
-            columns: None,
-            column_default: stream_config.column_default.clone(),
-            single_line: stream_config.single_line,
-            spanning_cells: None,
-            header: None,
+    for (col_idx, column) in column_stats.iter().enumerate() {
+        let name = match column.name.as_str() {
+            "" => format!("Column {}", col_idx + 1),
+            name => name.to_string(),
         };
+        eprintln!("{name}: {}", column.summary());
+    }
 
-        let _temp_table = crate::table(&all_sample_data, Some(&temp_config))?;
-
-        let column_count = all_sample_data.first().map(|row| row.len()).unwrap_or(4);
+    Ok(())
+}
 
-        let default_widths = [6, 12, 8, 12];
+// The columns of an NDJSON stream, fixed by its first record. An object is
+// laid out by the first object's keys, leaving cells for missing keys empty
+// and dropping keys first seen later; an array or value by position, padding
+// short records with empty cells and cutting long ones. Each key dropped, and
+// each record cut, is warned about once.
+#[cfg(feature = "cli")]
+struct RecordLayout {
+    keys: Option<Vec<String>>,
+    column_count: usize,
+    dropped_keys: std::collections::BTreeSet<String>,
+    warnings: Vec<String>,
+}
 
-        let column_configs: Vec<ColumnUserConfig> = default_widths
-            .iter()
-            .take(column_count)
-            .map(|&width| ColumnUserConfig {
-                width: Some(width),
-                ..Default::default()
-            })
-            .collect();
-        stream_config.columns = Some(column_configs);
+#[cfg(feature = "cli")]
+impl RecordLayout {
+    fn new(first: &serde_json::Value) -> Self {
+        let keys: Option<Vec<String>> = first
+            .as_object()
+            .map(|object| object.keys().cloned().collect());
+        let column_count = match (&keys, first) {
+            (Some(keys), _) => keys.len(),
+            (None, serde_json::Value::Array(values)) => values.len(),
+            (None, _) => 1,
+        };
+        Self {
+            keys,
+            column_count,
+            dropped_keys: Default::default(),
+            warnings: Vec::new(),
+        }
     }
 
-    use crate::features::streaming::create_string_stream;
+    fn row(&mut self, record: &serde_json::Value) -> Row {
+        use crate::features::json::value_to_cell;
+
+        let mut row: Row = match (&self.keys, record) {
+            (Some(keys), serde_json::Value::Object(object)) => {
+                for key in object.keys() {
+                    if !keys.contains(key) && self.dropped_keys.insert(key.clone()) {
+                        self.warnings.push(format!(
+                            "dropping key `{key}`, which the first record does not have"
+                        ));
+                    }
+                }
+                keys.iter()
+                    .map(|key| object.get(key).map(value_to_cell).unwrap_or_default())
+                    .collect()
+            }
+            (_, serde_json::Value::Array(values)) => values.iter().map(value_to_cell).collect(),
+            (_, other) => vec![value_to_cell(other)],
+        };
+        if row.len() > self.column_count {
+            self.warnings.push(format!(
+                "dropping {} of {} values, as the first record has {} columns",
+                row.len() - self.column_count,
+                row.len(),
+                self.column_count
+            ));
+        }
+        row.resize(self.column_count, String::new());
+        row
+    }
+}
 
-    let column_widths: Vec<usize> = stream_config
-        .columns
-        .as_ref()
-        .map(|cols| cols.iter().map(|col| col.width.unwrap_or(10)).collect())
-        .unwrap_or_else(|| vec![6, 12, 8, 12]);
+// Streaming demo: a `LiveTable` grows by one row per tick, rewriting only the
+// lines that changed, so the table stays in place while columns widen.
+#[cfg(feature = "cli")]
+fn stream_demo(
+    rows: usize,
+    delay: u64,
+    border: String,
+    colors: bool,
+    widths: Option<String>,
+    seed: u64,
+) -> TableResult<()> {
+    use std::io::{self, Write};
+    use std::thread;
+    use std::time::Duration;
 
-    let mut stream = create_string_stream(Some(stream_config));
+    use crate::features::live::LiveTable;
+    use crate::types::{BorderUserConfig, ColumnUserConfig, Width};
 
-    let header_output = stream.write_row(&all_sample_data[0])?;
-    print!("{header_output}");
-    io::stdout()
-        .flush()
-        .map_err(|e| TableError::InvalidConfig(format!("Failed to flush stdout: {e}")))?;
+    println!("🚀 ASCII ANSI Table Streaming Demo");
+    println!("📊 Streaming {rows} rows with {delay}ms delay (1 row per second)");
+    println!("🎨 Border style: {border}");
+    if colors {
+        println!("🌈 ANSI colors: enabled");
+    }
+    println!("────────────────────────────────────────");
 
-    let mut previous_output_lines = 0;
+    let border_config = crate::get_border_characters(&border)?;
+    let mut config = TableUserConfig {
+        border: Some(BorderUserConfig::from(border_config)),
+        ..Default::default()
+    };
 
-    for i in 0..rows {
-        thread::sleep(Duration::from_millis(delay));
+    if let Some(widths_str) = widths {
+        let widths: Result<Vec<Width>, _> =
+            widths_str.split(',').map(|w| w.parse::<Width>()).collect();
 
-        if i > 0 {
-            // Move up by the number of lines from previous iteration (row content + bottom border)
-            for _ in 0..previous_output_lines {
-                print!("\u{1b}[1A");
-                print!("\u{1b}[0K");
-            }
+        if let Ok(widths) = widths {
+            let column_configs: Vec<ColumnUserConfig> = widths
+                .into_iter()
+                .map(|width| ColumnUserConfig {
+                    width: Some(width),
+                    ..Default::default()
+                })
+                .collect();
+            config.columns = Some(column_configs);
         }
+    }
 
-        let row_output = stream.write_row(&all_sample_data[i + 1])?;
-        print!("{row_output}");
-
-        use crate::core::renderer::{BorderType, draw_border_line};
-
-        let border_config = crate::get_border_characters(&border)?;
-        let bottom_border = draw_border_line(&column_widths, &border_config, BorderType::Bottom);
-        println!("{bottom_border}");
+    let all_sample_data = crate::testing::sample_rows(rows, 4, seed, colors, colors);
+    let mut live = LiveTable::new(Some(config));
 
-        // Count lines in this iteration's output for next iteration
-        previous_output_lines = 1; // +1 for bottom border
+    for (row_idx, row) in all_sample_data.into_iter().enumerate() {
+        if row_idx > 0 {
+            thread::sleep(Duration::from_millis(delay));
+        }
 
+        print!("{}", live.push_row(row)?);
         io::stdout()
             .flush()
-            .map_err(|e| TableError::InvalidConfig(format!("Failed to flush stdout: {e}")))?;
+            .map_err(TableError::io("Failed to flush stdout"))?;
     }
 
     println!("\n✅ Streaming demo complete! {rows} rows processed.");
@@ -517,8 +1214,9 @@ fn table_demo(
     border: String,
     colors: bool,
     widths: Option<String>,
+    seed: u64,
 ) -> TableResult<()> {
-    use crate::types::{ColumnUserConfig, TableUserConfig};
+    use crate::types::{ColumnUserConfig, TableUserConfig, Width};
 
     println!("🚀 ASCII ANSI Table Demo");
     println!("📊 Generating table with {rows} rows");
@@ -528,112 +1226,22 @@ fn table_demo(
     }
     println!("────────────────────────────────────────");
 
-    let statuses = ["Active", "Sold", "Pending", "Shipped", "Delivered"];
-
-    let mut all_sample_data = vec![];
-
-    let header = if colors {
-        vec![
-            "\u{1b}[1;36mID\u{1b}[0m".to_string(),
-            "\u{1b}[1;32mProduct\u{1b}[0m".to_string(),
-            "\u{1b}[1;33mPrice\u{1b}[0m".to_string(),
-            "\u{1b}[1;35mStatus\u{1b}[0m".to_string(),
-        ]
-    } else {
-        vec![
-            "ID".to_string(),
-            "Product".to_string(),
-            "Price".to_string(),
-            "Status".to_string(),
-        ]
-    };
-    all_sample_data.push(header);
-
-    let products = [
-        "Gaming\nLaptop",
-        "Wireless\nMouse",
-        "Mechanical\nKeyboard",
-        "4K\nMonitor",
-        "Bluetooth\nSpeakers",
-        "HD\nWebcam",
-        "Noise-Cancelling\nHeadphones",
-        "Android\nTablet",
-        "Smartphone\nPro",
-        "Fast\nCharger",
-        "USB-C\nCable",
-        "Power\nAdapter",
-        "WiFi\nRouter",
-        "Network\nSwitch",
-        "Laser\nPrinter",
-    ];
-
-    for i in 0..rows {
-        let product = products[i % products.len()];
-        let status = statuses[i % statuses.len()];
-        let price = format!("${}", (i + 1) * 25 + 99);
-
-        let row = if colors {
-            vec![
-                format!("\u{1b}[37m{}\u{1b}[0m", i + 1),
-                format!("\u{1b}[34m{}\u{1b}[0m", product),
-                format!("\u{1b}[32m{}\u{1b}[0m", price),
-                match status {
-                    "Active" => format!("\u{1b}[32m✓ {status}\u{1b}[0m"),
-                    "Sold" => format!("\u{1b}[31m✗ {status}\u{1b}[0m"),
-                    "Pending" => format!("\u{1b}[33m⚠ {status}\u{1b}[0m"),
-                    "Shipped" => format!("\u{1b}[36m🚚 {status}\u{1b}[0m"),
-                    "Delivered" => format!("\u{1b}[35m📦 {status}\u{1b}[0m"),
-                    _ => status.to_string(),
-                },
-            ]
-        } else {
-            vec![
-                (i + 1).to_string(),
-                product.to_string(),
-                price,
-                status.to_string(),
-            ]
-        };
-        all_sample_data.push(row);
-    }
+    let all_sample_data = crate::testing::sample_rows(rows, 4, seed, colors, colors);
 
     let border_config = crate::get_border_characters(&border)?;
     let mut config = TableUserConfig {
-        border: Some(crate::types::BorderUserConfig {
-            top_body: Some(border_config.top_body),
-            top_join: Some(border_config.top_join),
-            top_left: Some(border_config.top_left),
-            top_right: Some(border_config.top_right),
-            bottom_body: Some(border_config.bottom_body),
-            bottom_join: Some(border_config.bottom_join),
-            bottom_left: Some(border_config.bottom_left),
-            bottom_right: Some(border_config.bottom_right),
-            body_left: Some(border_config.body_left),
-            body_right: Some(border_config.body_right),
-            body_join: Some(border_config.body_join),
-            header_join: Some(border_config.header_join),
-            join_body: Some(border_config.join_body),
-            join_left: Some(border_config.join_left),
-            join_right: Some(border_config.join_right),
-            join_join: Some(border_config.join_join),
-        }),
-        columns: None,
-        column_default: None,
-        single_line: None,
-        spanning_cells: None,
-        header: None,
+        border: Some(crate::types::BorderUserConfig::from(border_config)),
+        ..Default::default()
     };
 
     if let Some(widths_str) = widths {
-        let widths: Result<Vec<usize>, _> = widths_str
-            .split(',')
-            .map(|w| w.trim().parse::<usize>())
-            .collect();
+        let widths: Result<Vec<Width>, _> =
+            widths_str.split(',').map(|w| w.parse::<Width>()).collect();
 
         if let Ok(widths) = widths {
             let column_configs: Vec<ColumnUserConfig> = widths
-                .iter()
-                .map(|&width| ColumnUserConfig {
+                .into_iter()
+                .map(|width| ColumnUserConfig {
                     width: Some(width),
                     ..Default::default()
                 })
@@ -648,7 +1256,7 @@ fn table_demo(
             .iter()
             .take(column_count)
             .map(|&width| ColumnUserConfig {
-                width: Some(width),
+                width: Some(Width::Chars(width)),
                 ..Default::default()
             })
             .collect();
@@ -662,6 +1270,57 @@ fn table_demo(
     println!("💡 Try different options:");
     println!("   --rows 20 --colors --border ramac");
     println!("   --widths 4,12,8,12");
+    println!("   --widths 10%,40%,20%,30%");
+
+    Ok(())
+}
+
+// Times `table` and `TableStream` on the same rows and prints one result row
+// per measurement.
+#[cfg(feature = "cli")]
+fn run_bench(rows: usize, cols: usize, options: crate::bench::BenchOptions) -> TableResult<()> {
+    use crate::bench::{BenchTimings, bench_render, bench_stream};
+    use crate::types::{Alignment, ColumnUserConfig};
+
+    println!(
+        "Rendering {rows} x {cols} rows, {} timed runs after {} warm-up",
+        options.iterations.max(1),
+        options.warmup_iterations
+    );
+
+    let results = [
+        bench_render(rows, cols, None, &options)?,
+        bench_stream(rows, cols, None, &options)?,
+    ];
+
+    let millis = |duration: std::time::Duration| format!("{:.2}", duration.as_secs_f64() * 1e3);
+    let summary = |timings: &BenchTimings| {
+        vec![
+            timings.name.clone(),
+            millis(timings.mean()),
+            millis(timings.min()),
+            millis(timings.max()),
+            format!("{:.0}", timings.rows_per_second()),
+            format!("{:.1}", timings.bytes_per_second() / 1_048_576.0),
+        ]
+    };
+
+    let mut report: Vec<Row> = vec![
+        ["Renderer", "Mean ms", "Min ms", "Max ms", "Rows/s", "MiB/s"]
+            .map(String::from)
+            .to_vec(),
+    ];
+    report.extend(results.iter().map(summary));
+
+    let config = TableUserConfig {
+        header: Some(Box::default()),
+        column_default: Some(ColumnUserConfig {
+            alignment: Some(Alignment::Right),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    println!("{}", table(&report, Some(&config))?);
 
     Ok(())
 }
@@ -679,10 +1338,106 @@ mod tests {
         let config_json = r#"{"single_line": true}"#;
         temp_file.write_all(config_json.as_bytes()).unwrap();
 
-        let config = read_config_file(temp_file.path().to_string_lossy().to_string()).unwrap();
+        let config =
+            read_config_file(temp_file.path().to_string_lossy().to_string(), true).unwrap();
         assert_eq!(config.single_line, Some(true));
     }
 
+    #[test]
+    #[cfg(feature = "cli")]
+    fn test_validate_config_file_collects_issues() {
+        use crate::types::Severity;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file
+            .write_all(br#"{"flex_colum": 1, "column_default": {"width": 10, "padding_left": 6, "padding_right": 6}}"#)
+            .unwrap();
+        let path = temp_file.path().to_string_lossy().to_string();
+
+        let lenient = validate_config_file(&path, false);
+        let found: Vec<_> = lenient
+            .issues
+            .iter()
+            .map(|issue| (issue.code, issue.severity, issue.suggestion.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("unknown-key", Severity::Warning, Some("flex_column")),
+                ("invalid-config", Severity::Error, None),
+            ]
+        );
+        assert_eq!(
+            validate_config_file(&path, true).issues[0].severity,
+            Severity::Error
+        );
+
+        let missing = validate_config_file("/nonexistent/config.json", false);
+        assert_eq!(missing.issues[0].code, "io");
+    }
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn test_record_layout_keeps_the_first_records_columns() {
+        use serde_json::json;
+
+        let mut layout = RecordLayout::new(&json!({"count": 3, "name": "api"}));
+        assert_eq!(
+            layout.keys.as_deref(),
+            Some(&["count".to_string(), "name".to_string()][..])
+        );
+        assert_eq!(layout.row(&json!({"name": "web"})), ["", "web"]);
+        assert!(layout.warnings.is_empty());
+
+        assert_eq!(
+            layout.row(&json!({"name": "cron", "count": 1, "owner": "ops"})),
+            ["1", "cron"]
+        );
+        layout.row(&json!({"owner": "dev"}));
+        assert_eq!(
+            layout.warnings,
+            ["dropping key `owner`, which the first record does not have"]
+        );
+
+        let mut layout = RecordLayout::new(&json!(["a", "b"]));
+        assert_eq!(layout.keys, None);
+        assert_eq!(layout.row(&json!(["c"])), ["c", ""]);
+        assert_eq!(layout.row(&json!(["d", "e", "f"])), ["d", "e"]);
+        assert_eq!(
+            layout.warnings,
+            ["dropping 1 of 3 values, as the first record has 2 columns"]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn test_check_copy_format() {
+        assert!(matches!(
+            check_copy_format("html"),
+            Err(TableError::Parse {
+                kind: "copy format",
+                ..
+            })
+        ));
+        assert_eq!(
+            check_copy_format("plain").is_ok(),
+            cfg!(feature = "clipboard")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn test_parse_column_order() {
+        assert_eq!(parse_column_order("2, 0,3").unwrap(), vec![2, 0, 3]);
+        assert!(matches!(
+            parse_column_order("2,name"),
+            Err(TableError::Parse {
+                kind: "column order",
+                ..
+            })
+        ));
+    }
+
     #[test]
     #[cfg(feature = "cli")]
     fn test_generate_table_with_config() {
@@ -692,12 +1447,8 @@ mod tests {
         ];
 
         let config = TableUserConfig {
-            border: None,
-            columns: None,
-            column_default: None,
             single_line: Some(false),
-            spanning_cells: None,
-            header: None,
+            ..Default::default()
         };
 
         let result = table(&table_data, Some(&config)).unwrap();
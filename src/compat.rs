@@ -0,0 +1,151 @@
+//! Crate-root names for rendering-pipeline internals. They were once
+//! glob-exported from the crate root; they still work there, but warn, so the
+//! renderer and processors can be reorganised without breaking downstream
+//! builds. The module paths in each note are where the items live today and
+//! may move again; `crate::prelude` is the stable surface.
+
+use crate::core::renderer;
+use crate::features::{
+    aggregation, alignment_processor, borders, column_summary, conditional_format, filtering,
+    spanning, stats, styling,
+};
+use crate::types::{
+    CellCoordinates, FormatRule, RangeCoordinate, Row, SpanningCellConfig, TableConfig, TableResult,
+};
+use crate::utils::ansi;
+
+#[deprecated(note = "internal to the renderer; use `core::renderer::draw_table_body`")]
+pub fn draw_table_body(rows: &[Row], config: &TableConfig) -> String {
+    renderer::draw_table_body(rows, config)
+}
+
+#[deprecated(note = "internal to the renderer; use `core::renderer::position_table_block`")]
+pub fn position_table_block(output: String, config: &TableConfig) -> String {
+    renderer::position_table_block(output, config)
+}
+
+#[deprecated(note = "internal to the renderer; use `features::alignment_processor`")]
+pub type AlignmentProcessor = alignment_processor::AlignmentProcessor;
+
+#[deprecated(note = "internal to the renderer; use `features::alignment_processor`")]
+pub type CellLayout<'a> = alignment_processor::CellLayout<'a>;
+
+#[deprecated(note = "internal to the renderer; use `features::alignment_processor`")]
+pub fn push_spaces(out: &mut String, count: usize) {
+    alignment_processor::push_spaces(out, count)
+}
+
+#[deprecated(note = "internal to the renderer; use `features::borders`")]
+pub type BorderRenderer = borders::BorderRenderer;
+
+#[deprecated(note = "internal to the renderer; use `features::spanning`")]
+pub type SpanningCellManager = spanning::SpanningCellManager;
+
+#[deprecated(note = "internal to the renderer; use `features::spanning`")]
+pub type SpanInfo = spanning::SpanInfo;
+
+#[deprecated(note = "internal to the renderer; use `features::spanning`")]
+pub fn calculate_range_coordinate(spanning_cell_config: &SpanningCellConfig) -> RangeCoordinate {
+    spanning::calculate_range_coordinate(spanning_cell_config)
+}
+
+#[deprecated(note = "internal to the renderer; use `features::spanning`")]
+pub fn are_cells_equal(cell1: &CellCoordinates, cell2: &CellCoordinates) -> bool {
+    spanning::are_cells_equal(cell1, cell2)
+}
+
+#[deprecated(note = "internal to the renderer; use `features::spanning`")]
+pub fn is_cell_in_range(cell: &CellCoordinates, range: &RangeCoordinate) -> bool {
+    spanning::is_cell_in_range(cell, range)
+}
+
+#[deprecated(note = "applied by `table`; use `TableConfig::filters`")]
+pub fn filter_rows(rows: Vec<Row>, config: &TableConfig) -> TableResult<Vec<Row>> {
+    filtering::filter_rows(rows, config)
+}
+
+#[deprecated(note = "applied by `table`; use `TableConfig::aggregations`")]
+pub fn aggregate_row(body: &[Row], column_count: usize, config: &TableConfig) -> Row {
+    aggregation::aggregate_row(body, column_count, config)
+}
+
+#[deprecated(note = "applied by `table`; use `TableConfig::aggregations`")]
+pub fn reserve_aggregate_row(rows: &mut Vec<Row>, config: &TableConfig) {
+    aggregation::reserve_aggregate_row(rows, config)
+}
+
+#[deprecated(note = "applied by `table`; use `TableConfig::aggregations`")]
+pub fn fill_aggregate_row(rows: &mut [Row], config: &TableConfig) {
+    aggregation::fill_aggregate_row(rows, config)
+}
+
+#[deprecated(note = "applied by `table`; use `TableConfig::format_rules`")]
+pub fn rule_matches(rule: &FormatRule, value: &str) -> bool {
+    conditional_format::rule_matches(rule, value)
+}
+
+#[deprecated(note = "applied by `table`; use `TableConfig::format_rules`")]
+pub fn format_cell(cell: &str, column: usize, rules: &[FormatRule], symbols_only: bool) -> String {
+    conditional_format::format_cell(cell, column, rules, symbols_only)
+}
+
+#[deprecated(note = "applied by `table`; use `TableConfig::format_rules`")]
+pub fn apply_format_rules(rows: &mut [Row], config: &TableConfig) {
+    conditional_format::apply_format_rules(rows, config)
+}
+
+#[deprecated(note = "applied by `table`; use `TableConfig::style_rules`")]
+pub fn apply_style_rules(rows: &mut [Row], config: &TableConfig) -> TableResult<()> {
+    styling::apply_style_rules(rows, config)
+}
+
+#[deprecated(note = "applied by `table`; use `TableConfig::column_summaries`")]
+pub fn column_summaries(rows: &[Row], config: &TableConfig) -> Vec<(String, String)> {
+    column_summary::column_summaries(rows, config)
+}
+
+#[deprecated(note = "applied by `table`; use `TableConfig::column_summaries`")]
+pub fn draw_column_legend(rows: &[Row], config: &TableConfig, table_width: usize) -> Vec<String> {
+    column_summary::draw_column_legend(rows, config, table_width)
+}
+
+#[cfg(feature = "html")]
+#[deprecated(note = "use `table_to_html`")]
+pub fn draw_table_html(rows: &[Row], config: &TableConfig) -> String {
+    column_summary::draw_table_html(rows, config)
+}
+
+#[deprecated(note = "use `strip_ansi_sequences(value).trim()`")]
+pub fn normalize_value(value: &str) -> String {
+    stats::normalize_value(value)
+}
+
+#[deprecated(note = "use `calculate_column_stats`")]
+pub fn is_null_value(value: &str) -> bool {
+    stats::is_null_value(value)
+}
+
+#[deprecated(note = "use `calculate_column_stats`")]
+pub fn infer_value_type(value: &str) -> stats::ColumnType {
+    stats::infer_value_type(value)
+}
+
+#[deprecated(note = "use `calculate_column_stats`")]
+pub fn infer_column_type<'a>(values: impl IntoIterator<Item = &'a str>) -> stats::ColumnType {
+    stats::infer_column_type(values)
+}
+
+#[deprecated(note = "use `utils::ansi::split_ansi_string`")]
+pub fn split_ansi_string(text: &str) -> Vec<String> {
+    ansi::split_ansi_string(text)
+}
+
+#[deprecated(note = "use `utils::ansi::skip_ansi_sequence`")]
+pub fn skip_ansi_sequence(chars: &[char], pos: usize) -> usize {
+    ansi::skip_ansi_sequence(chars, pos)
+}
+
+#[deprecated(note = "use `utils::ansi::slice_ansi_string`")]
+pub fn slice_ansi_string(text: &str, start: usize, end: usize) -> ansi::AnsiString {
+    ansi::slice_ansi_string(text, start, end)
+}
@@ -1,74 +1,691 @@
-use crate::types::{Row, TableConfig};
-use crate::utils::{calculate_cell_height, calculate_maximum_column_widths, wrap_cell};
+use crate::core::processor::align_column_vertically;
+use crate::features::alignment_processor::{AlignmentProcessor, push_filled};
+use crate::features::spanning::{
+    SpanningCellManager, span_column_config, span_height, span_width, wrap_span_content,
+};
+use crate::types::{
+    Alignment, CellCoordinates, ColumnConfig, Overflow, OverflowPolicy, Row, TableConfig,
+    TruncatePosition,
+};
+use crate::utils::terminal::terminal_width;
+use crate::utils::wrapping::wrap_paragraphs;
+use crate::utils::{
+    calculate_display_width, calculate_maximum_column_widths, strip_ansi_sequences,
+    truncate_ansi_string_at,
+};
+use std::borrow::Cow;
+use std::collections::HashMap;
 
 pub fn calculate_output_column_widths(rows: &[Row], config: &TableConfig) -> Vec<usize> {
+    if !config.spanning_cells.is_empty() {
+        return spanned_column_widths(rows, config);
+    }
+
     let max_widths = calculate_maximum_column_widths(rows);
     let mut output_widths = Vec::new();
 
     for (i, &max_width) in max_widths.iter().enumerate() {
         let column_config = config.columns.get(i).unwrap_or(&config.column_default);
+        output_widths.push(column_width(column_config, max_width));
+    }
 
-        let width = if column_config.width > 0 {
-            column_config.width
-        } else {
-            max_width + column_config.padding_left + column_config.padding_right
-        };
+    output_widths
+}
 
-        let min_width = column_config.padding_left + column_config.padding_right + 1;
-        output_widths.push(width.max(min_width));
+// Spanned cells are measured as a whole: the columns are sized without them,
+// then the auto-sized columns of a span too narrow for its content share the
+// extra width.
+fn spanned_column_widths(rows: &[Row], config: &TableConfig) -> Vec<usize> {
+    let spans = SpanningCellManager::from_config(config);
+    let unspanned: Vec<Row> = rows
+        .iter()
+        .enumerate()
+        .map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .map(|(col, cell)| {
+                    if is_spanned(&spans, row, col) {
+                        String::new()
+                    } else {
+                        cell.clone()
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let unspanned_config = TableConfig {
+        spanning_cells: Vec::new(),
+        ..config.clone()
+    };
+    let mut widths = calculate_output_column_widths(&unspanned, &unspanned_config);
+
+    for range in spans.get_range_config_table() {
+        let column = span_column_config(range, config);
+        let content = &rows[range.top_left.row][range.top_left.col];
+        let needed = content
+            .lines()
+            .map(calculate_display_width)
+            .max()
+            .unwrap_or(0)
+            + column.padding_left
+            + column.padding_right;
+        let deficit = needed.saturating_sub(span_width(range, &widths, config));
+        let auto_columns: Vec<usize> = (range.top_left.col..=range.bottom_right.col)
+            .filter(|&col| {
+                config
+                    .columns
+                    .get(col)
+                    .unwrap_or(&config.column_default)
+                    .width
+                    == 0
+            })
+            .collect();
+        for (i, &col) in auto_columns.iter().enumerate() {
+            // Earlier columns take the rounding remainder.
+            widths[col] +=
+                deficit / auto_columns.len() + usize::from(i < deficit % auto_columns.len());
+        }
     }
 
-    output_widths
+    widths
+}
+
+fn is_spanned(spans: &SpanningCellManager, row: usize, col: usize) -> bool {
+    spans.is_cell_in_span(&CellCoordinates { col, row })
+}
+
+/// The width of a column whose widest cell is `content_width` wide: `width`
+/// when set, otherwise the content plus padding kept within `min_width` and
+/// `max_width`. Never narrower than the padding plus one character.
+pub fn column_width(column: &ColumnConfig, content_width: usize) -> usize {
+    let padding = column.padding_left + column.padding_right;
+    let width = if column.width > 0 {
+        column.width
+    } else {
+        let mut width = content_width + padding;
+        if column.max_width > 0 {
+            width = width.min(column.max_width);
+        }
+        width.max(column.min_width)
+    };
+
+    width.max(padding + 1)
+}
+
+pub fn calculate_table_width(column_widths: &[usize], config: &TableConfig) -> usize {
+    let border = &config.border;
+    column_widths.iter().sum::<usize>()
+        + calculate_display_width(&border.body_left)
+        + calculate_display_width(&border.body_right)
+        + calculate_display_width(&border.body_join) * column_widths.len().saturating_sub(1)
+}
+
+/// Grows or shrinks the columns so the table is `exact_table_width` wide.
+/// Slack goes to `flex_column`, else is shared evenly among the columns, the
+/// leftmost taking any remainder. A deficit comes off `flex_column` first,
+/// then the widest columns, down to their padding and a character; past that
+/// the columns degrade as under `max_table_width`.
+pub fn apply_exact_table_width(rows: &[Row], config: &TableConfig) -> TableConfig {
+    let mut adjusted = config.clone();
+
+    let Some(exact_width) = config.exact_table_width else {
+        return adjusted;
+    };
+
+    let mut widths = calculate_output_column_widths(rows, config);
+    if widths.is_empty() {
+        return adjusted;
+    }
+
+    let current_width = calculate_table_width(&widths, config);
+    let column_count = widths.len();
+
+    if adjusted.columns.len() < column_count {
+        adjusted
+            .columns
+            .resize(column_count, config.column_default.clone());
+    }
+
+    let flex_column = config.flex_column.filter(|&col| col < column_count);
+
+    if exact_width > current_width {
+        let slack = exact_width - current_width;
+
+        match flex_column {
+            Some(col) => widths[col] += slack,
+            None => {
+                for (col, width) in widths.iter_mut().enumerate() {
+                    *width += slack / column_count + usize::from(col < slack % column_count);
+                }
+            }
+        }
+    } else if exact_width < current_width {
+        let border_width = current_width - widths.iter().sum::<usize>();
+        let budget = exact_width.saturating_sub(border_width);
+        let minimum: usize = (0..column_count)
+            .map(|col| minimum_column_width(&adjusted, col))
+            .sum();
+        if minimum > budget {
+            let columns: Vec<usize> = (0..column_count).collect();
+            degrade_columns(&mut adjusted, &columns, &widths, budget);
+            return adjusted;
+        }
+
+        let mut deficit = current_width - exact_width;
+
+        while deficit > 0 {
+            let candidate = flex_column
+                .into_iter()
+                .chain((0..column_count).filter(|&col| Some(col) != flex_column))
+                .filter(|&col| widths[col] > minimum_column_width(&adjusted, col))
+                .max_by_key(|&col| (Some(col) == flex_column, widths[col]));
+
+            let Some(col) = candidate else {
+                break;
+            };
+
+            widths[col] -= 1;
+            deficit -= 1;
+        }
+    }
+
+    for (column, width) in adjusted.columns.iter_mut().zip(widths) {
+        column.width = width;
+    }
+
+    adjusted
+}
+
+/// Resolves `Width::Percent` columns to characters. The share is of the space
+/// inside the borders of a table `exact_table_width` wide, else
+/// `max_table_width`, else as wide as the terminal, less `table_indent`.
+/// Header and footer columns resolve against the same space.
+pub fn apply_percent_widths(rows: &[Row], config: &TableConfig) -> TableConfig {
+    let mut adjusted = config.clone();
+
+    let column_count = rows.first().map_or(0, Vec::len);
+    if column_count == 0 || !has_percent_widths(config) {
+        return adjusted;
+    }
+
+    let table_width = config
+        .exact_table_width
+        .or(config.max_table_width)
+        .unwrap_or_else(|| config.terminal_width.unwrap_or_else(terminal_width));
+    let border_width = calculate_table_width(&vec![0; column_count], config);
+    let inner_width = table_width.saturating_sub(config.table_indent + border_width);
+
+    resolve_percent_widths(&mut adjusted, inner_width);
+    adjusted
+}
+
+pub(crate) fn has_percent_widths(config: &TableConfig) -> bool {
+    config
+        .columns
+        .iter()
+        .chain([&config.column_default])
+        .any(|column| column.width_percent > 0)
+        || [&config.header, &config.footer]
+            .into_iter()
+            .flatten()
+            .any(|section| has_percent_widths(section))
+}
+
+fn resolve_percent_widths(config: &mut TableConfig, inner_width: usize) {
+    for column in config
+        .columns
+        .iter_mut()
+        .chain([&mut config.column_default])
+    {
+        // A share that rounds to nothing still leaves room for a character.
+        if column.width_percent > 0 {
+            column.width = (inner_width * column.width_percent / 100)
+                .max(column.padding_left + column.padding_right + 1);
+        }
+    }
+
+    for section in [&mut config.header, &mut config.footer]
+        .into_iter()
+        .flatten()
+    {
+        resolve_percent_widths(section, inner_width);
+    }
+}
+
+/// Fits the table within `max_table_width`, or the terminal width when
+/// `auto_layout` is on. Auto-sized columns narrower than an even share keep
+/// their width; the longer ones split the remaining space in proportion to
+/// their content and wrap. Columns with an explicit `width`, or that
+/// `Overflow::Expand`, are left alone, and
+/// `exact_table_width` takes precedence. Where the columns cannot all keep
+/// their padding and a character, they drop the padding and cut each cell to
+/// one line ending in its ellipsis.
+pub fn apply_max_table_width(rows: &[Row], config: &TableConfig) -> TableConfig {
+    let mut adjusted = config.clone();
+
+    if config.exact_table_width.is_some() {
+        return adjusted;
+    }
+
+    let Some(max_width) = config.max_table_width.or_else(|| {
+        config
+            .auto_layout
+            .then(|| config.terminal_width.unwrap_or_else(terminal_width))
+    }) else {
+        return adjusted;
+    };
+
+    let widths = calculate_output_column_widths(rows, config);
+    let current_width = calculate_table_width(&widths, config);
+    let available_width = max_width.saturating_sub(config.table_indent);
+    if widths.is_empty() || current_width <= available_width {
+        return adjusted;
+    }
+
+    let column_count = widths.len();
+    if adjusted.columns.len() < column_count {
+        adjusted
+            .columns
+            .resize(column_count, config.column_default.clone());
+    }
+
+    let flexible: Vec<usize> = (0..column_count)
+        .filter(|&col| {
+            let column = config.columns.get(col).unwrap_or(&config.column_default);
+            column.width == 0 && column.overflow_mode != Some(Overflow::Expand)
+        })
+        .collect();
+    let fixed_width: usize = (0..column_count)
+        .filter(|col| !flexible.contains(col))
+        .map(|col| widths[col])
+        .sum();
+    let border_width = current_width - widths.iter().sum::<usize>();
+    let budget = available_width.saturating_sub(border_width + fixed_width);
+
+    let natural: Vec<usize> = flexible.iter().map(|&col| widths[col]).collect();
+    let minimum: Vec<usize> = flexible
+        .iter()
+        .map(|&col| minimum_column_width(&adjusted, col))
+        .collect();
+    if minimum.iter().sum::<usize>() > budget {
+        degrade_columns(&mut adjusted, &flexible, &widths, budget);
+        return adjusted;
+    }
+
+    for (&col, width) in flexible
+        .iter()
+        .zip(distribute_width(&natural, &minimum, budget))
+    {
+        adjusted.columns[col].width = width;
+    }
+
+    adjusted
+}
+
+// Fits auto-sized columns that cannot all keep their padding and a character
+// within `budget`, as in a terminal a few columns wide: they lose their
+// padding, and a cell wider than its column is cut to one line ending in the
+// column's ellipsis, keeping at least its first character. Where the ellipsis
+// does not fit beside that character, its first character marks the cut, or
+// nothing in a column one character wide. The header and footer columns are
+// cut the same way, so every row fills the widths the borders are drawn
+// with. A table too wide even so runs past `budget` rather than drawing a
+// column no character fits in.
+fn degrade_columns(config: &mut TableConfig, columns: &[usize], widths: &[usize], budget: usize) {
+    let content: Vec<usize> = columns
+        .iter()
+        .map(|&col| {
+            let column = &config.columns[col];
+            widths[col].saturating_sub(column.padding_left + column.padding_right)
+        })
+        .collect();
+    let minimum = |ellipsis: bool| -> Vec<usize> {
+        columns
+            .iter()
+            .zip(&content)
+            .map(|(&col, &content)| {
+                let column = &config.columns[col];
+                let ellipsis_width = if ellipsis {
+                    calculate_display_width(&column.ellipsis)
+                } else {
+                    0
+                };
+                content.min(1 + ellipsis_width).max(column.min_width).max(1)
+            })
+            .collect()
+    };
+    let mut minimum_widths = minimum(true);
+    if minimum_widths.iter().sum::<usize>() > budget {
+        minimum_widths = minimum(false);
+    }
+
+    let column_count = widths.len();
+    let degraded: Vec<(usize, usize, usize)> = columns
+        .iter()
+        .zip(distribute_width(&content, &minimum_widths, budget))
+        .zip(content)
+        .map(|((&col, width), content)| (col, width, content))
+        .collect();
+    for section in [&mut config.header, &mut config.footer]
+        .into_iter()
+        .flatten()
+    {
+        if section.columns.len() < column_count {
+            let column_default = section.column_default.clone();
+            section.columns.resize(column_count, column_default);
+        }
+        for &(col, width, content) in &degraded {
+            degrade_column(&mut section.columns[col], width, content);
+        }
+    }
+    for &(col, width, content) in &degraded {
+        degrade_column(&mut config.columns[col], width, content);
+    }
+}
+
+// Gives `column` a `width` without padding, cutting cells wider than that to
+// one line.
+fn degrade_column(column: &mut ColumnConfig, width: usize, content: usize) {
+    column.padding_left = 0;
+    column.padding_right = 0;
+    column.width = width;
+    if width < content {
+        column.overflow = OverflowPolicy::WrapThenTruncateLines;
+        column.truncate = 1;
+        if 1 + calculate_display_width(&column.ellipsis) > width {
+            column.ellipsis = column
+                .ellipsis
+                .chars()
+                .next()
+                .filter(|&marker| calculate_display_width(&marker.to_string()) < width)
+                .map(String::from)
+                .unwrap_or_default();
+        }
+    }
+}
+
+fn distribute_width(natural: &[usize], minimum: &[usize], budget: usize) -> Vec<usize> {
+    let mut widths = natural.to_vec();
+    let mut open: Vec<usize> = (0..natural.len()).collect();
+    let mut remaining = budget;
+
+    loop {
+        let even_share = remaining / open.len().max(1);
+        let (short, long): (Vec<usize>, Vec<usize>) =
+            open.iter().partition(|&&i| natural[i] <= even_share);
+        if short.is_empty() {
+            break;
+        }
+        remaining -= short.iter().map(|&i| natural[i]).sum::<usize>();
+        open = long;
+    }
+
+    loop {
+        let total: usize = open.iter().map(|&i| natural[i]).sum();
+        let (pinned, rest): (Vec<usize>, Vec<usize>) = open
+            .iter()
+            .partition(|&&i| remaining * natural[i] / total.max(1) < minimum[i]);
+        if pinned.is_empty() {
+            break;
+        }
+        for i in pinned {
+            widths[i] = minimum[i];
+            remaining = remaining.saturating_sub(minimum[i]);
+        }
+        open = rest;
+    }
+
+    let total: usize = open.iter().map(|&i| natural[i]).sum();
+    let mut leftover = remaining;
+    for &i in &open {
+        widths[i] = remaining * natural[i] / total.max(1);
+        leftover -= widths[i];
+    }
+
+    // Rounding leftovers go to the widest columns.
+    open.sort_by_key(|&i| std::cmp::Reverse(natural[i]));
+    for &i in open.iter().take(leftover) {
+        widths[i] += 1;
+    }
+
+    widths
+}
+
+fn minimum_column_width(config: &TableConfig, col: usize) -> usize {
+    let column = &config.columns[col];
+    (column.padding_left + column.padding_right + 1).max(column.min_width)
 }
 
 pub fn calculate_row_heights(rows: &[Row], config: &TableConfig) -> Vec<usize> {
     let column_widths = calculate_output_column_widths(rows, config);
+    let spans = SpanningCellManager::from_config(config);
     let mut row_heights = Vec::new();
 
-    for row in rows {
+    for (row_idx, row) in rows.iter().enumerate() {
+        let row_config = row_config(config, row_idx, rows.len());
         let mut max_height = 1;
 
         for (col_idx, cell) in row.iter().enumerate() {
-            let column_config = config
+            if is_spanned(&spans, row_idx, col_idx) {
+                continue;
+            }
+            let column_config = row_config
                 .columns
                 .get(col_idx)
-                .unwrap_or(&config.column_default);
+                .unwrap_or(&row_config.column_default);
             let cell_width = column_widths.get(col_idx).unwrap_or(&0);
             let content_width =
                 cell_width.saturating_sub(column_config.padding_left + column_config.padding_right);
 
-            let height = calculate_cell_height(cell, content_width, column_config.wrap_word);
+            let height = wrap_column_cell(cell, content_width, column_config).len();
             max_height = max_height.max(height);
         }
 
         row_heights.push(max_height);
     }
 
+    // A span taller than the rows it covers stretches its last row.
+    for range in spans.get_range_config_table() {
+        let needed = wrap_span_content(range, rows, &column_widths, config).len();
+        let available = span_height(range, &row_heights, config);
+        row_heights[range.bottom_right.row] += needed.saturating_sub(available);
+    }
+
     row_heights
 }
 
+/// The config that styles row `row_idx` of `row_count`: the header config for
+/// the first row of a table with a header, the footer config for the last row
+/// of a table with a footer, the table config otherwise. Column widths always
+/// come from the table config so the header and footer line up with the body.
+pub fn row_config(config: &TableConfig, row_idx: usize, row_count: usize) -> &TableConfig {
+    let body = config.body_rows(row_count);
+    match (&config.header, &config.footer) {
+        (Some(header), _) if row_idx < body.start => header,
+        (_, Some(footer)) if row_idx >= body.end => footer,
+        _ => config,
+    }
+}
+
+/// Pads the cells of columns aligned on a character (`Alignment::Char`) so
+/// they line up on it once laid out flush left. Each column of the body and
+/// of the footer tracks the widest part of its cells before the character and
+/// the widest from the character on; every cell is padded on the left to the
+/// first, and the block of both widths is moved to the right of a column with
+/// a fixed `width`, as numbers are. The padding is the column's `pad_char`.
+/// Header cells, and cells with line breaks, are left as they are.
+pub(crate) fn align_on_char(rows: &mut [Row], config: &TableConfig) {
+    let row_count = rows.len();
+    let body = config.body_rows(row_count);
+    let section =
+        |row_idx: usize| usize::from(row_idx >= body.start) + usize::from(row_idx >= body.end);
+    // Header cells are labels rather than numbers, and stay flush left.
+    let aligned = |row_idx: usize, col_idx: usize| {
+        if row_idx < body.start {
+            return None;
+        }
+        let row_config = row_config(config, row_idx, row_count);
+        let column = row_config
+            .columns
+            .get(col_idx)
+            .unwrap_or(&row_config.column_default);
+        match column.alignment {
+            Alignment::Char(c) => Some((c, column)),
+            _ => None,
+        }
+    };
+
+    // The widest integer and fraction parts per section and column.
+    let mut parts: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    for (row_idx, row) in rows.iter().enumerate() {
+        for (col_idx, cell) in row.iter().enumerate() {
+            if let Some((c, _)) = aligned(row_idx, col_idx)
+                && !cell.is_empty()
+                && !cell.contains('\n')
+            {
+                let (integer, fraction) = split_on_char(cell, c);
+                let widest = parts.entry((section(row_idx), col_idx)).or_default();
+                *widest = (widest.0.max(integer), widest.1.max(fraction));
+            }
+        }
+    }
+    if parts.is_empty() {
+        return;
+    }
+
+    for (row_idx, row) in rows.iter_mut().enumerate() {
+        for (col_idx, cell) in row.iter_mut().enumerate() {
+            let Some((c, column)) = aligned(row_idx, col_idx) else {
+                continue;
+            };
+            let Some(&(integer, fraction)) = parts.get(&(section(row_idx), col_idx)) else {
+                continue;
+            };
+            if cell.is_empty() || cell.contains('\n') {
+                continue;
+            }
+            let content_width = column
+                .width
+                .saturating_sub(column.padding_left + column.padding_right);
+            let shift = content_width.saturating_sub(integer + fraction);
+            let pad = shift + integer - split_on_char(cell, c).0;
+            if pad > 0 {
+                let mut filled = String::with_capacity(cell.len() + pad);
+                push_filled(&mut filled, cell, column.pad_char, pad);
+                *cell = filled;
+            }
+        }
+    }
+}
+
+// The display widths of `cell` before its first `c` and from it on; a cell
+// without `c` is all before it.
+fn split_on_char(cell: &str, c: char) -> (usize, usize) {
+    let plain = strip_ansi_sequences(cell);
+    let width = calculate_display_width(&plain);
+    match plain.find(c) {
+        Some(at) => {
+            let integer = calculate_display_width(&plain[..at]);
+            (integer, width - integer)
+        }
+        None => (width, 0),
+    }
+}
+
+// Wraps a cell to its content width, then applies the line limit of
+// `OverflowPolicy::WrapThenTruncateLines`. In a justified column every line
+// but the last of each paragraph is spread out to the content width.
+pub(crate) fn wrap_column_cell(
+    cell: &str,
+    content_width: usize,
+    column_config: &ColumnConfig,
+) -> Vec<String> {
+    let justify = column_config.alignment == Alignment::Justify;
+    let mut paragraph_ends = Vec::new();
+    let mut lines = wrap_paragraphs(
+        cell,
+        content_width,
+        column_config.word_break_policy(),
+        &column_config.ellipsis,
+        |end| {
+            if justify {
+                paragraph_ends.push(end);
+            }
+        },
+    );
+
+    let max_lines = column_config.truncate;
+    if column_config.overflow == OverflowPolicy::WrapThenTruncateLines
+        && max_lines > 0
+        && lines.len() > max_lines
+    {
+        lines.truncate(max_lines);
+        if let Some(last) = lines.last_mut() {
+            let ellipsis = &column_config.ellipsis;
+            *last = truncate_ansi_string_at(
+                &format!("{}{ellipsis}", last.trim_end()),
+                content_width,
+                TruncatePosition::End,
+                ellipsis,
+            )
+            .content;
+        }
+    }
+
+    if justify && let Some((_, spread)) = lines.split_last_mut() {
+        for (line_idx, line) in spread.iter_mut().enumerate() {
+            if paragraph_ends.binary_search(&line_idx).is_err()
+                && let Cow::Owned(justified) = AlignmentProcessor::justify_line(line, content_width)
+            {
+                *line = justified;
+            }
+        }
+    }
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+
+    lines
+}
+
 pub fn map_data_using_row_heights(
     rows: &[Row],
     row_heights: &[usize],
     config: &TableConfig,
 ) -> Vec<Vec<Row>> {
     let column_widths = calculate_output_column_widths(rows, config);
+    let spans = SpanningCellManager::from_config(config);
     let mut result = Vec::new();
 
     for (row_idx, row) in rows.iter().enumerate() {
         let row_height = row_heights.get(row_idx).unwrap_or(&1);
         let mut mapped_rows = vec![Vec::new(); *row_height];
+        let row_config = row_config(config, row_idx, rows.len());
 
         for (col_idx, cell) in row.iter().enumerate() {
-            let column_config = config
+            // Spanned cells are drawn from `SpanLayout` instead.
+            if is_spanned(&spans, row_idx, col_idx) {
+                for mapped_row in mapped_rows.iter_mut() {
+                    mapped_row.push(String::new());
+                }
+                continue;
+            }
+
+            let column_config = row_config
                 .columns
                 .get(col_idx)
-                .unwrap_or(&config.column_default);
+                .unwrap_or(&row_config.column_default);
             let cell_width = column_widths.get(col_idx).unwrap_or(&0);
             let content_width =
                 cell_width.saturating_sub(column_config.padding_left + column_config.padding_right);
 
-            let wrapped_lines = wrap_cell(cell, content_width, column_config.wrap_word);
+            let wrapped_lines = align_column_vertically(
+                &wrap_column_cell(cell, content_width, column_config),
+                *row_height,
+                column_config.vertical_alignment,
+            );
 
             for (line_idx, mapped_row) in mapped_rows.iter_mut().enumerate() {
                 let line_content = wrapped_lines
@@ -102,6 +719,368 @@ mod tests {
         assert_eq!(widths, vec![7, 13]);
     }
 
+    #[test]
+    fn test_min_and_max_width_bound_auto_sized_columns() {
+        let rows = vec![vec![
+            "a".to_string(),
+            "a fairly long description".to_string(),
+        ]];
+        let config = TableConfig {
+            columns: vec![
+                ColumnConfig {
+                    min_width: 6,
+                    ..Default::default()
+                },
+                ColumnConfig {
+                    max_width: 12,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(calculate_output_column_widths(&rows, &config), vec![6, 12]);
+        assert!(calculate_row_heights(&rows, &config)[0] > 1);
+
+        let squeezed = TableConfig {
+            max_table_width: Some(12),
+            ..config
+        };
+        let adjusted = apply_max_table_width(&rows, &squeezed);
+        assert_eq!(calculate_output_column_widths(&rows, &adjusted)[0], 6);
+    }
+
+    #[test]
+    fn test_percent_widths_share_the_space_inside_the_borders() {
+        let rows = vec![vec!["a".to_string(), "b".to_string(), "c".to_string()]];
+        let config = TableConfig {
+            columns: vec![
+                ColumnConfig {
+                    width_percent: 25,
+                    ..Default::default()
+                },
+                ColumnConfig {
+                    width_percent: 75,
+                    ..Default::default()
+                },
+                ColumnConfig::default(),
+            ],
+            max_table_width: Some(46),
+            ..Default::default()
+        };
+
+        // 46 less four borders leaves 42 columns: 25% is 10, 75% is 31.
+        let resolved = apply_percent_widths(&rows, &config);
+        assert_eq!(
+            calculate_output_column_widths(&rows, &resolved),
+            vec![10, 31, 3]
+        );
+
+        let terminal = TableConfig {
+            max_table_width: None,
+            terminal_width: Some(84),
+            table_indent: 2,
+            ..config
+        };
+        let resolved = apply_percent_widths(&rows, &terminal);
+        assert_eq!(calculate_output_column_widths(&rows, &resolved)[0], 19);
+    }
+
+    #[test]
+    fn test_apply_exact_table_width_with_flex_column() {
+        let rows = vec![vec!["a".to_string(), "b".to_string()]];
+        let config = TableConfig {
+            exact_table_width: Some(20),
+            flex_column: Some(1),
+            ..Default::default()
+        };
+
+        let adjusted = apply_exact_table_width(&rows, &config);
+        let widths = calculate_output_column_widths(&rows, &adjusted);
+        assert_eq!(widths, vec![3, 14]);
+        assert_eq!(calculate_table_width(&widths, &adjusted), 20);
+    }
+
+    #[test]
+    fn test_apply_exact_table_width_shares_slack_between_columns() {
+        let rows = vec![vec!["a".to_string(), "b".to_string()]];
+        let config = TableConfig {
+            exact_table_width: Some(14),
+            ..Default::default()
+        };
+
+        let adjusted = apply_exact_table_width(&rows, &config);
+        assert_eq!(adjusted.columns[0].padding_left, 1);
+        assert_eq!(adjusted.columns[1].padding_right, 1);
+        let widths = calculate_output_column_widths(&rows, &adjusted);
+        assert_eq!(widths, vec![6, 5]);
+        assert_eq!(calculate_table_width(&widths, &adjusted), 14);
+    }
+
+    #[test]
+    fn test_apply_exact_table_width_shrinks_widest_column() {
+        let rows = vec![vec!["short".to_string(), "much longer text".to_string()]];
+        let config = TableConfig {
+            exact_table_width: Some(20),
+            ..Default::default()
+        };
+
+        let adjusted = apply_exact_table_width(&rows, &config);
+        let widths = calculate_output_column_widths(&rows, &adjusted);
+        assert_eq!(widths, vec![7, 10]);
+    }
+
+    #[test]
+    fn test_apply_exact_table_width_degrades_below_padding() {
+        let rows = vec![vec![
+            "alpha".to_string(),
+            "beta".to_string(),
+            "gamma".to_string(),
+        ]];
+        let config = TableConfig {
+            exact_table_width: Some(10),
+            ..Default::default()
+        };
+
+        let adjusted = apply_exact_table_width(&rows, &config);
+        let widths = calculate_output_column_widths(&rows, &adjusted);
+        assert_eq!(calculate_table_width(&widths, &adjusted), 10);
+        assert!(
+            adjusted
+                .columns
+                .iter()
+                .all(|column| column.padding_left == 0)
+        );
+
+        let output = crate::core::renderer::draw_table(&rows, &config);
+        assert!(
+            output
+                .lines()
+                .all(|line| calculate_display_width(line) == 10)
+        );
+    }
+
+    #[test]
+    fn test_apply_max_table_width_shrinks_long_columns() {
+        let rows = vec![vec![
+            "id".to_string(),
+            "a fairly long description".to_string(),
+            "another long-ish value".to_string(),
+        ]];
+        let config = TableConfig {
+            max_table_width: Some(40),
+            ..Default::default()
+        };
+
+        let adjusted = apply_max_table_width(&rows, &config);
+        let widths = calculate_output_column_widths(&rows, &adjusted);
+        assert_eq!(widths, vec![4, 17, 15]);
+        assert_eq!(calculate_table_width(&widths, &adjusted), 40);
+        assert_eq!(calculate_row_heights(&rows, &adjusted), vec![2]);
+    }
+
+    #[test]
+    fn test_apply_max_table_width_keeps_fitting_and_fixed_columns() {
+        let rows = vec![vec!["short".to_string(), "much longer text".to_string()]];
+        let fitting = TableConfig {
+            max_table_width: Some(80),
+            ..Default::default()
+        };
+        let adjusted = apply_max_table_width(&rows, &fitting);
+        assert_eq!(
+            calculate_output_column_widths(&rows, &adjusted),
+            vec![7, 18]
+        );
+
+        let fixed = TableConfig {
+            max_table_width: Some(20),
+            columns: vec![ColumnConfig {
+                width: 12,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let adjusted = apply_max_table_width(&rows, &fixed);
+        assert_eq!(
+            calculate_output_column_widths(&rows, &adjusted),
+            vec![12, 5]
+        );
+    }
+
+    #[test]
+    fn test_apply_max_table_width_auto_layout_uses_terminal_width() {
+        let rows = vec![vec!["x".repeat(50)]];
+        let config = TableConfig {
+            auto_layout: true,
+            terminal_width: Some(32),
+            table_indent: 2,
+            ..Default::default()
+        };
+
+        let adjusted = apply_max_table_width(&rows, &config);
+        let widths = calculate_output_column_widths(&rows, &adjusted);
+        assert_eq!(calculate_table_width(&widths, &adjusted), 30);
+    }
+
+    #[test]
+    fn test_tiny_terminals_cut_cells_to_one_line() {
+        let rows = vec![
+            vec!["Name".to_string(), "Status".to_string(), "Age".to_string()],
+            vec![
+                "web-01".to_string(),
+                "running".to_string(),
+                "3d".to_string(),
+            ],
+        ];
+        for terminal_width in 10..=40 {
+            let config = TableConfig {
+                auto_layout: true,
+                terminal_width: Some(terminal_width),
+                column_default: ColumnConfig {
+                    ellipsis: "…".to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            let output = crate::core::renderer::draw_table(&rows, &config);
+            let line_widths: Vec<usize> = output.lines().map(calculate_display_width).collect();
+            assert!(
+                line_widths.iter().all(|&width| width == line_widths[0]),
+                "{output}"
+            );
+            assert!(line_widths[0] <= terminal_width, "{output}");
+            if terminal_width == 10 {
+                assert_eq!(
+                    output,
+                    "┌──┬──┬──┐\n│N…│S…│A…│\n├──┼──┼──┤\n│w…│r…│3d│\n└──┴──┴──┘"
+                );
+            }
+        }
+
+        // The default `...` gives way to a `.`, then to a bare cut, down to a
+        // character per column.
+        for terminal_width in 7..=15 {
+            let config = TableConfig {
+                auto_layout: true,
+                terminal_width: Some(terminal_width),
+                ..Default::default()
+            };
+            let output = crate::core::renderer::draw_table(&rows, &config);
+            assert!(
+                output
+                    .lines()
+                    .all(|line| calculate_display_width(line) <= terminal_width),
+                "{output}"
+            );
+            if terminal_width == 7 {
+                assert_eq!(output, "┌─┬─┬─┐\n│N│S│A│\n├─┼─┼─┤\n│w│r│3│\n└─┴─┴─┘");
+            }
+        }
+
+        // Past that, the table runs wider than the terminal but stays whole.
+        let config = TableConfig {
+            auto_layout: true,
+            terminal_width: Some(4),
+            ..Default::default()
+        };
+        let output = crate::core::renderer::draw_table(&rows, &config);
+        assert_eq!(output.lines().count(), 5, "{output}");
+        assert_eq!(output.lines().next(), Some("┌─┬─┬─┐"), "{output}");
+    }
+
+    #[test]
+    fn test_tiny_terminals_keep_every_row_as_wide_as_the_borders() {
+        use crate::features::aggregation::{Aggregation, ColumnAggregation};
+        use crate::features::styling::CellStyle;
+        use crate::types::TableUserConfig;
+
+        let rows = |cells: &[&[&str]]| -> Vec<Row> {
+            cells
+                .iter()
+                .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+                .collect()
+        };
+        let tables = [
+            rows(&[&["", "", ""]]),
+            rows(&[&["", "", "", ""], &["", "", "", ""]]),
+            rows(&[&["a", "b", "c"], &["d", "e", "f"]]),
+            rows(&[&["He\u{301}ader", "Name", "c"], &["1", "web-01", "3d"]]),
+        ];
+        let configs = [
+            TableUserConfig::default(),
+            TableUserConfig {
+                header: Some(Box::default()),
+                header_style: Some(CellStyle {
+                    bold: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            TableUserConfig {
+                aggregations: Some(vec![ColumnAggregation {
+                    column: 0,
+                    aggregation: Aggregation::Sum,
+                }]),
+                ..Default::default()
+            },
+        ];
+
+        for rows in &tables {
+            for config in &configs {
+                for terminal_width in 10..=20 {
+                    let config = TableUserConfig {
+                        auto_layout: Some(true),
+                        terminal_width: Some(terminal_width),
+                        ..config.clone()
+                    };
+                    let output = crate::table(rows, Some(&config)).unwrap();
+                    if let Err(message) = crate::fixtures::check_layout_invariants(&output) {
+                        panic!("{terminal_width} columns: {message}\n{output}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_percent_widths_keep_a_character() {
+        let rows = vec![vec!["a long value".to_string(), "b".to_string()]];
+        let config = TableConfig {
+            terminal_width: Some(10),
+            column_default: ColumnConfig {
+                width_percent: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let adjusted = apply_percent_widths(&rows, &config);
+        assert_eq!(calculate_output_column_widths(&rows, &adjusted), vec![3, 3]);
+    }
+
+    #[test]
+    fn test_wrap_then_truncate_lines() {
+        let rows = vec![vec!["one two three four five".to_string()]];
+        let config = TableConfig {
+            columns: vec![ColumnConfig {
+                width: 9,
+                truncate: 2,
+                wrap_word: true,
+                overflow: OverflowPolicy::WrapThenTruncateLines,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let heights = calculate_row_heights(&rows, &config);
+        assert_eq!(heights, vec![2]);
+
+        let mapped = map_data_using_row_heights(&rows, &heights, &config);
+        assert_eq!(mapped[0][0][0].trim_end(), "one two");
+        assert_eq!(mapped[0][1][0], "thre...");
+    }
+
     #[test]
     fn test_calculate_row_heights() {
         let rows = vec![
@@ -171,6 +1150,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn test_map_data_with_realistic_multiline() {
         let rows = vec![
             vec!["ID".to_string(), "Status".to_string()],
@@ -0,0 +1,281 @@
+//! Explains the column widths `draw_table` picks: which cell set each column's
+//! content width, and every step of the layout that widened or narrowed it
+//! from there. Tracing repeats the layout, so it is only done on request.
+
+use std::fmt;
+
+use crate::core::calculator::{
+    apply_max_table_width, apply_percent_widths, calculate_output_column_widths,
+};
+use crate::core::processor::truncate_table_data;
+use crate::core::renderer::{TableLayout, calculate_table_layout};
+use crate::types::{ColumnConfig, Row, TableConfig};
+use crate::utils::ansi::calculate_display_width;
+
+/// A layout step that can change a column's width, in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LayoutStep {
+    /// `truncate` or `overflow` cut the widest cell.
+    Truncation,
+    /// `padding_left` and `padding_right` were added to the content.
+    Padding,
+    /// The column's `width` replaced the content width.
+    FixedWidth,
+    /// A `Width::Percent` share of the table replaced the content width.
+    PercentWidth,
+    MaxWidth,
+    MinWidth,
+    /// A spanning cell too wide for its columns widened them.
+    SpanningCell,
+    /// Shrunk to fit `max_table_width` or, with `auto_layout`, the terminal.
+    MaxTableWidth,
+    /// Grown or shrunk to meet `exact_table_width`.
+    ExactTableWidth,
+}
+
+impl fmt::Display for LayoutStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LayoutStep::Truncation => "truncation",
+            LayoutStep::Padding => "padding",
+            LayoutStep::FixedWidth => "width",
+            LayoutStep::PercentWidth => "percent width",
+            LayoutStep::MaxWidth => "max_width",
+            LayoutStep::MinWidth => "min_width",
+            LayoutStep::SpanningCell => "spanning cell",
+            LayoutStep::MaxTableWidth => "max_table_width",
+            LayoutStep::ExactTableWidth => "exact_table_width",
+        })
+    }
+}
+
+/// One step that changed a column's width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WidthChange {
+    pub step: LayoutStep,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// How one column got its width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnTrace {
+    pub column: usize,
+    /// The row holding the widest line of the column, the first of several
+    /// as wide; `None` when every cell is empty.
+    pub widest_row: Option<usize>,
+    /// The widest line's width before truncation.
+    pub content_width: usize,
+    /// Every step that changed the width, starting from `content_width`.
+    pub changes: Vec<WidthChange>,
+    /// The drawn width, padding included.
+    pub width: usize,
+}
+
+/// `TableLayout` plus a `ColumnTrace` for every column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutReport {
+    pub layout: TableLayout,
+    pub columns: Vec<ColumnTrace>,
+}
+
+impl fmt::Display for LayoutReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, column) in self.columns.iter().enumerate() {
+            if index > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "column {}: {} wide", column.column, column.width)?;
+            match column.widest_row {
+                Some(row) => write!(f, ", content {} from row {row}", column.content_width)?,
+                None => f.write_str(", no content")?,
+            }
+            for change in &column.changes {
+                write!(f, "; {} {} -> {}", change.step, change.from, change.to)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lays out `rows` as `calculate_table_layout` does, recording each column's
+/// widest cell and the steps that changed its width.
+pub fn trace_table_layout(rows: &[Row], config: &TableConfig) -> LayoutReport {
+    let layout = calculate_table_layout(rows, config);
+    let truncated_rows = truncate_table_data(rows, config);
+
+    let percent_config = apply_percent_widths(&truncated_rows, config);
+    let max_config = apply_max_table_width(&truncated_rows, &percent_config);
+    let base_widths = calculate_output_column_widths(&truncated_rows, &percent_config);
+    let max_widths = calculate_output_column_widths(&truncated_rows, &max_config);
+
+    let columns = (0..layout.column_widths.len())
+        .map(|col| {
+            let (_, content_width) = widest_line(rows, col);
+            let (widest_row, truncated_width) = widest_line(&truncated_rows, col);
+
+            let mut width = content_width;
+            let mut changes = Vec::new();
+            let mut record = |step, to| {
+                if to != width {
+                    changes.push(WidthChange {
+                        step,
+                        from: width,
+                        to,
+                    });
+                    width = to;
+                }
+            };
+
+            record(LayoutStep::Truncation, truncated_width);
+            let column = config.columns.get(col).unwrap_or(&config.column_default);
+            let resolved = percent_config
+                .columns
+                .get(col)
+                .unwrap_or(&percent_config.column_default);
+            for (step, to) in column_width_steps(column, resolved, truncated_width) {
+                record(step, to);
+            }
+            record(LayoutStep::SpanningCell, base_widths[col]);
+            record(LayoutStep::MaxTableWidth, max_widths[col]);
+            // The last step ends at the width the table is drawn with.
+            record(LayoutStep::ExactTableWidth, layout.column_widths[col]);
+
+            ColumnTrace {
+                column: col,
+                widest_row,
+                content_width,
+                changes,
+                width,
+            }
+        })
+        .collect();
+
+    LayoutReport { layout, columns }
+}
+
+// The row and width of the widest line in column `col`.
+fn widest_line(rows: &[Row], col: usize) -> (Option<usize>, usize) {
+    let mut widest = (None, 0);
+    for (row, cells) in rows.iter().enumerate() {
+        let width = cells[col]
+            .lines()
+            .map(calculate_display_width)
+            .max()
+            .unwrap_or(0);
+        if width > widest.1 {
+            widest = (Some(row), width);
+        }
+    }
+    widest
+}
+
+// `column_width` one setting at a time; `resolved` is `column` with any
+// percent width turned into characters.
+fn column_width_steps(
+    column: &ColumnConfig,
+    resolved: &ColumnConfig,
+    content_width: usize,
+) -> Vec<(LayoutStep, usize)> {
+    let padding = resolved.padding_left + resolved.padding_right;
+    let mut steps = Vec::new();
+    let mut width = content_width;
+
+    if resolved.width > 0 {
+        let step = if column.width_percent > 0 {
+            LayoutStep::PercentWidth
+        } else {
+            LayoutStep::FixedWidth
+        };
+        width = resolved.width;
+        steps.push((step, width));
+    } else {
+        width += padding;
+        steps.push((LayoutStep::Padding, width));
+        if resolved.max_width > 0 {
+            width = width.min(resolved.max_width);
+            steps.push((LayoutStep::MaxWidth, width));
+        }
+        width = width.max(resolved.min_width);
+        steps.push((LayoutStep::MinWidth, width));
+    }
+    steps.push((LayoutStep::Padding, width.max(padding + 1)));
+
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<Row> {
+        vec![
+            vec!["id".to_string(), "name".to_string()],
+            vec!["1".to_string(), "a rather long name".to_string()],
+            vec!["22".to_string(), "short".to_string()],
+        ]
+    }
+
+    #[test]
+    fn test_trace_names_the_widest_cell_and_each_step() {
+        let config = TableConfig {
+            columns: vec![
+                ColumnConfig {
+                    min_width: 6,
+                    ..Default::default()
+                },
+                ColumnConfig {
+                    truncate: 10,
+                    ..Default::default()
+                },
+            ],
+            max_table_width: Some(16),
+            ..Default::default()
+        };
+
+        let report = trace_table_layout(&rows(), &config);
+        assert_eq!(
+            report.layout.column_widths,
+            report.columns.iter().map(|c| c.width).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            report.to_string(),
+            "column 0: 6 wide, content 2 from row 0; padding 2 -> 4; min_width 4 -> 6\n\
+             column 1: 7 wide, content 18 from row 1; truncation 18 -> 10; padding 10 -> 12; \
+             max_table_width 12 -> 7"
+        );
+    }
+
+    #[test]
+    fn test_trace_fixed_and_percent_widths() {
+        let config = TableConfig {
+            columns: vec![
+                ColumnConfig {
+                    width: 8,
+                    ..Default::default()
+                },
+                ColumnConfig {
+                    width_percent: 50,
+                    ..Default::default()
+                },
+            ],
+            exact_table_width: Some(30),
+            ..Default::default()
+        };
+
+        let report = trace_table_layout(&rows(), &config);
+        let steps: Vec<Vec<LayoutStep>> = report
+            .columns
+            .iter()
+            .map(|column| column.changes.iter().map(|change| change.step).collect())
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                vec![LayoutStep::FixedWidth, LayoutStep::ExactTableWidth],
+                vec![LayoutStep::PercentWidth, LayoutStep::ExactTableWidth],
+            ]
+        );
+        assert_eq!(report.columns[1].widest_row, Some(1));
+    }
+}
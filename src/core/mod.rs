@@ -1,9 +1,13 @@
 pub mod calculator;
+pub mod layout_report;
 pub mod processor;
 pub mod renderer;
+pub mod session;
 pub mod validator;
 
 pub use calculator::*;
+pub use layout_report::*;
 pub use processor::*;
 pub use renderer::*;
+pub use session::*;
 pub use validator::*;
@@ -1,5 +1,11 @@
-use crate::types::{ColumnConfig, Row, TableConfig, VerticalAlignment};
-use crate::utils::ansi::{pad_ansi_string, truncate_ansi_string};
+use crate::features::number_format::Locale;
+use crate::types::{
+    ColumnConfig, OverflowPolicy, Row, TableConfig, TruncatePosition, VerticalAlignment,
+};
+use crate::utils::ansi::{
+    pad_ansi_string, truncate_ansi_string_at, truncate_ansi_string_at_word_with,
+};
+use crate::utils::slab::spaces;
 
 pub fn align_table_data(rows: &[Row], config: &TableConfig) -> Vec<Row> {
     let mut result = Vec::new();
@@ -116,6 +122,38 @@ pub fn truncate_table_data(rows: &[Row], config: &TableConfig) -> Vec<Row> {
     result
 }
 
+/// Rewrites the numbers of every column with a `formatter`, in every row but
+/// the header rows, with the config's `Locale`. Runs before the widths are
+/// measured, so each column fits its formatted numbers.
+pub fn apply_formatters(rows: &mut [Row], config: &TableConfig) {
+    let formatter = |col_idx: usize| {
+        config
+            .columns
+            .get(col_idx)
+            .unwrap_or(&config.column_default)
+            .formatter
+            .as_ref()
+    };
+    if config.column_default.formatter.is_none()
+        && config
+            .columns
+            .iter()
+            .all(|column| column.formatter.is_none())
+    {
+        return;
+    }
+
+    let locale = Locale::for_config(config);
+    let header_rows = config.body_rows(rows.len()).start;
+    for row in &mut rows[header_rows..] {
+        for (col_idx, cell) in row.iter_mut().enumerate() {
+            if let Some(formatted) = formatter(col_idx).and_then(|f| f.format_cell(cell, &locale)) {
+                *cell = formatted;
+            }
+        }
+    }
+}
+
 fn align_cell(cell: &str, config: &ColumnConfig) -> String {
     if config.width > 0 {
         let content_width = config
@@ -139,14 +177,14 @@ fn align_cell_with_width(cell: &str, config: &ColumnConfig, total_width: usize)
 }
 
 fn pad_cell(cell: &str, config: &ColumnConfig) -> String {
-    let left_padding = " ".repeat(config.padding_left);
-    let right_padding = " ".repeat(config.padding_right);
+    let left_padding = spaces(config.padding_left);
+    let right_padding = spaces(config.padding_right);
     format!("{left_padding}{cell}{right_padding}")
 }
 
 fn pad_cell_with_width(cell: &str, config: &ColumnConfig, total_width: usize) -> String {
-    let left_padding = " ".repeat(config.padding_left);
-    let right_padding = " ".repeat(config.padding_right);
+    let left_padding = spaces(config.padding_left);
+    let right_padding = spaces(config.padding_right);
 
     let current_length = left_padding.len()
         + crate::utils::ansi::calculate_display_width(cell)
@@ -158,16 +196,188 @@ fn pad_cell_with_width(cell: &str, config: &ColumnConfig, total_width: usize) ->
         left_padding,
         cell,
         right_padding,
-        " ".repeat(additional_padding)
+        spaces(additional_padding)
     )
 }
 
-fn truncate_cell(cell: &str, config: &ColumnConfig) -> String {
-    if config.truncate > 0 {
-        let truncated = truncate_ansi_string(cell, config.truncate);
-        truncated.content
+/// Aligned and padded rows, allocated in a per-render arena so the many small
+/// cell strings and row vectors are freed together instead of one by one.
+#[cfg(feature = "arena")]
+pub(crate) type ArenaRows<'bump> = bumpalo::collections::Vec<
+    'bump,
+    bumpalo::collections::Vec<'bump, bumpalo::collections::Vec<'bump, &'bump str>>,
+>;
+
+/// The arena counterpart of `process_mapped_data`: aligns and pads every cell
+/// of every sub-row straight into `bump`, measuring each cell once.
+#[cfg(feature = "arena")]
+pub(crate) fn align_and_pad_in<'bump>(
+    bump: &'bump bumpalo::Bump,
+    mapped_data: &[Vec<Row>],
+    config: &TableConfig,
+    column_widths: &[usize],
+) -> ArenaRows<'bump> {
+    use crate::features::alignment_processor::AlignmentProcessor;
+    use bumpalo::collections::{String as BumpString, Vec as BumpVec};
+
+    let mut result = BumpVec::with_capacity_in(mapped_data.len(), bump);
+
+    for (row_idx, row_group) in mapped_data.iter().enumerate() {
+        let row_config = crate::core::calculator::row_config(config, row_idx, mapped_data.len());
+        let mut processed_row_group = BumpVec::with_capacity_in(row_group.len(), bump);
+
+        for sub_row in row_group {
+            let mut processed_sub_row = BumpVec::with_capacity_in(sub_row.len(), bump);
+
+            for (col_idx, cell) in sub_row.iter().enumerate() {
+                let column_config = row_config
+                    .columns
+                    .get(col_idx)
+                    .unwrap_or(&row_config.column_default);
+                let total_width = column_widths.get(col_idx).copied().unwrap_or(0);
+
+                let layout = AlignmentProcessor::layout_cell(cell, column_config, total_width);
+                let mut out = BumpString::with_capacity_in(
+                    layout.left + layout.text.len() + layout.right,
+                    bump,
+                );
+                out.push_str(&spaces(layout.left));
+                out.push_str(&layout.text);
+                out.push_str(&spaces(layout.right));
+                processed_sub_row.push(out.into_bump_str());
+            }
+
+            processed_row_group.push(processed_sub_row);
+        }
+
+        result.push(processed_row_group);
+    }
+
+    result
+}
+
+/// Wraps, aligns and pads the truncated `cells` of one row, laid out with
+/// `config` (the row's section) to `column_widths`, and draws its lines, each
+/// ending in a newline and painted with `stripe` when set.
+pub(crate) fn layout_row<S: AsRef<str>>(
+    cells: &[S],
+    column_widths: &[usize],
+    config: &TableConfig,
+    stripe: Option<&str>,
+) -> String {
+    use crate::core::calculator::wrap_column_cell;
+    use crate::core::renderer::{stripe_line, write_row};
+
+    let wrapped: Vec<_> = cells
+        .iter()
+        .enumerate()
+        .map(|(col_idx, cell)| {
+            let column = config
+                .columns
+                .get(col_idx)
+                .unwrap_or(&config.column_default);
+            let content_width =
+                column_widths[col_idx].saturating_sub(column.padding_left + column.padding_right);
+            (
+                column,
+                wrap_column_cell(cell.as_ref(), content_width, column),
+            )
+        })
+        .collect();
+    let height = wrapped
+        .iter()
+        .map(|(_, lines)| lines.len())
+        .max()
+        .unwrap_or(1)
+        .max(1);
+    let columns: Vec<_> = wrapped
+        .iter()
+        .map(|(column, lines)| align_column_vertically(lines, height, column.vertical_alignment))
+        .collect();
+
+    let mut output = String::new();
+    let mut line = Vec::with_capacity(columns.len());
+    for line_idx in 0..height {
+        line.clear();
+        line.extend(
+            columns
+                .iter()
+                .map(|lines| lines.get(line_idx).map_or("", String::as_str)),
+        );
+        let line_start = output.len();
+        write_row(&mut output, &line, column_widths, config);
+        if let Some(stripe) = stripe {
+            stripe_line(&mut output, line_start, stripe);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// `layout_row` for every row on the rayon thread pool, once the column
+/// widths are known, returned in row order.
+#[cfg(feature = "parallel")]
+pub(crate) fn layout_rows_parallel(
+    rows: &[Row],
+    config: &TableConfig,
+    column_widths: &[usize],
+) -> Vec<String> {
+    use crate::core::calculator::row_config;
+    use crate::core::renderer::row_stripe;
+    use rayon::prelude::*;
+
+    let row_count = rows.len();
+    rows.par_iter()
+        .enumerate()
+        .map(|(row_idx, row)| {
+            layout_row(
+                row,
+                column_widths,
+                row_config(config, row_idx, row_count),
+                row_stripe(config, row_idx, row_count),
+            )
+        })
+        .collect()
+}
+
+pub(crate) fn truncate_cell(cell: &str, config: &ColumnConfig) -> String {
+    match config.overflow {
+        OverflowPolicy::TruncateThenWrap if config.truncate > 0 => {
+            truncate_to_width(cell, config.truncate, config)
+        }
+        OverflowPolicy::TruncateOnly => truncate_to_single_line(cell, config),
+        _ => cell.to_string(),
+    }
+}
+
+// Word boundaries only apply when the end is cut.
+fn truncate_to_width(cell: &str, max_width: usize, config: &ColumnConfig) -> String {
+    if config.truncate_at_word && config.truncate_position == TruncatePosition::End {
+        truncate_ansi_string_at_word_with(cell, max_width, &config.ellipsis).content
     } else {
-        cell.to_string()
+        truncate_ansi_string_at(cell, max_width, config.truncate_position, &config.ellipsis).content
+    }
+}
+
+fn truncate_to_single_line(cell: &str, config: &ColumnConfig) -> String {
+    let content_width = config
+        .width
+        .saturating_sub(config.padding_left + config.padding_right);
+    let limit = [config.truncate, content_width]
+        .into_iter()
+        .filter(|&width| width > 0)
+        .min();
+
+    let (first_line, rest) = cell.split_once('\n').unwrap_or((cell, ""));
+    let first_line = if rest.is_empty() {
+        first_line.to_string()
+    } else {
+        format!("{}{}", first_line.trim_end(), config.ellipsis)
+    };
+
+    match limit {
+        Some(limit) => truncate_to_width(&first_line, limit, config),
+        None => first_line,
     }
 }
 
@@ -217,7 +427,7 @@ pub fn apply_vertical_alignment(
     result
 }
 
-fn align_column_vertically(
+pub(crate) fn align_column_vertically(
     column_data: &[String],
     target_height: usize,
     alignment: VerticalAlignment,
@@ -263,6 +473,7 @@ mod tests {
     use crate::types::{Alignment, ColumnConfig, VerticalAlignment};
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_align_cell() {
         let mut config = ColumnConfig::default();
         config.width = 10;
@@ -275,6 +486,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_pad_cell() {
         let mut config = ColumnConfig::default();
         config.padding_left = 2;
@@ -285,6 +497,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_truncate_cell() {
         let mut config = ColumnConfig::default();
         config.truncate = 5;
@@ -293,6 +506,101 @@ mod tests {
         assert_eq!(result, "th...");
     }
 
+    #[test]
+    fn test_truncate_cell_overflow_policies() {
+        let wrap_only = ColumnConfig {
+            truncate: 5,
+            overflow: OverflowPolicy::WrapOnly,
+            ..Default::default()
+        };
+        assert_eq!(
+            truncate_cell("this is a long text", &wrap_only),
+            "this is a long text"
+        );
+
+        let truncate_only = ColumnConfig {
+            width: 10,
+            overflow: OverflowPolicy::TruncateOnly,
+            ..Default::default()
+        };
+        assert_eq!(
+            truncate_cell("this is a long text", &truncate_only),
+            "this ..."
+        );
+        assert_eq!(truncate_cell("ab\ncd", &truncate_only), "ab...");
+    }
+
+    #[test]
+    fn test_truncate_cell_at_word() {
+        let config = ColumnConfig {
+            truncate: 12,
+            truncate_at_word: true,
+            ..Default::default()
+        };
+
+        let result = truncate_cell("this is a long text", &config);
+        assert_eq!(result, "this is a...");
+    }
+
+    #[test]
+    fn test_truncate_cell_position_and_ellipsis() {
+        let config = ColumnConfig {
+            truncate: 15,
+            truncate_position: TruncatePosition::Middle,
+            ellipsis: "…".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            truncate_cell("/home/me/src/app/file.rs", &config),
+            "/home/m…file.rs"
+        );
+
+        let single_line = ColumnConfig {
+            overflow: OverflowPolicy::TruncateOnly,
+            truncate_position: TruncatePosition::Start,
+            ..config
+        };
+        assert_eq!(truncate_cell("first\nsecond", &single_line), "first…");
+        assert_eq!(
+            truncate_cell("/home/me/src/app/file.rs", &single_line),
+            "…rc/app/file.rs"
+        );
+    }
+
+    #[test]
+    fn test_apply_formatters_skips_header_rows() {
+        use crate::features::number_format::Formatter;
+
+        let mut rows = vec![
+            vec!["2024".to_string(), "size".to_string()],
+            vec!["1234567".to_string(), "1536".to_string()],
+            vec!["n/a".to_string(), "\u{1b}[31m2048\u{1b}[0m".to_string()],
+        ];
+        let config = TableConfig {
+            header: Some(Box::default()),
+            columns: vec![
+                ColumnConfig {
+                    formatter: Some(Formatter::Integer),
+                    ..Default::default()
+                },
+                ColumnConfig {
+                    formatter: Some(Formatter::Bytes),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        apply_formatters(&mut rows, &config);
+        assert_eq!(
+            rows,
+            [
+                ["2024", "size"],
+                ["1,234,567", "1.5 KiB"],
+                ["n/a", "\u{1b}[31m2 KiB\u{1b}[0m"],
+            ]
+        );
+    }
+
     #[test]
     fn test_align_column_vertically() {
         let column_data = vec!["line1".to_string(), "line2".to_string()];
@@ -306,4 +614,50 @@ mod tests {
         let middle_aligned = align_column_vertically(&column_data, 4, VerticalAlignment::Middle);
         assert_eq!(middle_aligned, vec!["", "line1", "line2", ""]);
     }
+
+    #[cfg(feature = "arena")]
+    #[test]
+    fn test_align_and_pad_in_matches_process_mapped_data() {
+        use crate::core::renderer::process_mapped_data;
+
+        let cells = [
+            "",
+            "ab",
+            "\u{1b}[31mred\u{1b}[0m",
+            "two words",
+            "much too wide",
+        ];
+        let mapped: Vec<Vec<Row>> = cells
+            .iter()
+            .map(|cell| vec![vec![cell.to_string(); 5]])
+            .collect();
+        let column = |alignment, padding_left, padding_right| ColumnConfig {
+            alignment,
+            padding_left,
+            padding_right,
+            ..Default::default()
+        };
+        let config = TableConfig {
+            columns: vec![
+                column(Alignment::Left, 1, 1),
+                column(Alignment::Right, 0, 2),
+                column(Alignment::Center, 2, 0),
+                column(Alignment::Justify, 1, 1),
+                column(Alignment::Center, 1, 1),
+            ],
+            ..Default::default()
+        };
+        let widths = [8, 9, 10, 12, 0];
+
+        let bump = bumpalo::Bump::new();
+        let in_arena = align_and_pad_in(&bump, &mapped, &config, &widths);
+        let expected = process_mapped_data(&mapped, &config, &widths);
+
+        assert_eq!(in_arena.len(), expected.len());
+        for (arena_group, expected_group) in in_arena.iter().zip(&expected) {
+            for (arena_row, expected_row) in arena_group.iter().zip(expected_group) {
+                assert_eq!(arena_row.as_slice(), expected_row.as_slice());
+            }
+        }
+    }
 }
@@ -1,101 +1,989 @@
 use crate::core::calculator::{
-    calculate_output_column_widths, calculate_row_heights, map_data_using_row_heights,
+    apply_exact_table_width, apply_max_table_width, apply_percent_widths,
+    calculate_output_column_widths, calculate_row_heights, column_width,
+    map_data_using_row_heights, row_config, wrap_column_cell,
 };
-use crate::core::processor::{
-    align_table_data_with_widths, pad_table_data_with_widths, truncate_table_data,
+use crate::core::processor::{layout_row, truncate_cell, truncate_table_data};
+use crate::features::alignment_processor::AlignmentProcessor;
+use crate::features::spanning::SpanLayout;
+use crate::features::title::draw_title_and_caption;
+use crate::types::{
+    Alignment, BorderConfig, ColumnConfig, Row, RowAnnotation, TableAlignment, TableConfig,
+    TableResult,
 };
-use crate::types::{BorderConfig, Row, TableConfig};
+use crate::utils::ansi::calculate_display_width;
+use crate::utils::formatting::stringify_table_data;
+use crate::utils::line::LineBuilder;
+use crate::utils::slab::{push_repeated, spaces};
+use crate::utils::wrapping::wrap_text;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt::{self, Write};
+
+const STRING_WRITE: &str = "writing to a String cannot fail";
 
 pub fn draw_table(rows: &[Row], config: &TableConfig) -> String {
+    let mut output = String::new();
+    draw_table_to(rows, config, &mut output).expect(STRING_WRITE);
+    output
+}
+
+/// Writes the table `draw_table` returns into `out`, one line at a time, so a
+/// large table can go to a pre-allocated buffer or a stream without being
+/// built up as one `String` first. A title, a caption, `table_indent` and a
+/// `table_alignment` other than left are applied to the whole table, which is
+/// then drawn in memory before being written.
+pub fn draw_table_to<W: Write + ?Sized>(
+    rows: &[Row],
+    config: &TableConfig,
+    out: &mut W,
+) -> fmt::Result {
+    let positioned = config.title.is_some()
+        || config.caption.is_some()
+        || config.table_indent > 0
+        || config.table_alignment != TableAlignment::Left;
+    if !positioned {
+        return draw_table_body_to(rows, config, out);
+    }
+
+    let output = draw_table_body(rows, config);
+    out.write_str(&position_table_block(
+        draw_title_and_caption(output, config, rows.len()),
+        config,
+    ))
+}
+
+/// Adapts an `io::Write` to `fmt::Write` for `draw_table_to`, keeping the I/O
+/// error that `fmt::Error` cannot carry.
+pub struct IoWriter<W> {
+    inner: W,
+    error: Option<std::io::Error>,
+}
+
+impl<W: std::io::Write> IoWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, error: None }
+    }
+
+    /// The error behind the last `fmt::Error`, if one came from `inner`.
+    pub fn take_error(&mut self) -> Option<std::io::Error> {
+        self.error.take()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: std::io::Write> Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableLayout {
+    pub column_widths: Vec<usize>,
+    pub row_heights: Vec<usize>,
+    /// Whether the first output line is a top border.
+    pub has_top_border: bool,
+}
+
+/// Computes the column widths and row heights `draw_table` will use, without
+/// drawing anything.
+pub fn calculate_table_layout(rows: &[Row], config: &TableConfig) -> TableLayout {
+    let truncated_rows = truncate_table_data(rows, config);
+    let config = fit_table_config(&truncated_rows, config);
+
+    TableLayout {
+        column_widths: calculate_output_column_widths(&truncated_rows, &config),
+        row_heights: calculate_row_heights(&truncated_rows, &config),
+        has_top_border: !rows.is_empty() && config.draws_outer_line(0, rows.len()),
+    }
+}
+
+/// `config` with its percent widths resolved and its columns fitted to
+/// `max_table_width` and `exact_table_width` for the truncated `rows`, as the
+/// table is laid out with it.
+pub(crate) fn fit_table_config(truncated_rows: &[Row], config: &TableConfig) -> TableConfig {
+    let config = apply_percent_widths(truncated_rows, config);
+    let config = apply_max_table_width(truncated_rows, &config);
+    apply_exact_table_width(truncated_rows, &config)
+}
+
+/// Draws the table without applying `table_alignment`/`table_indent`, so callers
+/// can attach extra lines before positioning the whole block.
+pub fn draw_table_body(rows: &[Row], config: &TableConfig) -> String {
+    let mut output = String::new();
+    draw_table_body_to(rows, config, &mut output).expect(STRING_WRITE);
+    output
+}
+
+/// Writes the table `draw_table_body` returns into `out`, one line at a time.
+pub fn draw_table_body_to<W: Write + ?Sized>(
+    rows: &[Row],
+    config: &TableConfig,
+    out: &mut W,
+) -> fmt::Result {
     if rows.is_empty() {
-        return String::new();
+        return Ok(());
+    }
+    if is_small_plain_table(rows, config) {
+        draw_small_table(rows, config, out)
+    } else {
+        draw_full_table(rows, config, out)
     }
+}
+
+/// Tables of at least this many rows are laid out on the rayon thread pool
+/// with the `parallel` feature, unless they have spanning cells.
+pub const PARALLEL_MIN_ROWS: usize = 2_000;
+
+// The whole pipeline: truncate, fit to the table width, wrap, lay out, draw.
+fn draw_full_table<W: Write + ?Sized>(
+    rows: &[Row],
+    config: &TableConfig,
+    out: &mut W,
+) -> fmt::Result {
+    draw_full_table_from(rows, config, out, PARALLEL_MIN_ROWS)
+}
 
+// `draw_full_table`, laying rows out in parallel from `parallel_rows` rows up.
+fn draw_full_table_from<W: Write + ?Sized>(
+    rows: &[Row],
+    config: &TableConfig,
+    out: &mut W,
+    parallel_rows: usize,
+) -> fmt::Result {
     let truncated_rows = truncate_table_data(rows, config);
 
+    let fitted_config = fit_table_config(&truncated_rows, config);
+    let config = &fitted_config;
+
     let column_widths = calculate_output_column_widths(&truncated_rows, config);
 
+    // Each row is laid out on its own once the widths are known, so the rows
+    // can be wrapped and padded on every core and written in order.
+    #[cfg(feature = "parallel")]
+    if rows.len() >= parallel_rows && config.spanning_cells.is_empty() {
+        let drawn_rows =
+            crate::core::processor::layout_rows_parallel(&truncated_rows, config, &column_widths);
+        return write_table_frame(
+            config,
+            &column_widths,
+            None,
+            rows.len(),
+            out,
+            |out, row_idx| out.write_str(&drawn_rows[row_idx]),
+        );
+    }
+    #[cfg(not(feature = "parallel"))]
+    let _ = parallel_rows;
+
     let row_heights = calculate_row_heights(&truncated_rows, config);
     let mapped_data = map_data_using_row_heights(&truncated_rows, &row_heights, config);
+    let spans = (!config.spanning_cells.is_empty())
+        .then(|| SpanLayout::new(&truncated_rows, config, &column_widths, &row_heights));
+    let spans = spans.as_ref();
 
-    let processed_data = process_mapped_data(&mapped_data, config, &column_widths);
+    #[cfg(feature = "arena")]
+    let bump = bumpalo::Bump::new();
+    #[cfg(feature = "arena")]
+    let processed_data =
+        crate::core::processor::align_and_pad_in(&bump, &mapped_data, config, &column_widths);
 
-    let has_header = config.header.is_some();
-    let header_config = config.header.as_ref().map(|h| h.as_ref()).unwrap_or(config);
+    let row_count = mapped_data.len();
 
-    let mut result = String::new();
+    // Each row line is assembled here, so a stripe can be painted over it.
+    let mut line = String::new();
 
-    if (config.draw_horizontal_line)(0, rows.len()) {
-        let border_config = if has_header {
-            &header_config.border
-        } else {
-            &config.border
-        };
-        result.push_str(&draw_border_line(
+    write_table_frame(
+        config,
+        &column_widths,
+        spans,
+        row_count,
+        out,
+        |out, row_idx| {
+            let current_config = row_config(config, row_idx, row_count);
+            let stripe = row_stripe(config, row_idx, row_count);
+
+            // Without the arena, cells are laid out as each line is written.
+            #[cfg(feature = "arena")]
+            let row_group = &processed_data[row_idx];
+            #[cfg(not(feature = "arena"))]
+            let row_group = &mapped_data[row_idx];
+
+            for (line_idx, sub_row) in row_group.iter().enumerate() {
+                line.clear();
+                match spans {
+                    Some(spans) if spans.touches_row(row_idx) => write_spanned_row(
+                        &mut line,
+                        &mapped_data[row_idx][line_idx],
+                        &column_widths,
+                        current_config,
+                        spans,
+                        (row_idx, line_idx),
+                    ),
+                    #[cfg(feature = "arena")]
+                    _ => line.push_str(&draw_row(sub_row, &column_widths, &current_config.border)),
+                    #[cfg(not(feature = "arena"))]
+                    _ => write_row(&mut line, sub_row, &column_widths, current_config),
+                }
+                if let Some(stripe) = stripe {
+                    stripe_line(&mut line, 0, stripe);
+                }
+                out.write_str(&line)?;
+                out.write_char('\n')?;
+            }
+            Ok(())
+        },
+    )
+}
+
+// Writes the borders, the lines between rows and the row annotations around
+// the `row_count` rows `write_row_lines` writes.
+fn write_table_frame<W: Write + ?Sized>(
+    config: &TableConfig,
+    column_widths: &[usize],
+    spans: Option<&SpanLayout>,
+    row_count: usize,
+    out: &mut W,
+    mut write_row_lines: impl FnMut(&mut W, usize) -> fmt::Result,
+) -> fmt::Result {
+    let header_config = config.header.as_deref().unwrap_or(config);
+    let footer_config = config.footer.as_deref().unwrap_or(config);
+
+    let (draw_top, top_horizontal) = line_override(config, 0);
+    if draw_top.unwrap_or_else(|| config.draws_outer_line(0, row_count)) {
+        out.write_str(&draw_table_border_line(
+            column_widths,
+            &with_horizontal(&header_config.border, BorderType::Top, top_horizontal),
+            BorderType::Top,
+            spans,
+            (None, Some(0)),
+        ))?;
+        out.write_char('\n')?;
+    }
+
+    for row_idx in 0..row_count {
+        write_row_lines(out, row_idx)?;
+
+        let current_config = row_config(config, row_idx, row_count);
+        for annotation in config.row_annotations.iter().filter(|a| a.row == row_idx) {
+            for line in draw_row_annotation(annotation, column_widths, &current_config.border) {
+                out.write_str(&line)?;
+                out.write_char('\n')?;
+            }
+        }
+
+        if let Some((border, border_type)) = separator_after(config, row_idx, row_count) {
+            let (_, horizontal) = line_override(config, row_idx + 1);
+            out.write_str(&draw_table_border_line(
+                column_widths,
+                &with_horizontal(border, border_type, horizontal),
+                border_type,
+                spans,
+                (Some(row_idx), Some(row_idx + 1)),
+            ))?;
+            out.write_char('\n')?;
+        }
+    }
+
+    let (draw_bottom, bottom_horizontal) = line_override(config, row_count);
+    if draw_bottom.unwrap_or_else(|| config.draws_outer_line(row_count, row_count)) {
+        out.write_str(&draw_table_border_line(
+            column_widths,
+            &with_horizontal(&footer_config.border, BorderType::Bottom, bottom_horizontal),
+            BorderType::Bottom,
+            spans,
+            (row_count.checked_sub(1), None),
+        ))?;
+    }
+
+    Ok(())
+}
+
+// Rows read ahead of the one being drawn by `draw_rows_in_one_pass`: enough
+// to know whether the next row is the last, i.e. the footer.
+const ROWS_AHEAD: usize = 2;
+
+/// Draws the rows `rows` yields as they come, holding the row being drawn and
+/// `ROWS_AHEAD` more, as `draw_table_body` would draw them all. Nothing is
+/// measured, so every column must set `width`, and nothing fitted to the
+/// whole table, such as spanning cells, is drawn. Until `rows` runs out,
+/// `draw_horizontal_line` is asked with the rows read so far as the count.
+/// Returns how many rows were drawn.
+pub(crate) fn draw_rows_in_one_pass<W: Write + ?Sized>(
+    rows: impl Iterator<Item = Row>,
+    config: &TableConfig,
+    out: &mut W,
+) -> Result<usize, fmt::Error> {
+    let mut rows = rows.fuse();
+    let mut ahead: VecDeque<Row> = rows.by_ref().take(ROWS_AHEAD + 1).collect();
+    let Some(first) = ahead.front() else {
+        return Ok(0);
+    };
+    let column_widths: Vec<usize> = (0..first.len())
+        .map(|col_idx| {
+            column_width(
+                config
+                    .columns
+                    .get(col_idx)
+                    .unwrap_or(&config.column_default),
+                0,
+            )
+        })
+        .collect();
+    let header_config = config.header.as_deref().unwrap_or(config);
+    let footer_config = config.footer.as_deref().unwrap_or(config);
+
+    let (draw_top, top_horizontal) = line_override(config, 0);
+    if draw_top.unwrap_or_else(|| config.draws_outer_line(0, ahead.len())) {
+        out.write_str(&draw_border_line(
             &column_widths,
-            border_config,
+            &with_horizontal(&header_config.border, BorderType::Top, top_horizontal),
             BorderType::Top,
-        ));
-        result.push('\n');
+        ))?;
+        out.write_char('\n')?;
+    }
+
+    let mut row_idx = 0;
+    while let Some(row) = ahead.pop_front() {
+        ahead.extend(rows.by_ref().take(ROWS_AHEAD - ahead.len()));
+        // Exact once fewer rows are left than are read ahead.
+        let row_count = row_idx + 1 + ahead.len();
+
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(col_idx, cell)| {
+                truncate_cell(
+                    cell,
+                    config
+                        .columns
+                        .get(col_idx)
+                        .unwrap_or(&config.column_default),
+                )
+            })
+            .collect();
+        let current_config = row_config(config, row_idx, row_count);
+        out.write_str(&layout_row(
+            &cells,
+            &column_widths,
+            current_config,
+            row_stripe(config, row_idx, row_count),
+        ))?;
+
+        for annotation in config.row_annotations.iter().filter(|a| a.row == row_idx) {
+            for line in draw_row_annotation(annotation, &column_widths, &current_config.border) {
+                out.write_str(&line)?;
+                out.write_char('\n')?;
+            }
+        }
+
+        if let Some((border, border_type)) = separator_after(config, row_idx, row_count) {
+            let (_, horizontal) = line_override(config, row_idx + 1);
+            out.write_str(&draw_border_line(
+                &column_widths,
+                &with_horizontal(border, border_type, horizontal),
+                border_type,
+            ))?;
+            out.write_char('\n')?;
+        }
+        row_idx += 1;
     }
 
-    for (row_idx, row_group) in processed_data.iter().enumerate() {
-        let is_header_row = has_header && row_idx == 0;
-        let current_config = if is_header_row { header_config } else { config };
+    let (draw_bottom, bottom_horizontal) = line_override(config, row_idx);
+    if draw_bottom.unwrap_or_else(|| config.draws_outer_line(row_idx, row_idx)) {
+        out.write_str(&draw_border_line(
+            &column_widths,
+            &with_horizontal(&footer_config.border, BorderType::Bottom, bottom_horizontal),
+            BorderType::Bottom,
+        ))?;
+    }
 
-        for sub_row in row_group.iter() {
-            result.push_str(&draw_row(sub_row, &column_widths, &current_config.border));
-            result.push('\n');
+    Ok(row_idx)
+}
+
+pub(crate) fn separator_after(
+    config: &TableConfig,
+    row_idx: usize,
+    row_count: usize,
+) -> Option<(&BorderConfig, BorderType)> {
+    let body_rows = config.body_rows(row_count);
+    let (draw, _) = line_override(config, row_idx + 1);
+    let is_section_line = row_idx < body_rows.start || row_idx + 1 >= body_rows.end;
+    if row_idx + 1 >= row_count
+        || !draw.unwrap_or(!is_section_line || config.border_visibility.row_separators)
+    {
+        None
+    } else if row_idx < body_rows.start {
+        let header_config = config.header.as_deref().unwrap_or(config);
+        Some((&header_config.border, BorderType::Header))
+    } else if row_idx + 1 >= body_rows.end {
+        let footer_config = config.footer.as_deref().unwrap_or(config);
+        Some((&footer_config.border, BorderType::Footer))
+    } else if draw.unwrap_or_else(|| config.draws_body_row_line(row_idx + 1, row_count)) {
+        Some((&config.border, BorderType::Join))
+    } else {
+        None
+    }
+}
+
+// The `row_overrides` settings for the line above row `line_idx`: whether to
+// draw it and its horizontal character.
+pub(crate) fn line_override(config: &TableConfig, line_idx: usize) -> (Option<bool>, Option<&str>) {
+    let below = config.row_overrides.get(&line_idx);
+    let above = line_idx
+        .checked_sub(1)
+        .and_then(|row| config.row_overrides.get(&row));
+
+    let draw = below
+        .and_then(|row| row.separator_before)
+        .or_else(|| above.and_then(|row| row.separator_after));
+    let horizontal = below
+        .and_then(|row| row.horizontal_before.as_deref())
+        .or_else(|| above.and_then(|row| row.horizontal_after.as_deref()));
+    (draw, horizontal)
+}
+
+/// Whether a table of `row_count` rows is drawn with a top and a bottom border.
+pub(crate) fn outer_borders(config: &TableConfig, row_count: usize) -> (bool, bool) {
+    let (draw_top, _) = line_override(config, 0);
+    let (draw_bottom, _) = line_override(config, row_count);
+    (
+        draw_top.unwrap_or_else(|| config.draws_outer_line(0, row_count)),
+        draw_bottom.unwrap_or_else(|| config.draws_outer_line(row_count, row_count)),
+    )
+}
+
+// `border` with the horizontal character of `border_type` lines replaced.
+pub(crate) fn with_horizontal<'a>(
+    border: &'a BorderConfig,
+    border_type: BorderType,
+    horizontal: Option<&str>,
+) -> Cow<'a, BorderConfig> {
+    let border = section_border(border, border_type);
+    let Some(horizontal) = horizontal else {
+        return border;
+    };
+
+    let mut border = border.into_owned();
+    let body = match border_type {
+        BorderType::Top => &mut border.top_body,
+        BorderType::Bottom => &mut border.bottom_body,
+        BorderType::Join => &mut border.join_body,
+        BorderType::Header | BorderType::Footer => &mut border.header_join,
+    };
+    *body = horizontal.to_string();
+    Cow::Owned(border)
+}
+
+// `border` with the header or footer separator, when it sets one, in place of
+// the flat characters that draw a `border_type` line.
+fn section_border(border: &BorderConfig, border_type: BorderType) -> Cow<'_, BorderConfig> {
+    let line = match border_type {
+        BorderType::Header if border.header_separator.is_some() => border.header_line(),
+        BorderType::Footer
+            if border.footer_separator.is_some() || border.header_separator.is_some() =>
+        {
+            border.footer_line()
         }
+        _ => return Cow::Borrowed(border),
+    };
 
-        if row_idx < processed_data.len() - 1 {
-            if is_header_row {
-                result.push_str(&draw_border_line(
-                    &column_widths,
-                    &header_config.border,
-                    BorderType::Header,
-                ));
-                result.push('\n');
-            } else if (config.draw_horizontal_line)(row_idx + 1, rows.len()) {
-                result.push_str(&draw_border_line(
-                    &column_widths,
-                    &config.border,
-                    BorderType::Join,
-                ));
-                result.push('\n');
+    Cow::Owned(BorderConfig {
+        join_left: line.left,
+        header_join: line.body,
+        join_join: line.join,
+        join_right: line.right,
+        header_separator: None,
+        footer_separator: None,
+        ..border.clone()
+    })
+}
+
+// The `row_striping` background for row `row_idx`: every other body row,
+// starting with the second.
+pub(crate) fn row_stripe(config: &TableConfig, row_idx: usize, row_count: usize) -> Option<&str> {
+    let body_rows = config.body_rows(row_count);
+    let stripe = config.row_striping.as_deref()?;
+    (body_rows.contains(&row_idx) && (row_idx - body_rows.start) % 2 == 1).then_some(stripe)
+}
+
+// Paints the line written from `line_start` with the stripe, re-applying it
+// after every escape in the cells that clears the background, and resets at
+// the end of the line so nothing bleeds into the next one.
+pub(crate) fn stripe_line(out: &mut String, line_start: usize, stripe: &str) {
+    let line = out.split_off(line_start);
+    let open = format!("\u{1b}[{stripe}m");
+    out.push_str(&open);
+
+    let mut rest = line.as_str();
+    while let Some(start) = rest.find("\u{1b}[") {
+        let Some(len) = rest[start + 2..].find(|c: char| !c.is_ascii_digit() && c != ';') else {
+            break;
+        };
+        let end = start + 2 + len;
+        let is_sgr = rest[end..].starts_with('m');
+        out.push_str(&rest[..end + usize::from(is_sgr)]);
+        if is_sgr && clears_background(&rest[start + 2..end]) {
+            out.push_str(&open);
+        }
+        rest = &rest[end + usize::from(is_sgr)..];
+    }
+    out.push_str(rest);
+    out.push_str("\u{1b}[0m");
+}
+
+// Whether SGR parameters reset the background: a full reset (`0` or no
+// parameters) or the default background (`49`). Extended colors are skipped
+// over so their components are not mistaken for resets.
+fn clears_background(params: &str) -> bool {
+    let mut params = params
+        .split(';')
+        .map(|param| param.parse::<u16>().unwrap_or(0));
+    let mut clears = false;
+    while let Some(param) = params.next() {
+        match param {
+            0 | 49 => clears = true,
+            38 | 48 | 58 => {
+                let skip = match params.next() {
+                    Some(5) => 1,
+                    Some(2) => 3,
+                    _ => 0,
+                };
+                params.by_ref().take(skip).for_each(drop);
+                clears &= param != 48;
             }
+            40..=47 | 100..=107 => clears = false,
+            _ => {}
+        }
+    }
+    clears
+}
+
+const SMALL_TABLE_MAX_CELLS: usize = 20;
+
+// Small tables of plain values skip truncation, wrapping and the intermediate
+// row structures: every cell is a single line of printable ASCII, so its width
+// is its length and nothing needs cutting or wrapping.
+fn is_small_plain_table(rows: &[Row], config: &TableConfig) -> bool {
+    let column_count = rows[0].len();
+    if rows.len() * column_count > SMALL_TABLE_MAX_CELLS
+        || !config.row_annotations.is_empty()
+        || !config.row_overrides.is_empty()
+        || !config.spanning_cells.is_empty()
+        || config.max_table_width.is_some()
+        || config.exact_table_width.is_some()
+        || config.auto_layout
+    {
+        return false;
+    }
+
+    let sections = [
+        config.header.as_deref(),
+        config.footer.as_deref(),
+        Some(config),
+    ];
+    // A column may only set what a single line of plain text is laid out
+    // with, its padding alike in every section; any other setting, including
+    // ones added later, takes the full pipeline.
+    let plain_column = |col_idx: usize| {
+        let (body, _) = column_layout(config, &[], col_idx);
+        sections.iter().flatten().all(|section| {
+            let (column, _) = column_layout(section, &[], col_idx);
+            let plain = ColumnConfig {
+                alignment: column.alignment,
+                vertical_alignment: column.vertical_alignment,
+                padding_left: body.padding_left,
+                padding_right: body.padding_right,
+                ..Default::default()
+            };
+            *column == plain && !matches!(column.alignment, Alignment::Justify | Alignment::Char(_))
+        })
+    };
+    let plain_cell = |cell: &String| {
+        cell.bytes()
+            .all(|byte| byte.is_ascii_graphic() || byte == b' ')
+            && !cell.starts_with(' ')
+            && !cell.ends_with(' ')
+    };
+
+    (0..column_count).all(plain_column) && rows.iter().flatten().all(plain_cell)
+}
+
+fn draw_small_table<W: Write + ?Sized>(
+    rows: &[Row],
+    config: &TableConfig,
+    out: &mut W,
+) -> fmt::Result {
+    let mut column_widths = vec![0; rows[0].len()];
+    for row in rows {
+        for (width, cell) in column_widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
         }
     }
+    for (col_idx, width) in column_widths.iter_mut().enumerate() {
+        let (column, _) = column_layout(config, &[], col_idx);
+        let padding = column.padding_left + column.padding_right;
+        *width = (*width + padding).max(padding + 1);
+    }
+
+    let row_count = rows.len();
+    let line_width = column_widths.iter().sum::<usize>() + column_widths.len() + 1;
+    let mut line = String::with_capacity(line_width * 4);
 
-    if (config.draw_horizontal_line)(rows.len(), rows.len()) {
-        result.push_str(&draw_border_line(
+    if config.draws_outer_line(0, row_count) {
+        let header_config = config.header.as_deref().unwrap_or(config);
+        out.write_str(&draw_border_line(
             &column_widths,
-            &config.border,
+            &header_config.border,
+            BorderType::Top,
+        ))?;
+        out.write_char('\n')?;
+    }
+
+    for (row_idx, row) in rows.iter().enumerate() {
+        line.clear();
+        write_row(
+            &mut line,
+            row,
+            &column_widths,
+            row_config(config, row_idx, row_count),
+        );
+        if let Some(stripe) = row_stripe(config, row_idx, row_count) {
+            stripe_line(&mut line, 0, stripe);
+        }
+        out.write_str(&line)?;
+        out.write_char('\n')?;
+
+        if let Some((border, border_type)) = separator_after(config, row_idx, row_count) {
+            out.write_str(&draw_border_line(&column_widths, border, border_type))?;
+            out.write_char('\n')?;
+        }
+    }
+
+    if config.draws_outer_line(row_count, row_count) {
+        let footer_config = config.footer.as_deref().unwrap_or(config);
+        out.write_str(&draw_border_line(
+            &column_widths,
+            &footer_config.border,
             BorderType::Bottom,
-        ));
+        ))?;
     }
 
-    result
+    Ok(())
+}
+
+/// Splits the table into pages of at most `page_size` body rows and draws each
+/// as a complete table: the header row is repeated on every page and the
+/// footer row closes the last one. All pages share the whole table's column
+/// widths, so they line up when printed one after another. With `page_height`
+/// set, pages also end when the next row would not fit, and a row taller than
+/// the space left is split across pages.
+///
+/// Rows are validated, filtered, sorted, totalled and formatted as `table`
+/// does before they are split, and colors stripped per `color_mode`.
+pub fn paginate_table(
+    rows: &[Row],
+    config: &TableConfig,
+    page_size: usize,
+) -> TableResult<Vec<String>> {
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut config = config.clone();
+    let rows = &crate::prepare_table_data(stringify_table_data(rows)?, &mut config)?;
+    let config = &config;
+    let page_size = page_size.max(1);
+    let column_widths = calculate_table_layout(rows, config).column_widths;
+    let pinned = with_column_widths(config, &column_widths);
+
+    let body_rows = config.body_rows(rows.len());
+    let header = &rows[..body_rows.start];
+    let footer = &rows[body_rows.end..];
+
+    let draw_page = |page: &[PageRow], last_page: bool| {
+        let mut page_rows = header.to_vec();
+        page_rows.extend(page.iter().map(|page_row| page_row.cells.clone()));
+
+        let mut page_config = pinned.clone();
+        if last_page {
+            page_rows.extend_from_slice(footer);
+        } else {
+            page_config.footer = None;
+        }
+
+        // Annotations and overrides follow their rows, or the last part of a
+        // split row, onto the page.
+        let to_page_row = |row: usize| {
+            if row < body_rows.start {
+                Some(row)
+            } else if let Some(position) = page
+                .iter()
+                .position(|page_row| page_row.source == row && page_row.ends_row)
+            {
+                Some(position + header.len())
+            } else if last_page && row >= body_rows.end {
+                Some(row - body_rows.end + header.len() + page.len())
+            } else {
+                None
+            }
+        };
+        page_config.row_annotations = config
+            .row_annotations
+            .iter()
+            .filter_map(|annotation| {
+                to_page_row(annotation.row).map(|row| RowAnnotation {
+                    row,
+                    ..annotation.clone()
+                })
+            })
+            .collect();
+        page_config.row_overrides = config
+            .row_overrides
+            .iter()
+            .filter_map(|(&row, row_override)| {
+                to_page_row(row).map(|row| (row, row_override.clone()))
+            })
+            .collect();
+
+        let row_count = page_rows.len();
+        draw_title_and_caption(
+            draw_table_body(&page_rows, &page_config),
+            &page_config,
+            row_count,
+        )
+    };
+    // Every page keeps room for the footer, so the last one always fits it.
+    let fits = |page: &[PageRow]| {
+        config.page_height.is_none_or(|height| {
+            draw_page(page, true).lines().count() + usize::from(config.page_captions) <= height
+        })
+    };
+
+    let mut queue: VecDeque<PageRow> = body_rows
+        .clone()
+        .map(|row| PageRow {
+            source: row,
+            cells: rows[row].clone(),
+            ends_row: true,
+        })
+        .collect();
+    let mut pages = Vec::new();
+    loop {
+        // A page only grows taller with each row, so the most rows that fit
+        // are found by doubling and then bisecting the row count, rather than
+        // by drawing the page again after every row.
+        let limit = page_size.min(queue.len());
+        let queued = queue.make_contiguous();
+        let (mut count, mut too_many) = (0, limit + 1);
+        while count < limit {
+            let next = (count * 2).clamp(1, limit);
+            if !fits(&queued[..next]) {
+                too_many = next;
+                break;
+            }
+            count = next;
+        }
+        while count + 1 < too_many {
+            let middle = (count + too_many) / 2;
+            if fits(&queued[..middle]) {
+                count = middle;
+            } else {
+                too_many = middle;
+            }
+        }
+        let mut page: Vec<PageRow> = queue.drain(..count).collect();
+
+        if page.len() < limit {
+            let page_row = queue.pop_front().expect("a row did not fit");
+            match split_page_row(&page, &page_row, &pinned, fits) {
+                Some((head, tail)) => {
+                    page.push(head);
+                    queue.push_front(tail);
+                }
+                // Nothing smaller fits either; overflowing beats looping.
+                None if page.is_empty() => page.push(page_row),
+                None => queue.push_front(page_row),
+            }
+        }
+
+        pages.push(page);
+        if queue.is_empty() {
+            break;
+        }
+    }
+
+    let page_count = pages.len();
+    Ok(pages
+        .iter()
+        .enumerate()
+        .map(|(page_idx, page)| {
+            let mut output = draw_page(page, page_idx + 1 == page_count);
+            if config.page_captions {
+                output.push_str(&format!("\nPage {} of {page_count}", page_idx + 1));
+            }
+            crate::finish_output(position_table_block(output, config), config)
+        })
+        .collect())
 }
 
-fn process_mapped_data(
+// A body row, or part of one, placed on a page.
+#[derive(Clone)]
+struct PageRow {
+    source: usize,
+    cells: Row,
+    // False for every part of a split row but the last.
+    ends_row: bool,
+}
+
+// Splits `page_row` after as many lines as still fit on `page`, cut cells
+// ending with their ellipsis. `None` when not even one line fits.
+fn split_page_row(
+    page: &[PageRow],
+    page_row: &PageRow,
+    config: &TableConfig,
+    fits: impl Fn(&[PageRow]) -> bool,
+) -> Option<(PageRow, PageRow)> {
+    let columns: Vec<&ColumnConfig> = (0..page_row.cells.len())
+        .map(|col| config.columns.get(col).unwrap_or(&config.column_default))
+        .collect();
+    let lines: Vec<Vec<String>> = page_row
+        .cells
+        .iter()
+        .zip(&columns)
+        .map(|(cell, column)| {
+            let content_width = column
+                .width
+                .saturating_sub(column.padding_left + column.padding_right);
+            wrap_column_cell(&truncate_cell(cell, column), content_width, column)
+        })
+        .collect();
+    let height = lines.iter().map(Vec::len).max().unwrap_or(0);
+
+    // Keeping `height - 1` lines plus the ellipsis is no shorter than the row.
+    (1..height.saturating_sub(1)).rev().find_map(|kept| {
+        let head = PageRow {
+            source: page_row.source,
+            cells: lines
+                .iter()
+                .zip(&columns)
+                .map(|(cell_lines, column)| {
+                    let mut head_lines = cell_lines[..kept.min(cell_lines.len())].to_vec();
+                    if cell_lines.len() > kept {
+                        head_lines.push(column.ellipsis.clone());
+                    }
+                    head_lines.join("\n")
+                })
+                .collect(),
+            ends_row: false,
+        };
+
+        let mut candidate = page.to_vec();
+        candidate.push(head.clone());
+        fits(&candidate).then(|| {
+            let tail = PageRow {
+                cells: lines
+                    .iter()
+                    .map(|cell_lines| cell_lines.get(kept..).unwrap_or_default().join("\n"))
+                    .collect(),
+                ..page_row.clone()
+            };
+            (head, tail)
+        })
+    })
+}
+
+// Pins every column, in the body and in the header and footer sections, to
+// the given width; the widths already fit any table width limits.
+fn with_column_widths(config: &TableConfig, column_widths: &[usize]) -> TableConfig {
+    let mut pinned = TableConfig {
+        max_table_width: None,
+        exact_table_width: None,
+        auto_layout: false,
+        header: config
+            .header
+            .as_deref()
+            .map(|header| Box::new(with_column_widths(header, column_widths))),
+        footer: config
+            .footer
+            .as_deref()
+            .map(|footer| Box::new(with_column_widths(footer, column_widths))),
+        ..config.clone()
+    };
+    pinned
+        .columns
+        .resize(column_widths.len(), config.column_default.clone());
+    for (column, &width) in pinned.columns.iter_mut().zip(column_widths) {
+        column.width = width;
+    }
+    pinned
+}
+
+pub fn position_table_block(output: String, config: &TableConfig) -> String {
+    let offset = match config.table_alignment {
+        TableAlignment::Left => 0,
+        alignment => {
+            let available_width = config
+                .terminal_width
+                .unwrap_or_else(crate::utils::terminal::terminal_width)
+                .saturating_sub(config.table_indent);
+            let table_width = output
+                .lines()
+                .map(calculate_display_width)
+                .max()
+                .unwrap_or(0);
+            let slack = available_width.saturating_sub(table_width);
+
+            if alignment == TableAlignment::Center {
+                slack / 2
+            } else {
+                slack
+            }
+        }
+    };
+
+    let indent = config.table_indent + offset;
+    if indent == 0 {
+        return output;
+    }
+
+    let prefix = spaces(indent);
+    output
+        .split('\n')
+        .map(|line| format!("{prefix}{line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub(crate) fn process_mapped_data(
     mapped_data: &[Vec<Vec<String>>],
     config: &TableConfig,
     column_widths: &[usize],
 ) -> Vec<Vec<Vec<String>>> {
     let mut result = Vec::new();
 
-    for row_group in mapped_data {
+    for (row_idx, row_group) in mapped_data.iter().enumerate() {
+        let row_config = row_config(config, row_idx, mapped_data.len());
         let mut processed_row_group = Vec::new();
 
         for sub_row in row_group {
-            let aligned_row =
-                align_table_data_with_widths(std::slice::from_ref(sub_row), config, column_widths);
-            let padded_row = pad_table_data_with_widths(&aligned_row, config, column_widths);
-
-            if let Some(processed_sub_row) = padded_row.first() {
-                processed_row_group.push(processed_sub_row.clone());
-            }
+            let processed_sub_row = sub_row
+                .iter()
+                .enumerate()
+                .map(|(col_idx, cell)| {
+                    let (column_config, total_width) =
+                        column_layout(row_config, column_widths, col_idx);
+                    AlignmentProcessor::layout_cell(cell, column_config, total_width).to_string()
+                })
+                .collect();
+            processed_row_group.push(processed_sub_row);
         }
 
         result.push(processed_row_group);
@@ -104,30 +992,229 @@ fn process_mapped_data(
     result
 }
 
-pub fn draw_row(row: &[String], _column_widths: &[usize], border: &BorderConfig) -> String {
-    let mut result = String::new();
-
-    result.push_str(&border.body_left);
+fn column_layout<'a>(
+    config: &'a TableConfig,
+    column_widths: &[usize],
+    col_idx: usize,
+) -> (&'a crate::types::ColumnConfig, usize) {
+    let column_config = config
+        .columns
+        .get(col_idx)
+        .unwrap_or(&config.column_default);
+    (
+        column_config,
+        column_widths.get(col_idx).copied().unwrap_or(0),
+    )
+}
 
+/// Aligns, pads and joins the cells of one output line straight into `out`, as
+/// `draw_row` does with cells `process_mapped_data` has laid out.
+pub fn write_row<S: AsRef<str>>(
+    out: &mut String,
+    row: &[S],
+    column_widths: &[usize],
+    config: &TableConfig,
+) {
+    let mut line = LineBuilder::body(out, &config.border);
     for (col_idx, cell) in row.iter().enumerate() {
-        result.push_str(cell);
+        let (column_config, total_width) = column_layout(config, column_widths, col_idx);
+        line.segment_with(|out| {
+            AlignmentProcessor::layout_cell(cell.as_ref(), column_config, total_width).write_to(out)
+        });
+    }
+    line.finish();
+}
+
+// `write_row` for a line of a row some spanning cell covers: each span is drawn
+// once across its columns, without the vertical lines inside it.
+fn write_spanned_row(
+    out: &mut String,
+    row: &[String],
+    column_widths: &[usize],
+    config: &TableConfig,
+    spans: &SpanLayout,
+    (row_idx, line_idx): (usize, usize),
+) {
+    let mut line = LineBuilder::body(out, &config.border);
+    let mut col_idx = 0;
+    while col_idx < row.len() {
+        match spans.span_at(row_idx, col_idx) {
+            Some(span) => {
+                line.segment(spans.row_line(span, row_idx, line_idx));
+                col_idx = spans.end_col(span) + 1;
+            }
+            None => {
+                let (column_config, total_width) = column_layout(config, column_widths, col_idx);
+                line.segment_with(|out| {
+                    AlignmentProcessor::layout_cell(&row[col_idx], column_config, total_width)
+                        .write_to(out)
+                });
+                col_idx += 1;
+            }
+        }
+    }
+    line.finish();
+}
 
-        if col_idx < row.len() - 1 {
-            result.push_str(&border.body_join);
+// `draw_border_line`, or with spanning cells, a line between the rows `above`
+// and `below` (`None` at the top and bottom edges) that a span may cross or end
+// at, so each junction is drawn for the lines that actually meet there.
+fn draw_table_border_line(
+    column_widths: &[usize],
+    border: &BorderConfig,
+    border_type: BorderType,
+    spans: Option<&SpanLayout>,
+    (above, below): (Option<usize>, Option<usize>),
+) -> String {
+    let Some(spans) = spans else {
+        return draw_border_line(column_widths, border, border_type);
+    };
+    let border = &*section_border(border, border_type);
+
+    let horizontal = match border_type {
+        BorderType::Top => &border.top_body,
+        BorderType::Bottom => &border.bottom_body,
+        BorderType::Join => &border.join_body,
+        BorderType::Header | BorderType::Footer => &border.header_join,
+    };
+    // A header or footer separator has its own crossing, so the junctions
+    // where only one divider meets it, as under a group of header columns,
+    // are drawn to match. Without box-drawing tees for that crossing, as in
+    // ASCII and Markdown styles, the crossing itself marks them.
+    let (down_join, up_join): (&str, &str) = match border_type {
+        BorderType::Header | BorderType::Footer => separator_tees(&border.join_join)
+            .unwrap_or_else(|| {
+                let join = if border.join_join.is_empty() {
+                    horizontal
+                } else {
+                    &border.join_join
+                };
+                (join, join)
+            }),
+        _ => (&border.top_join, &border.bottom_join),
+    };
+    let column_count = column_widths.len();
+    let crossing = |col: usize| {
+        above
+            .zip(below)
+            .and_then(|(row, _)| spans.crossing(row, col))
+    };
+    let junction = |boundary: usize| -> std::borrow::Cow<'_, str> {
+        let up = above.is_some_and(|row| spans.has_divider(row, boundary));
+        let down = below.is_some_and(|row| spans.has_divider(row, boundary));
+        let left = boundary > 0 && crossing(boundary - 1).is_none();
+        let right = boundary < column_count && crossing(boundary).is_none();
+        let junction = match (up, down, left, right) {
+            (false, true, false, true) => &border.top_left,
+            (false, true, true, false) => &border.top_right,
+            (true, false, false, true) => &border.bottom_left,
+            (true, false, true, false) => &border.bottom_right,
+            (false, true, true, true) => down_join,
+            (true, false, true, true) => up_join,
+            (true, true, false, true) => &border.join_left,
+            (true, true, true, false) => &border.join_right,
+            (true, true, true, true) => &border.join_join,
+            (_, _, true, _) | (_, _, _, true) => horizontal,
+            (true, _, _, _) | (_, true, _, _) if boundary == 0 => &border.body_left,
+            (true, _, _, _) | (_, true, _, _) if boundary == column_count => &border.body_right,
+            (true, _, _, _) | (_, true, _, _) => &border.body_join,
+            _ => {
+                return " "
+                    .repeat(calculate_display_width(&border.join_join))
+                    .into();
+            }
+        };
+        junction.into()
+    };
+
+    let mut result = junction(0).into_owned();
+    let mut col_idx = 0;
+    while col_idx < column_count {
+        match (above, crossing(col_idx)) {
+            (Some(row), Some(span)) => {
+                result.push_str(spans.separator_line(span, row));
+                col_idx = spans.end_col(span) + 1;
+            }
+            _ => {
+                push_repeated(&mut result, horizontal, column_widths[col_idx]);
+                col_idx += 1;
+            }
         }
+        result.push_str(&junction(col_idx));
     }
 
-    result.push_str(&border.body_right);
+    result
+}
 
+// The T-junctions that go with a box-drawing crossing: where a line meets it
+// only from below, and only from above.
+fn separator_tees(join: &str) -> Option<(&'static str, &'static str)> {
+    match join {
+        "┼" => Some(("┬", "┴")),
+        "┿" => Some(("┯", "┷")),
+        "╂" => Some(("┰", "┸")),
+        "╋" => Some(("┳", "┻")),
+        "╪" => Some(("╤", "╧")),
+        "╫" => Some(("╥", "╨")),
+        "╬" => Some(("╦", "╩")),
+        _ => None,
+    }
+}
+
+pub fn draw_row<S: AsRef<str>>(
+    row: &[S],
+    _column_widths: &[usize],
+    border: &BorderConfig,
+) -> String {
+    let mut result = String::new();
+    let mut line = LineBuilder::body(&mut result, border);
+    line.segments(row);
+    line.finish();
     result
 }
 
-#[derive(Debug, Clone, Copy)]
+pub fn draw_row_annotation(
+    annotation: &RowAnnotation,
+    column_widths: &[usize],
+    border: &BorderConfig,
+) -> Vec<String> {
+    let inner_width = column_widths.iter().sum::<usize>()
+        + calculate_display_width(&border.body_join) * column_widths.len().saturating_sub(1);
+    let indent = annotation
+        .indent
+        .unwrap_or(2)
+        .min(inner_width.saturating_sub(1));
+    let text_width = inner_width - indent;
+
+    wrap_text(&annotation.text, text_width, true)
+        .into_iter()
+        .map(|line| {
+            let padding = text_width.saturating_sub(calculate_display_width(&line));
+            let content = if annotation.dim.unwrap_or(true) {
+                format!("\u{1b}[2m{line}\u{1b}[0m")
+            } else {
+                line
+            };
+
+            format!(
+                "{}{}{}{}{}",
+                border.body_left,
+                spaces(indent),
+                content,
+                spaces(padding),
+                border.body_right
+            )
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BorderType {
     Top,
     Bottom,
     Join,
     Header,
+    Footer,
 }
 
 pub fn draw_border_line(
@@ -137,6 +1224,7 @@ pub fn draw_border_line(
 ) -> String {
     let mut result = String::new();
 
+    let border = &*section_border(border, border_type);
     let (left, right, body, join) = match border_type {
         BorderType::Top => (
             &border.top_left,
@@ -156,7 +1244,7 @@ pub fn draw_border_line(
             &border.join_body,
             &border.join_join,
         ),
-        BorderType::Header => (
+        BorderType::Header | BorderType::Footer => (
             &border.join_left,
             &border.join_right,
             &border.header_join,
@@ -164,17 +1252,11 @@ pub fn draw_border_line(
         ),
     };
 
-    result.push_str(left);
-
-    for (col_idx, &width) in column_widths.iter().enumerate() {
-        result.push_str(&body.repeat(width));
-
-        if col_idx < column_widths.len() - 1 {
-            result.push_str(join);
-        }
+    let mut line = LineBuilder::new(&mut result, left, join, right);
+    for &width in column_widths {
+        line.run(body, width);
     }
-
-    result.push_str(right);
+    line.finish();
 
     result
 }
@@ -182,7 +1264,7 @@ pub fn draw_border_line(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::TableConfig;
+    use crate::types::{ColumnConfig, TableConfig};
 
     #[test]
     fn test_draw_row() {
@@ -231,6 +1313,505 @@ mod tests {
         assert!(result.contains("└"));
     }
 
+    #[test]
+    fn test_draw_table_to_writes_what_draw_table_returns() {
+        let rows = vec![
+            vec!["a".to_string(), "wrapped text".to_string()],
+            vec!["c".to_string(), "d".to_string()],
+        ];
+        let wrapped = TableConfig {
+            columns: vec![
+                ColumnConfig::default(),
+                ColumnConfig {
+                    width: 5,
+                    ..Default::default()
+                },
+            ],
+            row_striping: Some("7".to_string()),
+            ..Default::default()
+        };
+        let indented = TableConfig {
+            table_indent: 2,
+            ..Default::default()
+        };
+
+        for config in [TableConfig::default(), wrapped, indented] {
+            let mut output = String::from("> ");
+            draw_table_to(&rows, &config, &mut output).unwrap();
+            assert_eq!(output, format!("> {}", draw_table(&rows, &config)));
+        }
+
+        struct Full;
+        impl std::io::Write for Full {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::StorageFull.into())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        let mut writer = IoWriter::new(Full);
+        assert!(draw_table_to(&rows, &TableConfig::default(), &mut writer).is_err());
+        assert_eq!(
+            writer.take_error().map(|error| error.kind()),
+            Some(std::io::ErrorKind::StorageFull)
+        );
+    }
+
+    #[test]
+    fn test_row_striping_paints_every_other_body_row() {
+        let rows: Vec<Row> = [
+            ["h"; 2],
+            ["a", "\u{1b}[41mx\u{1b}[0m"],
+            ["b", "y"],
+            ["c", "z"],
+        ]
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect();
+        let config = TableConfig {
+            header: Some(Box::default()),
+            row_striping: Some("48;5;236".to_string()),
+            draw_horizontal_line: |index, size| index == 0 || index == size,
+            ..Default::default()
+        };
+        let result = draw_table(&rows, &config);
+        let lines: Vec<&str> = result.lines().collect();
+
+        assert_eq!(lines[1], "│ h │ h │");
+        assert_eq!(lines[3], "│ a │ \u{1b}[41mx\u{1b}[0m │");
+        assert_eq!(
+            lines[4], "\u{1b}[48;5;236m│ b │ y │\u{1b}[0m",
+            "second body row is striped"
+        );
+        assert_eq!(lines[5], "│ c │ z │");
+
+        // The stripe comes back after resets inside cells, but a cell's own
+        // background wins until it is reset.
+        let mut out = String::from("│ ");
+        let start = out.len();
+        out.push_str("\u{1b}[31mx\u{1b}[0m \u{1b}[38;5;0;41my\u{1b}[m");
+        stripe_line(&mut out, start, "100");
+        assert_eq!(
+            out,
+            "│ \u{1b}[100m\u{1b}[31mx\u{1b}[0m\u{1b}[100m \u{1b}[38;5;0;41my\u{1b}[m\u{1b}[100m\u{1b}[0m"
+        );
+        assert!(!clears_background("48;5;0"));
+        assert!(clears_background("1;49"));
+    }
+
+    #[test]
+    fn test_draw_table_with_row_annotation() {
+        let rows = vec![
+            vec!["check".to_string(), "FAIL".to_string()],
+            vec!["other".to_string(), "OK".to_string()],
+        ];
+
+        let config = TableConfig {
+            row_annotations: vec![RowAnnotation {
+                row: 0,
+                text: "expected 3 but got 4".to_string(),
+                indent: None,
+                dim: Some(false),
+            }],
+            ..Default::default()
+        };
+        let result = draw_table(&rows, &config);
+        let lines: Vec<&str> = result.lines().collect();
+
+        assert_eq!(lines[2], "│  expected 3  │");
+        assert_eq!(lines[3], "│  but got 4   │");
+        assert!(lines[4].starts_with("├"));
+
+        let widths: Vec<usize> = lines.iter().map(|l| calculate_display_width(l)).collect();
+        assert!(widths.iter().all(|&w| w == widths[0]));
+    }
+
+    #[test]
+    fn test_paginate_table_repeats_header_and_keeps_widths() {
+        let rows: Vec<Row> = ["Name", "a", "bbbbbb", "c", "Total"]
+            .iter()
+            .map(|name| vec![name.to_string()])
+            .collect();
+        let config = TableConfig {
+            header: Some(Box::default()),
+            footer: Some(Box::default()),
+            page_captions: true,
+            row_annotations: vec![RowAnnotation {
+                row: 3,
+                text: "note".to_string(),
+                indent: None,
+                dim: Some(false),
+            }],
+            ..Default::default()
+        };
+
+        let pages = paginate_table(&rows, &config, 2).unwrap();
+        assert_eq!(
+            pages,
+            vec![
+                [
+                    "┌────────┐",
+                    "│ Name   │",
+                    "├────────┤",
+                    "│ a      │",
+                    "├────────┤",
+                    "│ bbbbbb │",
+                    "└────────┘",
+                    "Page 1 of 2",
+                ]
+                .join("\n"),
+                [
+                    "┌────────┐",
+                    "│ Name   │",
+                    "├────────┤",
+                    "│ c      │",
+                    "│  note  │",
+                    "├────────┤",
+                    "│ Total  │",
+                    "└────────┘",
+                    "Page 2 of 2",
+                ]
+                .join("\n"),
+            ]
+        );
+
+        let single = paginate_table(&rows, &TableConfig::default(), 10).unwrap();
+        assert_eq!(single, vec![draw_table(&rows, &TableConfig::default())]);
+
+        // The totals row is added below the data, as `table` adds it.
+        let costs: Vec<Row> = [["Item", "Cost"], ["a", "3"], ["b", "4"]]
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        let totalled = TableConfig {
+            header: Some(Box::default()),
+            footer: Some(Box::default()),
+            aggregations: vec!["1:sum".parse().unwrap()],
+            ..Default::default()
+        };
+        let pages = paginate_table(&costs, &totalled, 1).unwrap();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(
+            pages[1].lines().skip(3).collect::<Vec<_>>(),
+            [
+                "│ b    │ 4    │",
+                "├──────┼──────┤",
+                "│      │ 7    │",
+                "└──────┴──────┘"
+            ]
+        );
+        assert!(
+            paginate_table(
+                &costs,
+                &TableConfig {
+                    columns: vec![ColumnConfig::default(); 3],
+                    column_order: Some(vec![5]),
+                    ..Default::default()
+                },
+                1
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_paginate_table_splits_rows_taller_than_the_page() {
+        let rows: Vec<Row> = [
+            ["Step", "Log"],
+            ["1", "ok"],
+            ["2", "one\ntwo\nthree\nfour\nfive"],
+        ]
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect();
+        let config = TableConfig {
+            header: Some(Box::default()),
+            page_height: Some(8),
+            column_default: ColumnConfig {
+                ellipsis: "…".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let pages = paginate_table(&rows, &config, 10).unwrap();
+        assert_eq!(
+            pages,
+            vec![
+                [
+                    "┌──────┬───────┐",
+                    "│ Step │ Log   │",
+                    "├──────┼───────┤",
+                    "│ 1    │ ok    │",
+                    "├──────┼───────┤",
+                    "│ 2    │ one   │",
+                    "│      │ …     │",
+                    "└──────┴───────┘",
+                ]
+                .join("\n"),
+                [
+                    "┌──────┬───────┐",
+                    "│ Step │ Log   │",
+                    "├──────┼───────┤",
+                    "│      │ two   │",
+                    "│      │ three │",
+                    "│      │ four  │",
+                    "│      │ five  │",
+                    "└──────┴───────┘",
+                ]
+                .join("\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_paginate_table_fills_pages_to_the_page_height() {
+        let mut rows: Vec<Row> = vec![vec!["Id".to_string()]];
+        rows.extend((0..5_000).map(|id| vec![id.to_string()]));
+        rows[7][0] = "tall\nrow".to_string();
+        let config = TableConfig {
+            header: Some(Box::default()),
+            page_height: Some(1_001),
+            ..Default::default()
+        };
+
+        let pages = paginate_table(&rows, &config, usize::MAX).unwrap();
+        // A border, the header and its line, then 499 rows and their lines,
+        // or 498 where one of them is two lines tall.
+        assert_eq!(pages[0].lines().count(), 1_000);
+        assert_eq!(pages[1].lines().count(), 1_001);
+        assert!(pages.iter().all(|page| page.lines().count() <= 1_001));
+        let body_lines: usize = pages
+            .iter()
+            .map(|page| page.lines().filter(|line| line.starts_with('│')).count() - 1)
+            .sum();
+        assert_eq!(body_lines, 5_001);
+    }
+
+    #[test]
+    fn test_row_overrides_force_omit_and_restyle_lines() {
+        use crate::types::RowOverride;
+
+        let rows: Vec<Row> = ["Item", "a", "b", "c", "Total"]
+            .iter()
+            .map(|name| vec![name.to_string()])
+            .collect();
+        let config = TableConfig {
+            header: Some(Box::default()),
+            footer: Some(Box::default()),
+            draw_horizontal_line: |line, count| line == 0 || line == count,
+            row_overrides: [
+                (
+                    1,
+                    RowOverride {
+                        separator_before: Some(false),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    2,
+                    RowOverride {
+                        separator_after: Some(true),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    4,
+                    RowOverride {
+                        horizontal_before: Some("═".to_string()),
+                        ..Default::default()
+                    },
+                ),
+            ]
+            .into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            draw_table(&rows, &config),
+            [
+                "┌───────┐",
+                "│ Item  │",
+                "│ a     │",
+                "│ b     │",
+                "├───────┤",
+                "│ c     │",
+                "├═══════┤",
+                "│ Total │",
+                "└───────┘",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_small_table_fast_path_matches_full_pipeline() {
+        let rows: Vec<Row> = [
+            ["ID", "Name", ""],
+            ["1", "web-01", "up"],
+            ["22", "db", "a b"],
+        ]
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect();
+
+        let columns = vec![
+            ColumnConfig {
+                alignment: Alignment::Right,
+                padding_left: 2,
+                ..Default::default()
+            };
+            3
+        ];
+        let header = TableConfig {
+            columns: columns
+                .iter()
+                .map(|column| ColumnConfig {
+                    alignment: Alignment::Center,
+                    ..column.clone()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let footer = TableConfig {
+            border: crate::get_border_characters("ramac").unwrap(),
+            columns: columns.clone(),
+            ..Default::default()
+        };
+        let mut config = TableConfig {
+            header: Some(Box::new(header)),
+            footer: Some(Box::new(footer)),
+            columns,
+            ..Default::default()
+        };
+        config.draw_horizontal_line = |line, row_count| line == 0 || line == row_count;
+
+        assert!(is_small_plain_table(&rows, &config));
+        let mut full = String::new();
+        draw_full_table(&rows, &config, &mut full).unwrap();
+        assert_eq!(draw_table_body(&rows, &config), full);
+
+        let mut colored = rows.clone();
+        colored[1][1] = "\u{1b}[31mweb-01\u{1b}[0m".to_string();
+        assert!(!is_small_plain_table(&colored, &config));
+        let mut padded = rows.clone();
+        padded[2][2] = "a b ".to_string();
+        assert!(!is_small_plain_table(&padded, &config));
+        let big: Vec<Row> = vec![rows[1].clone(); 7];
+        assert!(!is_small_plain_table(&big, &config));
+
+        for (min_width, max_width) in [(12, 0), (0, 4)] {
+            let mut bounded = config.clone();
+            bounded.columns[1].min_width = min_width;
+            bounded.columns[1].max_width = max_width;
+            let mut full = String::new();
+            draw_full_table(&rows, &bounded, &mut full).unwrap();
+            assert_eq!(draw_table_body(&rows, &bounded), full);
+        }
+
+        // Every column setting on its own, in the body or the header.
+        let variations: Vec<fn(&mut ColumnConfig)> = vec![
+            |column| column.alignment = Alignment::Justify,
+            |column| column.alignment = Alignment::Char('.'),
+            |column| column.vertical_alignment = crate::types::VerticalAlignment::Bottom,
+            |column| column.padding_left = 0,
+            |column| column.padding_right = 3,
+            |column| column.pad_char = '·',
+            |column| column.truncate = 2,
+            |column| {
+                column.truncate = 3;
+                column.truncate_at_word = true;
+            },
+            |column| {
+                column.truncate = 3;
+                column.truncate_position = crate::types::TruncatePosition::Start;
+            },
+            |column| {
+                column.truncate = 3;
+                column.ellipsis = "~".to_string();
+            },
+            |column| column.overflow = crate::types::OverflowPolicy::TruncateOnly,
+            |column| column.wrap_word = true,
+            |column| column.word_break = Some(crate::types::WordBreakPolicy::BreakAtHyphen),
+            |column| column.width = 3,
+            |column| column.width_percent = 50,
+            |column| column.min_width = 9,
+            |column| column.max_width = 3,
+            |column| column.hidden = true,
+            |column| column.direction = crate::types::TextDirection::Rtl,
+            |column| column.bidi = true,
+            |column| column.overflow_mode = Some(crate::types::Overflow::Clip),
+        ];
+        for vary in variations {
+            for in_header in [false, true] {
+                let mut varied = config.clone();
+                let section = match &mut varied.header {
+                    Some(header) if in_header => header,
+                    _ => &mut varied,
+                };
+                vary(&mut section.columns[1]);
+                let mut full = String::new();
+                draw_full_table(&rows, &varied, &mut full).unwrap();
+                assert_eq!(
+                    draw_table_body(&rows, &varied),
+                    full,
+                    "{:?}",
+                    varied.columns[1]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_draw_row_annotation_dimmed_by_default() {
+        let annotation = RowAnnotation {
+            row: 0,
+            text: "note".to_string(),
+            indent: Some(0),
+            dim: None,
+        };
+        let lines = draw_row_annotation(&annotation, &[6], &BorderConfig::default());
+        assert_eq!(lines, vec!["│\u{1b}[2mnote\u{1b}[0m  │"]);
+    }
+
+    #[test]
+    fn test_position_table_block() {
+        let rows = vec![vec!["a".to_string()]];
+
+        let indented = draw_table(
+            &rows,
+            &TableConfig {
+                table_indent: 2,
+                ..Default::default()
+            },
+        );
+        assert!(indented.lines().all(|line| line.starts_with("  ┌")
+            || line.starts_with("  │")
+            || line.starts_with("  └")));
+
+        let centered = draw_table(
+            &rows,
+            &TableConfig {
+                table_alignment: TableAlignment::Center,
+                terminal_width: Some(15),
+                ..Default::default()
+            },
+        );
+        assert_eq!(centered.lines().next(), Some("     ┌───┐"));
+
+        let right = draw_table(
+            &rows,
+            &TableConfig {
+                table_alignment: TableAlignment::Right,
+                table_indent: 1,
+                terminal_width: Some(10),
+                ..Default::default()
+            },
+        );
+        assert_eq!(right.lines().last(), Some("     └───┘"));
+    }
+
     #[test]
     fn test_draw_table_empty() {
         let rows: Vec<Vec<String>> = vec![];
@@ -239,4 +1820,46 @@ mod tests {
 
         assert_eq!(result, "");
     }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_layout_matches_sequential() {
+        let rows: Vec<Row> = (0..300)
+            .map(|row| {
+                let middle = if row % 7 == 0 {
+                    "a cell long enough to wrap".to_string()
+                } else {
+                    "\u{1b}[31mred\u{1b}[0m".to_string()
+                };
+                vec![format!("row {row}"), middle, (row * 13).to_string()]
+            })
+            .collect();
+        let config = TableConfig {
+            header: Some(Box::default()),
+            footer: Some(Box::default()),
+            row_striping: Some("48;5;236".to_string()),
+            row_annotations: vec![crate::types::RowAnnotation {
+                row: 5,
+                text: "note".to_string(),
+                indent: None,
+                dim: None,
+            }],
+            columns: vec![
+                ColumnConfig::default(),
+                ColumnConfig {
+                    width: 12,
+                    vertical_alignment: crate::types::VerticalAlignment::Middle,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let draw = |parallel_rows| {
+            let mut output = String::new();
+            draw_full_table_from(&rows, &config, &mut output, parallel_rows).unwrap();
+            output
+        };
+
+        assert_eq!(draw(0), draw(usize::MAX));
+    }
 }
@@ -0,0 +1,408 @@
+use crate::core::calculator::{column_width, has_percent_widths, row_config};
+use crate::core::processor::{layout_row, truncate_cell};
+use crate::core::renderer::{
+    BorderType, draw_border_line, draw_table, outer_borders, position_table_block, row_stripe,
+    separator_after, with_horizontal,
+};
+use crate::features::terminal_profile::{TerminalProfile, terminal_profile};
+use crate::features::title::draw_title_and_caption;
+use crate::types::{BorderConfig, Row, TableConfig, TableResult, TableUserConfig};
+use crate::utils::ansi::calculate_display_width;
+
+/// Draws the same table shape frame after frame, as a dashboard does, keeping
+/// what the last frame computed: each cell's measured width, each row's drawn
+/// lines and the border lines. A frame re-measures only the cells that
+/// changed and redraws only their rows, unless the column widths move, and
+/// starts over when the config does.
+///
+/// Spanning cells, row overrides and annotations, and widths fitted to the
+/// table (`max_table_width`, `exact_table_width`, `auto_layout` and percent
+/// widths) are drawn by `draw_table` every frame.
+#[derive(Debug, Default)]
+pub struct RenderSession {
+    // The config and terminal profile the kept frame was drawn for.
+    drawn_for: Option<(TableConfig, TerminalProfile)>,
+    cells: Vec<Vec<MeasuredCell>>,
+    changed: Vec<bool>,
+    column_widths: Vec<usize>,
+    rows: Vec<Option<DrawnRow>>,
+    borders: Vec<(BorderType, String)>,
+    stats: SessionStats,
+}
+
+/// What the last `RenderSession` frame had to redo.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionStats {
+    /// Cells truncated and measured because they changed.
+    pub cells_measured: usize,
+    /// Rows wrapped, aligned and drawn again.
+    pub rows_drawn: usize,
+    /// Rows whose lines were kept from the frame before.
+    pub rows_reused: usize,
+}
+
+#[derive(Debug)]
+struct MeasuredCell {
+    source: String,
+    truncated: String,
+    width: usize,
+}
+
+impl AsRef<str> for MeasuredCell {
+    fn as_ref(&self) -> &str {
+        &self.truncated
+    }
+}
+
+#[derive(Debug)]
+struct DrawnRow {
+    section: Section,
+    striped: bool,
+    lines: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Header,
+    Body,
+    Footer,
+}
+
+impl RenderSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draws `rows` as `draw_table` does, reusing what the last frame drew
+    /// for the rows that did not change.
+    pub fn draw(&mut self, rows: &[Row], config: &TableConfig) -> String {
+        self.stats = SessionStats::default();
+        if rows.is_empty() {
+            return String::new();
+        }
+        if !reuses_layout(config) {
+            self.clear();
+            self.stats.rows_drawn = rows.len();
+            return draw_table(rows, config);
+        }
+
+        let profile = terminal_profile();
+        if self
+            .drawn_for
+            .as_ref()
+            .is_none_or(|(drawn, drawn_profile)| drawn != config || *drawn_profile != profile)
+        {
+            self.clear();
+            self.drawn_for = Some((config.clone(), profile));
+        }
+
+        self.measure(rows, config);
+        let column_widths = self.output_column_widths(config);
+        if column_widths != self.column_widths {
+            self.column_widths = column_widths;
+            self.rows.clear();
+            self.borders.clear();
+        }
+
+        let output = self.draw_body(config);
+        if config.title.is_none()
+            && config.caption.is_none()
+            && config.table_indent == 0
+            && config.table_alignment == crate::types::TableAlignment::Left
+        {
+            return output;
+        }
+        position_table_block(draw_title_and_caption(output, config, rows.len()), config)
+    }
+
+    /// `table` for `data`, drawn through the session.
+    pub fn table(
+        &mut self,
+        data: &[Row],
+        user_config: Option<&TableUserConfig>,
+    ) -> TableResult<String> {
+        if data.is_empty() {
+            return Ok(String::new());
+        }
+        let (data, config) = crate::prepare_table(data, user_config)?;
+        if config.column_summaries {
+            self.clear();
+            return Ok(crate::finish_output(
+                crate::render_table(&data, &config),
+                &config,
+            ));
+        }
+        Ok(crate::finish_output(self.draw(&data, &config), &config))
+    }
+
+    /// What the last frame had to redo.
+    pub fn stats(&self) -> SessionStats {
+        self.stats
+    }
+
+    /// Forgets everything, e.g. after a terminal resize that changed nothing
+    /// the config says.
+    pub fn clear(&mut self) {
+        self.drawn_for = None;
+        self.cells.clear();
+        self.column_widths.clear();
+        self.rows.clear();
+        self.borders.clear();
+    }
+
+    // Truncates and measures the cells that differ from the last frame's,
+    // noting which rows changed.
+    fn measure(&mut self, rows: &[Row], config: &TableConfig) {
+        self.cells.truncate(rows.len());
+        self.changed.clear();
+
+        for (row_idx, row) in rows.iter().enumerate() {
+            if row_idx == self.cells.len() {
+                self.cells.push(Vec::new());
+            }
+            let cells = &mut self.cells[row_idx];
+            cells.truncate(row.len());
+
+            let mut changed = false;
+            for (col_idx, cell) in row.iter().enumerate() {
+                if cells
+                    .get(col_idx)
+                    .is_some_and(|cached| cached.source == *cell)
+                {
+                    continue;
+                }
+                let column = config
+                    .columns
+                    .get(col_idx)
+                    .unwrap_or(&config.column_default);
+                let truncated = truncate_cell(cell, column);
+                let measured = MeasuredCell {
+                    source: cell.clone(),
+                    width: truncated
+                        .lines()
+                        .map(calculate_display_width)
+                        .max()
+                        .unwrap_or(0),
+                    truncated,
+                };
+                if col_idx < cells.len() {
+                    cells[col_idx] = measured;
+                } else {
+                    cells.push(measured);
+                }
+                changed = true;
+                self.stats.cells_measured += 1;
+            }
+            self.changed.push(changed);
+        }
+    }
+
+    fn output_column_widths(&self, config: &TableConfig) -> Vec<usize> {
+        let mut max_widths = vec![0; self.cells[0].len()];
+        for row in &self.cells {
+            for (max_width, cell) in max_widths.iter_mut().zip(row) {
+                *max_width = (*max_width).max(cell.width);
+            }
+        }
+        max_widths
+            .into_iter()
+            .enumerate()
+            .map(|(col_idx, max_width)| {
+                let column = config
+                    .columns
+                    .get(col_idx)
+                    .unwrap_or(&config.column_default);
+                column_width(column, max_width)
+            })
+            .collect()
+    }
+
+    fn draw_body(&mut self, config: &TableConfig) -> String {
+        let row_count = self.cells.len();
+        let body_rows = config.body_rows(row_count);
+        let header_config = config.header.as_deref().unwrap_or(config);
+        let footer_config = config.footer.as_deref().unwrap_or(config);
+        let (draw_top, draw_bottom) = outer_borders(config, row_count);
+        self.rows.resize_with(row_count, || None);
+
+        let mut output = String::new();
+        if draw_top {
+            output.push_str(self.border_line(&header_config.border, BorderType::Top));
+            output.push('\n');
+        }
+
+        for row_idx in 0..row_count {
+            let section = if row_idx < body_rows.start {
+                Section::Header
+            } else if row_idx >= body_rows.end {
+                Section::Footer
+            } else {
+                Section::Body
+            };
+            let stripe = row_stripe(config, row_idx, row_count);
+
+            let reusable = !self.changed[row_idx]
+                && self.rows[row_idx]
+                    .as_ref()
+                    .is_some_and(|row| row.section == section && row.striped == stripe.is_some());
+            if reusable {
+                self.stats.rows_reused += 1;
+            } else {
+                self.rows[row_idx] = Some(DrawnRow {
+                    section,
+                    striped: stripe.is_some(),
+                    lines: layout_row(
+                        &self.cells[row_idx],
+                        &self.column_widths,
+                        row_config(config, row_idx, row_count),
+                        stripe,
+                    ),
+                });
+                self.stats.rows_drawn += 1;
+            }
+            if let Some(row) = &self.rows[row_idx] {
+                output.push_str(&row.lines);
+            }
+
+            if let Some((border, border_type)) = separator_after(config, row_idx, row_count) {
+                output.push_str(self.border_line(border, border_type));
+                output.push('\n');
+            }
+        }
+
+        if draw_bottom {
+            output.push_str(self.border_line(&footer_config.border, BorderType::Bottom));
+        }
+        output
+    }
+
+    // Each kind of line is always drawn from the same section's border, so
+    // the kind alone keys it while the widths stay the same.
+    fn border_line(&mut self, border: &BorderConfig, border_type: BorderType) -> &str {
+        let idx = match self
+            .borders
+            .iter()
+            .position(|(kind, _)| *kind == border_type)
+        {
+            Some(idx) => idx,
+            None => {
+                let line = draw_border_line(
+                    &self.column_widths,
+                    &with_horizontal(border, border_type, None),
+                    border_type,
+                );
+                self.borders.push((border_type, line));
+                self.borders.len() - 1
+            }
+        };
+        &self.borders[idx].1
+    }
+}
+
+// Layouts fitted to the whole table, or drawn from more than the cells of a
+// row, are left to `draw_table`.
+fn reuses_layout(config: &TableConfig) -> bool {
+    config.spanning_cells.is_empty()
+        && config.row_overrides.is_empty()
+        && config.row_annotations.is_empty()
+        && config.max_table_width.is_none()
+        && config.exact_table_width.is_none()
+        && !config.auto_layout
+        && !has_percent_widths(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(tick: usize) -> Vec<Row> {
+        let mut rows = vec![vec!["Service".to_string(), "Requests".to_string()]];
+        for (idx, name) in ["api", "web", "worker", "cron"].iter().enumerate() {
+            let count = if idx == 1 { tick * 7 } else { idx * 100 };
+            rows.push(vec![name.to_string(), count.to_string()]);
+        }
+        rows
+    }
+
+    #[test]
+    fn test_session_matches_draw_table_and_redraws_changed_rows() {
+        let config = TableConfig {
+            header: Some(Box::default()),
+            row_striping: Some("48;5;236".to_string()),
+            ..Default::default()
+        };
+        let mut session = RenderSession::new();
+
+        assert_eq!(
+            session.draw(&frame(1), &config),
+            draw_table(&frame(1), &config)
+        );
+        assert_eq!(session.stats().rows_drawn, 5);
+
+        // Only the "web" row changes, and its count stays two digits wide.
+        assert_eq!(
+            session.draw(&frame(2), &config),
+            draw_table(&frame(2), &config)
+        );
+        assert_eq!(
+            session.stats(),
+            SessionStats {
+                cells_measured: 1,
+                rows_drawn: 1,
+                rows_reused: 4,
+            }
+        );
+
+        // A wider count moves the column, so every row is drawn again.
+        assert_eq!(
+            session.draw(&frame(100_000_000), &config),
+            draw_table(&frame(100_000_000), &config)
+        );
+        assert_eq!(session.stats().rows_drawn, 5);
+
+        // Dropping the last row leaves the others as they were, but a new
+        // config starts over.
+        let mut rows = frame(100_000_000);
+        rows.pop();
+        assert_eq!(session.draw(&rows, &config), draw_table(&rows, &config));
+        assert_eq!(session.stats().rows_reused, 4);
+        let titled = TableConfig {
+            title: Some(crate::types::TableLabel {
+                text: "Traffic".to_string(),
+                alignment: None,
+                placement: None,
+            }),
+            ..config
+        };
+        assert_eq!(session.draw(&rows, &titled), draw_table(&rows, &titled));
+        assert_eq!(session.stats().rows_drawn, 4);
+    }
+
+    #[test]
+    fn test_session_starts_over_for_any_config_change() {
+        let config = TableConfig::default();
+        let mut session = RenderSession::new();
+        session.draw(&frame(1), &config);
+
+        session.draw(&frame(1), &config.clone());
+        assert_eq!(session.stats().rows_reused, 5);
+
+        let mut padded = config.clone();
+        padded.column_default.padding_left = 2;
+        assert_eq!(
+            session.draw(&frame(1), &padded),
+            draw_table(&frame(1), &padded)
+        );
+        assert_eq!(session.stats().rows_drawn, 5);
+
+        let ruled = TableConfig {
+            draw_horizontal_line: |_, _| true,
+            ..padded
+        };
+        assert_eq!(
+            session.draw(&frame(1), &ruled),
+            draw_table(&frame(1), &ruled)
+        );
+        assert_eq!(session.stats().rows_drawn, 5);
+    }
+}
@@ -1,6 +1,307 @@
-use crate::types::{ColumnConfig, Row, SpanningCellConfig, TableConfig, TableError};
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::core::calculator::{calculate_table_width, wrap_column_cell};
+use crate::core::processor::truncate_cell;
+use crate::features::column_order::visible_columns;
+use crate::features::hygiene::lint_rows;
+use crate::types::{
+    CellCoordinates, ColumnConfig, ColumnUserConfig, DataHygiene, OverflowPolicy, RedundantColumns,
+    Row, Severity, SpanningCellConfig, TableConfig, TableError, TableUserConfig, WordBreakPolicy,
+};
+use crate::utils::ansi::calculate_display_width;
 use crate::utils::formatting::validate_table_data;
 
+/// One finding of `validate_table`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssue {
+    /// What was found, as a stable kebab-case name: `TableError::code` for
+    /// errors, otherwise `unclean-data`, `column-overflow` or `truncated`.
+    pub code: &'static str,
+    pub severity: Severity,
+    /// The data cell the issue is in, when it concerns a single cell.
+    pub cell: Option<CellCoordinates>,
+    /// The config key the issue is at, e.g. `columns[0].alignment`.
+    pub path: Option<String>,
+    /// The valid name closest to a misspelled key or value.
+    pub suggestion: Option<String>,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    /// `error` as an issue of `Severity::Error`, located when it names a cell.
+    pub fn from_error(error: &TableError) -> Self {
+        let cell = match error {
+            TableError::UncleanData(issue) => Some(CellCoordinates {
+                col: issue.column,
+                row: issue.row,
+            }),
+            _ => None,
+        };
+
+        ValidationIssue {
+            code: error.code(),
+            severity: Severity::Error,
+            cell,
+            path: None,
+            suggestion: None,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.cell {
+            Some(cell) => write!(
+                f,
+                "{} at row {}, column {}: {}",
+                self.severity, cell.row, cell.col, self.message
+            ),
+            None => write!(f, "{}: {}", self.severity, self.message),
+        }
+    }
+}
+
+/// Everything `validate_table` found, in the order it was found.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct ValidationResult {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationResult {
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// The most serious severity found, or `None` when nothing was.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.issues.iter().map(|issue| issue.severity).max()
+    }
+
+    /// Whether any issue is at or above `threshold`.
+    pub fn fails(&self, threshold: Severity) -> bool {
+        self.max_severity()
+            .is_some_and(|severity| severity >= threshold)
+    }
+
+    /// The issues as pretty-printed JSON, for CI pipelines:
+    /// `{"issues": [{"code": "truncated", "severity": "info",
+    /// "cell": {"col": 1, "row": 0}, "path": null, "suggestion": null,
+    /// "message": "content is truncated"}]}`, with `cell` null for issues not
+    /// about one cell and `path` null for issues not about one config key.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> crate::types::TableResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl fmt::Display for ValidationResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, issue) in self.issues.iter().enumerate() {
+            if index > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{issue}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs every check on `config` and `rows`, collecting issues instead of
+/// stopping at the first:
+///
+/// - an error for whatever `validate_config` or
+///   `validate_table_data_with_config` rejects, after which nothing else is
+///   checked;
+/// - a warning for every `lint_rows` issue, unless `data_hygiene` rejects or
+///   cleans them, and for every cell with a word that overflows its fixed
+///   width column under `WordBreakPolicy::NoBreakOverflow`;
+/// - an info for every cell that `truncate` cuts.
+///
+/// Cells are given as data rows and columns, before sorting, filtering or
+/// column selection.
+pub fn validate_table(rows: &[Row], config: &TableConfig) -> ValidationResult {
+    let mut result = ValidationResult::default();
+
+    if let Err(error) =
+        validate_config(config).and_then(|()| validate_table_data_with_config(rows, config))
+    {
+        result.issues.push(ValidationIssue::from_error(&error));
+        return result;
+    }
+
+    if config.data_hygiene == DataHygiene::Allow {
+        result
+            .issues
+            .extend(lint_rows(rows).into_iter().map(|issue| ValidationIssue {
+                code: "unclean-data",
+                severity: Severity::Warning,
+                cell: Some(CellCoordinates {
+                    col: issue.column,
+                    row: issue.row,
+                }),
+                path: None,
+                suggestion: None,
+                message: format!("{} on line {}", issue.kind, issue.line),
+            }));
+    }
+
+    for (row, cells) in rows.iter().enumerate() {
+        for (col, cell) in cells.iter().enumerate() {
+            let column_config = config.columns.get(col).unwrap_or(&config.column_default);
+            let mut issue = |code, severity, message: &str| {
+                result.issues.push(ValidationIssue {
+                    code,
+                    severity,
+                    cell: Some(CellCoordinates { col, row }),
+                    path: None,
+                    suggestion: None,
+                    message: message.to_string(),
+                })
+            };
+
+            let truncated = truncate_cell(cell, column_config);
+            if truncated != *cell {
+                issue("truncated", Severity::Info, "content is truncated");
+            }
+
+            let content_width = column_config
+                .width
+                .saturating_sub(column_config.padding_left + column_config.padding_right);
+            if content_width > 0
+                && column_config.word_break_policy() == WordBreakPolicy::NoBreakOverflow
+                && wrap_column_cell(&truncated, content_width, column_config)
+                    .iter()
+                    .any(|line| calculate_display_width(line) > content_width)
+            {
+                issue(
+                    "column-overflow",
+                    Severity::Warning,
+                    "a word is wider than the column and overflows its border",
+                );
+            }
+        }
+    }
+
+    result
+}
+
+/// Checks the config file `content` as `table validate` does, collecting
+/// every issue with the key path it is at and, for a misspelling, the valid
+/// name closest to it:
+///
+/// - an `invalid-config` error when the config does not parse, after which
+///   nothing else is checked;
+/// - an `unknown-key` warning for every key the config types do not know, or
+///   an error when `strict`;
+/// - whatever `validate_table` finds in the config merged with the defaults.
+#[cfg(feature = "config-files")]
+pub fn validate_user_config_verbose(
+    content: &str,
+    format: crate::utils::config_file::ConfigFormat,
+    strict: bool,
+) -> ValidationResult {
+    let (config, warnings) = match crate::utils::config_file::parse_config_detailed(content, format)
+    {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            return ValidationResult {
+                issues: vec![ValidationIssue {
+                    code: "invalid-config",
+                    severity: Severity::Error,
+                    cell: None,
+                    path: error.path.clone(),
+                    suggestion: error.suggestion.clone(),
+                    message: error.to_string(),
+                }],
+            };
+        }
+    };
+
+    let mut result = ValidationResult {
+        issues: warnings
+            .iter()
+            .map(|warning| ValidationIssue {
+                code: "unknown-key",
+                severity: if strict {
+                    Severity::Error
+                } else {
+                    Severity::Warning
+                },
+                cell: None,
+                path: Some(if warning.path.is_empty() {
+                    warning.key.clone()
+                } else {
+                    format!("{}.{}", warning.path, warning.key)
+                }),
+                suggestion: warning.suggestion.clone(),
+                message: warning.to_string(),
+            })
+            .collect(),
+    };
+    if let Err(error) = crate::features::themes::check_theme(&config) {
+        result.issues.push(ValidationIssue::from_error(&error));
+    }
+    for (path, error) in overflow_mode_conflicts(&config, "") {
+        result.issues.push(ValidationIssue {
+            path: Some(format!("{path}.overflow_mode")),
+            ..ValidationIssue::from_error(&error)
+        });
+    }
+    let full_config = config.merge_with_default(&TableConfig::default());
+    result
+        .issues
+        .extend(validate_table(&[], &full_config).issues);
+    result
+}
+
+/// Fails for the first column, in the body, header or footer settings, whose
+/// `overflow_mode` is set along with a setting it contradicts, such as
+/// `truncate` or `overflow`.
+pub fn validate_overflow_modes(config: &TableUserConfig) -> Result<(), TableError> {
+    match overflow_mode_conflicts(config, "").into_iter().next() {
+        Some((_, error)) => Err(error),
+        None => Ok(()),
+    }
+}
+
+// Every column whose `overflow_mode` conflicts, with its key path, e.g.
+// `header.columns[1]`.
+fn overflow_mode_conflicts(config: &TableUserConfig, prefix: &str) -> Vec<(String, TableError)> {
+    let columns = config
+        .column_default
+        .iter()
+        .map(|column| (format!("{prefix}column_default"), column))
+        .chain(
+            config
+                .columns
+                .iter()
+                .flatten()
+                .enumerate()
+                .map(|(idx, column)| (format!("{prefix}columns[{idx}]"), column)),
+        );
+    let mut conflicts: Vec<(String, TableError)> = columns
+        .filter_map(|(path, column): (String, &ColumnUserConfig)| {
+            let key = column.overflow_mode_conflict()?;
+            let error = TableError::InvalidConfig(format!(
+                "`{path}.overflow_mode` cannot be combined with `{key}`"
+            ));
+            Some((path, error))
+        })
+        .collect();
+    for (name, section) in [("header", &config.header), ("footer", &config.footer)] {
+        if let Some(section) = section {
+            conflicts.extend(overflow_mode_conflicts(
+                section,
+                &format!("{prefix}{name}."),
+            ));
+        }
+    }
+    conflicts
+}
+
 pub fn validate_config(config: &TableConfig) -> Result<(), TableError> {
     validate_column_configs(&config.columns)?;
     validate_column_config(&config.column_default)?;
@@ -22,12 +323,45 @@ pub fn validate_column_config(config: &ColumnConfig) -> Result<(), TableError> {
         ));
     }
 
-    if config.truncate > 0 && config.truncate < 3 {
+    if config.width_percent > 100 {
+        return Err(TableError::InvalidConfig(format!(
+            "Column width percentage must be at most 100, got {}",
+            config.width_percent
+        )));
+    }
+
+    if config.max_width > 0 && config.max_width < config.padding_left + config.padding_right {
         return Err(TableError::InvalidConfig(
-            "Truncate width must be at least 3 characters".to_string(),
+            "Column max width must be greater than padding".to_string(),
         ));
     }
 
+    if config.max_width > 0 && config.min_width > config.max_width {
+        return Err(TableError::InvalidConfig(format!(
+            "Column min width {} is greater than max width {}",
+            config.min_width, config.max_width
+        )));
+    }
+
+    if config.pad_char.is_control()
+        || calculate_display_width(config.pad_char.encode_utf8(&mut [0; 4])) != 1
+    {
+        return Err(TableError::InvalidConfig(format!(
+            "Column pad_char must be one column wide, got {:?}",
+            config.pad_char
+        )));
+    }
+
+    let ellipsis_width = calculate_display_width(&config.ellipsis);
+    if config.truncate > 0
+        && config.truncate < ellipsis_width
+        && config.overflow != OverflowPolicy::WrapThenTruncateLines
+    {
+        return Err(TableError::InvalidConfig(format!(
+            "Truncate width must be at least {ellipsis_width} characters to fit the ellipsis"
+        )));
+    }
+
     Ok(())
 }
 
@@ -98,17 +432,8 @@ pub fn validate_table_data_with_config(
     let column_count = rows[0].len();
 
     for spanning_cell in &config.spanning_cells {
-        if spanning_cell.col >= column_count {
-            return Err(TableError::InvalidConfig(
-                "Spanning cell column index out of bounds".to_string(),
-            ));
-        }
-
-        if spanning_cell.row >= rows.len() {
-            return Err(TableError::InvalidConfig(
-                "Spanning cell row index out of bounds".to_string(),
-            ));
-        }
+        check_bounds("Spanning cell column", [spanning_cell.col], column_count)?;
+        check_bounds("Spanning cell row", [spanning_cell.row], rows.len())?;
 
         let end_col = spanning_cell.col + spanning_cell.col_span.unwrap_or(1);
         let end_row = spanning_cell.row + spanning_cell.row_span.unwrap_or(1);
@@ -124,17 +449,162 @@ pub fn validate_table_data_with_config(
                 "Spanning cell extends beyond table rows".to_string(),
             ));
         }
+
+        let body = config.body_rows(rows.len());
+        let last_row = end_row - 1;
+        if (spanning_cell.row < body.start) != (last_row < body.start)
+            || (spanning_cell.row < body.end) != (last_row < body.end)
+        {
+            return Err(TableError::InvalidConfig(
+                "Spanning cells cannot cross into the header or footer".to_string(),
+            ));
+        }
+
+        if config
+            .row_annotations
+            .iter()
+            .any(|annotation| (spanning_cell.row..last_row).contains(&annotation.row))
+        {
+            return Err(TableError::InvalidConfig(
+                "Row annotations cannot fall inside a row span".to_string(),
+            ));
+        }
+    }
+
+    // Computed columns are appended after the data columns, and drawn like
+    // them.
+    let drawn_columns = column_count + config.computed_columns.len();
+    check_bounds("Flex column", config.flex_column, drawn_columns)?;
+    check_bounds(
+        "Column order",
+        config.column_order.iter().flatten().copied(),
+        drawn_columns,
+    )?;
+    let visible = visible_columns(drawn_columns, config);
+    if visible.is_empty() && column_count > 0 {
+        return Err(TableError::InvalidConfig(
+            "At least one column must be visible".to_string(),
+        ));
+    }
+    if !config.spanning_cells.is_empty()
+        && (!visible.iter().copied().eq(0..drawn_columns)
+            || config.redundant_columns != RedundantColumns::Keep)
+    {
+        return Err(TableError::InvalidConfig(
+            "Spanning cells cannot be combined with hidden or reordered columns".to_string(),
+        ));
+    }
+    if let Some(exact_width) = config.exact_table_width {
+        let narrowest = calculate_table_width(&vec![1; visible.len()], config)
+            + visible
+                .iter()
+                .map(|&col| {
+                    let column = config.columns.get(col).unwrap_or(&config.column_default);
+                    column.min_width.saturating_sub(1)
+                })
+                .sum::<usize>();
+        if exact_width < narrowest {
+            return Err(TableError::InvalidConfig(format!(
+                "Exact table width {exact_width} is narrower than the {narrowest} characters \
+                 the borders and one character per column need"
+            )));
+        }
+    }
+    check_row_indices(config, rows.len())?;
+    check_bounds(
+        "Sort column",
+        config.sort.iter().map(|key| key.column),
+        column_count,
+    )?;
+    check_bounds(
+        "Format rule column",
+        config.format_rules.iter().filter_map(|rule| rule.column),
+        drawn_columns,
+    )?;
+    check_bounds(
+        "Filter column",
+        config.filters.iter().filter_map(|filter| filter.column()),
+        column_count,
+    )?;
+    check_bounds(
+        "Aggregation column",
+        config
+            .aggregations
+            .iter()
+            .map(|aggregation| aggregation.column),
+        column_count,
+    )?;
+    check_bounds(
+        "Style rule column",
+        config.style_rules.iter().filter_map(|rule| rule.column),
+        drawn_columns,
+    )?;
+    check_bounds(
+        "Computed column source",
+        config
+            .computed_columns
+            .iter()
+            .map(|computed| computed.column),
+        column_count,
+    )?;
+
+    if let Some(stripe) = &config.row_striping
+        && (stripe.is_empty() || !stripe.chars().all(|c| c.is_ascii_digit() || c == ';'))
+    {
+        return Err(TableError::InvalidConfig(format!(
+            "Row striping must be SGR parameters such as `48;5;236`, got `{stripe}`"
+        )));
+    }
+
+    if config.data_hygiene == DataHygiene::Reject
+        && let Some(issue) = lint_rows(rows).into_iter().next()
+    {
+        return Err(TableError::UncleanData(issue));
     }
 
     Ok(())
 }
 
+// Fails when a row annotation or row override names a row past `row_count`.
+pub(crate) fn check_row_indices(config: &TableConfig, row_count: usize) -> Result<(), TableError> {
+    check_bounds(
+        "Row annotation",
+        config
+            .row_annotations
+            .iter()
+            .map(|annotation| annotation.row),
+        row_count,
+    )?;
+    check_bounds(
+        "Row override",
+        config.row_overrides.keys().copied(),
+        row_count,
+    )
+}
+
+// The first index that is not below `bound`, as an `IndexOutOfBounds` error.
+fn check_bounds(
+    setting: &'static str,
+    indices: impl IntoIterator<Item = usize>,
+    bound: usize,
+) -> Result<(), TableError> {
+    match indices.into_iter().find(|&index| index >= bound) {
+        Some(index) => Err(TableError::IndexOutOfBounds {
+            setting,
+            index,
+            bound,
+        }),
+        None => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::types::{ColumnConfig, SpanningCellConfig};
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_validate_column_config() {
         let mut config = ColumnConfig::default();
         config.width = 10;
@@ -144,6 +614,16 @@ mod tests {
 
         config.width = 3;
         assert!(validate_column_config(&config).is_err());
+
+        config.width = 10;
+        for (pad_char, valid) in [('.', true), ('0', true), ('\t', false), ('日', false)] {
+            config.pad_char = pad_char;
+            assert_eq!(
+                validate_column_config(&config).is_ok(),
+                valid,
+                "{pad_char:?}"
+            );
+        }
     }
 
     #[test]
@@ -222,4 +702,312 @@ mod tests {
 
         assert!(!spanning_cells_overlap(&cell1, &cell3));
     }
+
+    #[test]
+    fn test_row_spans_stay_within_a_section() {
+        let rows = vec![vec!["a".to_string()]; 4];
+        let span = SpanningCellConfig {
+            col: 0,
+            row: 0,
+            col_span: None,
+            row_span: Some(2),
+            alignment: None,
+            vertical_alignment: None,
+            padding_left: None,
+            padding_right: None,
+            truncate: None,
+            wrap_word: None,
+        };
+        let mut config = TableConfig {
+            spanning_cells: vec![span],
+            ..Default::default()
+        };
+        assert!(validate_table_data_with_config(&rows, &config).is_ok());
+
+        config.header = Some(Box::default());
+        assert!(validate_table_data_with_config(&rows, &config).is_err());
+
+        config.header = None;
+        config.row_annotations = vec![crate::types::RowAnnotation {
+            row: 0,
+            text: "note".to_string(),
+            indent: None,
+            dim: None,
+        }];
+        assert!(validate_table_data_with_config(&rows, &config).is_err());
+    }
+
+    #[test]
+    fn test_out_of_bounds_indices_are_matchable() {
+        let rows = vec![vec!["a".to_string(), "b".to_string()]];
+        let config = TableConfig {
+            flex_column: Some(2),
+            ..Default::default()
+        };
+
+        match validate_table_data_with_config(&rows, &config) {
+            Err(TableError::IndexOutOfBounds {
+                setting,
+                index,
+                bound,
+            }) => assert_eq!((setting, index, bound), ("Flex column", 2, 2)),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_exact_table_width_must_fit_a_character_per_column() {
+        let rows = vec![vec!["a".to_string(), "b".to_string(), "c".to_string()]];
+        let config = |width| TableConfig {
+            exact_table_width: Some(width),
+            ..Default::default()
+        };
+
+        assert!(validate_table_data_with_config(&rows, &config(7)).is_ok());
+        assert!(matches!(
+            validate_table_data_with_config(&rows, &config(6)),
+            Err(TableError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_table_collects_issues_by_severity() {
+        let rows = vec![
+            vec!["id".to_string(), "a long description".to_string()],
+            vec!["7\u{200B}".to_string(), "short".to_string()],
+        ];
+        let mut config = TableConfig {
+            columns: vec![
+                ColumnConfig::default(),
+                ColumnConfig {
+                    truncate: 8,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let result = validate_table(&rows, &config);
+        let found: Vec<_> = result
+            .issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.severity,
+                    issue.cell.clone().map(|cell| (cell.row, cell.col)),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (Severity::Warning, Some((1, 0))),
+                (Severity::Info, Some((0, 1))),
+            ]
+        );
+        assert!(result.fails(Severity::Warning));
+        assert!(!result.fails(Severity::Error));
+
+        config.data_hygiene = DataHygiene::Clean;
+        assert_eq!(
+            validate_table(&rows, &config).max_severity(),
+            Some(Severity::Info)
+        );
+
+        config.data_hygiene = DataHygiene::Reject;
+        let result = validate_table(&rows, &config);
+        assert_eq!(result.max_severity(), Some(Severity::Error));
+        assert_eq!(result.issues.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_validation_result_to_json() {
+        let rows = vec![vec!["id".to_string(), "notes".to_string()]];
+        let config = TableConfig {
+            column_default: ColumnConfig {
+                truncate: 3,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let json: serde_json::Value =
+            serde_json::from_str(&validate_table(&rows, &config).to_json().unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"issues": [{
+                "code": "truncated",
+                "severity": "info",
+                "cell": {"col": 1, "row": 0},
+                "path": null,
+                "suggestion": null,
+                "message": "content is truncated",
+            }]})
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "config-files")]
+    fn test_validate_user_config_verbose() {
+        use crate::utils::config_file::ConfigFormat;
+
+        let content = r#"{"columns": [{"alignmnet": "left"}], "colour": "red"}"#;
+        let result = validate_user_config_verbose(content, ConfigFormat::Json, false);
+        let found: Vec<_> = result
+            .issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.code,
+                    issue.severity,
+                    issue.path.as_deref(),
+                    issue.suggestion.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "unknown-key",
+                    Severity::Warning,
+                    Some("columns[0].alignmnet"),
+                    Some("alignment")
+                ),
+                ("unknown-key", Severity::Warning, Some("colour"), None),
+            ]
+        );
+        assert!(
+            validate_user_config_verbose(content, ConfigFormat::Json, true).fails(Severity::Error)
+        );
+
+        let result = validate_user_config_verbose(
+            r#"{"column_default": {"alignment": "centre"}}"#,
+            ConfigFormat::Json,
+            false,
+        );
+        let issue = &result.issues[0];
+        assert_eq!(issue.code, "invalid-config");
+        assert_eq!(issue.path.as_deref(), Some("column_default.alignment"));
+        assert_eq!(issue.suggestion.as_deref(), Some("center"));
+
+        let result = validate_user_config_verbose(
+            r#"{"column_default": {"width": 2, "padding_left": 2}}"#,
+            ConfigFormat::Json,
+            false,
+        );
+        assert_eq!(result.issues[0].code, "invalid-config");
+        assert!(result.issues[0].path.is_none());
+
+        let result = validate_user_config_verbose(
+            r#"{"header": {"columns": [{}, {"overflow_mode": "clip", "wrap_word": true}]}}"#,
+            ConfigFormat::Json,
+            false,
+        );
+        assert_eq!(
+            result.issues[0].path.as_deref(),
+            Some("header.columns[1].overflow_mode")
+        );
+    }
+
+    #[test]
+    fn test_validate_overflow_modes() {
+        use crate::types::{Overflow, TruncatePosition};
+
+        let column = |overflow_mode: Overflow| ColumnUserConfig {
+            overflow_mode: Some(overflow_mode),
+            ..Default::default()
+        };
+        let config = |columns: Vec<ColumnUserConfig>| TableUserConfig {
+            columns: Some(columns),
+            ..Default::default()
+        };
+        let truncate = Overflow::Truncate {
+            position: TruncatePosition::Middle,
+            ellipsis: Some("~".to_string()),
+        };
+
+        assert!(validate_overflow_modes(&config(vec![column(Overflow::Wrap)])).is_ok());
+        let conflicts = [
+            (
+                ColumnUserConfig {
+                    truncate: Some(5),
+                    ..column(Overflow::Wrap)
+                },
+                "truncate",
+            ),
+            (
+                ColumnUserConfig {
+                    ellipsis: Some("…".to_string()),
+                    ..column(truncate.clone())
+                },
+                "ellipsis",
+            ),
+            (
+                ColumnUserConfig {
+                    overflow: Some(OverflowPolicy::WrapOnly),
+                    ..column(Overflow::Clip)
+                },
+                "overflow",
+            ),
+            (
+                ColumnUserConfig {
+                    max_width: Some(10),
+                    ..column(Overflow::Expand)
+                },
+                "max_width",
+            ),
+        ];
+        for (conflicting, key) in conflicts {
+            let error =
+                validate_overflow_modes(&config(vec![ColumnUserConfig::default(), conflicting]))
+                    .unwrap_err()
+                    .to_string();
+            assert!(
+                error.contains(&format!(
+                    "`columns[1].overflow_mode` cannot be combined with `{key}`"
+                )),
+                "{error}"
+            );
+        }
+
+        let resolved = column(truncate).merge_with_default(&ColumnConfig {
+            truncate: 8,
+            ..Default::default()
+        });
+        assert_eq!(
+            (
+                resolved.truncate,
+                resolved.overflow,
+                resolved.truncate_position,
+                resolved.ellipsis.as_str()
+            ),
+            (
+                0,
+                OverflowPolicy::TruncateOnly,
+                TruncatePosition::Middle,
+                "~"
+            )
+        );
+    }
+
+    #[test]
+    fn test_reject_unclean_data() {
+        let rows = vec![vec!["id".to_string(), "total ".to_string()]];
+        let mut config = TableConfig::default();
+        assert!(validate_table_data_with_config(&rows, &config).is_ok());
+
+        config.data_hygiene = DataHygiene::Reject;
+        match validate_table_data_with_config(&rows, &config) {
+            Err(error @ TableError::UncleanData(issue)) => {
+                assert_eq!((issue.row, issue.column), (0, 1));
+                assert_eq!(
+                    error.to_string(),
+                    "Unclean cell data at row 0, column 1, line 0: trailing whitespace"
+                );
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,298 @@
+use crate::features::stats::normalize_value;
+use crate::types::{Row, TableConfig, TableError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub type AggregateFn = Arc<dyn Fn(&[&str]) -> String + Send + Sync>;
+
+/// How a column's body cells are reduced to the value shown in the totals row.
+/// Empty cells are skipped; numeric aggregations show the config's
+/// `aggregate_placeholder` when any other cell is not a number. `Custom` sees
+/// the raw cells and cannot be written to or read from config files.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Aggregation {
+    Sum,
+    Avg,
+    Min,
+    Max,
+    /// The number of non-empty cells.
+    Count,
+    /// Fixed text, e.g. `"Total"`.
+    Label(String),
+    #[serde(skip)]
+    Custom(AggregateFn),
+}
+
+impl Aggregation {
+    pub fn custom(aggregate: impl Fn(&[&str]) -> String + Send + Sync + 'static) -> Self {
+        Aggregation::Custom(Arc::new(aggregate))
+    }
+}
+
+impl std::fmt::Debug for Aggregation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Aggregation::Sum => f.write_str("Sum"),
+            Aggregation::Avg => f.write_str("Avg"),
+            Aggregation::Min => f.write_str("Min"),
+            Aggregation::Max => f.write_str("Max"),
+            Aggregation::Count => f.write_str("Count"),
+            Aggregation::Label(label) => f.debug_tuple("Label").field(label).finish(),
+            Aggregation::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+// Custom aggregations are only equal to themselves.
+impl PartialEq for Aggregation {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Aggregation::Label(a), Aggregation::Label(b)) => a == b,
+            (Aggregation::Custom(a), Aggregation::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for Aggregation {}
+
+// Consistent with `PartialEq`: custom aggregations hash by identity.
+impl std::hash::Hash for Aggregation {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Aggregation::Label(label) => label.hash(state),
+            Aggregation::Custom(aggregate) => Arc::as_ptr(aggregate).cast::<()>().hash(state),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ColumnAggregation {
+    pub column: usize,
+    pub aggregation: Aggregation,
+}
+
+impl std::str::FromStr for ColumnAggregation {
+    type Err = TableError;
+
+    /// Parses `COLUMN:sum`, `avg`, `min`, `max` or `count`; any other text after
+    /// the colon becomes a label, e.g. `0:Total`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            TableError::parse(
+                "aggregation",
+                s,
+                "expected COLUMN:sum|avg|min|max|count or COLUMN:LABEL",
+            )
+        };
+
+        let (column, kind) = s.split_once(':').ok_or_else(invalid)?;
+        let column = column.trim().parse().map_err(|_| invalid())?;
+        let aggregation = match kind.trim().to_lowercase().as_str() {
+            "sum" => Aggregation::Sum,
+            "avg" => Aggregation::Avg,
+            "min" => Aggregation::Min,
+            "max" => Aggregation::Max,
+            "count" => Aggregation::Count,
+            _ => Aggregation::Label(kind.to_string()),
+        };
+
+        Ok(ColumnAggregation {
+            column,
+            aggregation,
+        })
+    }
+}
+
+// Numbers are shown with as many decimals as the most precise input.
+fn decimals(value: &str) -> usize {
+    if value.contains(['e', 'E']) {
+        return 0;
+    }
+    value
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len())
+}
+
+fn aggregate_column(cells: &[&str], aggregation: &Aggregation, placeholder: &str) -> String {
+    let numeric = match aggregation {
+        Aggregation::Label(label) => return label.clone(),
+        Aggregation::Custom(aggregate) => return aggregate(cells),
+        Aggregation::Count => {
+            let count = cells
+                .iter()
+                .filter(|cell| !normalize_value(cell).is_empty());
+            return count.count().to_string();
+        }
+        Aggregation::Sum | Aggregation::Avg | Aggregation::Min | Aggregation::Max => aggregation,
+    };
+
+    let mut values = Vec::with_capacity(cells.len());
+    let mut precision = 0;
+    for cell in cells {
+        let value = normalize_value(cell);
+        if value.is_empty() {
+            continue;
+        }
+        match value.parse::<f64>() {
+            Ok(number) if number.is_finite() => values.push(number),
+            _ => return placeholder.to_string(),
+        }
+        precision = precision.max(decimals(&value));
+    }
+
+    let result = match numeric {
+        Aggregation::Sum => Some(values.iter().sum()),
+        Aggregation::Avg => {
+            precision = precision.max(2);
+            (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+        }
+        Aggregation::Min => values.iter().copied().reduce(f64::min),
+        _ => values.iter().copied().reduce(f64::max),
+    };
+
+    result.map_or_else(
+        || placeholder.to_string(),
+        |value: f64| format!("{value:.precision$}"),
+    )
+}
+
+/// Computes the totals row for `body`, the rows to aggregate, from
+/// `config.aggregations`; columns without an aggregation are left empty.
+pub fn aggregate_row(body: &[Row], column_count: usize, config: &TableConfig) -> Row {
+    let mut row = vec![String::new(); column_count];
+    let mut cells = Vec::with_capacity(body.len());
+
+    for ColumnAggregation {
+        column,
+        aggregation,
+    } in &config.aggregations
+    {
+        let Some(slot) = row.get_mut(*column) else {
+            continue;
+        };
+        cells.clear();
+        cells.extend(
+            body.iter()
+                .map(|row| row.get(*column).map_or("", String::as_str)),
+        );
+        *slot = aggregate_column(&cells, aggregation, &config.aggregate_placeholder);
+    }
+
+    row
+}
+
+/// Filters and sorts keep the footer row in place, so before either runs the
+/// totals row is reserved as an empty last row, and filled in afterwards from
+/// the body rows that are left.
+pub fn reserve_aggregate_row(rows: &mut Vec<Row>, config: &TableConfig) {
+    if !config.aggregations.is_empty() {
+        rows.push(Row::new());
+    }
+}
+
+/// Fills in the row reserved by `reserve_aggregate_row`.
+pub fn fill_aggregate_row(rows: &mut [Row], config: &TableConfig) {
+    if config.aggregations.is_empty() || rows.len() < 2 {
+        return;
+    }
+
+    let column_count = rows[0].len();
+    let body = config.body_rows(rows.len());
+    let totals = aggregate_row(&rows[body], column_count, config);
+    if let Some(last) = rows.last_mut() {
+        *last = totals;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_body() -> Vec<Row> {
+        vec![
+            vec!["web".to_string(), "1.5".to_string(), "3".to_string()],
+            vec![
+                "db".to_string(),
+                "\u{1b}[32m2\u{1b}[0m".to_string(),
+                "".to_string(),
+            ],
+            vec!["cache".to_string(), "4".to_string(), "n/a".to_string()],
+        ]
+    }
+
+    fn totals(aggregations: Vec<ColumnAggregation>) -> Row {
+        let config = TableConfig {
+            aggregations,
+            ..Default::default()
+        };
+        aggregate_row(&sample_body(), 3, &config)
+    }
+
+    fn on(column: usize, aggregation: Aggregation) -> ColumnAggregation {
+        ColumnAggregation {
+            column,
+            aggregation,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_row_builtin_aggregations() {
+        assert_eq!(
+            totals(vec![on(0, Aggregation::Count), on(1, Aggregation::Sum)]),
+            vec!["3", "7.5", ""]
+        );
+        assert_eq!(
+            totals(vec![
+                on(1, Aggregation::Avg),
+                on(0, Aggregation::Label("Total".into()))
+            ]),
+            vec!["Total", "2.50", ""]
+        );
+        assert_eq!(
+            totals(vec![on(1, Aggregation::Min), on(0, Aggregation::Max)]),
+            vec!["n/a", "1.5", ""]
+        );
+    }
+
+    #[test]
+    fn test_aggregate_row_uses_placeholder_for_text() {
+        let config = TableConfig {
+            aggregations: vec![on(1, Aggregation::Max), on(2, Aggregation::Sum)],
+            aggregate_placeholder: "?".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            aggregate_row(&sample_body(), 3, &config),
+            vec!["", "4.0", "?"]
+        );
+        assert_eq!(totals(vec![on(2, Aggregation::Count)]), vec!["", "", "2"]);
+    }
+
+    #[test]
+    fn test_aggregate_row_custom_and_parse() {
+        let longest = Aggregation::custom(|cells| {
+            cells
+                .iter()
+                .max_by_key(|cell| cell.len())
+                .unwrap_or(&"")
+                .to_string()
+        });
+        assert_eq!(totals(vec![on(0, longest.clone())]), vec!["cache", "", ""]);
+        assert_eq!(longest, longest.clone());
+        assert_ne!(longest, Aggregation::custom(|_| String::new()));
+
+        assert_eq!(
+            "1:SUM".parse::<ColumnAggregation>().unwrap(),
+            on(1, Aggregation::Sum)
+        );
+        assert_eq!(
+            "0:Total".parse::<ColumnAggregation>().unwrap(),
+            on(0, Aggregation::Label("Total".to_string()))
+        );
+        assert!("sum".parse::<ColumnAggregation>().is_err());
+    }
+}
@@ -1,12 +1,169 @@
-use crate::types::{Alignment, VerticalAlignment};
-use crate::utils::ansi::pad_ansi_string;
+use crate::types::{Alignment, ColumnConfig, TextDirection, VerticalAlignment};
+use crate::utils::ansi::{calculate_display_width, closing_sequences, split_ansi_string};
+use crate::utils::unicode::{detect_direction, grapheme_clusters, reorder_bidi_line};
+use std::borrow::Cow;
+
+/// Where a cell's text sits in its column: `left` spaces, the text, then
+/// `right` spaces. Only justified text has to be rebuilt, so `text` otherwise
+/// borrows the cell and nothing is allocated until the layout is written out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellLayout<'a> {
+    pub left: usize,
+    pub text: Cow<'a, str>,
+    pub right: usize,
+}
+
+impl CellLayout<'_> {
+    pub fn write_to(&self, out: &mut String) {
+        push_spaces(out, self.left);
+        out.push_str(&self.text);
+        push_spaces(out, self.right);
+    }
+}
+
+impl std::fmt::Display for CellLayout<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:left$}{}{:right$}",
+            "",
+            self.text,
+            "",
+            left = self.left,
+            right = self.right
+        )
+    }
+}
+
+pub fn push_spaces(out: &mut String, count: usize) {
+    crate::utils::slab::push_repeated(out, " ", count);
+}
+
+// `layout` with the space around a non-empty text filled with `fill`, after
+// closing any style or hyperlink the text leaves open so the fill is drawn
+// plain.
+fn fill_layout(layout: CellLayout<'_>, fill: char) -> CellLayout<'_> {
+    if layout.text.is_empty() || layout.left + layout.right == 0 {
+        return layout;
+    }
+    let mut text = String::with_capacity(layout.text.len() + layout.left + layout.right);
+    push_filled(&mut text, &layout.text, fill, layout.left);
+    text.push_str(closing_sequences(&layout.text));
+    text.extend(std::iter::repeat_n(fill, layout.right));
+    CellLayout {
+        left: 0,
+        text: Cow::Owned(text),
+        right: 0,
+    }
+}
+
+// Pushes `text` with `count` of `fill` before it. Zeros go after a leading
+// sign, so -42 fills to `-00042`.
+pub(crate) fn push_filled(out: &mut String, text: &str, fill: char, count: usize) {
+    let sign_end = if fill == '0' {
+        let mut offset = 0;
+        split_ansi_string(text)
+            .iter()
+            .find_map(|segment| {
+                let start = offset;
+                offset += segment.len();
+                (!segment.starts_with('\u{1b}'))
+                    .then(|| segment.starts_with(['-', '+']).then_some(start + 1))
+            })
+            .flatten()
+            .unwrap_or(0)
+    } else {
+        0
+    };
+    out.push_str(&text[..sign_end]);
+    out.extend(std::iter::repeat_n(fill, count));
+    out.push_str(&text[sign_end..]);
+}
 
 pub struct AlignmentProcessor;
 
 impl AlignmentProcessor {
     pub fn align_text_horizontal(text: &str, width: usize, alignment: Alignment) -> String {
-        let padded = pad_ansi_string(text, width, alignment);
-        padded.content
+        Self::layout(text, width, alignment).to_string()
+    }
+
+    /// Lays `text` out `width` columns wide; text already that wide is left
+    /// as it is. `Justify` sets the text flush left, as the last line of a
+    /// paragraph: the lines before it are spread out by `justify_line` when
+    /// the cell is wrapped.
+    pub fn layout(text: &str, width: usize, alignment: Alignment) -> CellLayout<'_> {
+        let slack = width.saturating_sub(calculate_display_width(text));
+        let (left, right) = match alignment {
+            _ if slack == 0 => (0, 0),
+            // Justified lines are spread out when the cell is wrapped, and
+            // cells aligned on a character padded to line up when the table
+            // is prepared; what reaches here is set flush left.
+            Alignment::Left | Alignment::Justify | Alignment::Char(_) => (0, slack),
+            Alignment::Right => (slack, 0),
+            Alignment::Center => (slack / 2, slack - slack / 2),
+        };
+
+        CellLayout {
+            left,
+            text: Cow::Borrowed(text),
+            right,
+        }
+    }
+
+    /// Lays a cell out in a column `total_width` wide, padding included: the
+    /// text is aligned within the width left after padding and the layout
+    /// filled out to `total_width`. A zero width only adds the padding.
+    /// Right-to-left text has its alignment and padding mirrored, and is
+    /// reordered for display when the column sets `bidi`.
+    pub fn layout_cell<'a>(
+        text: &'a str,
+        config: &ColumnConfig,
+        total_width: usize,
+    ) -> CellLayout<'a> {
+        let rtl = match config.direction {
+            TextDirection::Ltr => false,
+            TextDirection::Rtl => true,
+            TextDirection::Auto => detect_direction(text) == Some(TextDirection::Rtl),
+        };
+        let (alignment, padding_left, padding_right) = if rtl {
+            let alignment = match config.alignment {
+                Alignment::Left | Alignment::Justify => Alignment::Right,
+                Alignment::Right => Alignment::Left,
+                alignment => alignment,
+            };
+            (alignment, config.padding_right, config.padding_left)
+        } else {
+            (config.alignment, config.padding_left, config.padding_right)
+        };
+        let text = if config.bidi {
+            reorder_bidi_line(text)
+        } else {
+            Cow::Borrowed(text)
+        };
+
+        let padding = padding_left + padding_right;
+        let mut layout = match text {
+            Cow::Borrowed(text) => {
+                Self::layout(text, total_width.saturating_sub(padding), alignment)
+            }
+            Cow::Owned(text) => {
+                let layout = Self::layout(&text, total_width.saturating_sub(padding), alignment);
+                CellLayout {
+                    text: Cow::Owned(layout.text.into_owned()),
+                    ..layout
+                }
+            }
+        };
+
+        let aligned_width = layout.left + calculate_display_width(&layout.text) + layout.right;
+        // Cells aligned on a character were filled before their integer part
+        // when the table was prepared; the space after them stays blank.
+        if config.pad_char != ' ' && !matches!(alignment, Alignment::Char(_)) {
+            layout = fill_layout(layout, config.pad_char);
+        }
+        layout.left += padding_left;
+        layout.right += padding_right + total_width.saturating_sub(padding + aligned_width);
+        layout
     }
 
     pub fn align_text_vertical(
@@ -42,35 +199,56 @@ impl AlignmentProcessor {
     }
 
     pub fn distribute_content_evenly(content: &str, width: usize) -> String {
-        let words: Vec<&str> = content.split_whitespace().collect();
-
-        if words.len() <= 1 {
-            return content.to_string();
-        }
+        Self::justify_line(content, width).into_owned()
+    }
 
-        let total_word_width: usize = words.iter().map(|w| w.len()).sum();
-        let total_spaces = width.saturating_sub(total_word_width);
-        let gaps = words.len() - 1;
+    /// Spreads the words of `line` out to `width` columns by widening the
+    /// gaps between them, the leftmost gaps taking one more space when the
+    /// slack does not divide evenly. Widths are measured per grapheme cluster
+    /// and escape sequences are kept where they are, at zero width, so colors
+    /// and hyperlinks survive. Leading spaces stay as they are and trailing
+    /// ones are dropped; a line with no gap, or already `width` wide, is
+    /// returned as it is.
+    pub fn justify_line(line: &str, width: usize) -> Cow<'_, str> {
+        let line = line.trim_end_matches(' ');
+        let slack = width.saturating_sub(calculate_display_width(line));
 
-        if gaps == 0 {
-            return content.to_string();
+        // The byte offsets just past each run of spaces between words.
+        let mut gaps = Vec::new();
+        let mut pending = None;
+        let mut seen_word = false;
+        let mut offset = 0;
+        for segment in split_ansi_string(line) {
+            if !segment.starts_with('\u{1b}') {
+                let mut cluster_end = offset;
+                for (cluster, _) in grapheme_clusters(&segment) {
+                    cluster_end += cluster.len();
+                    if cluster == " " {
+                        pending = seen_word.then_some(cluster_end);
+                    } else {
+                        gaps.extend(pending.take());
+                        seen_word = true;
+                    }
+                }
+            }
+            offset += segment.len();
         }
 
-        let spaces_per_gap = total_spaces / gaps;
-        let extra_spaces = total_spaces % gaps;
-
-        let mut result = String::new();
-
-        for (i, word) in words.iter().enumerate() {
-            result.push_str(word);
-
-            if i < words.len() - 1 {
-                let spaces = spaces_per_gap + if i < extra_spaces { 1 } else { 0 };
-                result.push_str(&" ".repeat(spaces));
-            }
+        if slack == 0 || gaps.is_empty() {
+            return Cow::Borrowed(line);
         }
 
-        result
+        let per_gap = slack / gaps.len();
+        let extra = slack % gaps.len();
+        let mut result = String::with_capacity(line.len() + slack);
+        let mut start = 0;
+        for (i, &gap) in gaps.iter().enumerate() {
+            result.push_str(&line[start..gap]);
+            push_spaces(&mut result, per_gap + usize::from(i < extra));
+            start = gap;
+        }
+        result.push_str(&line[start..]);
+        Cow::Owned(result)
     }
 }
 
@@ -92,6 +270,135 @@ mod tests {
         assert_eq!(center_aligned, "  hello   ");
     }
 
+    #[test]
+    fn test_layout_cell_matches_align_then_pad() {
+        let config = ColumnConfig {
+            alignment: Alignment::Center,
+            padding_left: 2,
+            padding_right: 1,
+            ..Default::default()
+        };
+
+        let layout = AlignmentProcessor::layout_cell("hello", &config, 12);
+        assert_eq!(
+            layout,
+            CellLayout {
+                left: 4,
+                text: Cow::Borrowed("hello"),
+                right: 3
+            }
+        );
+        assert_eq!(layout.to_string(), "    hello   ");
+        assert_eq!(
+            AlignmentProcessor::layout_cell("\u{1b}[31mtoo wide\u{1b}[0m", &config, 8).to_string(),
+            "  \u{1b}[31mtoo wide\u{1b}[0m "
+        );
+        assert_eq!(
+            AlignmentProcessor::layout_cell("hi", &config, 0).to_string(),
+            "  hi "
+        );
+
+        let justified = ColumnConfig {
+            alignment: Alignment::Justify,
+            ..config
+        };
+        let mut out = String::from("|");
+        AlignmentProcessor::layout_cell("a b c", &justified, 12).write_to(&mut out);
+        assert!(out.starts_with("|  a b c"));
+    }
+
+    #[test]
+    fn test_layout_cell_fills_with_pad_char() {
+        let leaders = ColumnConfig {
+            pad_char: '.',
+            ..Default::default()
+        };
+        assert_eq!(
+            AlignmentProcessor::layout_cell("Intro", &leaders, 10).to_string(),
+            " Intro... "
+        );
+        assert_eq!(
+            AlignmentProcessor::layout_cell("\u{1b}[1mIntro", &leaders, 10).to_string(),
+            " \u{1b}[1mIntro\u{1b}[0m... "
+        );
+        assert_eq!(
+            AlignmentProcessor::layout_cell("", &leaders, 10).to_string(),
+            "          "
+        );
+
+        let zeros = ColumnConfig {
+            alignment: Alignment::Right,
+            pad_char: '0',
+            ..Default::default()
+        };
+        assert_eq!(
+            AlignmentProcessor::layout_cell("42", &zeros, 7).to_string(),
+            " 00042 "
+        );
+        assert_eq!(
+            AlignmentProcessor::layout_cell("-42", &zeros, 8).to_string(),
+            " -00042 "
+        );
+        assert_eq!(
+            AlignmentProcessor::layout_cell("\u{1b}[31m-42\u{1b}[0m", &zeros, 8).to_string(),
+            " \u{1b}[31m-00042\u{1b}[0m "
+        );
+    }
+
+    #[test]
+    fn test_justify_line() {
+        assert_eq!(
+            AlignmentProcessor::justify_line("a bb  c", 11),
+            "a   bb    c"
+        );
+        assert_eq!(
+            AlignmentProcessor::justify_line("a b c d ", 11),
+            "a   b  c  d"
+        );
+        assert_eq!(
+            AlignmentProcessor::justify_line("\u{1b}[31mred\u{1b}[0m text", 10),
+            "\u{1b}[31mred\u{1b}[0m   text"
+        );
+        assert_eq!(AlignmentProcessor::justify_line("日本 語", 9), "日本   語");
+        assert_eq!(
+            AlignmentProcessor::justify_line("  indented text", 17),
+            "  indented   text"
+        );
+        assert!(matches!(
+            AlignmentProcessor::justify_line("single", 10),
+            Cow::Borrowed("single")
+        ));
+    }
+
+    #[test]
+    fn test_layout_cell_mirrors_right_to_left_text() {
+        let config = ColumnConfig {
+            padding_left: 2,
+            padding_right: 0,
+            direction: TextDirection::Auto,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            AlignmentProcessor::layout_cell("שלום", &config, 8).to_string(),
+            "  שלום  "
+        );
+        assert_eq!(
+            AlignmentProcessor::layout_cell("hello", &config, 8).to_string(),
+            "  hello "
+        );
+
+        let reordered = ColumnConfig {
+            direction: TextDirection::Rtl,
+            bidi: true,
+            ..config
+        };
+        assert_eq!(
+            AlignmentProcessor::layout_cell("אבג 12", &reordered, 8).to_string(),
+            "12 גבא  "
+        );
+    }
+
     #[test]
     fn test_align_text_vertical() {
         let lines = vec!["line1".to_string(), "line2".to_string()];
@@ -1,4 +1,54 @@
-use crate::types::BorderConfig;
+use crate::types::{BorderConfig, BorderUserConfig, TableResult, TableUserConfig};
+use crate::types::{border_style_names, get_border_characters};
+
+/// A border style given by name, built-in or registered, or by its characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderStyle<'a> {
+    Named(&'a str),
+    Config(&'a BorderConfig),
+}
+
+impl<'a> From<&'a str> for BorderStyle<'a> {
+    fn from(name: &'a str) -> Self {
+        BorderStyle::Named(name)
+    }
+}
+
+impl<'a> From<&'a BorderConfig> for BorderStyle<'a> {
+    fn from(border: &'a BorderConfig) -> Self {
+        BorderStyle::Config(border)
+    }
+}
+
+/// The 2×2 sample table `A B / C D` drawn with `style`, as the CLI `borders`
+/// command shows it. Fails for an unknown style name.
+pub fn preview_border_style<'a>(style: impl Into<BorderStyle<'a>>) -> TableResult<String> {
+    let border = match style.into() {
+        BorderStyle::Named(name) => get_border_characters(name)?,
+        BorderStyle::Config(border) => border.clone(),
+    };
+    let sample = vec![
+        vec!["A".to_string(), "B".to_string()],
+        vec!["C".to_string(), "D".to_string()],
+    ];
+    let config = TableUserConfig {
+        border: Some(BorderUserConfig::from(border)),
+        ..Default::default()
+    };
+
+    crate::table(&sample, Some(&config))
+}
+
+/// Every style in `border_style_names` with its `preview_border_style`.
+pub fn preview_border_styles() -> Vec<(String, String)> {
+    border_style_names()
+        .into_iter()
+        .filter_map(|name| {
+            let preview = preview_border_style(name.as_str()).ok()?;
+            Some((name, preview))
+        })
+        .collect()
+}
 
 pub struct BorderRenderer {
     config: BorderConfig,
@@ -62,7 +112,7 @@ impl BorderRenderer {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::get_border_characters;
+    use crate::types::register_border_style;
 
     #[test]
     fn test_border_renderer() {
@@ -86,4 +136,28 @@ mod tests {
         assert!(separator.contains("┤"));
         assert!(separator.contains("┼"));
     }
+
+    #[test]
+    fn test_preview_border_styles_include_registered_ones() {
+        assert_eq!(
+            preview_border_style("ramac").unwrap(),
+            "+---+---+\n| A | B |\n+---+---+\n| C | D |\n+---+---+"
+        );
+        assert!(preview_border_style("nonexistent").is_err());
+
+        let mut dotted = get_border_characters("ramac").unwrap();
+        dotted.top_body = ".".to_string();
+        register_border_style("preview-dotted", dotted.clone()).unwrap();
+        assert!(register_border_style("void", dotted.clone()).is_err());
+
+        let gallery = preview_border_styles();
+        let names: Vec<&str> = gallery.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names[..4], ["honeywell", "norc", "ramac", "void"]);
+        let (_, preview) = gallery
+            .iter()
+            .find(|(name, _)| name == "preview-dotted")
+            .unwrap();
+        assert_eq!(preview, &preview_border_style(&dotted).unwrap());
+        assert!(preview.starts_with("+...+...+"));
+    }
 }
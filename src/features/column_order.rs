@@ -0,0 +1,216 @@
+use crate::features::stats::{ColumnRedundancy, suggest_redundant_columns};
+use crate::types::{RedundantColumns, Row, TableConfig};
+
+/// The data columns drawn, in order: `column_order` when set, otherwise every
+/// column, leaving out the `hidden` ones either way.
+pub fn visible_columns(column_count: usize, config: &TableConfig) -> Vec<usize> {
+    config
+        .column_order
+        .clone()
+        .unwrap_or_else(|| (0..column_count).collect())
+        .into_iter()
+        .filter(|&col| {
+            !config
+                .columns
+                .get(col)
+                .unwrap_or(&config.column_default)
+                .hidden
+        })
+        .collect()
+}
+
+/// Rearranges `rows` into the visible columns, moving the per-column settings
+/// of `config` along, so the layout only sees what is drawn. Both are left
+/// alone when every column is drawn in place.
+pub fn select_columns(rows: Vec<Row>, config: &mut TableConfig) -> Vec<Row> {
+    let column_count = rows.first().map_or(0, Vec::len);
+    let visible = visible_columns(column_count, config);
+    if visible.iter().copied().eq(0..column_count) {
+        return rows;
+    }
+
+    select_column_configs(config, &visible);
+    rows.into_iter()
+        .map(|row| visible.iter().map(|&col| row[col].clone()).collect())
+        .collect()
+}
+
+/// Hides the columns `suggest_redundant_columns` finds in the header and body
+/// rows, per `config.redundant_columns`; under `Merge` a copy's header cell is
+/// appended to its original's. A copy `column_order` draws is replaced there
+/// by its original. When every drawn column is redundant the first stays.
+pub fn drop_redundant_columns(rows: &mut [Row], config: &mut TableConfig) {
+    if config.redundant_columns == RedundantColumns::Keep {
+        return;
+    }
+
+    let has_header = config.header.is_some();
+    let body_end = config.body_rows(rows.len()).end;
+    let suggestions = suggest_redundant_columns(&rows[..body_end], has_header);
+    if suggestions.is_empty() {
+        return;
+    }
+    let column_count = rows.first().map_or(0, Vec::len);
+    let visible = visible_columns(column_count, config);
+
+    if config.columns.len() < column_count {
+        let column_default = config.column_default.clone();
+        config.columns.resize(column_count, column_default);
+    }
+    for suggestion in suggestions {
+        config.columns[suggestion.column].hidden = true;
+
+        if let ColumnRedundancy::DuplicateOf(original) = suggestion.redundancy
+            && config.redundant_columns == RedundantColumns::Merge
+            && has_header
+        {
+            let name = rows[0][suggestion.column].clone();
+            rows[0][original] = format!("{} / {name}", rows[0][original]);
+        }
+        if let ColumnRedundancy::DuplicateOf(original) = suggestion.redundancy
+            && let Some(order) = &mut config.column_order
+        {
+            if order.contains(&original) {
+                order.retain(|&col| col != suggestion.column);
+            } else {
+                for col in order.iter_mut().filter(|col| **col == suggestion.column) {
+                    *col = original;
+                }
+            }
+        }
+    }
+
+    if visible_columns(column_count, config).is_empty()
+        && let Some(&first) = visible.first()
+    {
+        config.columns[first].hidden = false;
+        if let Some(order) = &mut config.column_order
+            && !order.contains(&first)
+        {
+            order.insert(0, first);
+        }
+    }
+}
+
+fn select_column_configs(config: &mut TableConfig, visible: &[usize]) {
+    config.columns = visible
+        .iter()
+        .map(|&col| {
+            config
+                .columns
+                .get(col)
+                .unwrap_or(&config.column_default)
+                .clone()
+        })
+        .collect();
+    config.flex_column = config
+        .flex_column
+        .and_then(|flex| visible.iter().position(|&col| col == flex));
+    config.column_order = None;
+
+    for section in [&mut config.header, &mut config.footer]
+        .into_iter()
+        .flatten()
+    {
+        select_column_configs(section, visible);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ColumnConfig;
+
+    fn rows() -> Vec<Row> {
+        vec![
+            vec!["id".to_string(), "name".to_string(), "port".to_string()],
+            vec!["1".to_string(), "web".to_string(), "443".to_string()],
+        ]
+    }
+
+    #[test]
+    fn test_select_columns_orders_and_hides() {
+        let hidden = ColumnConfig {
+            hidden: true,
+            ..Default::default()
+        };
+        let mut config = TableConfig {
+            columns: vec![ColumnConfig::default(), hidden, ColumnConfig::default()],
+            column_order: Some(vec![2, 1, 0]),
+            flex_column: Some(0),
+            header: Some(Box::default()),
+            ..Default::default()
+        };
+
+        assert_eq!(visible_columns(3, &config), vec![2, 0]);
+        let selected = select_columns(rows(), &mut config);
+        assert_eq!(selected, vec![vec!["port", "id"], vec!["443", "1"]]);
+        assert_eq!(config.columns.len(), 2);
+        assert_eq!(config.flex_column, Some(1));
+        assert_eq!(config.header.unwrap().columns.len(), 2);
+    }
+
+    #[test]
+    fn test_drop_and_merge_redundant_columns() {
+        let data: Vec<Row> = [
+            ["host", "env", "hostname", "port"],
+            ["web", "prod", "web", "443"],
+            ["db", "prod", "db", "5432"],
+        ]
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect();
+
+        for (mode, header) in [
+            (RedundantColumns::Drop, "host"),
+            (RedundantColumns::Merge, "host / hostname"),
+        ] {
+            let mut rows = data.clone();
+            let mut config = TableConfig {
+                header: Some(Box::default()),
+                redundant_columns: mode,
+                ..Default::default()
+            };
+            drop_redundant_columns(&mut rows, &mut config);
+
+            let selected = select_columns(rows, &mut config);
+            assert_eq!(selected[0], vec![header, "port"]);
+            assert_eq!(selected[2], vec!["db", "5432"]);
+        }
+
+        // A copy `column_order` picks is drawn as its original.
+        for (order, drawn) in [
+            (vec![2, 3], vec!["host", "port"]),
+            (vec![3, 2, 0], vec!["port", "host"]),
+            (vec![1], vec!["env"]),
+        ] {
+            let mut rows = data.clone();
+            let mut config = TableConfig {
+                header: Some(Box::default()),
+                redundant_columns: RedundantColumns::Drop,
+                column_order: Some(order),
+                ..Default::default()
+            };
+            drop_redundant_columns(&mut rows, &mut config);
+            assert_eq!(select_columns(rows, &mut config)[0], drawn);
+        }
+    }
+
+    #[test]
+    fn test_drop_redundant_columns_keeps_one_column() {
+        let mut rows = vec![vec!["x".to_string(), "x".to_string()]; 3];
+        let mut config = TableConfig {
+            redundant_columns: RedundantColumns::Drop,
+            ..Default::default()
+        };
+        drop_redundant_columns(&mut rows, &mut config);
+        assert_eq!(visible_columns(2, &config), vec![0]);
+    }
+
+    #[test]
+    fn test_select_columns_keeps_identity() {
+        let mut config = TableConfig::default();
+        assert_eq!(select_columns(rows(), &mut config), rows());
+        assert!(config.columns.is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+#[cfg(feature = "html")]
+use crate::core::calculator::calculate_table_width;
+#[cfg(feature = "html")]
+use crate::core::renderer::{calculate_table_layout, draw_table};
+use crate::features::stats::calculate_column_stats;
+#[cfg(feature = "html")]
+use crate::features::title::title_lines;
+use crate::types::{Row, TableConfig};
+#[cfg(feature = "html")]
+use crate::utils::ansi::{apply_color_mode, calculate_display_width};
+#[cfg(feature = "html")]
+use crate::utils::html::{HtmlTitle, convert_ansi_to_html_with_titles};
+use crate::utils::wrapping::wrap_text;
+
+/// Per-column `name: summary` strings. The first row is treated as the header
+/// only when the config has a header section, matching how the table is drawn,
+/// and a footer row is left out of the statistics.
+pub fn column_summaries(rows: &[Row], config: &TableConfig) -> Vec<(String, String)> {
+    let body_end = config.body_rows(rows.len()).end;
+    calculate_column_stats(&rows[..body_end], config.header.is_some())
+        .into_iter()
+        .map(|stats| {
+            let summary = stats.summary();
+            (stats.name, summary)
+        })
+        .collect()
+}
+
+/// Legend lines drawn beneath the table when `column_summaries` is enabled,
+/// dimmed like row annotations and wrapped to the table width.
+pub fn draw_column_legend(rows: &[Row], config: &TableConfig, table_width: usize) -> Vec<String> {
+    column_summaries(rows, config)
+        .into_iter()
+        .flat_map(|(name, summary)| wrap_text(&format!("{name}: {summary}"), table_width, true))
+        .map(|line| format!("\u{1b}[2m{}\u{1b}[0m", line.trim_end()))
+        .collect()
+}
+
+/// Renders the table as HTML. With `column_summaries` enabled, the header row
+/// cells carry the column summary as a `title=` tooltip instead of a legend.
+#[cfg(feature = "html")]
+pub fn draw_table_html(rows: &[Row], config: &TableConfig) -> String {
+    let output = apply_color_mode(draw_table(rows, config), config.color_mode);
+    if !config.column_summaries || rows.is_empty() {
+        return convert_ansi_to_html_with_titles(&output, 0..0, &[]);
+    }
+
+    let layout = calculate_table_layout(rows, config);
+    let table_width = calculate_table_width(&layout.column_widths, config);
+    let first_line = title_lines(config, rows.len()) + usize::from(layout.has_top_border);
+    let block_offset = output
+        .lines()
+        .nth(first_line)
+        .map(calculate_display_width)
+        .unwrap_or(0)
+        .saturating_sub(table_width);
+
+    let border = &config.border;
+    let mut start = block_offset + calculate_display_width(&border.body_left);
+    let titles: Vec<HtmlTitle> = layout
+        .column_widths
+        .iter()
+        .zip(column_summaries(rows, config))
+        .map(|(&width, (_, summary))| {
+            let title = HtmlTitle {
+                start,
+                end: start + width,
+                title: summary,
+            };
+            start += width + calculate_display_width(&border.body_join);
+            title
+        })
+        .collect();
+
+    let header_height = layout.row_heights.first().copied().unwrap_or(1);
+    convert_ansi_to_html_with_titles(&output, first_line..first_line + header_height, &titles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_rows() -> Vec<Row> {
+        vec![
+            vec!["Name".to_string(), "Age".to_string()],
+            vec!["John".to_string(), "30".to_string()],
+            vec!["Jane".to_string(), "25".to_string()],
+        ]
+    }
+
+    fn summary_config() -> TableConfig {
+        TableConfig {
+            column_summaries: true,
+            header: Some(Box::default()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_draw_column_legend() {
+        let legend = draw_column_legend(&sample_rows(), &summary_config(), 80);
+        assert_eq!(
+            legend,
+            vec![
+                "\u{1b}[2mName: text, 2 distinct, min Jane, max John\u{1b}[0m",
+                "\u{1b}[2mAge: integer, 2 distinct, min 25, max 30\u{1b}[0m",
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "html")]
+    fn test_draw_table_html_header_titles() {
+        let html = draw_table_html(&sample_rows(), &summary_config());
+        assert!(
+            html.contains("│<span title=\"text, 2 distinct, min Jane, max John\"> Name </span>│")
+        );
+        assert!(html.contains("<span title=\"integer, 2 distinct, min 25, max 30\"> Age </span>"));
+        assert_eq!(html.matches("<span title=").count(), 2);
+    }
+}
@@ -0,0 +1,404 @@
+use crate::features::number_format::{Formatter, Locale as NumberLocale};
+use crate::features::stats::normalize_value;
+use crate::types::{Row, TableConfig, TableError};
+use serde::{Deserialize, Serialize};
+
+/// What a computed column shows for each body row, worked out from the cells
+/// of its source column and written the way `locale` writes it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Computation {
+    /// The cell's percentage of the column total, e.g. `12.5%`, or `12,5 %`
+    /// in `de`, written with the table's `format_locale` separators.
+    Share,
+    /// The cell's rank, highest first, as an ordinal, e.g. `2nd`, or `2.` in
+    /// `de`. Equal values share a rank.
+    Rank,
+    /// The week of a `YYYY-MM-DD` date, e.g. `2024-W11`: ISO 8601 weeks, or
+    /// weeks from Sunday counted from the one holding January 1 where the
+    /// locale's region does, e.g. `en-US`.
+    Week,
+}
+
+impl Computation {
+    fn default_header(self) -> &'static str {
+        match self {
+            Computation::Share => "Share",
+            Computation::Rank => "Rank",
+            Computation::Week => "Week",
+        }
+    }
+}
+
+/// A column appended after the data columns, computed over the body rows
+/// left after filtering and sorting. Its index follows the data columns', so
+/// `columns`, `format_rules`, `style_rules` and `column_order` can refer to
+/// it; sorts, filters and aggregations cannot. Cells the computation cannot
+/// read, such as text in a `Share` column, stay empty, as does the footer's.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ComputedColumn {
+    pub column: usize,
+    pub computation: Computation,
+    /// The header row's cell, when there is a header; the computation's name
+    /// unless set.
+    pub header: Option<String>,
+}
+
+impl std::str::FromStr for ComputedColumn {
+    type Err = TableError;
+
+    /// Parses `COLUMN:share`, `rank` or `week`, optionally followed by
+    /// `:HEADER`, e.g. `2:share:Share of sales`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            TableError::parse(
+                "computed column",
+                s,
+                "expected COLUMN:share|rank|week[:HEADER]",
+            )
+        };
+
+        let mut parts = s.splitn(3, ':');
+        let column = parts
+            .next()
+            .and_then(|column| column.trim().parse().ok())
+            .ok_or_else(invalid)?;
+        let computation = match parts.next().map(|kind| kind.trim().to_lowercase()) {
+            Some(kind) if kind == "share" => Computation::Share,
+            Some(kind) if kind == "rank" => Computation::Rank,
+            Some(kind) if kind == "week" => Computation::Week,
+            _ => return Err(invalid()),
+        };
+
+        Ok(ComputedColumn {
+            column,
+            computation,
+            header: parts.next().map(str::to_string),
+        })
+    }
+}
+
+/// Appends `config.computed_columns` to every row: the header row gets each
+/// column's header, body rows the computed cells, and the footer an empty
+/// cell.
+pub fn append_computed_columns(rows: &mut [Row], config: &TableConfig) {
+    if config.computed_columns.is_empty() || rows.is_empty() {
+        return;
+    }
+
+    let locale = Locale::parse(config.locale.as_deref());
+    let number_locale = NumberLocale::for_config(config);
+    let body = config.body_rows(rows.len());
+    let computed: Vec<Vec<String>> = config
+        .computed_columns
+        .iter()
+        .map(|computed| {
+            let cells: Vec<&str> = rows[body.clone()]
+                .iter()
+                .map(|row| row.get(computed.column).map_or("", String::as_str))
+                .collect();
+            compute(&cells, computed.computation, &locale, &number_locale)
+        })
+        .collect();
+
+    for (row_idx, row) in rows.iter_mut().enumerate() {
+        for (computed, cells) in config.computed_columns.iter().zip(&computed) {
+            let cell = if row_idx < body.start {
+                computed
+                    .header
+                    .as_deref()
+                    .unwrap_or(computed.computation.default_header())
+                    .to_string()
+            } else if body.contains(&row_idx) {
+                cells[row_idx - body.start].clone()
+            } else {
+                String::new()
+            };
+            row.push(cell);
+        }
+    }
+}
+
+fn compute(
+    cells: &[&str],
+    computation: Computation,
+    locale: &Locale,
+    number_locale: &NumberLocale,
+) -> Vec<String> {
+    let numbers: Vec<Option<f64>> = cells
+        .iter()
+        .map(|cell| {
+            normalize_value(cell)
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+        })
+        .collect();
+
+    match computation {
+        Computation::Share => {
+            let total: f64 = numbers.iter().flatten().sum();
+            numbers
+                .iter()
+                .map(|number| match number {
+                    Some(number) if total != 0.0 => {
+                        Formatter::Percent(1).format(number / total, number_locale)
+                    }
+                    _ => String::new(),
+                })
+                .collect()
+        }
+        Computation::Rank => {
+            let mut descending: Vec<f64> = numbers.iter().flatten().copied().collect();
+            descending.sort_by(|a, b| b.total_cmp(a));
+            numbers
+                .iter()
+                .map(|number| {
+                    number.map_or_else(String::new, |number| {
+                        locale.ordinal(descending.partition_point(|&other| other > number) + 1)
+                    })
+                })
+                .collect()
+        }
+        Computation::Week => cells
+            .iter()
+            .map(|cell| {
+                parse_date(&normalize_value(cell))
+                    .map_or_else(String::new, |date| locale.week(date))
+            })
+            .collect(),
+    }
+}
+
+// Regions whose calendars start weeks on Sunday and count week 1 from
+// January 1.
+const SUNDAY_WEEK_REGIONS: &[&str] = &["US", "CA", "MX", "BR", "JP", "KR", "TW", "IL", "PH", "IN"];
+
+// The parts of a BCP 47 tag ordinals and weeks depend on: the language, and
+// whether the region starts weeks on Sunday.
+struct Locale {
+    language: String,
+    sunday_weeks: bool,
+}
+
+impl Locale {
+    fn parse(tag: Option<&str>) -> Self {
+        let mut subtags = tag.unwrap_or("en").split(['-', '_']);
+        let language = subtags.next().unwrap_or("en").to_ascii_lowercase();
+        let sunday_weeks = subtags.any(|subtag| {
+            subtag.len() == 2 && SUNDAY_WEEK_REGIONS.contains(&subtag.to_ascii_uppercase().as_str())
+        });
+        Locale {
+            language,
+            sunday_weeks,
+        }
+    }
+
+    fn ordinal(&self, n: usize) -> String {
+        match self.language.as_str() {
+            "fr" if n == 1 => "1er".to_string(),
+            "fr" | "nl" => format!("{n}e"),
+            "es" | "it" | "pt" => format!("{n}º"),
+            "de" | "da" | "nb" | "no" | "fi" | "cs" | "pl" | "tr" | "hu" => format!("{n}."),
+            "sv" if matches!(n % 10, 1 | 2) && !matches!(n % 100, 11 | 12) => format!("{n}:a"),
+            "sv" => format!("{n}:e"),
+            "ru" => format!("{n}-й"),
+            "ja" | "zh" => format!("第{n}"),
+            "ko" => format!("{n}번째"),
+            _ => {
+                let suffix = match (n % 10, n % 100) {
+                    (_, 11..=13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                format!("{n}{suffix}")
+            }
+        }
+    }
+
+    fn week(&self, (year, month, day): (i64, u32, u32)) -> String {
+        let ordinal = (days_from_civil(year, month, day) - days_from_civil(year, 1, 1)) as u32;
+        let (week_year, week) = if self.sunday_weeks {
+            // January 1's weekday, Sunday being 0, shifts the first week.
+            let january_first = (days_from_civil(year, 1, 1) + 4).rem_euclid(7) as u32;
+            (year, (ordinal + january_first) / 7 + 1)
+        } else {
+            iso_week(year, ordinal + 1, iso_weekday(year, month, day))
+        };
+        format!("{week_year}-W{week:02}")
+    }
+}
+
+// `YYYY-MM-DD`, with anything after the day, such as a time, ignored.
+pub(crate) fn parse_date(text: &str) -> Option<(i64, u32, u32)> {
+    let date = text.get(..10)?;
+    let mut parts = date.split('-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+    let (year, month, day) = (year.parse().ok()?, month.parse().ok()?, day.parse().ok()?);
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+    (1..=days_in_month)
+        .contains(&day)
+        .then_some((year, month, day))
+}
+
+// Days from 1970-01-01 to the date, in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// The date `days` after 1970-01-01, the inverse of `days_from_civil`.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// Monday is 1 and Sunday 7; 1970-01-01 was a Thursday.
+fn iso_weekday(year: i64, month: u32, day: u32) -> u32 {
+    (days_from_civil(year, month, day) + 3).rem_euclid(7) as u32 + 1
+}
+
+// The ISO week-numbering year and week of the `ordinal`th day of `year`.
+fn iso_week(year: i64, ordinal: u32, weekday: u32) -> (i64, u32) {
+    let week = (ordinal as i64 - weekday as i64 + 10) / 7;
+    if week < 1 {
+        (year - 1, iso_weeks_in(year - 1))
+    } else if week as u32 > iso_weeks_in(year) {
+        (year + 1, 1)
+    } else {
+        (year, week as u32)
+    }
+}
+
+// Years starting on a Thursday, and leap years starting on a Wednesday, have
+// 53 ISO weeks.
+fn iso_weeks_in(year: i64) -> u32 {
+    let starts = |year: i64| {
+        (year + year.div_euclid(4) - year.div_euclid(100) + year.div_euclid(400)).rem_euclid(7)
+    };
+    if starts(year) == 4 || starts(year - 1) == 3 {
+        53
+    } else {
+        52
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sales() -> Vec<Row> {
+        [
+            ["Region", "Sales", "Since"],
+            ["North", "50", "2024-12-30"],
+            ["South", "30", "2021-01-03"],
+            ["East", "20", "2024-03-15"],
+            ["West", "n/a", "soon"],
+            ["Total", "100", ""],
+        ]
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect()
+    }
+
+    fn computed(locale: Option<&str>) -> Vec<Row> {
+        let mut rows = sales();
+        let config = TableConfig {
+            header: Some(Box::default()),
+            footer: Some(Box::default()),
+            locale: locale.map(str::to_string),
+            computed_columns: vec![
+                "1:share:Share %".parse().unwrap(),
+                "1:rank".parse().unwrap(),
+                "2:week".parse().unwrap(),
+            ],
+            ..Default::default()
+        };
+        append_computed_columns(&mut rows, &config);
+        rows.iter().map(|row| row[3..].to_vec()).collect()
+    }
+
+    #[test]
+    fn test_share_rank_and_week_columns() {
+        assert_eq!(
+            computed(None),
+            [
+                ["Share %", "Rank", "Week"],
+                ["50.0%", "1st", "2025-W01"],
+                ["30.0%", "2nd", "2020-W53"],
+                ["20.0%", "3rd", "2024-W11"],
+                ["", "", ""],
+                ["", "", ""],
+            ]
+        );
+        assert_eq!(
+            computed(Some("de-DE")),
+            [
+                ["Share %", "Rank", "Week"],
+                ["50,0\u{a0}%", "1.", "2025-W01"],
+                ["30,0\u{a0}%", "2.", "2020-W53"],
+                ["20,0\u{a0}%", "3.", "2024-W11"],
+                ["", "", ""],
+                ["", "", ""],
+            ]
+        );
+        assert_eq!(
+            computed(Some("en-US"))
+                .iter()
+                .map(|row| row[2].as_str())
+                .collect::<Vec<_>>(),
+            ["Week", "2024-W53", "2021-W02", "2024-W11", "", ""]
+        );
+    }
+
+    #[test]
+    fn test_ordinals_and_parsing() {
+        let en = Locale::parse(None);
+        let ranks: Vec<String> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 111]
+            .map(|n| en.ordinal(n))
+            .into();
+        assert_eq!(
+            ranks,
+            [
+                "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "111th"
+            ]
+        );
+        assert_eq!(Locale::parse(Some("fr")).ordinal(1), "1er");
+        assert_eq!(Locale::parse(Some("sv-SE")).ordinal(12), "12:e");
+
+        assert!("1:median".parse::<ComputedColumn>().is_err());
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date("2024-02-29T08:00"), Some((2024, 2, 29)));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::features::stats::normalize_value;
+use crate::types::{FormatCondition, FormatRule, Row, TableConfig};
+
+pub fn rule_matches(rule: &FormatRule, value: &str) -> bool {
+    let value = normalize_value(value);
+    match &rule.condition {
+        FormatCondition::Equals(expected) => value == *expected,
+        FormatCondition::Contains(needle) => value.contains(needle.as_str()),
+        FormatCondition::Positive => value.parse::<f64>().is_ok_and(|number| number > 0.0),
+        FormatCondition::Negative => value.parse::<f64>().is_ok_and(|number| number < 0.0),
+    }
+}
+
+/// Formats one cell with the first matching rule: wrapped in the rule's color,
+/// or prefixed with its symbol in `symbols_only` mode.
+pub fn format_cell(cell: &str, column: usize, rules: &[FormatRule], symbols_only: bool) -> String {
+    let Some(rule) = rules
+        .iter()
+        .filter(|rule| rule.column.is_none_or(|col| col == column))
+        .find(|rule| rule_matches(rule, cell))
+    else {
+        return cell.to_string();
+    };
+
+    if symbols_only {
+        let symbol = rule
+            .symbol
+            .as_deref()
+            .unwrap_or_else(|| rule.condition.default_symbol());
+        format!("{symbol} {cell}")
+    } else {
+        format!("\u{1b}[{}m{cell}\u{1b}[0m", rule.color)
+    }
+}
+
+/// Applies `config.format_rules` to every body cell; the header and footer
+/// rows, when the config has them, are left alone. Runs before layout, so ANSI and HTML output
+/// (which is converted from ANSI) show the same marks.
+pub fn apply_format_rules(rows: &mut [Row], config: &TableConfig) {
+    if config.format_rules.is_empty() {
+        return;
+    }
+
+    let body_rows = config.body_rows(rows.len());
+    for row in &mut rows[body_rows] {
+        for (column, cell) in row.iter_mut().enumerate() {
+            *cell = format_cell(cell, column, &config.format_rules, config.symbols_only);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> Vec<FormatRule> {
+        vec![
+            FormatRule {
+                column: Some(0),
+                condition: FormatCondition::Equals("down".to_string()),
+                color: "31".to_string(),
+                symbol: Some("!".to_string()),
+            },
+            FormatRule {
+                column: None,
+                condition: FormatCondition::Negative,
+                color: "31".to_string(),
+                symbol: None,
+            },
+            FormatRule {
+                column: None,
+                condition: FormatCondition::Positive,
+                color: "32".to_string(),
+                symbol: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_format_cell_colors_and_symbols() {
+        let rules = rules();
+        assert_eq!(
+            format_cell("down", 0, &rules, false),
+            "\u{1b}[31mdown\u{1b}[0m"
+        );
+        assert_eq!(format_cell("down", 0, &rules, true), "! down");
+        assert_eq!(format_cell("down", 1, &rules, true), "down");
+        assert_eq!(format_cell("-2.5", 1, &rules, true), "↓ -2.5");
+        assert_eq!(format_cell("3", 1, &rules, true), "↑ 3");
+        assert_eq!(format_cell("0", 1, &rules, true), "0");
+    }
+
+    #[test]
+    fn test_apply_format_rules_skips_header() {
+        let mut rows = vec![
+            vec!["Status".to_string(), "-1".to_string()],
+            vec!["down".to_string(), "-1".to_string()],
+        ];
+        let config = TableConfig {
+            header: Some(Box::default()),
+            format_rules: rules(),
+            symbols_only: true,
+            ..Default::default()
+        };
+
+        apply_format_rules(&mut rows, &config);
+        assert_eq!(rows[0], vec!["Status", "-1"]);
+        assert_eq!(rows[1], vec!["! down", "↓ -1"]);
+    }
+}
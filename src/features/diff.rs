@@ -0,0 +1,280 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::features::styling::style_cell;
+use crate::types::{Row, TableResult, TableUserConfig};
+
+// SGR parameters for each kind of change.
+const ADDED: &str = "32";
+const REMOVED: &str = "31;9";
+const CHANGED: &str = "33";
+
+/// What happened to a row between the two tables, as its marker shows it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RowChange {
+    /// Only in the new table; marked `+` and drawn green.
+    Added,
+    /// Only in the old table; marked `-` and drawn red and struck through.
+    Removed,
+    /// In both with different cells, which are drawn yellow as `old → new`;
+    /// marked `~`.
+    Changed,
+    /// In both as it was; unmarked.
+    Unchanged,
+}
+
+impl RowChange {
+    pub fn marker(self) -> &'static str {
+        match self {
+            RowChange::Added => "+",
+            RowChange::Removed => "-",
+            RowChange::Changed => "~",
+            RowChange::Unchanged => "",
+        }
+    }
+}
+
+/// The combined rows of a comparison of `old` with `new`, each with what
+/// changed about it; changed cells read `old → new`. Body rows are matched
+/// by their first cell, the n-th row with a key in `old` pairing with the
+/// n-th in `new`, so reordered rows are not reported. Rows keep the order of
+/// `new`, with removed rows after the row that preceded them in `old`.
+///
+/// Header and footer rows, as `user_config` sets them, are taken from `new`
+/// and not compared. Rows shorter than the widest are filled with empty
+/// cells.
+pub fn diff_table_data(
+    old: &[Row],
+    new: &[Row],
+    user_config: Option<&TableUserConfig>,
+) -> Vec<(RowChange, Row)> {
+    let has_header = user_config.is_some_and(|config| {
+        config.header.is_some()
+            || config
+                .first_row_is_header
+                .is_some_and(|header| header.resolve(new))
+    });
+    let has_footer = user_config.is_some_and(|config| config.footer.is_some());
+    let body = |rows: &[Row]| {
+        let start = usize::from(has_header).min(rows.len());
+        let end = rows
+            .len()
+            .saturating_sub(usize::from(has_footer))
+            .max(start);
+        start..end
+    };
+    let (old_body, new_body) = (body(old), body(new));
+    let column_count = old.iter().chain(new).map(Vec::len).max().unwrap_or(0);
+    let padded = |row: &Row| {
+        let mut row = row.clone();
+        row.resize(column_count, String::new());
+        row
+    };
+
+    let mut unmatched: HashMap<Option<&str>, VecDeque<usize>> = HashMap::new();
+    for old_idx in old_body.clone() {
+        let key = old[old_idx].first().map(String::as_str);
+        unmatched.entry(key).or_default().push_back(old_idx);
+    }
+    let matches: Vec<Option<usize>> = new[new_body.clone()]
+        .iter()
+        .map(|row| {
+            let key = row.first().map(String::as_str);
+            unmatched.get_mut(&key).and_then(VecDeque::pop_front)
+        })
+        .collect();
+    // Removed rows by the matched row before them in `old`, if any.
+    let mut removed_after: HashMap<Option<usize>, Vec<usize>> = HashMap::new();
+    let mut preceding = None;
+    let mut matched = vec![false; old.len()];
+    for &old_idx in matches.iter().flatten() {
+        matched[old_idx] = true;
+    }
+    for old_idx in old_body {
+        if matched[old_idx] {
+            preceding = Some(old_idx);
+        } else {
+            removed_after.entry(preceding).or_default().push(old_idx);
+        }
+    }
+    let removed_rows = |after: Option<usize>| {
+        removed_after
+            .get(&after)
+            .into_iter()
+            .flatten()
+            .map(|&old_idx| (RowChange::Removed, padded(&old[old_idx])))
+    };
+
+    let mut rows = Vec::with_capacity(new.len() + old.len());
+    rows.extend(
+        new[..new_body.start]
+            .iter()
+            .map(|row| (RowChange::Unchanged, padded(row))),
+    );
+    rows.extend(removed_rows(None));
+    for (row, matched) in new[new_body.clone()].iter().zip(&matches) {
+        match *matched {
+            Some(old_idx) => {
+                rows.push(compare_rows(&padded(&old[old_idx]), padded(row)));
+                rows.extend(removed_rows(Some(old_idx)));
+            }
+            None => rows.push((RowChange::Added, padded(row))),
+        }
+    }
+    rows.extend(
+        new[new_body.end..]
+            .iter()
+            .map(|row| (RowChange::Unchanged, padded(row))),
+    );
+    rows
+}
+
+// `new` as the comparison with `old` draws it: changed cells as `old → new`.
+fn compare_rows(old: &Row, mut new: Row) -> (RowChange, Row) {
+    let mut change = RowChange::Unchanged;
+    for (old_cell, new_cell) in old.iter().zip(new.iter_mut()) {
+        if old_cell != new_cell {
+            *new_cell = style_cell(&format!("{old_cell} → {new_cell}"), CHANGED);
+            change = RowChange::Changed;
+        }
+    }
+    (change, new)
+}
+
+/// Draws `old` and `new` as one table, for comparing two exports of the same
+/// data, e.g. in CI logs: a first column marks added (`+`), removed (`-`)
+/// and changed (`~`) rows, added rows are green, removed rows red and struck
+/// through, and changed cells yellow as `old → new`. Rows are matched as
+/// `diff_table_data` matches them.
+///
+/// `user_config` draws the combined table, so its column settings count the
+/// marker column first. Under `ColorMode::Strip` only the markers and
+/// arrows tell the changes apart.
+pub fn diff_tables(
+    old: &[Row],
+    new: &[Row],
+    user_config: Option<&TableUserConfig>,
+) -> TableResult<String> {
+    let rows: Vec<Row> = diff_table_data(old, new, user_config)
+        .into_iter()
+        .map(|(change, cells)| {
+            let style = match change {
+                RowChange::Added => Some(ADDED),
+                RowChange::Removed => Some(REMOVED),
+                RowChange::Changed => Some(CHANGED),
+                RowChange::Unchanged => None,
+            };
+            let marker = change.marker().to_string();
+            let cells = cells.into_iter().map(|cell| match change {
+                RowChange::Added | RowChange::Removed if !cell.is_empty() => {
+                    style_cell(&cell, style.unwrap_or_default())
+                }
+                _ => cell,
+            });
+            std::iter::once(match style {
+                Some(style) => style_cell(&marker, style),
+                None => marker,
+            })
+            .chain(cells)
+            .collect()
+        })
+        .collect();
+    crate::table(&rows, user_config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(data: &[&[&str]]) -> Vec<Row> {
+        data.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_table_data_matches_rows_by_key() {
+        let old = rows(&[
+            &["id", "name"],
+            &["1", "web"],
+            &["2", "db"],
+            &["3", "cache"],
+        ]);
+        let new = rows(&[
+            &["id", "name"],
+            &["3", "cache"],
+            &["1", "www"],
+            &["4", "queue"],
+        ]);
+        let config = TableUserConfig {
+            header: Some(Box::default()),
+            ..Default::default()
+        };
+
+        let diff = diff_table_data(&old, &new, Some(&config));
+        let changes: Vec<(RowChange, &str)> = diff
+            .iter()
+            .map(|(change, row)| (*change, row[0].as_str()))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                (RowChange::Unchanged, "id"),
+                (RowChange::Unchanged, "3"),
+                (RowChange::Changed, "1"),
+                (RowChange::Removed, "2"),
+                (RowChange::Added, "4"),
+            ]
+        );
+        assert_eq!(diff[2].1[1], "\u{1b}[33mweb → www\u{1b}[0m");
+
+        // Rows are compared with the cells an added or removed column gives
+        // them.
+        let wider = rows(&[&["id", "name", "port"], &["1", "web", "80"]]);
+        let narrower = rows(&[&["id", "name"], &["1", "web"]]);
+        let cells = |old: &[Row], new: &[Row]| {
+            let diff = diff_table_data(old, new, Some(&config));
+            (diff[1].0, diff[1].1.clone())
+        };
+        assert_eq!(
+            cells(&narrower, &wider),
+            (
+                RowChange::Changed,
+                vec![
+                    "1".to_string(),
+                    "web".to_string(),
+                    "\u{1b}[33m → 80\u{1b}[0m".to_string()
+                ]
+            )
+        );
+        assert_eq!(
+            cells(&wider, &narrower),
+            (
+                RowChange::Changed,
+                vec![
+                    "1".to_string(),
+                    "web".to_string(),
+                    "\u{1b}[33m80 → \u{1b}[0m".to_string()
+                ]
+            )
+        );
+
+        let output = diff_tables(
+            &old,
+            &new,
+            Some(&TableUserConfig {
+                color_mode: Some(crate::types::ColorMode::Strip),
+                ..config
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            output.lines().skip(3).step_by(2).collect::<Vec<_>>(),
+            [
+                "│   │ 3  │ cache     │",
+                "│ ~ │ 1  │ web → www │",
+                "│ - │ 2  │ db        │",
+                "│ + │ 4  │ queue     │",
+            ]
+        );
+    }
+}
@@ -0,0 +1,446 @@
+use crate::core::calculator::{calculate_table_width, row_config};
+use crate::core::processor::{layout_row, truncate_table_data};
+use crate::core::renderer::{
+    BorderType, calculate_table_layout, draw_row_annotation, fit_table_config, line_override,
+    outer_borders, row_stripe, separator_after,
+};
+use crate::features::column_summary::draw_column_legend;
+use crate::types::{BorderConfig, BorderLine, Row, TableAlignment, TableConfig};
+
+// Bytes of the reset `stripe_line` ends a striped line with.
+const RESET_BYTES: usize = "\u{1b}[0m".len();
+
+/// The size of a drawn table, as `estimate_render_size` works it out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SizeEstimate {
+    /// Lines of output, as `str::lines` counts them.
+    pub lines: usize,
+    /// Length of the output in bytes.
+    pub bytes: usize,
+    /// Whether `lines` and `bytes` are the exact size. Otherwise they are
+    /// upper bounds: striped rows with ANSI sequences, spanning cells, a title
+    /// and a caption are bounded rather than counted, as is output whose
+    /// colors `ColorMode::Strip` removes.
+    pub exact: bool,
+}
+
+// The output counted so far, in the order the renderer writes it.
+struct Tally {
+    lines: usize,
+    bytes: usize,
+    exact: bool,
+    ends_with_newline: bool,
+}
+
+impl Tally {
+    fn line(&mut self, bytes: usize) {
+        self.lines += 1;
+        self.bytes += bytes + 1;
+        self.ends_with_newline = true;
+    }
+}
+
+/// The size `draw_table` output would have for `rows` and `config`, from the
+/// table layout and the lines each row is laid out to, but without drawing
+/// the table.
+pub fn estimate_table_size(rows: &[Row], config: &TableConfig) -> SizeEstimate {
+    if rows.is_empty() {
+        return SizeEstimate {
+            exact: true,
+            ..Default::default()
+        };
+    }
+
+    let layout = calculate_table_layout(rows, config);
+    let widths = &layout.column_widths;
+    let truncated_rows = truncate_table_data(rows, config);
+    let fitted_config = fit_table_config(&truncated_rows, config);
+    let row_count = rows.len();
+    let span_line = span_line_bound(&truncated_rows, widths, config);
+    let mut tally = Tally {
+        lines: 0,
+        bytes: 0,
+        exact: span_line.is_none(),
+        ends_with_newline: false,
+    };
+    let border_line = |border, border_type, line_idx| {
+        span_line
+            .unwrap_or_else(|| border_line_bytes(border, border_type, line_idx, widths, config))
+    };
+
+    let (draw_top, draw_bottom) = outer_borders(config, row_count);
+    if draw_top {
+        let border = &config.header.as_deref().unwrap_or(config).border;
+        tally.line(border_line(border, BorderType::Top, 0));
+    }
+
+    for (row_idx, (row, &height)) in truncated_rows.iter().zip(&layout.row_heights).enumerate() {
+        let section = row_config(config, row_idx, row_count);
+        let mut bytes = match span_line {
+            Some(line) => height * line,
+            None => row_bytes(row, widths, row_config(&fitted_config, row_idx, row_count)),
+        };
+        if let Some(stripe) = row_stripe(config, row_idx, row_count) {
+            // The stripe is opened again after every escape that clears it.
+            let escapes = row
+                .iter()
+                .map(|cell| cell.matches('\u{1b}').count())
+                .sum::<usize>();
+            tally.exact &= escapes == 0;
+            bytes += height * ((stripe.len() + 3) * (1 + escapes) + RESET_BYTES);
+        }
+        tally.lines += height;
+        tally.bytes += bytes + height;
+        tally.ends_with_newline = true;
+
+        for annotation in config.row_annotations.iter().filter(|a| a.row == row_idx) {
+            for line in draw_row_annotation(annotation, widths, &section.border) {
+                tally.line(line.len());
+            }
+        }
+
+        if let Some((border, border_type)) = separator_after(config, row_idx, row_count) {
+            tally.line(border_line(border, border_type, row_idx + 1));
+        }
+    }
+
+    if draw_bottom {
+        let border = &config.footer.as_deref().unwrap_or(config).border;
+        let bytes = border_line(border, BorderType::Bottom, row_count);
+        // An empty bottom line leaves the output ending with a newline.
+        if bytes > 0 {
+            tally.lines += 1;
+            tally.bytes += bytes;
+            tally.ends_with_newline = false;
+        }
+    }
+
+    let table_width = calculate_table_width(widths, config);
+    for label in [&config.title, &config.caption].into_iter().flatten() {
+        // Set into a border line or drawn on its own, padded to the table.
+        tally.exact = false;
+        tally.lines += 1;
+        tally.bytes += label.text.len() + config.column_default.ellipsis.len() + table_width + 3;
+    }
+
+    if config.column_summaries {
+        for line in draw_column_legend(rows, config, table_width) {
+            if !tally.ends_with_newline {
+                tally.bytes += 1;
+            }
+            tally.lines += 1;
+            tally.bytes += line.len();
+            tally.ends_with_newline = false;
+        }
+    }
+
+    let indent = config.table_indent + alignment_offset(config, table_width);
+    if indent > 0 {
+        let pieces = tally.lines + usize::from(tally.ends_with_newline);
+        tally.bytes += indent * pieces;
+        tally.lines = pieces;
+    }
+
+    SizeEstimate {
+        lines: tally.lines,
+        bytes: tally.bytes,
+        exact: tally.exact,
+    }
+}
+
+// Bytes of the lines of `row` as the renderer lays them out with the
+// `section` config, without their newlines.
+fn row_bytes(row: &[String], widths: &[usize], section: &TableConfig) -> usize {
+    let lines = layout_row(row, widths, section, None);
+    lines.len() - lines.matches('\n').count()
+}
+
+// Bytes of the `border_type` line above row `line_idx`, with the horizontal
+// `row_overrides` may set.
+fn border_line_bytes(
+    border: &BorderConfig,
+    border_type: BorderType,
+    line_idx: usize,
+    widths: &[usize],
+    config: &TableConfig,
+) -> usize {
+    let mut line = match border_type {
+        BorderType::Top => border.top_line(),
+        BorderType::Header => border.header_line(),
+        BorderType::Join => border.body_row_line(),
+        BorderType::Footer => border.footer_line(),
+        BorderType::Bottom => border.bottom_line(),
+    };
+    if let (_, Some(horizontal)) = line_override(config, line_idx) {
+        line.body = horizontal.to_string();
+    }
+    let BorderLine {
+        left,
+        body,
+        join,
+        right,
+    } = line;
+    left.len()
+        + widths.iter().sum::<usize>() * body.len()
+        + widths.len().saturating_sub(1) * join.len()
+        + right.len()
+}
+
+// With spanning cells, a bound on the bytes of any line: every border
+// character and column as long as the longest border string (or a four-byte
+// character), plus the widest row's cells and every span's content with their
+// escapes.
+fn span_line_bound(rows: &[Row], widths: &[usize], config: &TableConfig) -> Option<usize> {
+    if config.spanning_cells.is_empty() {
+        return None;
+    }
+    let cells = |row: &Row| -> usize { row.iter().map(|cell| cell.len() + RESET_BYTES).sum() };
+    let spans: usize = config
+        .spanning_cells
+        .iter()
+        .filter_map(|span| rows.get(span.row)?.get(span.col))
+        .map(|cell| cell.len() + RESET_BYTES)
+        .sum();
+    let sections = [
+        config.header.as_deref(),
+        config.footer.as_deref(),
+        Some(config),
+    ];
+    let longest_border = sections
+        .into_iter()
+        .flatten()
+        .map(|section| longest_border_string(&section.border))
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let characters = widths.len() + 1 + widths.iter().sum::<usize>();
+    Some(characters * longest_border + rows.iter().map(cells).max().unwrap_or(0) + spans)
+}
+
+fn longest_border_string(border: &BorderConfig) -> usize {
+    let lines = [
+        border.top_line(),
+        border.header_line(),
+        border.body_row_line(),
+        border.footer_line(),
+        border.bottom_line(),
+        BorderLine::new(
+            &border.body_left,
+            &border.body_join,
+            &border.body_join,
+            &border.body_right,
+        ),
+    ];
+    lines
+        .iter()
+        .flat_map(|line| [&line.left, &line.body, &line.join, &line.right])
+        .map(String::len)
+        .max()
+        .unwrap_or(0)
+}
+
+// The spaces `position_table_block` adds for `table_alignment`.
+fn alignment_offset(config: &TableConfig, table_width: usize) -> usize {
+    if config.table_alignment == TableAlignment::Left {
+        return 0;
+    }
+    let available_width = config
+        .terminal_width
+        .unwrap_or_else(crate::utils::terminal::terminal_width)
+        .saturating_sub(config.table_indent);
+    let slack = available_width.saturating_sub(table_width);
+    if config.table_alignment == TableAlignment::Center {
+        slack / 2
+    } else {
+        slack
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::features::styling::CellStyle;
+    use crate::types::{
+        Alignment, ColumnUserConfig, RowAnnotation, RowOverride, SpanningCellConfig, TableLabel,
+        TableUserConfig, Width,
+    };
+    use crate::{SizeEstimate, estimate_render_size, table};
+
+    fn rows() -> Vec<Vec<String>> {
+        [
+            ["Name", "Description", "Price"],
+            ["Café", "A long description that wraps", "3.50"],
+            ["\u{1b}[31mTea\u{1b}[0m", "東京 green", "2"],
+            ["Total", "", "5.50"],
+        ]
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect()
+    }
+
+    fn check(rows: &[Vec<String>], config: &TableUserConfig) -> SizeEstimate {
+        let output = table(rows, Some(config)).unwrap();
+        let estimate = estimate_render_size(rows, Some(config)).unwrap();
+        let actual = (output.lines().count(), output.len());
+        if estimate.exact {
+            assert_eq!((estimate.lines, estimate.bytes), actual, "{output}");
+        } else {
+            assert!(
+                estimate.lines >= actual.0 && estimate.bytes >= actual.1,
+                "{output}"
+            );
+        }
+        estimate
+    }
+
+    #[test]
+    fn test_estimates_are_exact_for_plain_cells() {
+        let plain: Vec<Vec<String>> = rows()
+            .into_iter()
+            .filter(|row| row[0] != rows()[2][0])
+            .collect();
+        let narrow = TableUserConfig {
+            columns: Some(vec![
+                ColumnUserConfig::default(),
+                ColumnUserConfig {
+                    width: Some(Width::Chars(12)),
+                    truncate: Some(20),
+                    ..Default::default()
+                },
+                ColumnUserConfig {
+                    alignment: Some(Alignment::Right),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+        let configs = [
+            TableUserConfig::default(),
+            narrow.clone(),
+            TableUserConfig {
+                header: Some(Box::default()),
+                footer: Some(Box::default()),
+                border: Some(crate::get_border_characters("double").unwrap().into()),
+                row_striping: Some("7".to_string()),
+                ..narrow.clone()
+            },
+            TableUserConfig {
+                row_annotations: Some(vec![RowAnnotation {
+                    row: 1,
+                    text: "Fresh from the market".to_string(),
+                    indent: None,
+                    dim: None,
+                }]),
+                row_overrides: Some(
+                    [(
+                        2,
+                        RowOverride {
+                            horizontal_before: Some("═".to_string()),
+                            ..Default::default()
+                        },
+                    )]
+                    .into(),
+                ),
+                table_indent: Some(4),
+                ..Default::default()
+            },
+            TableUserConfig {
+                border: Some(crate::get_border_characters("void").unwrap().into()),
+                table_indent: Some(2),
+                column_summaries: Some(true),
+                ..Default::default()
+            },
+        ];
+
+        for config in &configs {
+            assert!(check(&plain, config).exact);
+        }
+    }
+
+    #[test]
+    fn test_estimates_count_the_lines_rows_are_laid_out_to() {
+        // Fill characters wider than a byte and styled cells are counted from
+        // the laid out lines.
+        let configs = [
+            TableUserConfig {
+                color_mode: Some(crate::ColorMode::Preserve),
+                ..Default::default()
+            },
+            TableUserConfig {
+                color_mode: Some(crate::ColorMode::Preserve),
+                column_default: Some(ColumnUserConfig {
+                    pad_char: Some('·'),
+                    ..Default::default()
+                }),
+                columns: Some(vec![
+                    ColumnUserConfig::default(),
+                    ColumnUserConfig {
+                        width: Some(Width::Chars(12)),
+                        alignment: Some(Alignment::Center),
+                        ..Default::default()
+                    },
+                    ColumnUserConfig {
+                        alignment: Some(Alignment::Right),
+                        ..Default::default()
+                    },
+                ]),
+                ..Default::default()
+            },
+        ];
+
+        for config in &configs {
+            assert!(check(&rows(), config).exact);
+        }
+    }
+
+    #[test]
+    fn test_estimates_bound_what_they_cannot_count() {
+        let configs = [
+            TableUserConfig {
+                style_rules: Some(vec![crate::StyleRule {
+                    column: Some(1),
+                    row: None,
+                    condition: crate::StyleCondition::Any,
+                    style: CellStyle::color("32"),
+                    symbol: None,
+                }]),
+                row_striping: Some("48;5;236".to_string()),
+                ..Default::default()
+            },
+            TableUserConfig {
+                spanning_cells: Some(vec![SpanningCellConfig {
+                    col: 0,
+                    row: 3,
+                    col_span: Some(2),
+                    row_span: None,
+                    alignment: None,
+                    vertical_alignment: None,
+                    padding_left: None,
+                    padding_right: None,
+                    truncate: None,
+                    wrap_word: None,
+                }]),
+                ..Default::default()
+            },
+            TableUserConfig {
+                title: Some(TableLabel {
+                    text: "Prices".to_string(),
+                    alignment: None,
+                    placement: None,
+                }),
+                ..Default::default()
+            },
+        ];
+
+        for config in &configs {
+            assert!(!check(&rows(), config).exact);
+        }
+        assert_eq!(
+            estimate_render_size(&[], None).unwrap(),
+            SizeEstimate {
+                lines: 0,
+                bytes: 0,
+                exact: true
+            }
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use crate::features::stats::normalize_value;
+use crate::types::{Row, TableConfig, TableError, TableResult};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub type RowPredicate = Arc<dyn Fn(&[String]) -> bool + Send + Sync>;
+
+/// Keeps only the rows that match. Built-in matchers compare a column's text
+/// with ANSI styling and surrounding whitespace removed; `Predicate` sees the
+/// whole row and cannot be written to or read from config files.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RowFilter {
+    Equals {
+        column: usize,
+        value: String,
+    },
+    Contains {
+        column: usize,
+        value: String,
+    },
+    Regex {
+        column: usize,
+        pattern: String,
+    },
+    #[serde(skip)]
+    Predicate(RowPredicate),
+}
+
+impl RowFilter {
+    pub fn predicate(predicate: impl Fn(&[String]) -> bool + Send + Sync + 'static) -> Self {
+        RowFilter::Predicate(Arc::new(predicate))
+    }
+
+    /// Parses `COLUMN=VALUE` (equals), `COLUMN~VALUE` (contains) or
+    /// `COLUMN=~PATTERN` (regex). `COLUMN` is a 0-based index, or a name looked
+    /// up in `header` when one is given.
+    pub fn parse(spec: &str, header: Option<&[String]>) -> TableResult<Self> {
+        let invalid = |reason: &str| TableError::parse("filter", spec, reason);
+
+        let (column, operator, value) = ["=~", "~", "="]
+            .iter()
+            .filter_map(|operator| {
+                spec.split_once(operator)
+                    .map(|(column, value)| (column.trim(), *operator, value))
+            })
+            .min_by_key(|(column, _, _)| column.len())
+            .ok_or_else(|| invalid("expected COLUMN=VALUE, COLUMN~VALUE or COLUMN=~PATTERN"))?;
+
+        let column = match column.parse::<usize>() {
+            Ok(index) => index,
+            Err(_) => header
+                .and_then(|header| {
+                    header
+                        .iter()
+                        .position(|name| normalize_value(name) == column)
+                })
+                .ok_or_else(|| invalid(&format!("unknown column `{column}`")))?,
+        };
+
+        let value = value.to_string();
+        Ok(match operator {
+            "=~" => RowFilter::Regex {
+                column,
+                pattern: value,
+            },
+            "~" => RowFilter::Contains { column, value },
+            _ => RowFilter::Equals { column, value },
+        })
+    }
+
+    /// The column a built-in matcher reads.
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            RowFilter::Equals { column, .. }
+            | RowFilter::Contains { column, .. }
+            | RowFilter::Regex { column, .. } => Some(*column),
+            RowFilter::Predicate(_) => None,
+        }
+    }
+}
+
+impl std::str::FromStr for RowFilter {
+    type Err = TableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RowFilter::parse(s, None)
+    }
+}
+
+impl std::fmt::Debug for RowFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RowFilter::Equals { column, value } => f
+                .debug_struct("Equals")
+                .field("column", column)
+                .field("value", value)
+                .finish(),
+            RowFilter::Contains { column, value } => f
+                .debug_struct("Contains")
+                .field("column", column)
+                .field("value", value)
+                .finish(),
+            RowFilter::Regex { column, pattern } => f
+                .debug_struct("Regex")
+                .field("column", column)
+                .field("pattern", pattern)
+                .finish(),
+            RowFilter::Predicate(_) => f.write_str("Predicate(..)"),
+        }
+    }
+}
+
+// Predicates are only equal to themselves.
+impl PartialEq for RowFilter {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                RowFilter::Equals { column, value },
+                RowFilter::Equals {
+                    column: other_column,
+                    value: other_value,
+                },
+            )
+            | (
+                RowFilter::Contains { column, value },
+                RowFilter::Contains {
+                    column: other_column,
+                    value: other_value,
+                },
+            )
+            | (
+                RowFilter::Regex {
+                    column,
+                    pattern: value,
+                },
+                RowFilter::Regex {
+                    column: other_column,
+                    pattern: other_value,
+                },
+            ) => column == other_column && value == other_value,
+            (RowFilter::Predicate(a), RowFilter::Predicate(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for RowFilter {}
+
+// Consistent with `PartialEq`: predicates hash by identity.
+impl std::hash::Hash for RowFilter {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            RowFilter::Equals { column, value } | RowFilter::Contains { column, value } => {
+                (column, value).hash(state)
+            }
+            RowFilter::Regex { column, pattern } => (column, pattern).hash(state),
+            RowFilter::Predicate(predicate) => Arc::as_ptr(predicate).cast::<()>().hash(state),
+        }
+    }
+}
+
+enum CompiledFilter<'a> {
+    Equals(usize, &'a str),
+    Contains(usize, &'a str),
+    Regex(usize, Regex),
+    Predicate(&'a RowPredicate),
+}
+
+impl CompiledFilter<'_> {
+    fn matches(&self, row: &[String]) -> bool {
+        let cell = |column: usize| normalize_value(row.get(column).map_or("", String::as_str));
+        match self {
+            CompiledFilter::Equals(column, value) => cell(*column) == *value,
+            CompiledFilter::Contains(column, value) => cell(*column).contains(value),
+            CompiledFilter::Regex(column, regex) => regex.is_match(&cell(*column)),
+            CompiledFilter::Predicate(predicate) => predicate(row),
+        }
+    }
+}
+
+fn compile(filters: &[RowFilter]) -> TableResult<Vec<CompiledFilter<'_>>> {
+    filters
+        .iter()
+        .map(|filter| {
+            Ok(match filter {
+                RowFilter::Equals { column, value } => CompiledFilter::Equals(*column, value),
+                RowFilter::Contains { column, value } => CompiledFilter::Contains(*column, value),
+                RowFilter::Regex { column, pattern } => {
+                    let regex =
+                        Regex::new(pattern).map_err(|source| TableError::InvalidPattern {
+                            kind: "filter",
+                            pattern: pattern.clone(),
+                            source,
+                        })?;
+                    CompiledFilter::Regex(*column, regex)
+                }
+                RowFilter::Predicate(predicate) => CompiledFilter::Predicate(predicate),
+            })
+        })
+        .collect()
+}
+
+/// Drops the rows that do not match every filter in `config.filters`. The
+/// header and footer rows, when the config has them, are always kept.
+pub fn filter_rows(rows: Vec<Row>, config: &TableConfig) -> TableResult<Vec<Row>> {
+    if config.filters.is_empty() {
+        return Ok(rows);
+    }
+
+    let filters = compile(&config.filters)?;
+    let body_rows = config.body_rows(rows.len());
+
+    Ok(rows
+        .into_iter()
+        .enumerate()
+        .filter(|(row_idx, row)| {
+            !body_rows.contains(row_idx) || filters.iter().all(|filter| filter.matches(row))
+        })
+        .map(|(_, row)| row)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_rows() -> Vec<Row> {
+        vec![
+            vec!["Name".to_string(), "Status".to_string()],
+            vec!["web-01".to_string(), "Active".to_string()],
+            vec!["web-02".to_string(), "\u{1b}[31mDown\u{1b}[0m".to_string()],
+            vec!["db-01".to_string(), "Active".to_string()],
+        ]
+    }
+
+    fn filtered(filters: Vec<RowFilter>) -> Vec<String> {
+        let config = TableConfig {
+            header: Some(Box::default()),
+            filters,
+            ..Default::default()
+        };
+        filter_rows(sample_rows(), &config)
+            .unwrap()
+            .into_iter()
+            .map(|row| row[0].clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_rows_builtin_matchers() {
+        assert_eq!(
+            filtered(vec!["1=Active".parse().unwrap()]),
+            vec!["Name", "web-01", "db-01"]
+        );
+        assert_eq!(
+            filtered(vec!["1=Down".parse().unwrap(), "0~web".parse().unwrap()]),
+            vec!["Name", "web-02"]
+        );
+        assert_eq!(
+            filtered(vec!["0=~^db-\\d+$".parse().unwrap()]),
+            vec!["Name", "db-01"]
+        );
+    }
+
+    #[test]
+    fn test_filter_rows_predicate() {
+        let filter = RowFilter::predicate(|row| row[0].ends_with("01"));
+        assert_eq!(
+            filtered(vec![filter.clone()]),
+            vec!["Name", "web-01", "db-01"]
+        );
+        assert_eq!(filter, filter.clone());
+        assert_ne!(filter, RowFilter::predicate(|_| true));
+    }
+
+    #[test]
+    fn test_row_filter_parse() {
+        let header = &sample_rows()[0];
+        assert_eq!(
+            RowFilter::parse("Status=Active", Some(header)).unwrap(),
+            RowFilter::Equals {
+                column: 1,
+                value: "Active".to_string()
+            }
+        );
+        assert_eq!(
+            "2=~a=b".parse::<RowFilter>().unwrap(),
+            RowFilter::Regex {
+                column: 2,
+                pattern: "a=b".to_string()
+            }
+        );
+        assert!(RowFilter::parse("Owner=me", Some(header)).is_err());
+        assert!("no operator".parse::<RowFilter>().is_err());
+
+        let config = TableConfig {
+            filters: vec!["0=~(".parse().unwrap()],
+            ..Default::default()
+        };
+        assert!(filter_rows(sample_rows(), &config).is_err());
+    }
+}
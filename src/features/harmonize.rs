@@ -0,0 +1,190 @@
+use crate::core::renderer::calculate_table_layout;
+use crate::types::{ColumnUserConfig, Row, TableError, TableResult, TableUserConfig, Width};
+use crate::utils::formatting::stringify_table_data;
+
+#[derive(Debug, Clone)]
+pub struct TableSection<'a> {
+    pub data: &'a [Row],
+    pub config: TableUserConfig,
+}
+
+impl<'a> TableSection<'a> {
+    pub fn new(data: &'a [Row], config: Option<TableUserConfig>) -> Self {
+        Self {
+            data,
+            config: config.unwrap_or_default(),
+        }
+    }
+
+    pub fn render(&self) -> TableResult<String> {
+        crate::table(self.data, Some(&self.config))
+    }
+}
+
+/// Gives every section the same column widths, each the widest any section
+/// draws that column with, and returns them, one per drawn column. Sections
+/// are measured as `table` draws them, with their cells formatted, computed
+/// and filled in, and must draw as many columns, whichever they hide or add.
+pub fn harmonize_widths(sections: &mut [TableSection]) -> TableResult<Vec<usize>> {
+    let mut shared_widths: Vec<usize> = Vec::new();
+    let mut column_count = None;
+    let mut drawn_columns = Vec::with_capacity(sections.len());
+
+    for section in sections.iter() {
+        if section.data.is_empty() {
+            drawn_columns.push(None);
+            continue;
+        }
+
+        // Measured as `table` lays the section out, on the cells it draws.
+        let string_data = stringify_table_data(section.data)?;
+        let mut config = crate::resolve_table_config(&string_data, Some(&section.config))?;
+        let (rows, drawn) = crate::prepare_table_columns(string_data, &mut config)?;
+        let widths = calculate_table_layout(&rows, &config).column_widths;
+
+        match column_count {
+            Some(count) if count != widths.len() => {
+                return Err(TableError::InvalidConfig(
+                    "Tables must have the same number of columns to harmonize widths".to_string(),
+                ));
+            }
+            Some(_) => {}
+            None => {
+                column_count = Some(widths.len());
+                shared_widths = vec![0; widths.len()];
+            }
+        }
+
+        for (shared, width) in shared_widths.iter_mut().zip(widths) {
+            *shared = (*shared).max(width);
+        }
+        drawn_columns.push(Some(drawn));
+    }
+
+    for (section, drawn) in sections.iter_mut().zip(drawn_columns) {
+        let drawn = drawn.unwrap_or_else(|| (0..shared_widths.len()).collect());
+        let columns = section.config.columns.get_or_insert_with(Vec::new);
+        let needed = drawn.iter().max().map_or(0, |&col| col + 1);
+        if columns.len() < needed {
+            columns.resize(needed, ColumnUserConfig::default());
+        }
+
+        for (&col, &width) in drawn.iter().zip(&shared_widths) {
+            columns[col].width = Some(Width::Chars(width));
+        }
+    }
+
+    Ok(shared_widths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ansi::calculate_display_width;
+
+    #[test]
+    fn test_harmonize_widths() {
+        let first = vec![vec!["Name".to_string(), "Total".to_string()]];
+        let second = vec![vec!["A much longer name".to_string(), "1".to_string()]];
+
+        let mut sections = vec![
+            TableSection::new(&first, None),
+            TableSection::new(&second, None),
+        ];
+
+        let widths = harmonize_widths(&mut sections).unwrap();
+        assert_eq!(widths, vec![20, 7]);
+
+        let rendered: Vec<String> = sections.iter().map(|s| s.render().unwrap()).collect();
+        let first_line_widths: Vec<usize> = rendered
+            .iter()
+            .map(|table| calculate_display_width(table.lines().next().unwrap()))
+            .collect();
+        assert_eq!(first_line_widths[0], first_line_widths[1]);
+    }
+
+    #[test]
+    fn test_harmonize_widths_measures_formatted_cells() {
+        let first = vec![vec!["Item".to_string(), "1234567".to_string()]];
+        let second = vec![vec!["A longer item".to_string(), "12".to_string()]];
+        let config = TableUserConfig {
+            columns: Some(vec![
+                ColumnUserConfig::default(),
+                ColumnUserConfig {
+                    formatter: Some(crate::Formatter::Currency("$".to_string())),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let mut sections = vec![
+            TableSection::new(&first, Some(config.clone())),
+            TableSection::new(&second, Some(config)),
+        ];
+
+        let widths = harmonize_widths(&mut sections).unwrap();
+        assert_eq!(widths, vec![15, 15]);
+        let first = sections[0].render().unwrap();
+        assert_eq!(
+            first.lines().nth(1),
+            Some("│ Item          │ $1,234,567.00 │")
+        );
+    }
+
+    #[test]
+    fn test_harmonize_widths_follows_the_drawn_columns() {
+        let first = vec![vec![
+            "id".to_string(),
+            "Name".to_string(),
+            "7".to_string(),
+            "x".to_string(),
+        ]];
+        let second = vec![vec!["A much longer name".to_string(), "12".to_string()]];
+        let hide_id = TableUserConfig {
+            columns: Some(vec![ColumnUserConfig {
+                hidden: Some(true),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+        let with_rank = TableUserConfig {
+            computed_columns: Some(vec![crate::ComputedColumn {
+                column: 1,
+                computation: crate::Computation::Rank,
+                header: None,
+            }]),
+            ..Default::default()
+        };
+
+        let mut sections = vec![
+            TableSection::new(&first, Some(hide_id)),
+            TableSection::new(&second, Some(with_rank)),
+        ];
+
+        let widths = harmonize_widths(&mut sections).unwrap();
+        assert_eq!(widths, vec![20, 4, 5]);
+        let tables: Vec<String> = sections.iter().map(|s| s.render().unwrap()).collect();
+        assert_eq!(
+            tables[0].lines().nth(1),
+            Some("│ Name               │ 7  │ x   │")
+        );
+        assert_eq!(
+            tables[1].lines().nth(1),
+            Some("│ A much longer name │ 12 │ 1st │")
+        );
+    }
+
+    #[test]
+    fn test_harmonize_widths_column_mismatch() {
+        let first = vec![vec!["a".to_string()]];
+        let second = vec![vec!["a".to_string(), "b".to_string()]];
+
+        let mut sections = vec![
+            TableSection::new(&first, None),
+            TableSection::new(&second, None),
+        ];
+
+        assert!(harmonize_widths(&mut sections).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::types::Row;
+use crate::utils::ansi::strip_ansi_sequences;
+
+/// SGR parameters used by `HashColorizer::default()`: the six basic and six
+/// bright foreground colors, skipping black and white so values stay readable
+/// on both dark and light backgrounds.
+pub const DEFAULT_HASH_PALETTE: &[&str] = &[
+    "31", "32", "33", "34", "35", "36", "91", "92", "93", "94", "95", "96",
+];
+
+/// Colors values by a stable hash of their text, so the same hostname or user
+/// ID gets the same color in every table and across runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashColorizer {
+    palette: Vec<String>,
+}
+
+impl Default for HashColorizer {
+    fn default() -> Self {
+        Self {
+            palette: DEFAULT_HASH_PALETTE.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl HashColorizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses custom SGR parameters, e.g. `"38;5;208"` for 256-color orange or
+    /// `"1;34"` for bold blue. An empty palette leaves values uncolored.
+    pub fn with_palette<I, S>(palette: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            palette: palette.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub fn palette(&self) -> &[String] {
+        &self.palette
+    }
+
+    /// Returns the palette entry for `value`, ignoring existing ANSI styling and
+    /// surrounding whitespace.
+    pub fn color_for(&self, value: &str) -> Option<&str> {
+        if self.palette.is_empty() {
+            return None;
+        }
+
+        let key = strip_ansi_sequences(value);
+        let index = stable_hash(key.trim()) % self.palette.len() as u64;
+        Some(&self.palette[index as usize])
+    }
+
+    pub fn colorize(&self, value: &str) -> String {
+        match self.color_for(value) {
+            Some(color) if !value.trim().is_empty() => {
+                format!("\u{1b}[{color}m{value}\u{1b}[0m")
+            }
+            _ => value.to_string(),
+        }
+    }
+
+    /// Colors every cell of `column`, leaving the first row alone when it is a header.
+    pub fn apply_to_column(&self, rows: &mut [Row], column: usize, has_header: bool) {
+        let skip = usize::from(has_header);
+        for row in rows.iter_mut().skip(skip) {
+            if let Some(cell) = row.get_mut(column) {
+                *cell = self.colorize(cell);
+            }
+        }
+    }
+}
+
+// FNV-1a: unlike `DefaultHasher`, its output is fixed across Rust releases.
+fn stable_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_value_same_color() {
+        let colorizer = HashColorizer::new();
+        assert_eq!(
+            colorizer.color_for("web-01"),
+            colorizer.color_for(" \u{1b}[1mweb-01\u{1b}[0m ")
+        );
+        assert_eq!(stable_hash("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_custom_palette() {
+        let colorizer = HashColorizer::with_palette(["38;5;208"]);
+        assert_eq!(colorizer.colorize("x"), "\u{1b}[38;5;208mx\u{1b}[0m");
+        assert_eq!(colorizer.colorize(""), "");
+
+        let plain = HashColorizer::with_palette(Vec::<String>::new());
+        assert_eq!(plain.colorize("x"), "x");
+    }
+
+    #[test]
+    fn test_apply_to_column_skips_header() {
+        let mut rows = vec![
+            vec!["Host".to_string(), "Status".to_string()],
+            vec!["web-01".to_string(), "up".to_string()],
+        ];
+
+        HashColorizer::new().apply_to_column(&mut rows, 0, true);
+        assert_eq!(rows[0][0], "Host");
+        assert!(rows[1][0].starts_with("\u{1b}["));
+        assert_eq!(rows[1][1], "up");
+    }
+}
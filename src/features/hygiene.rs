@@ -0,0 +1,311 @@
+use std::fmt;
+
+use crate::types::Row;
+use crate::utils::ansi::skip_ansi_sequence;
+
+// Tab stops used when `clean_cell` expands tabs in mixed indentation.
+const TAB_WIDTH: usize = 4;
+
+/// A problem in cell text that draws fine but reads or compares wrongly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HygieneIssueKind {
+    /// A bidi control or zero-width character (see `is_invisible_character`),
+    /// which reorders or hides text without taking any width.
+    InvisibleCharacter(char),
+    /// Spaces or tabs at the end of a line, before any trailing ANSI sequences.
+    TrailingWhitespace,
+    /// Indentation that mixes tabs and spaces.
+    MixedTabs,
+}
+
+impl fmt::Display for HygieneIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HygieneIssueKind::InvisibleCharacter(c) => {
+                write!(f, "invisible character U+{:04X}", *c as u32)
+            }
+            HygieneIssueKind::TrailingWhitespace => f.write_str("trailing whitespace"),
+            HygieneIssueKind::MixedTabs => f.write_str("indentation mixes tabs and spaces"),
+        }
+    }
+}
+
+/// One issue found by `lint_rows`: the data row, column and line within the
+/// cell, all counted from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HygieneIssue {
+    pub row: usize,
+    pub column: usize,
+    pub line: usize,
+    pub kind: HygieneIssueKind,
+}
+
+impl fmt::Display for HygieneIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "row {}, column {}, line {}: {}",
+            self.row, self.column, self.line, self.kind
+        )
+    }
+}
+
+/// Bidi embedding, override and isolate controls, directional marks, and
+/// zero-width spaces. The joiners U+200C and U+200D are not included: emoji
+/// sequences and several scripts need them.
+pub fn is_invisible_character(c: char) -> bool {
+    matches!(
+        c,
+        '\u{061C}'
+            | '\u{200B}'
+            | '\u{200E}'
+            | '\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{FEFF}'
+    )
+}
+
+fn is_blank(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+enum Token {
+    Ansi(String),
+    Char(char),
+}
+
+impl Token {
+    fn is_char(&self, test: impl Fn(char) -> bool) -> bool {
+        matches!(self, Token::Char(c) if test(*c))
+    }
+}
+
+fn tokenize(line: &str) -> Vec<Token> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let end = skip_ansi_sequence(&chars, pos);
+        if end > pos {
+            tokens.push(Token::Ansi(chars[pos..end].iter().collect()));
+            pos = end;
+        } else {
+            tokens.push(Token::Char(chars[pos]));
+            pos += 1;
+        }
+    }
+
+    tokens
+}
+
+fn has_mixed_indentation(tokens: &[Token]) -> bool {
+    let indent = tokens
+        .iter()
+        .filter_map(|token| match token {
+            Token::Char(c) => Some(*c),
+            Token::Ansi(_) => None,
+        })
+        .take_while(|&c| is_blank(c));
+    let (mut spaces, mut tabs) = (false, false);
+    for c in indent {
+        spaces |= c == ' ';
+        tabs |= c == '\t';
+    }
+    spaces && tabs
+}
+
+fn line_issues(line: &str) -> Vec<HygieneIssueKind> {
+    let tokens = tokenize(line);
+    let mut issues: Vec<HygieneIssueKind> = tokens
+        .iter()
+        .filter_map(|token| match token {
+            Token::Char(c) if is_invisible_character(*c) => {
+                Some(HygieneIssueKind::InvisibleCharacter(*c))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let last_visible = tokens
+        .iter()
+        .rev()
+        .find(|token| token.is_char(|c| !is_invisible_character(c)));
+    if last_visible.is_some_and(|token| token.is_char(is_blank)) {
+        issues.push(HygieneIssueKind::TrailingWhitespace);
+    }
+
+    if has_mixed_indentation(&tokens) {
+        issues.push(HygieneIssueKind::MixedTabs);
+    }
+
+    issues
+}
+
+/// Every invisible character, trailing whitespace and mixed indentation in
+/// `rows`, in row, column and line order. ANSI sequences are skipped, so a
+/// color reset after the last word does not hide trailing spaces.
+pub fn lint_rows(rows: &[Row]) -> Vec<HygieneIssue> {
+    let mut issues = Vec::new();
+
+    for (row, cells) in rows.iter().enumerate() {
+        for (column, cell) in cells.iter().enumerate() {
+            for (line, text) in cell.split('\n').enumerate() {
+                issues.extend(line_issues(text).into_iter().map(|kind| HygieneIssue {
+                    row,
+                    column,
+                    line,
+                    kind,
+                }));
+            }
+        }
+    }
+
+    issues
+}
+
+fn clean_line(line: &str) -> String {
+    let tokens: Vec<Token> = tokenize(line)
+        .into_iter()
+        .filter(|token| !token.is_char(is_invisible_character))
+        .collect();
+    let content_end = tokens
+        .iter()
+        .rposition(|token| token.is_char(|c| !is_blank(c)))
+        .map_or(0, |index| index + 1);
+    let indent_end = tokens
+        .iter()
+        .position(|token| token.is_char(|c| !is_blank(c)))
+        .unwrap_or(tokens.len());
+    let expand_tabs = has_mixed_indentation(&tokens);
+
+    let mut cleaned = String::with_capacity(line.len());
+    let mut column = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Ansi(sequence) => cleaned.push_str(sequence),
+            Token::Char(_) if index >= content_end => {}
+            Token::Char('\t') if expand_tabs && index < indent_end => {
+                let stop = TAB_WIDTH - column % TAB_WIDTH;
+                cleaned.extend(std::iter::repeat_n(' ', stop));
+                column += stop;
+            }
+            Token::Char(c) => {
+                cleaned.push(*c);
+                column += 1;
+            }
+        }
+    }
+
+    cleaned
+}
+
+/// `cell` without the issues `lint_rows` reports: invisible characters are
+/// removed, trailing whitespace is trimmed from every line (keeping any ANSI
+/// sequences after it), and tabs in mixed indentation are expanded to spaces.
+pub fn clean_cell(cell: &str) -> String {
+    cell.split('\n')
+        .map(clean_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Cleans every cell in place with `clean_cell`, returning how many changed.
+pub fn clean_rows(rows: &mut [Row]) -> usize {
+    let mut changed = 0;
+
+    for cell in rows.iter_mut().flatten() {
+        let cleaned = clean_cell(cell);
+        if cleaned != *cell {
+            *cell = cleaned;
+            changed += 1;
+        }
+    }
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(cells: &[&[&str]]) -> Vec<Row> {
+        cells
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_rows_locates_each_issue() {
+        let data = rows(&[
+            &["name", "note"],
+            &["admin\u{202E}txt.exe", "fine"],
+            &["ok", "first line  \n\t  indented"],
+        ]);
+
+        assert_eq!(
+            lint_rows(&data),
+            vec![
+                HygieneIssue {
+                    row: 1,
+                    column: 0,
+                    line: 0,
+                    kind: HygieneIssueKind::InvisibleCharacter('\u{202E}'),
+                },
+                HygieneIssue {
+                    row: 2,
+                    column: 1,
+                    line: 0,
+                    kind: HygieneIssueKind::TrailingWhitespace,
+                },
+                HygieneIssue {
+                    row: 2,
+                    column: 1,
+                    line: 1,
+                    kind: HygieneIssueKind::MixedTabs,
+                },
+            ]
+        );
+        assert_eq!(
+            lint_rows(&data)[0].to_string(),
+            "row 1, column 0, line 0: invisible character U+202E"
+        );
+    }
+
+    #[test]
+    fn test_lint_rows_sees_through_ansi_and_allows_joiners() {
+        let data = rows(&[
+            &["\u{1b}[31mred \u{1b}[0m", "\u{1b}[1m\u{1b}[0m"],
+            &["👨\u{200D}👩\u{200D}👧", "\t\tindented"],
+        ]);
+
+        let kinds: Vec<_> = lint_rows(&data)
+            .into_iter()
+            .map(|issue| issue.kind)
+            .collect();
+        assert_eq!(kinds, vec![HygieneIssueKind::TrailingWhitespace]);
+    }
+
+    #[test]
+    fn test_clean_cell() {
+        assert_eq!(clean_cell("\u{FEFF}id\u{200B}"), "id");
+        assert_eq!(
+            clean_cell("\u{1b}[32mdone  \u{1b}[0m"),
+            "\u{1b}[32mdone\u{1b}[0m"
+        );
+        assert_eq!(clean_cell(" \tx\ty \n   "), "    x\ty\n");
+        assert_eq!(clean_cell("\t\tkept"), "\t\tkept");
+    }
+
+    #[test]
+    fn test_clean_rows_leaves_nothing_to_report() {
+        let mut data = rows(&[&["a\u{2066}b\u{2069}", "plain"], &["x \n  \ty", "\u{200F}"]]);
+
+        assert_eq!(clean_rows(&mut data), 3);
+        assert!(lint_rows(&data).is_empty());
+        assert_eq!(data, rows(&[&["ab", "plain"], &["x\n    y", ""]]));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::types::{Row, TableError, TableResult, TableUserConfig};
+use serde_json::{Map, Value};
+
+/// How `json_to_rows` turns objects into columns.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonColumns {
+    /// The keys to show, in order. When unset, every key found in any object,
+    /// in the order first seen.
+    pub columns: Option<Vec<String>>,
+    /// The cell text for keys an object does not have.
+    pub fill: String,
+}
+
+/// Cell text for a JSON value: strings unquoted, `null` empty, and numbers,
+/// booleans and nested values in their JSON form.
+pub fn value_to_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+// A header row of keys followed by one row per object.
+pub(crate) fn objects_to_rows<'a>(
+    objects: impl IntoIterator<Item = &'a Map<String, Value>> + Clone,
+    columns: &JsonColumns,
+) -> Vec<Row> {
+    let header = columns.columns.clone().unwrap_or_else(|| {
+        let mut keys: Vec<String> = Vec::new();
+        for object in objects.clone() {
+            for key in object.keys() {
+                if !keys.contains(key) {
+                    keys.push(key.clone());
+                }
+            }
+        }
+        keys
+    });
+
+    let body = objects.into_iter().map(|object| {
+        header
+            .iter()
+            .map(|key| {
+                object
+                    .get(key)
+                    .map_or_else(|| columns.fill.clone(), value_to_cell)
+            })
+            .collect::<Row>()
+    });
+
+    std::iter::once(header.clone()).chain(body).collect()
+}
+
+/// Converts a JSON array of objects to a header row of keys followed by one
+/// row per object. Key order is kept as written in the input.
+pub fn json_to_rows(value: &Value, columns: &JsonColumns) -> TableResult<Vec<Row>> {
+    let Value::Array(items) = value else {
+        return Err(TableError::InvalidData);
+    };
+    let objects = items
+        .iter()
+        .map(|item| item.as_object().ok_or(TableError::InvalidRowData))
+        .collect::<TableResult<Vec<_>>>()?;
+
+    Ok(objects_to_rows(objects.iter().copied(), columns))
+}
+
+/// Renders a JSON array of objects as a table, with the keys of every object as
+/// a header row styled by `config.header` (the body style when unset).
+pub fn table_from_json(value: &Value, config: Option<&TableUserConfig>) -> TableResult<String> {
+    table_from_json_with(value, &JsonColumns::default(), config)
+}
+
+/// Like `table_from_json`, choosing the columns and the text for missing keys.
+pub fn table_from_json_with(
+    value: &Value,
+    columns: &JsonColumns,
+    config: Option<&TableUserConfig>,
+) -> TableResult<String> {
+    let rows = json_to_rows(value, columns)?;
+    if rows[0].is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut config = config.cloned().unwrap_or_default();
+    config.header.get_or_insert_with(Box::default);
+    crate::table(&rows, Some(&config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn services() -> Value {
+        json!([
+            {"name": "web", "port": 443, "tls": true},
+            {"name": "db", "replicas": 3, "port": null},
+        ])
+    }
+
+    #[test]
+    fn test_value_to_cell() {
+        assert_eq!(value_to_cell(&json!("text")), "text");
+        assert_eq!(value_to_cell(&json!(42)), "42");
+        assert_eq!(value_to_cell(&json!(true)), "true");
+        assert_eq!(value_to_cell(&Value::Null), "");
+    }
+
+    #[test]
+    fn test_json_to_rows_unions_keys_in_order() {
+        let rows = json_to_rows(&services(), &JsonColumns::default()).unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                vec!["name", "port", "tls", "replicas"],
+                vec!["web", "443", "true", ""],
+                vec!["db", "", "", "3"],
+            ]
+        );
+
+        assert!(matches!(
+            json_to_rows(&json!({"a": 1}), &JsonColumns::default()),
+            Err(TableError::InvalidData)
+        ));
+        assert!(matches!(
+            json_to_rows(&json!([{"a": 1}, [1]]), &JsonColumns::default()),
+            Err(TableError::InvalidRowData)
+        ));
+    }
+
+    #[test]
+    fn test_json_to_rows_explicit_columns_and_fill() {
+        let columns = JsonColumns {
+            columns: Some(vec!["replicas".to_string(), "name".to_string()]),
+            fill: "-".to_string(),
+        };
+        let rows = json_to_rows(&services(), &columns).unwrap();
+        assert_eq!(
+            rows,
+            vec![vec!["replicas", "name"], vec!["-", "web"], vec!["3", "db"]]
+        );
+    }
+
+    #[test]
+    fn test_table_from_json() {
+        let output = table_from_json(&json!([{"a": 1, "b": "x"}]), None).unwrap();
+        assert_eq!(
+            output,
+            [
+                "┌───┬───┐",
+                "│ a │ b │",
+                "├───┼───┤",
+                "│ 1 │ x │",
+                "└───┴───┘",
+            ]
+            .join("\n")
+        );
+        assert_eq!(table_from_json(&json!([]), None).unwrap(), "");
+    }
+}
@@ -0,0 +1,263 @@
+use crate::features::viewport::{TableView, Viewport};
+use crate::types::{Row, TableError, TableResult, TableUserConfig};
+use crate::utils::terminal::{clear_below, clear_line, cursor_down, cursor_up};
+
+/// A table redrawn in place, e.g. for a progress dashboard. Every change
+/// returns what to print to turn the table printed last into the new one:
+/// cursor movement plus only the lines that differ. The output assumes the
+/// cursor was left where the previous output ended and that no line is wider
+/// than the terminal.
+///
+/// With a `max_height`, a taller table keeps its header and footer rows, such
+/// as a totals row, on screen while the body rows scroll between them.
+#[derive(Debug, Clone, Default)]
+pub struct LiveTable {
+    rows: Vec<Row>,
+    config: Option<TableUserConfig>,
+    printed: Vec<String>,
+    max_height: Option<usize>,
+    // The first body row shown when the table is cut to `max_height`; `None`
+    // follows the last rows.
+    scroll: Option<usize>,
+}
+
+impl LiveTable {
+    pub fn new(config: Option<TableUserConfig>) -> Self {
+        Self {
+            rows: Vec::new(),
+            config,
+            printed: Vec::new(),
+            max_height: None,
+            scroll: None,
+        }
+    }
+
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    /// The number of terminal lines the table took when last drawn.
+    pub fn printed_lines(&self) -> usize {
+        self.printed.len()
+    }
+
+    /// Appends a row and redraws. On error the row is not kept and nothing
+    /// needs printing.
+    pub fn push_row(&mut self, row: Row) -> TableResult<String> {
+        self.rows.push(row);
+        self.redraw().inspect_err(|_| {
+            self.rows.pop();
+        })
+    }
+
+    /// Replaces the row at `index` and redraws. On error the old row is kept
+    /// and nothing needs printing.
+    pub fn update_row(&mut self, index: usize, row: Row) -> TableResult<String> {
+        let bound = self.rows.len();
+        let slot = self
+            .rows
+            .get_mut(index)
+            .ok_or(TableError::IndexOutOfBounds {
+                setting: "Live table row",
+                index,
+                bound,
+            })?;
+        let previous = std::mem::replace(slot, row);
+
+        self.redraw().inspect_err(|_| {
+            self.rows[index] = previous;
+        })
+    }
+
+    /// Replaces the config, e.g. after a terminal resize, and redraws.
+    pub fn set_config(&mut self, config: Option<TableUserConfig>) -> TableResult<String> {
+        let previous = std::mem::replace(&mut self.config, config);
+        self.redraw().inspect_err(|_| {
+            self.config = previous;
+        })
+    }
+
+    /// Caps the table at `max_height` lines and redraws. A taller table is
+    /// drawn as `TableView::pinned_view` draws it, without title, caption or
+    /// positioning, showing the body rows `scroll_to` picked.
+    pub fn set_max_height(&mut self, max_height: Option<usize>) -> TableResult<String> {
+        let previous = std::mem::replace(&mut self.max_height, max_height);
+        self.redraw().inspect_err(|_| {
+            self.max_height = previous;
+        })
+    }
+
+    /// Shows the body rows from `body_row` (counted from the first body row)
+    /// when the table is cut to `max_height`, or with `None`, the last ones, so
+    /// new rows come into view as they are pushed. Redraws.
+    pub fn scroll_to(&mut self, body_row: Option<usize>) -> TableResult<String> {
+        let previous = std::mem::replace(&mut self.scroll, body_row);
+        self.redraw().inspect_err(|_| {
+            self.scroll = previous;
+        })
+    }
+
+    /// Erases the printed table, leaving the cursor where it started.
+    pub fn clear(&mut self) -> String {
+        let lines = std::mem::take(&mut self.printed).len();
+        format!("{}{}", cursor_up(lines), clear_below())
+    }
+
+    fn redraw(&mut self) -> TableResult<String> {
+        let mut lines: Vec<String> = crate::table(&self.rows, self.config.as_ref())?
+            .lines()
+            .map(str::to_string)
+            .collect();
+        if let Some(height) = self.max_height.filter(|&height| lines.len() > height) {
+            lines = self.pinned_lines(height)?;
+        }
+        let output = diff_lines(&self.printed, &lines);
+        self.printed = lines;
+        Ok(output)
+    }
+
+    // The table cut to `height` lines, header and footer kept.
+    fn pinned_lines(&self, height: usize) -> TableResult<Vec<String>> {
+        let (rows, config) = crate::prepare_table(&self.rows, self.config.as_ref())?;
+        let view = TableView::new(&rows, &config);
+        let viewport = Viewport {
+            width: usize::MAX,
+            height,
+        };
+        let body_top = self
+            .scroll
+            .unwrap_or_else(|| view.last_body_top(0, viewport));
+        Ok(view
+            .pinned_view(body_top, 0, viewport)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+// Rewrites the lines of `new` that differ from `old`, starting and ending on
+// the line below the last one. Unchanged lines are stepped over; lines past
+// the end of `old` are printed with newlines so the terminal scrolls.
+fn diff_lines(old: &[String], new: &[String]) -> String {
+    let first_change = old
+        .iter()
+        .zip(new)
+        .position(|(old_line, new_line)| old_line != new_line)
+        .unwrap_or(old.len().min(new.len()));
+    if first_change == old.len() && first_change == new.len() {
+        return String::new();
+    }
+
+    let mut output = cursor_up(old.len() - first_change);
+    let mut skipped = 0;
+    for (index, line) in new.iter().enumerate().skip(first_change) {
+        if old.get(index) == Some(line) {
+            skipped += 1;
+            continue;
+        }
+        output.push_str(&cursor_down(std::mem::take(&mut skipped)));
+        if index < old.len() {
+            output.push_str(&clear_line());
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+    output.push_str(&cursor_down(skipped));
+
+    if old.len() > new.len() {
+        output.push_str(&clear_below());
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(cells: &[&str]) -> Row {
+        cells.iter().map(|cell| cell.to_string()).collect()
+    }
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_diff_lines_rewrites_only_changes() {
+        let old = lines(&["a", "b", "c"]);
+        assert_eq!(diff_lines(&old, &old), "");
+        assert_eq!(diff_lines(&[], &old), "a\nb\nc\n");
+        assert_eq!(
+            diff_lines(&old, &lines(&["a", "B", "c"])),
+            format!("{}{}B\n{}", cursor_up(2), clear_line(), cursor_down(1))
+        );
+        assert_eq!(
+            diff_lines(&old, &lines(&["a", "b", "C", "d"])),
+            format!("{}{}C\nd\n", cursor_up(1), clear_line())
+        );
+        assert_eq!(
+            diff_lines(&old, &lines(&["a"])),
+            format!("{}{}", cursor_up(2), clear_below())
+        );
+    }
+
+    #[test]
+    fn test_live_table_push_and_update() {
+        let mut live = LiveTable::new(None);
+        let first = live.push_row(row(&["job", "50%"])).unwrap();
+        assert_eq!(first, crate::table(live.rows(), None).unwrap() + "\n");
+        assert_eq!(live.printed_lines(), 3);
+
+        // Only the row line changes; the borders are stepped over.
+        let update = live.update_row(0, row(&["job", "75%"])).unwrap();
+        assert_eq!(
+            update,
+            format!(
+                "{}{}│ job │ 75% │\n{}",
+                cursor_up(2),
+                clear_line(),
+                cursor_down(1)
+            )
+        );
+
+        assert!(matches!(
+            live.update_row(3, row(&["x", "y"])),
+            Err(TableError::IndexOutOfBounds { index: 3, .. })
+        ));
+        assert!(live.push_row(row(&["too", "many", "cells"])).is_err());
+        assert_eq!(live.rows().len(), 1);
+
+        live.push_row(row(&["other", "0%"])).unwrap();
+        assert_eq!(live.printed_lines(), 5);
+        assert_eq!(live.clear(), format!("{}{}", cursor_up(5), clear_below()));
+        assert_eq!(live.printed_lines(), 0);
+    }
+
+    #[test]
+    fn test_max_height_pins_header_and_footer() {
+        let mut live = LiveTable::new(Some(TableUserConfig {
+            header: Some(Box::default()),
+            footer: Some(Box::default()),
+            ..Default::default()
+        }));
+        live.set_max_height(Some(9)).unwrap();
+        for cells in [["job", "state"], ["a", "1"], ["b", "2"], ["c", "3"]] {
+            live.push_row(row(&cells)).unwrap();
+        }
+        assert_eq!(live.printed_lines(), 9);
+
+        // The table is now taller than 9 lines: the newest body rows show
+        // between the header and the footer, whose wider cell redraws them all.
+        let update = live.push_row(row(&["total", "6"])).unwrap();
+        let screen = |live: &LiveTable| live.printed.join("\n");
+        assert_eq!(live.printed_lines(), 9);
+        assert!(update.starts_with(&cursor_up(9)), "{update:?}");
+        assert!(screen(&live).contains("│ job   │ state │"));
+        assert!(screen(&live).contains("│ b     │ 2     │\n├"));
+        assert!(screen(&live).ends_with("│ total │ 6     │\n└───────┴───────┘"));
+
+        live.scroll_to(Some(0)).unwrap();
+        assert!(screen(&live).contains("│ a     │ 1     │"));
+        assert!(!screen(&live).contains("│ c"));
+    }
+}
@@ -0,0 +1,367 @@
+use crate::features::terminal_profile::{TerminalProfile, terminal_profile};
+use crate::features::themes::{BUILTIN_THEMES, theme_generation};
+use crate::types::{ColorMode, Row, TableResult, TableUserConfig};
+use crate::utils::terminal::stdout_supports_ansi;
+use rustc_hash::{FxHashMap, FxHasher};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+
+/// A table returned by `RenderCache`. Clones share the rendered text, so a
+/// cache hit costs a reference count rather than a copy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedTable(Arc<str>);
+
+impl RenderedTable {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::ops::Deref for RenderedTable {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for RenderedTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Hashes everything `table` renders from: the rows, the user config and the
+/// process-wide settings it reads, which are the `set_terminal_profile`
+/// profile, whether stdout takes colors when `color_mode` is auto, and, for
+/// a `theme` added with `register_theme`, the themes registered so far.
+/// Predicates and custom
+/// aggregations hash by identity, so a config rebuilt with new closures gets
+/// a new key.
+pub fn render_key(data: &[Row], config: Option<&TableUserConfig>) -> u64 {
+    key_in(data, config, Environment::current(config))
+}
+
+fn key_in(data: &[Row], config: Option<&TableUserConfig>, environment: Environment) -> u64 {
+    let mut hasher = FxHasher::default();
+    data.hash(&mut hasher);
+    config.hash(&mut hasher);
+    environment.hash(&mut hasher);
+    hasher.finish()
+}
+
+// The process-wide settings a render depends on besides its inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Environment {
+    profile: TerminalProfile,
+    stdout_colors: bool,
+    themes: u64,
+}
+
+impl Environment {
+    fn current(config: Option<&TableUserConfig>) -> Self {
+        let auto_colors = config.is_some_and(|config| config.color_mode == Some(ColorMode::Auto));
+        let custom_theme = config
+            .and_then(|config| config.theme.as_deref())
+            .is_some_and(|theme| !BUILTIN_THEMES.contains(&theme));
+        Self {
+            profile: terminal_profile(),
+            stdout_colors: auto_colors && stdout_supports_ansi(),
+            themes: if custom_theme { theme_generation() } else { 0 },
+        }
+    }
+}
+
+// The inputs are kept to confirm a hit, as different inputs can share a key.
+struct Entry {
+    data: Vec<Row>,
+    config: Option<TableUserConfig>,
+    environment: Environment,
+    table: RenderedTable,
+    last_used: u64,
+}
+
+impl Entry {
+    fn renders(
+        &self,
+        data: &[Row],
+        config: Option<&TableUserConfig>,
+        environment: Environment,
+    ) -> bool {
+        self.data == data && self.config.as_ref() == config && self.environment == environment
+    }
+}
+
+/// How many tables `RenderCache::global` keeps.
+pub const GLOBAL_CACHE_CAPACITY: usize = 256;
+
+/// Memoizes `table` for callers that redraw the same table again and again,
+/// such as TUIs. Renders are keyed by `render_key` and kept with the rows and
+/// config they came from, which a hit must match, so inputs whose keys
+/// collide are rendered rather than mixed up. Once `capacity` tables are
+/// cached the least recently used one is dropped. A new terminal profile,
+/// color detection result or registered theme changes the key, so tables are
+/// redrawn for it. Output that depends on
+/// the terminal width (`auto_layout`, or a centered or right `table_alignment`)
+/// keeps the width of its first render, so `clear` the cache on resize.
+///
+/// The cache is `Send + Sync` and every method takes `&self`, so one cache can
+/// be shared between threads. The lock is only held for lookups and inserts,
+/// never while a table is rendered.
+pub struct RenderCache {
+    capacity: usize,
+    entries: Mutex<FxHashMap<u64, Entry>>,
+    clock: AtomicU64,
+}
+
+impl RenderCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(FxHashMap::default()),
+            clock: AtomicU64::new(0),
+        }
+    }
+
+    /// The process-wide cache that `table` renders through when `memoize` is
+    /// set, holding up to `GLOBAL_CACHE_CAPACITY` tables.
+    pub fn global() -> &'static RenderCache {
+        static GLOBAL: OnceLock<RenderCache> = OnceLock::new();
+        GLOBAL.get_or_init(|| RenderCache::new(GLOBAL_CACHE_CAPACITY))
+    }
+
+    /// Returns the cached table for `data` and `config`, rendering and caching
+    /// it first on a miss. Errors are returned as they are and never cached.
+    pub fn render(
+        &self,
+        data: &[Row],
+        config: Option<&TableUserConfig>,
+    ) -> TableResult<RenderedTable> {
+        let environment = Environment::current(config);
+        let key = key_in(data, config, environment);
+
+        if let Some(entry) = self.lock().get_mut(&key)
+            && entry.renders(data, config, environment)
+        {
+            entry.last_used = self.tick();
+            return Ok(entry.table.clone());
+        }
+
+        let table = RenderedTable(crate::render_uncached(data, config)?.into());
+        if self.capacity > 0 {
+            let mut entries = self.lock();
+            if entries.len() >= self.capacity && !entries.contains_key(&key) {
+                evict_least_recently_used(&mut entries);
+            }
+            entries.insert(
+                key,
+                Entry {
+                    data: data.to_vec(),
+                    config: config.cloned(),
+                    environment,
+                    table: table.clone(),
+                    last_used: self.tick(),
+                },
+            );
+        }
+        Ok(table)
+    }
+
+    /// Drops the cached render of `data` and `config`, returning whether there
+    /// was one.
+    pub fn invalidate(&self, data: &[Row], config: Option<&TableUserConfig>) -> bool {
+        let environment = Environment::current(config);
+        let key = key_in(data, config, environment);
+        let mut entries = self.lock();
+        if entries
+            .get(&key)
+            .is_some_and(|entry| entry.renders(data, config, environment))
+        {
+            entries.remove(&key);
+            return true;
+        }
+        false
+    }
+
+    /// Drops the cached render with the given `render_key`.
+    pub fn invalidate_key(&self, key: u64) -> bool {
+        self.lock().remove(&key).is_some()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    // A panic elsewhere cannot leave the map half-updated, so a poisoned lock
+    // is still safe to use.
+    fn lock(&self) -> MutexGuard<'_, FxHashMap<u64, Entry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn evict_least_recently_used(entries: &mut FxHashMap<u64, Entry>) {
+    let oldest = entries
+        .iter()
+        .min_by_key(|(_, entry)| entry.last_used)
+        .map(|(key, _)| *key);
+    if let Some(key) = oldest {
+        entries.remove(&key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::filtering::RowFilter;
+
+    fn rows(first: &str) -> Vec<Row> {
+        vec![
+            vec![first.to_string(), "b".to_string()],
+            vec!["c".to_string(), "d".to_string()],
+        ]
+    }
+
+    #[test]
+    fn test_render_cache_hits_and_invalidation() {
+        let cache = RenderCache::new(4);
+        let data = rows("a");
+
+        let first = cache.render(&data, None).unwrap();
+        let second = cache.render(&data, None).unwrap();
+        assert_eq!(first.as_str(), crate::table(&data, None).unwrap());
+        assert!(std::ptr::eq(first.as_str(), second.as_str()));
+        assert_eq!(cache.len(), 1);
+
+        assert!(cache.invalidate(&data, None));
+        assert!(!cache.invalidate(&data, None));
+        let third = cache.render(&data, None).unwrap();
+        assert!(!std::ptr::eq(first.as_str(), third.as_str()));
+
+        assert!(cache.render(&[vec!["\u{7}".to_string()]], None).is_err());
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_render_cache_confirms_hits_against_the_inputs() {
+        let cache = RenderCache::new(4);
+        let data = rows("a");
+        // Another table cached under the same key, as a collision would.
+        cache.lock().insert(
+            render_key(&data, None),
+            Entry {
+                data: rows("b"),
+                config: None,
+                environment: Environment::current(None),
+                table: RenderedTable("stale".into()),
+                last_used: 0,
+            },
+        );
+
+        assert!(!cache.invalidate(&data, None));
+        let table = cache.render(&data, None).unwrap();
+        assert_eq!(table.as_str(), crate::table(&data, None).unwrap());
+        assert_eq!(cache.len(), 1);
+        assert!(cache.invalidate(&data, None));
+    }
+
+    #[test]
+    fn test_render_cache_evicts_least_recently_used() {
+        let cache = RenderCache::new(2);
+        cache.render(&rows("a"), None).unwrap();
+        cache.render(&rows("b"), None).unwrap();
+        cache.render(&rows("a"), None).unwrap();
+        cache.render(&rows("c"), None).unwrap();
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.invalidate(&rows("a"), None));
+        assert!(!cache.invalidate(&rows("b"), None));
+        assert!(cache.invalidate(&rows("c"), None));
+    }
+
+    #[test]
+    fn test_render_cache_is_shared_across_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<RenderCache>();
+
+        let cache = RenderCache::new(8);
+        let tables: Vec<RenderedTable> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| cache.render(&rows("a"), None).unwrap()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        assert!(tables.windows(2).all(|pair| pair[0] == pair[1]));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_table_memoize_uses_global_cache() {
+        let data = rows("memoized");
+        let config = TableUserConfig {
+            memoize: Some(true),
+            ..Default::default()
+        };
+
+        let output = crate::table(&data, Some(&config)).unwrap();
+        assert_eq!(output, crate::table(&data, None).unwrap());
+        assert!(RenderCache::global().invalidate(&data, Some(&config)));
+    }
+
+    #[test]
+    fn test_render_key_covers_config() {
+        let data = rows("a");
+        let filtered = |filter: RowFilter| TableUserConfig {
+            filters: Some(vec![filter]),
+            ..Default::default()
+        };
+        let predicate = RowFilter::predicate(|_| true);
+
+        assert_ne!(
+            render_key(&data, None),
+            render_key(&data, Some(&TableUserConfig::default()))
+        );
+        assert_eq!(
+            render_key(&data, Some(&filtered(predicate.clone()))),
+            render_key(&data, Some(&filtered(predicate)))
+        );
+        assert_ne!(
+            render_key(&data, Some(&filtered(RowFilter::predicate(|_| true)))),
+            render_key(&data, Some(&filtered(RowFilter::predicate(|_| true))))
+        );
+    }
+
+    #[test]
+    fn test_render_key_covers_registered_themes() {
+        let data = rows("a");
+        let config = TableUserConfig {
+            theme: Some("memo-test".to_string()),
+            ..Default::default()
+        };
+        let cache = RenderCache::new(4);
+        crate::register_theme("memo-test", crate::get_theme("compact").unwrap()).unwrap();
+        let compact = cache.render(&data, Some(&config)).unwrap();
+        let before = render_key(&data, Some(&config));
+
+        crate::register_theme("memo-test", crate::get_theme("double").unwrap()).unwrap();
+
+        assert_ne!(render_key(&data, Some(&config)), before);
+        assert_eq!(render_key(&data, None), render_key(&data, None));
+        let double = cache.render(&data, Some(&config)).unwrap();
+        assert_ne!(compact, double);
+        assert_eq!(cache.len(), 2);
+    }
+}
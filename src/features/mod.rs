@@ -1,9 +1,66 @@
+pub mod aggregation;
 pub mod alignment_processor;
 pub mod borders;
+pub mod column_order;
+pub mod column_summary;
+pub mod computed;
+pub mod conditional_format;
+pub mod diff;
+pub mod estimate;
+pub mod filtering;
+pub mod harmonize;
+pub mod hash_color;
+pub mod hygiene;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod live;
+#[cfg(feature = "memo")]
+pub mod memo;
+pub mod number_format;
+pub mod render_target;
+#[cfg(feature = "serde-rows")]
+pub mod serde_rows;
+pub mod sorting;
 pub mod spanning;
+pub mod stats;
 pub mod streaming;
+pub mod styling;
+pub mod template;
+pub mod terminal_profile;
+pub mod themes;
+pub mod title;
+pub mod typed;
+pub mod viewport;
 
+pub use aggregation::*;
 pub use alignment_processor::*;
 pub use borders::*;
+pub use column_order::*;
+pub use column_summary::*;
+pub use conditional_format::*;
+pub use diff::*;
+pub use estimate::*;
+pub use filtering::*;
+pub use harmonize::*;
+pub use hash_color::*;
+pub use hygiene::*;
+#[cfg(feature = "json")]
+pub use json::*;
+pub use live::*;
+#[cfg(feature = "memo")]
+pub use memo::*;
+pub use number_format::*;
+pub use render_target::*;
+#[cfg(feature = "serde-rows")]
+pub use serde_rows::*;
+pub use sorting::*;
 pub use spanning::*;
+pub use stats::*;
 pub use streaming::*;
+pub use styling::*;
+pub use template::*;
+pub use terminal_profile::*;
+pub use themes::*;
+pub use title::*;
+pub use typed::*;
+pub use viewport::*;
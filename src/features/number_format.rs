@@ -0,0 +1,436 @@
+use crate::features::computed::{civil_from_days, parse_date};
+use crate::types::{TableConfig, TableError, TableResult};
+use crate::utils::ansi::split_ansi_string;
+use serde::{Deserialize, Serialize};
+
+/// How formatters write numbers and dates: the separators, the percent sign's
+/// spacing and the order of a date's parts.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Locale {
+    /// Between the whole part and the decimals, e.g. `.` or `,`.
+    pub decimal_separator: String,
+    /// Between groups of three digits, e.g. `,`, `.` or a no-break space;
+    /// empty for no grouping.
+    pub thousands_separator: String,
+    /// How `Formatter::Date` writes dates, `%Y`, `%m` and `%d` standing for
+    /// the year, month and day, e.g. `%d.%m.%Y`.
+    pub date_format: String,
+    /// Puts a no-break space before percent signs: `25 %`.
+    pub spaced_percent: bool,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::from_tag(None)
+    }
+}
+
+impl Locale {
+    /// The conventions of a BCP 47 tag's language and region, e.g. `1.234,56`
+    /// and `31.12.2024` for `de`. English, ISO dates and no tag are the
+    /// default.
+    pub fn from_tag(tag: Option<&str>) -> Self {
+        let mut subtags = tag.unwrap_or("en").split(['-', '_']);
+        let language = subtags.next().unwrap_or("en").to_ascii_lowercase();
+        let region = subtags
+            .find(|subtag| subtag.len() == 2)
+            .map(str::to_ascii_uppercase);
+        let language = language.as_str();
+
+        let comma_decimal = COMMA_DECIMAL_LANGUAGES.contains(&language);
+        let thousands_separator = match language {
+            _ if !comma_decimal => ",",
+            "de" | "es" | "it" | "pt" | "nl" | "da" | "tr" => ".",
+            _ => "\u{a0}",
+        };
+        let date_format = match (language, region.as_deref()) {
+            ("en", Some("US")) => "%m/%d/%Y",
+            ("en", Some(_)) | ("fr" | "es" | "it" | "pt", _) => "%d/%m/%Y",
+            ("nl", _) => "%d-%m-%Y",
+            ("de" | "da" | "nb" | "no" | "fi" | "ru" | "uk" | "pl" | "cs" | "tr", _) => "%d.%m.%Y",
+            ("ja" | "zh", _) => "%Y/%m/%d",
+            _ => "%Y-%m-%d",
+        };
+        Locale {
+            decimal_separator: if comma_decimal { "," } else { "." }.to_string(),
+            thousands_separator: thousands_separator.to_string(),
+            date_format: date_format.to_string(),
+            spaced_percent: SPACED_PERCENT_LANGUAGES.contains(&language),
+        }
+    }
+
+    /// `config.format_locale`, or the conventions of `config.locale` when it
+    /// is unset.
+    pub fn for_config(config: &TableConfig) -> Self {
+        config
+            .format_locale
+            .clone()
+            .unwrap_or_else(|| Locale::from_tag(config.locale.as_deref()))
+    }
+}
+
+/// A `Locale` in a user config: the settings left out are taken from the
+/// config's `locale` tag.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub struct LocaleUserConfig {
+    pub decimal_separator: Option<String>,
+    pub thousands_separator: Option<String>,
+    pub date_format: Option<String>,
+    pub spaced_percent: Option<bool>,
+}
+
+impl LocaleUserConfig {
+    pub fn merge(self, other: LocaleUserConfig) -> LocaleUserConfig {
+        LocaleUserConfig {
+            decimal_separator: other.decimal_separator.or(self.decimal_separator),
+            thousands_separator: other.thousands_separator.or(self.thousands_separator),
+            date_format: other.date_format.or(self.date_format),
+            spaced_percent: other.spaced_percent.or(self.spaced_percent),
+        }
+    }
+
+    pub fn merge_with_default(self, default: &Locale) -> Locale {
+        Locale {
+            decimal_separator: self
+                .decimal_separator
+                .unwrap_or_else(|| default.decimal_separator.clone()),
+            thousands_separator: self
+                .thousands_separator
+                .unwrap_or_else(|| default.thousands_separator.clone()),
+            date_format: self
+                .date_format
+                .unwrap_or_else(|| default.date_format.clone()),
+            spaced_percent: self.spaced_percent.unwrap_or(default.spaced_percent),
+        }
+    }
+}
+
+const COMMA_DECIMAL_LANGUAGES: &[&str] = &[
+    "de", "fr", "es", "it", "pt", "nl", "ru", "uk", "pl", "cs", "sv", "da", "nb", "no", "fi", "tr",
+];
+
+// Languages that put a space between a number and its percent sign.
+const SPACED_PERCENT_LANGUAGES: &[&str] = &[
+    "de", "fr", "es", "ru", "uk", "cs", "sv", "da", "nb", "no", "fi",
+];
+
+/// How a column writes its numbers, or its dates, with the table's `Locale`;
+/// the examples below are in the default, English one. Cells that are not
+/// numbers, or dates for `Date`, are left as they are.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Formatter {
+    /// Rounded to a whole number, with thousands separators: `1,234,568`.
+    Integer,
+    /// This many decimals, with thousands separators: `1,234.57`.
+    Float(usize),
+    /// A fraction as a percentage with this many decimals: `0.256` is `25.6%`.
+    Percent(usize),
+    /// Two decimals with thousands separators after the symbol: `$1,234.50`,
+    /// and `-$5.00` for negative amounts.
+    Currency(String),
+    /// A byte count in binary units: `512 B`, `1.5 KiB`, `3.2 GiB`.
+    Bytes,
+    /// A `YYYY-MM-DD` date in the locale's `date_format`, e.g. `12/31/2024`
+    /// for `en-US`; numbers are taken as Unix timestamps in seconds.
+    Date,
+}
+
+impl Formatter {
+    pub fn format(&self, value: f64, locale: &Locale) -> String {
+        match self {
+            Formatter::Integer => localize(&fixed(value, 0), locale),
+            Formatter::Float(precision) => localize(&fixed(value, *precision), locale),
+            Formatter::Percent(precision) => {
+                let number = localize(&fixed(value * 100.0, *precision), locale);
+                // A no-break space keeps the sign with its number when cells wrap.
+                let space = if locale.spaced_percent { "\u{a0}" } else { "" };
+                format!("{number}{space}%")
+            }
+            Formatter::Currency(symbol) => {
+                let amount = localize(&fixed(value, 2), locale);
+                match amount.strip_prefix('-') {
+                    Some(amount) => format!("-{symbol}{amount}"),
+                    None => format!("{symbol}{amount}"),
+                }
+            }
+            Formatter::Bytes => format_bytes(value, locale),
+            Formatter::Date => {
+                let days = (value / 86_400.0).floor() as i64;
+                format_date(civil_from_days(days), &locale.date_format)
+            }
+        }
+    }
+
+    /// Formats the number, or for `Date` the date, `cell` holds, keeping the
+    /// colors around it and a symbol a format rule put before it. `None` when
+    /// there is none.
+    pub fn format_cell(&self, cell: &str, locale: &Locale) -> Option<String> {
+        let text = crate::features::stats::normalize_value(cell);
+        if *self == Formatter::Date
+            && let Some(date) = parse_date(&text)
+        {
+            // Only the date is replaced, so a time after it is kept.
+            let start = find_outside_escapes(cell, &text[..10]).next()?;
+            let mut formatted = cell[..start].to_string();
+            formatted.push_str(&format_date(date, &locale.date_format));
+            formatted.push_str(&cell[start + 10..]);
+            return Some(formatted);
+        }
+        let number = [
+            text.as_str(),
+            text.split_once(' ').map_or("", |(_, rest)| rest),
+        ]
+        .into_iter()
+        .find(|number| number.parse::<f64>().is_ok_and(f64::is_finite))?;
+        let value: f64 = number.parse().ok()?;
+
+        let start = find_outside_escapes(cell, number).last()?;
+        let mut formatted = String::with_capacity(cell.len() + 4);
+        formatted.push_str(&cell[..start]);
+        formatted.push_str(&self.format(value, locale));
+        formatted.push_str(&cell[start + number.len()..]);
+        Some(formatted)
+    }
+}
+
+// The byte offsets in `cell` where `needle` appears in its text, never in the
+// escape sequences between, so a `0` is not found in a `\x1b[0m` reset.
+fn find_outside_escapes<'a>(cell: &'a str, needle: &'a str) -> impl Iterator<Item = usize> + 'a {
+    let mut offset = 0;
+    split_ansi_string(cell)
+        .into_iter()
+        .map(move |segment| {
+            let start = offset;
+            offset += segment.len();
+            (start, segment)
+        })
+        .filter(|(_, segment)| !segment.starts_with('\u{1b}'))
+        .flat_map(move |(start, segment)| {
+            segment
+                .match_indices(needle)
+                .map(|(at, _)| start + at)
+                .collect::<Vec<_>>()
+        })
+}
+
+/// Parses `integer`, `float[:PRECISION]`, `percent[:PRECISION]`,
+/// `currency[:SYMBOL]`, `bytes` or `date`. Precision defaults to 2 for floats and 0
+/// for percentages, and the currency symbol to `$`.
+impl std::str::FromStr for Formatter {
+    type Err = TableError;
+
+    fn from_str(spec: &str) -> TableResult<Self> {
+        let error = |reason: &str| TableError::parse("number format", spec, reason);
+        let (name, arg) = match spec.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (spec, None),
+        };
+        let precision = |default: usize| {
+            arg.map_or(Ok(default), |arg| {
+                arg.parse()
+                    .map_err(|_| error("precision must be a whole number"))
+            })
+        };
+
+        match (name.trim().to_lowercase().as_str(), arg) {
+            ("integer", None) => Ok(Formatter::Integer),
+            ("float", _) => Ok(Formatter::Float(precision(2)?)),
+            ("percent", _) => Ok(Formatter::Percent(precision(0)?)),
+            ("currency", _) => Ok(Formatter::Currency(arg.unwrap_or("$").to_string())),
+            ("bytes", None) => Ok(Formatter::Bytes),
+            ("date", None) => Ok(Formatter::Date),
+            ("integer" | "bytes" | "date", Some(_)) => Err(error("takes no argument")),
+            _ => Err(error(
+                "expected integer, float, percent, currency, bytes or date",
+            )),
+        }
+    }
+}
+
+// `value` with `precision` decimals, without the sign of a value that
+// rounds to zero.
+fn fixed(value: f64, precision: usize) -> String {
+    let fixed = format!("{value:.precision$}");
+    match fixed.strip_prefix('-') {
+        Some(unsigned) if unsigned.bytes().all(|b| matches!(b, b'0' | b'.')) => {
+            unsigned.to_string()
+        }
+        _ => fixed,
+    }
+}
+
+// Swaps the decimal point of `number` for the locale's, and puts its
+// thousands separator between each group of three digits before it.
+fn localize(number: &str, locale: &Locale) -> String {
+    let (sign, unsigned) = match number.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", number),
+    };
+    let (whole, fraction) = match unsigned.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (unsigned, None),
+    };
+
+    let mut grouped = String::with_capacity(number.len() + whole.len() / 3);
+    grouped.push_str(sign);
+    for (idx, digit) in whole.chars().enumerate() {
+        if idx > 0 && (whole.len() - idx) % 3 == 0 {
+            grouped.push_str(&locale.thousands_separator);
+        }
+        grouped.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        grouped.push_str(&locale.decimal_separator);
+        grouped.push_str(fraction);
+    }
+    grouped
+}
+
+const BYTE_UNITS: [&str; 7] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB", "ZiB"];
+
+fn format_bytes(value: f64, locale: &Locale) -> String {
+    if value.abs() < 1024.0 {
+        return format!("{} B", fixed(value, 0));
+    }
+    let mut scaled = value / 1024.0;
+    let mut unit = 0;
+    while scaled.abs() >= 1024.0 && unit + 1 < BYTE_UNITS.len() {
+        scaled /= 1024.0;
+        unit += 1;
+    }
+    let scaled = format!("{scaled:.1}");
+    let scaled = scaled.strip_suffix(".0").unwrap_or(&scaled);
+    format!("{} {}", localize(scaled, locale), BYTE_UNITS[unit])
+}
+
+// Writes `%Y`, `%m` and `%d` in `pattern` as the date's parts, and `%%` as a
+// percent sign.
+fn format_date((year, month, day): (i64, u32, u32), pattern: &str) -> String {
+    let mut formatted = String::with_capacity(pattern.len() + 4);
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            formatted.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => formatted.push_str(&format!("{year:04}")),
+            Some('m') => formatted.push_str(&format!("{month:02}")),
+            Some('d') => formatted.push_str(&format!("{day:02}")),
+            Some('%') => formatted.push('%'),
+            Some(other) => {
+                formatted.push('%');
+                formatted.push(other);
+            }
+            None => formatted.push('%'),
+        }
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formatters() {
+        let en = Locale::default();
+        assert_eq!(Formatter::Integer.format(1234567.6, &en), "1,234,568");
+        assert_eq!(Formatter::Integer.format(-999.0, &en), "-999");
+        assert_eq!(Formatter::Integer.format(-0.4, &en), "0");
+        assert_eq!(Formatter::Float(2).format(-1234.567, &en), "-1,234.57");
+        assert_eq!(Formatter::Percent(1).format(0.256, &en), "25.6%");
+        assert_eq!(
+            Formatter::Currency("€".to_string()).format(-5.0, &en),
+            "-€5.00"
+        );
+        assert_eq!(
+            Formatter::Currency("$".to_string()).format(-0.001, &en),
+            "$0.00"
+        );
+        assert_eq!(Formatter::Bytes.format(512.0, &en), "512 B");
+        assert_eq!(Formatter::Bytes.format(1536.0, &en), "1.5 KiB");
+        assert_eq!(Formatter::Bytes.format(3.0 * 1024.0 * 1024.0, &en), "3 MiB");
+        assert_eq!(Formatter::Date.format(1_735_603_200.0, &en), "2024-12-31");
+
+        let colored = "\u{1b}[32m1234.6\u{1b}[0m";
+        assert_eq!(
+            Formatter::Integer.format_cell(colored, &en).as_deref(),
+            Some("\u{1b}[32m1,235\u{1b}[0m")
+        );
+        assert_eq!(
+            Formatter::Float(1).format_cell("▲ 1000", &en).as_deref(),
+            Some("▲ 1,000.0")
+        );
+        assert_eq!(
+            Formatter::Currency("$".to_string())
+                .format_cell("\u{1b}[31m0\u{1b}[0m", &en)
+                .as_deref(),
+            Some("\u{1b}[31m$0.00\u{1b}[0m")
+        );
+        assert_eq!(
+            Formatter::Date
+                .format_cell(
+                    "\u{1b}[1m2024-12-31\u{1b}[0m",
+                    &Locale::from_tag(Some("de"))
+                )
+                .as_deref(),
+            Some("\u{1b}[1m31.12.2024\u{1b}[0m")
+        );
+        assert_eq!(Formatter::Integer.format_cell("n/a", &en), None);
+        assert_eq!(Formatter::Date.format_cell("soon", &en), None);
+    }
+
+    #[test]
+    fn test_parse_formatter() {
+        assert_eq!("integer".parse::<Formatter>().unwrap(), Formatter::Integer);
+        assert_eq!("float".parse::<Formatter>().unwrap(), Formatter::Float(2));
+        assert_eq!(
+            "percent:1".parse::<Formatter>().unwrap(),
+            Formatter::Percent(1)
+        );
+        assert_eq!(
+            "currency:£".parse::<Formatter>().unwrap(),
+            Formatter::Currency("£".to_string())
+        );
+        assert!("float:x".parse::<Formatter>().is_err());
+        assert!("bytes:2".parse::<Formatter>().is_err());
+        assert_eq!("date".parse::<Formatter>().unwrap(), Formatter::Date);
+        assert!("time".parse::<Formatter>().is_err());
+    }
+
+    #[test]
+    fn test_locales() {
+        let de = Locale::from_tag(Some("de-DE"));
+        assert_eq!(Formatter::Float(2).format(1234.56, &de), "1.234,56");
+        assert_eq!(Formatter::Percent(1).format(0.256, &de), "25,6\u{a0}%");
+        assert_eq!(Formatter::Bytes.format(1536.0, &de), "1,5 KiB");
+        assert_eq!(
+            Formatter::Date
+                .format_cell("\u{1b}[1m2024-12-31T08:00\u{1b}[0m", &de)
+                .as_deref(),
+            Some("\u{1b}[1m31.12.2024T08:00\u{1b}[0m")
+        );
+
+        let fr = Locale::from_tag(Some("fr"));
+        assert_eq!(
+            Formatter::Integer.format(-1234567.0, &fr),
+            "-1\u{a0}234\u{a0}567"
+        );
+        let us = Locale::from_tag(Some("en-US"));
+        assert_eq!(
+            Formatter::Date.format_cell("2024-03-05", &us).as_deref(),
+            Some("03/05/2024")
+        );
+
+        let swiss = LocaleUserConfig {
+            thousands_separator: Some("'".to_string()),
+            date_format: Some("%d.%m.%y %%".to_string()),
+            ..Default::default()
+        }
+        .merge_with_default(&de);
+        assert_eq!(Formatter::Float(1).format(12345.0, &swiss), "12'345,0");
+        assert_eq!(Formatter::Date.format(0.0, &swiss), "01.01.%y %");
+    }
+}
@@ -0,0 +1,225 @@
+use crate::core::calculator::{calculate_output_column_widths, calculate_table_width};
+use crate::types::{Row, TableConfig, TableError, TableResult};
+use crate::utils::terminal::{detect_terminal_width, stdout_supports_ansi};
+use std::io::{IsTerminal, Write};
+
+/// Destination for rendered tables. The capabilities let the crate decide
+/// whether to keep colors and how wide the table may grow.
+pub trait RenderTarget {
+    fn supports_ansi(&self) -> bool;
+
+    fn max_width(&self) -> Option<usize>;
+
+    fn write(&mut self, output: &str) -> TableResult<()>;
+}
+
+/// Standard output. ANSI is enabled only for a real terminal without
+/// `NO_COLOR` set, and the width follows the terminal size.
+#[derive(Debug, Default)]
+pub struct TtyTarget;
+
+impl TtyTarget {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RenderTarget for TtyTarget {
+    fn supports_ansi(&self) -> bool {
+        stdout_supports_ansi()
+    }
+
+    fn max_width(&self) -> Option<usize> {
+        if std::io::stdout().is_terminal() {
+            detect_terminal_width()
+        } else {
+            None
+        }
+    }
+
+    fn write(&mut self, output: &str) -> TableResult<()> {
+        let mut stdout = std::io::stdout().lock();
+        stdout
+            .write_all(output.as_bytes())
+            .and_then(|_| stdout.flush())
+            .map_err(TableError::io("Failed to write to stdout"))
+    }
+}
+
+/// Plain-text file output: no escape sequences and no width limit.
+#[derive(Debug)]
+pub struct FileTarget {
+    file: std::fs::File,
+}
+
+impl FileTarget {
+    pub fn new(file: std::fs::File) -> Self {
+        Self { file }
+    }
+
+    pub fn create(path: impl AsRef<std::path::Path>) -> TableResult<Self> {
+        let path = path.as_ref();
+        std::fs::File::create(path)
+            .map(Self::new)
+            .map_err(TableError::io(format!(
+                "Failed to create '{}'",
+                path.display()
+            )))
+    }
+}
+
+impl RenderTarget for FileTarget {
+    fn supports_ansi(&self) -> bool {
+        false
+    }
+
+    fn max_width(&self) -> Option<usize> {
+        None
+    }
+
+    fn write(&mut self, output: &str) -> TableResult<()> {
+        self.file
+            .write_all(output.as_bytes())
+            .map_err(TableError::io("Failed to write to file"))
+    }
+}
+
+/// In-memory buffer with configurable capabilities.
+#[derive(Debug, Clone, Default)]
+pub struct StringTarget {
+    output: String,
+    ansi: bool,
+    max_width: Option<usize>,
+}
+
+impl StringTarget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_ansi(mut self, ansi: bool) -> Self {
+        self.ansi = ansi;
+        self
+    }
+
+    pub fn with_max_width(mut self, max_width: usize) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.output
+    }
+
+    pub fn into_string(self) -> String {
+        self.output
+    }
+}
+
+impl RenderTarget for StringTarget {
+    fn supports_ansi(&self) -> bool {
+        self.ansi
+    }
+
+    fn max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    fn write(&mut self, output: &str) -> TableResult<()> {
+        self.output.push_str(output);
+        Ok(())
+    }
+}
+
+/// Records every write separately so tests can assert on what a caller emitted.
+#[derive(Debug, Clone, Default)]
+pub struct TestTarget {
+    pub ansi: bool,
+    pub max_width: Option<usize>,
+    pub writes: Vec<String>,
+}
+
+impl TestTarget {
+    pub fn new(ansi: bool, max_width: Option<usize>) -> Self {
+        Self {
+            ansi,
+            max_width,
+            writes: Vec::new(),
+        }
+    }
+}
+
+impl RenderTarget for TestTarget {
+    fn supports_ansi(&self) -> bool {
+        self.ansi
+    }
+
+    fn max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    fn write(&mut self, output: &str) -> TableResult<()> {
+        self.writes.push(output.to_string());
+        Ok(())
+    }
+}
+
+/// Adapts a resolved config to the target: positioning uses the target width,
+/// and a table wider than the target is shrunk to fit unless the caller already
+/// asked for an exact width.
+pub fn fit_config_to_target(rows: &[Row], config: &mut TableConfig, target: &dyn RenderTarget) {
+    let Some(max_width) = target.max_width() else {
+        return;
+    };
+
+    if config.terminal_width.is_none() {
+        config.terminal_width = Some(max_width);
+    }
+
+    if config.exact_table_width.is_some() {
+        return;
+    }
+
+    let available_width = max_width.saturating_sub(config.table_indent);
+    let widths = calculate_output_column_widths(rows, config);
+    if calculate_table_width(&widths, config) > available_width {
+        config.exact_table_width = Some(available_width);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_rows() -> Vec<Row> {
+        vec![vec!["a".to_string(), "some longer text".to_string()]]
+    }
+
+    #[test]
+    fn test_fit_config_shrinks_wide_table() {
+        let mut config = TableConfig::default();
+        let target = TestTarget::new(true, Some(15));
+
+        fit_config_to_target(&sample_rows(), &mut config, &target);
+        assert_eq!(config.exact_table_width, Some(15));
+        assert_eq!(config.terminal_width, Some(15));
+    }
+
+    #[test]
+    fn test_fit_config_keeps_narrow_table() {
+        let mut config = TableConfig::default();
+        let target = StringTarget::new().with_max_width(80);
+
+        fit_config_to_target(&sample_rows(), &mut config, &target);
+        assert_eq!(config.exact_table_width, None);
+    }
+
+    #[test]
+    fn test_string_target_collects_output() {
+        let mut target = StringTarget::new();
+        target.write("a").unwrap();
+        target.write("b").unwrap();
+        assert_eq!(target.as_str(), "ab");
+        assert!(!target.supports_ansi());
+    }
+}
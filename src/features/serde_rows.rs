@@ -0,0 +1,127 @@
+use crate::features::json::{JsonColumns, objects_to_rows, value_to_cell};
+use crate::types::{Row, TableError, TableResult, TableUserConfig};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Converts records to rows via `serde_json::Value`.
+///
+/// Structs and maps produce a header row of field names followed by one row
+/// per record; keys missing from a record become empty cells, and keys first
+/// seen in later records are appended as extra columns. Sequences (tuples,
+/// `Vec`s) become rows as-is and scalars become one-cell rows, without a header.
+pub fn serializable_to_rows<T: Serialize>(records: &[T]) -> TableResult<Vec<Row>> {
+    records_to_rows(records).map(|(rows, _)| rows)
+}
+
+// Also reports whether the first row is a header of field names.
+fn records_to_rows<T: Serialize>(records: &[T]) -> TableResult<(Vec<Row>, bool)> {
+    let values = records
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !values.first().is_some_and(Value::is_object) {
+        let rows = values
+            .iter()
+            .map(|value| match value {
+                Value::Array(items) => Ok(items.iter().map(value_to_cell).collect()),
+                Value::Object(_) => Err(TableError::InvalidRowData),
+                scalar => Ok(vec![value_to_cell(scalar)]),
+            })
+            .collect::<TableResult<_>>()?;
+        return Ok((rows, false));
+    }
+
+    let objects = values
+        .iter()
+        .map(|value| value.as_object().ok_or(TableError::InvalidRowData))
+        .collect::<TableResult<Vec<_>>>()?;
+
+    Ok((
+        objects_to_rows(objects.iter().copied(), &JsonColumns::default()),
+        true,
+    ))
+}
+
+/// Renders any serializable records as a table. For structs and maps the field
+/// names become a header row, styled by `config.header` (the body style when
+/// unset).
+pub fn table_from_serializable<T: Serialize>(
+    records: &[T],
+    config: Option<&TableUserConfig>,
+) -> TableResult<String> {
+    let (rows, has_header) = records_to_rows(records)?;
+
+    if !has_header {
+        return crate::table(&rows, config);
+    }
+
+    let mut config = config.cloned().unwrap_or_default();
+    config.header.get_or_insert_with(Box::default);
+    crate::table(&rows, Some(&config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct Server {
+        name: &'static str,
+        cores: u32,
+        tags: Vec<&'static str>,
+        owner: Option<&'static str>,
+    }
+
+    fn servers() -> Vec<Server> {
+        vec![
+            Server {
+                name: "web-01",
+                cores: 8,
+                tags: vec!["prod"],
+                owner: Some("ops"),
+            },
+            Server {
+                name: "db-01",
+                cores: 32,
+                tags: vec![],
+                owner: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_serializable_to_rows_from_structs() {
+        let rows = serializable_to_rows(&servers()).unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                vec!["name", "cores", "tags", "owner"],
+                vec!["web-01", "8", "[\"prod\"]", "ops"],
+                vec!["db-01", "32", "[]", ""],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_serializable_to_rows_merges_map_keys() {
+        let first = BTreeMap::from([("a", 1)]);
+        let second = BTreeMap::from([("a", 2), ("b", 3)]);
+        let rows = serializable_to_rows(&[first, second]).unwrap();
+        assert_eq!(rows, vec![vec!["a", "b"], vec!["1", ""], vec!["2", "3"]]);
+
+        let tuples = serializable_to_rows(&[("x", 1), ("y", 2)]).unwrap();
+        assert_eq!(tuples, vec![vec!["x", "1"], vec!["y", "2"]]);
+    }
+
+    #[test]
+    fn test_table_from_serializable() {
+        let output = table_from_serializable(&servers(), None).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines[1].contains("name") && lines[1].contains("owner"));
+        assert!(lines[3].contains("web-01"));
+        assert_eq!(lines.len(), 7);
+    }
+}
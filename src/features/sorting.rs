@@ -0,0 +1,302 @@
+use crate::features::stats::{infer_column_type, normalize_value};
+use crate::types::{Row, SortKey, SortKind, SortOrder, TableConfig, TableError, TableResult};
+use std::cmp::Ordering;
+
+/// Compares cell text. With the `icu` feature and a `locale`, strings are
+/// ordered by the locale's collation rules (accents, CJK, `ä` after `z` in
+/// Swedish); otherwise by code point.
+pub struct TextCollator {
+    #[cfg(feature = "icu")]
+    collator: Option<icu_collator::Collator>,
+}
+
+impl TextCollator {
+    pub fn new(locale: Option<&str>) -> TableResult<Self> {
+        #[cfg(feature = "icu")]
+        {
+            let collator = locale
+                .map(|tag| {
+                    let locale = tag
+                        .parse::<icu_locid::Locale>()
+                        .map_err(|e| TableError::parse("locale", tag, e.to_string()))?;
+                    icu_collator::Collator::try_new(
+                        &(&locale).into(),
+                        icu_collator::CollatorOptions::new(),
+                    )
+                    .map_err(|e| {
+                        TableError::InvalidConfig(format!("No collation data for `{tag}`: {e}"))
+                    })
+                })
+                .transpose()?;
+            Ok(Self { collator })
+        }
+
+        #[cfg(not(feature = "icu"))]
+        {
+            let _ = locale;
+            Ok(Self {})
+        }
+    }
+
+    pub fn compare(&self, left: &str, right: &str) -> Ordering {
+        #[cfg(feature = "icu")]
+        if let Some(collator) = &self.collator {
+            return collator.compare(left, right);
+        }
+
+        left.cmp(right)
+    }
+}
+
+/// Sorts rows by `column`, leaving the header and footer rows in place.
+/// Numeric columns sort by value, others by `config.locale`'s collation;
+/// empty cells always go last. The sort is stable.
+pub fn sort_rows(
+    rows: &mut [Row],
+    column: usize,
+    order: SortOrder,
+    config: &TableConfig,
+) -> TableResult<()> {
+    let key = SortKey {
+        column,
+        order: Some(order),
+        kind: None,
+    };
+    sort_rows_by(rows, &[key], config)
+}
+
+/// Sorts rows by several keys, each later key breaking ties left by the ones
+/// before it. The header and footer rows, when the config has them, stay put.
+pub fn sort_rows_by(rows: &mut [Row], keys: &[SortKey], config: &TableConfig) -> TableResult<()> {
+    let body_rows = config.body_rows(rows.len());
+    let body = &mut rows[body_rows];
+
+    if let Some(key) = keys
+        .iter()
+        .find(|key| body.iter().any(|row| key.column >= row.len()))
+    {
+        return Err(TableError::IndexOutOfBounds {
+            setting: "Sort column",
+            index: key.column,
+            bound: body.iter().map(Vec::len).min().unwrap_or(0),
+        });
+    }
+
+    let collator = TextCollator::new(config.locale.as_deref())?;
+    let kinds: Vec<SortKind> = keys
+        .iter()
+        .map(|key| match key.kind.unwrap_or_default() {
+            SortKind::Auto => {
+                let values = body.iter().map(|row| row[key.column].as_str());
+                if infer_column_type(values).is_numeric() {
+                    SortKind::Numeric
+                } else {
+                    SortKind::Lexical
+                }
+            }
+            kind => kind,
+        })
+        .collect();
+
+    // Normalize each sort cell once rather than on every comparison.
+    let mut keyed: Vec<(Vec<String>, Row)> = body
+        .iter_mut()
+        .map(|row| {
+            let values = keys
+                .iter()
+                .map(|key| normalize_value(&row[key.column]))
+                .collect();
+            (values, std::mem::take(row))
+        })
+        .collect();
+
+    keyed.sort_by(|(a, _), (b, _)| {
+        keys.iter()
+            .zip(&kinds)
+            .enumerate()
+            .map(|(idx, (key, &kind))| {
+                compare_values(
+                    &a[idx],
+                    &b[idx],
+                    kind,
+                    key.order.unwrap_or_default(),
+                    &collator,
+                )
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+
+    for (slot, (_, row)) in body.iter_mut().zip(keyed) {
+        *slot = row;
+    }
+
+    Ok(())
+}
+
+fn compare_values(
+    a: &str,
+    b: &str,
+    kind: SortKind,
+    order: SortOrder,
+    collator: &TextCollator,
+) -> Ordering {
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Greater,
+        (false, true) => return Ordering::Less,
+        (false, false) => {}
+    }
+
+    let ordering = match kind {
+        SortKind::Numeric => compare_numbers(a, b),
+        SortKind::Natural => compare_natural(a, b, collator),
+        SortKind::Auto | SortKind::Lexical => collator.compare(a, b),
+    };
+
+    match order {
+        SortOrder::Ascending => ordering,
+        SortOrder::Descending => ordering.reverse(),
+    }
+}
+
+fn compare_numbers(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+fn compare_natural(a: &str, b: &str, collator: &TextCollator) -> Ordering {
+    let (a_chunks, b_chunks) = (digit_chunks(a), digit_chunks(b));
+
+    for (a, b) in a_chunks.iter().zip(&b_chunks) {
+        let is_number = |chunk: &str| chunk.starts_with(|c: char| c.is_ascii_digit());
+        let ordering = if is_number(a) && is_number(b) {
+            let (a, b) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+            a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+        } else {
+            collator.compare(a, b)
+        };
+
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+
+    a_chunks.len().cmp(&b_chunks.len())
+}
+
+// Splits text into alternating runs of ASCII digits and everything else.
+fn digit_chunks(text: &str) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut in_digits = None;
+
+    for (idx, c) in text.char_indices() {
+        let is_digit = c.is_ascii_digit();
+        if in_digits.is_some_and(|in_digits| in_digits != is_digit) {
+            chunks.push(&text[start..idx]);
+            start = idx;
+        }
+        in_digits = Some(is_digit);
+    }
+
+    if start < text.len() {
+        chunks.push(&text[start..]);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(values: &[&str]) -> Vec<Row> {
+        values.iter().map(|v| vec![v.to_string()]).collect()
+    }
+
+    fn column(rows: &[Row]) -> Vec<&str> {
+        rows.iter().map(|row| row[0].as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_rows_numeric_and_header() {
+        let config = TableConfig {
+            header: Some(Box::default()),
+            ..Default::default()
+        };
+        let mut data = rows(&["Size", "10", "", "9", "100"]);
+
+        sort_rows(&mut data, 0, SortOrder::Ascending, &config).unwrap();
+        assert_eq!(column(&data), vec!["Size", "9", "10", "100", ""]);
+
+        sort_rows(&mut data, 0, SortOrder::Descending, &config).unwrap();
+        assert_eq!(column(&data), vec!["Size", "100", "10", "9", ""]);
+
+        assert!(sort_rows(&mut data, 1, SortOrder::Ascending, &config).is_err());
+    }
+
+    #[test]
+    fn test_sort_rows_without_locale_uses_code_points() {
+        let mut data = rows(&["b", "\u{1b}[1ma\u{1b}[0m", "B"]);
+        sort_rows(&mut data, 0, SortOrder::Ascending, &TableConfig::default()).unwrap();
+        assert_eq!(column(&data), vec!["B", "\u{1b}[1ma\u{1b}[0m", "b"]);
+    }
+
+    #[test]
+    fn test_sort_rows_by_kinds_and_multiple_keys() {
+        let config = TableConfig::default();
+        let mut data = rows(&["item10", "item2", "Item1", "item02b"]);
+
+        sort_rows_by(&mut data, &["0:natural".parse().unwrap()], &config).unwrap();
+        assert_eq!(column(&data), vec!["Item1", "item2", "item02b", "item10"]);
+
+        let mut data = rows(&["10", "9", "x"]);
+        sort_rows_by(&mut data, &["0:lexical".parse().unwrap()], &config).unwrap();
+        assert_eq!(column(&data), vec!["10", "9", "x"]);
+        sort_rows_by(&mut data, &["0:numeric".parse().unwrap()], &config).unwrap();
+        assert_eq!(column(&data), vec!["9", "10", "x"]);
+
+        let mut data = vec![
+            vec!["b".to_string(), "1".to_string()],
+            vec!["a".to_string(), "1".to_string()],
+            vec!["c".to_string(), "2".to_string()],
+        ];
+        let keys = ["1:desc".parse().unwrap(), "0".parse().unwrap()];
+        sort_rows_by(&mut data, &keys, &config).unwrap();
+        assert_eq!(data[0][0], "c");
+        assert_eq!(data[1][0], "a");
+        assert_eq!(data[2][0], "b");
+    }
+
+    #[test]
+    fn test_sort_key_from_str() {
+        let key: SortKey = "2:desc:natural".parse().unwrap();
+        assert_eq!(key.column, 2);
+        assert_eq!(key.order, Some(SortOrder::Descending));
+        assert_eq!(key.kind, Some(SortKind::Natural));
+        assert!("x:desc".parse::<SortKey>().is_err());
+        assert!("1:sideways".parse::<SortKey>().is_err());
+    }
+
+    #[cfg(feature = "icu")]
+    #[test]
+    fn test_sort_rows_locale_collation() {
+        let mut data = rows(&["Zoë", "ä", "Émile", "zebra", "apple"]);
+        let config = |locale: &str| TableConfig {
+            locale: Some(locale.to_string()),
+            ..Default::default()
+        };
+
+        sort_rows(&mut data, 0, SortOrder::Ascending, &config("en")).unwrap();
+        assert_eq!(column(&data), vec!["ä", "apple", "Émile", "zebra", "Zoë"]);
+
+        sort_rows(&mut data, 0, SortOrder::Ascending, &config("sv")).unwrap();
+        assert_eq!(column(&data), vec!["apple", "Émile", "zebra", "Zoë", "ä"]);
+
+        assert!(sort_rows(&mut data, 0, SortOrder::Ascending, &config("not a locale")).is_err());
+    }
+}
@@ -1,7 +1,113 @@
+use crate::core::calculator::wrap_column_cell;
+use crate::core::processor::{align_column_vertically, truncate_cell};
+use crate::core::renderer::separator_after;
+use crate::features::alignment_processor::AlignmentProcessor;
 use crate::types::{
-    CellConfig, CellCoordinates, ColumnConfig, RangeConfig, RangeCoordinate, Row,
-    SpanningCellConfig,
+    Alignment, CellConfig, CellCoordinates, ColumnConfig, RangeConfig, RangeCoordinate, Row,
+    RowAnnotation, SpanningCellConfig, TableConfig, TableError, TableResult,
 };
+use crate::utils::ansi::calculate_display_width;
+use serde::{Deserialize, Serialize};
+
+/// Rows of column groups drawn above the header row, top row first, such as
+/// `Q1` over `Revenue` and `Cost`. They are drawn as header rows, with the
+/// `header` settings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub struct HeaderSpec {
+    pub rows: Vec<Vec<HeaderGroup>>,
+}
+
+/// A cell of a `HeaderSpec` row. Groups cover the drawn columns from the
+/// left, `span` columns each; columns after a row's last group are left
+/// blank.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HeaderGroup {
+    pub title: String,
+    #[serde(default = "one")]
+    pub span: usize,
+    /// Centered when unset.
+    pub alignment: Option<Alignment>,
+}
+
+fn one() -> usize {
+    1
+}
+
+impl HeaderGroup {
+    pub fn new(title: impl Into<String>, span: usize) -> Self {
+        HeaderGroup {
+            title: title.into(),
+            span,
+            alignment: None,
+        }
+    }
+}
+
+/// Puts the rows of `config.header_groups` above `rows`, each group spanning
+/// its columns, and moves the row-indexed settings (spanning cells, row
+/// annotations and row overrides) down past them. Runs last while the table
+/// is prepared, once the drawn columns are settled.
+pub fn insert_header_groups(rows: &mut Vec<Row>, config: &mut TableConfig) -> TableResult<()> {
+    let Some(spec) = config
+        .header_groups
+        .as_ref()
+        .filter(|spec| !spec.rows.is_empty())
+    else {
+        return Ok(());
+    };
+    let column_count = rows.first().map_or(0, Vec::len);
+
+    let mut group_rows = Vec::with_capacity(spec.rows.len());
+    let mut spans = Vec::new();
+    for (row_idx, groups) in spec.rows.iter().enumerate() {
+        let mut cells = Vec::with_capacity(column_count);
+        for group in groups {
+            let col = cells.len();
+            if group.span == 0 || col + group.span > column_count {
+                return Err(TableError::InvalidConfig(format!(
+                    "header group row {row_idx} needs {} columns, but {column_count} are drawn",
+                    col + group.span.max(1)
+                )));
+            }
+            cells.push(group.title.clone());
+            cells.resize(col + group.span, String::new());
+            spans.push(SpanningCellConfig {
+                col,
+                row: row_idx,
+                col_span: Some(group.span),
+                row_span: None,
+                alignment: Some(group.alignment.unwrap_or(Alignment::Center)),
+                vertical_alignment: None,
+                padding_left: None,
+                padding_right: None,
+                truncate: None,
+                wrap_word: None,
+            });
+        }
+        cells.resize(column_count, String::new());
+        group_rows.push(cells);
+    }
+
+    let shift = group_rows.len();
+    rows.splice(0..0, group_rows);
+    for span in &mut config.spanning_cells {
+        span.row += shift;
+    }
+    config.spanning_cells.splice(0..0, spans);
+    config.row_annotations = std::mem::take(&mut config.row_annotations)
+        .into_iter()
+        .map(|annotation| RowAnnotation {
+            row: annotation.row + shift,
+            ..annotation
+        })
+        .collect();
+    config.row_overrides = std::mem::take(&mut config.row_overrides)
+        .into_iter()
+        .map(|(row, row_override)| (row + shift, row_override))
+        .collect();
+    config.header_group_rows = shift;
+    Ok(())
+}
 
 #[derive(Debug, Clone)]
 pub struct SpanningCellManager {
@@ -12,14 +118,32 @@ impl SpanningCellManager {
     pub fn new(configs: &[SpanningCellConfig], columns: &[ColumnConfig]) -> Self {
         let mut ranges = Vec::new();
 
+        let default_column = ColumnConfig::default();
         for config in configs {
-            let range = create_range_config(config, columns);
-            ranges.push(range);
+            let column = columns.get(config.col).unwrap_or(&default_column);
+            ranges.push(create_range_config(config, column));
         }
 
         Self { ranges }
     }
 
+    /// The spans of `config.spanning_cells`; settings a span leaves unset come
+    /// from its first column.
+    pub fn from_config(config: &TableConfig) -> Self {
+        let ranges = config
+            .spanning_cells
+            .iter()
+            .map(|span| {
+                let column = config
+                    .columns
+                    .get(span.col)
+                    .unwrap_or(&config.column_default);
+                create_range_config(span, column)
+            })
+            .collect();
+        Self { ranges }
+    }
+
     pub fn get_containing_range(&self, cell: &CellCoordinates) -> Option<&RangeConfig> {
         self.ranges.iter().find(|range| {
             cell.col >= range.top_left.col
@@ -108,7 +232,7 @@ pub struct SpanInfo {
     pub origin: CellCoordinates,
 }
 
-fn create_range_config(config: &SpanningCellConfig, columns: &[ColumnConfig]) -> RangeConfig {
+fn create_range_config(config: &SpanningCellConfig, default_column: &ColumnConfig) -> RangeConfig {
     let col_span = config.col_span.unwrap_or(1);
     let row_span = config.row_span.unwrap_or(1);
 
@@ -122,9 +246,6 @@ fn create_range_config(config: &SpanningCellConfig, columns: &[ColumnConfig]) ->
         row: config.row + row_span - 1,
     };
 
-    let default_column_config = ColumnConfig::default();
-    let default_column = columns.get(config.col).unwrap_or(&default_column_config);
-
     let cell_config = CellConfig {
         alignment: config.alignment.unwrap_or(default_column.alignment),
         vertical_alignment: config
@@ -143,6 +264,166 @@ fn create_range_config(config: &SpanningCellConfig, columns: &[ColumnConfig]) ->
     }
 }
 
+/// The settings a span's content is laid out with: those of its first column,
+/// overridden by the span's own.
+pub fn span_column_config(range: &RangeConfig, config: &TableConfig) -> ColumnConfig {
+    let column = config
+        .columns
+        .get(range.top_left.col)
+        .unwrap_or(&config.column_default);
+    ColumnConfig {
+        alignment: range.config.alignment,
+        vertical_alignment: range.config.vertical_alignment,
+        padding_left: range.config.padding_left,
+        padding_right: range.config.padding_right,
+        truncate: range.config.truncate,
+        wrap_word: range.config.wrap_word,
+        width: 0,
+        ..column.clone()
+    }
+}
+
+/// The width of a span: its columns plus the vertical lines between them.
+pub fn span_width(range: &RangeConfig, column_widths: &[usize], config: &TableConfig) -> usize {
+    let columns = range.top_left.col..=range.bottom_right.col;
+    let join_width = calculate_display_width(&config.border.body_join);
+    column_widths[columns].iter().sum::<usize>()
+        + join_width * (range.bottom_right.col - range.top_left.col)
+}
+
+/// The lines a span covers: those of its rows plus the separators drawn
+/// between them, as `row_overrides` leave them.
+pub fn span_height(range: &RangeConfig, row_heights: &[usize], config: &TableConfig) -> usize {
+    let offsets = row_line_offsets(row_heights, config);
+    offsets[range.bottom_right.row] - offsets[range.top_left.row]
+        + row_heights[range.bottom_right.row]
+}
+
+// The line each row starts on, counting the rows above it and the separators
+// the renderer draws after them.
+fn row_line_offsets(row_heights: &[usize], config: &TableConfig) -> Vec<usize> {
+    let row_count = row_heights.len();
+    let mut offsets = Vec::with_capacity(row_count);
+    let mut offset = 0;
+    for (row, &height) in row_heights.iter().enumerate() {
+        offsets.push(offset);
+        offset += height + usize::from(separator_after(config, row, row_count).is_some());
+    }
+    offsets
+}
+
+/// The span's content, truncated and wrapped to the span's width but not yet
+/// aligned.
+pub fn wrap_span_content(
+    range: &RangeConfig,
+    rows: &[Row],
+    column_widths: &[usize],
+    config: &TableConfig,
+) -> Vec<String> {
+    let column = span_column_config(range, config);
+    let content = &rows[range.top_left.row][range.top_left.col];
+    let content_width = span_width(range, column_widths, config)
+        .saturating_sub(column.padding_left + column.padding_right);
+    wrap_column_cell(&truncate_cell(content, &column), content_width, &column)
+}
+
+/// Spanning cells laid out for drawing: which cells each span covers, and the
+/// lines of its content, one for every line it covers, separators included.
+#[derive(Debug, Clone)]
+pub struct SpanLayout {
+    ranges: Vec<RangeConfig>,
+    lines: Vec<Vec<String>>,
+    owners: Vec<Vec<Option<usize>>>,
+    row_offsets: Vec<usize>,
+    row_heights: Vec<usize>,
+}
+
+impl SpanLayout {
+    pub fn new(
+        rows: &[Row],
+        config: &TableConfig,
+        column_widths: &[usize],
+        row_heights: &[usize],
+    ) -> Self {
+        let ranges = SpanningCellManager::from_config(config).ranges;
+        let mut owners = vec![vec![None; column_widths.len()]; rows.len()];
+        for (span, range) in ranges.iter().enumerate() {
+            for row in &mut owners[range.top_left.row..=range.bottom_right.row] {
+                row[range.top_left.col..=range.bottom_right.col].fill(Some(span));
+            }
+        }
+
+        let row_offsets = row_line_offsets(row_heights, config);
+
+        let lines = ranges
+            .iter()
+            .map(|range| {
+                let column = span_column_config(range, config);
+                let width = span_width(range, column_widths, config);
+                align_column_vertically(
+                    &wrap_span_content(range, rows, column_widths, config),
+                    span_height(range, row_heights, config),
+                    column.vertical_alignment,
+                )
+                .iter()
+                .map(|line| AlignmentProcessor::layout_cell(line, &column, width).to_string())
+                .collect()
+            })
+            .collect();
+
+        Self {
+            ranges,
+            lines,
+            owners,
+            row_offsets,
+            row_heights: row_heights.to_vec(),
+        }
+    }
+
+    /// The span covering a cell, if any.
+    pub fn span_at(&self, row: usize, col: usize) -> Option<usize> {
+        self.owners.get(row)?.get(col).copied().flatten()
+    }
+
+    pub fn touches_row(&self, row: usize) -> bool {
+        self.owners
+            .get(row)
+            .is_some_and(|cells| cells.iter().any(Option::is_some))
+    }
+
+    /// The last column of a span.
+    pub fn end_col(&self, span: usize) -> usize {
+        self.ranges[span].bottom_right.col
+    }
+
+    /// Whether a vertical line is drawn at `boundary` (0 is the left edge) in
+    /// `row`: always at the edges, and between columns not in the same span.
+    pub fn has_divider(&self, row: usize, boundary: usize) -> bool {
+        let columns = self.owners[row].len();
+        boundary == 0
+            || boundary >= columns
+            || self.span_at(row, boundary - 1).is_none()
+            || self.span_at(row, boundary - 1) != self.span_at(row, boundary)
+    }
+
+    /// The span that continues across the separator below `row` in `col`.
+    pub fn crossing(&self, row: usize, col: usize) -> Option<usize> {
+        self.span_at(row, col)
+            .filter(|&span| self.span_at(row + 1, col) == Some(span))
+    }
+
+    /// Line `line` of `row` within a span.
+    pub fn row_line(&self, span: usize, row: usize, line: usize) -> &str {
+        let origin = self.ranges[span].top_left.row;
+        &self.lines[span][self.row_offsets[row] - self.row_offsets[origin] + line]
+    }
+
+    /// The span's line on the separator below `row`.
+    pub fn separator_line(&self, span: usize, row: usize) -> &str {
+        self.row_line(span, row, self.row_heights[row])
+    }
+}
+
 pub fn calculate_range_coordinate(spanning_cell_config: &SpanningCellConfig) -> RangeCoordinate {
     let col_span = spanning_cell_config.col_span.unwrap_or(1);
     let row_span = spanning_cell_config.row_span.unwrap_or(1);
@@ -0,0 +1,573 @@
+use crate::types::Row;
+use crate::utils::ansi::{calculate_display_width, strip_ansi_sequences};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnType {
+    #[default]
+    Empty,
+    Boolean,
+    Integer,
+    Float,
+    Text,
+}
+
+impl std::fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColumnType::Empty => write!(f, "empty"),
+            ColumnType::Boolean => write!(f, "boolean"),
+            ColumnType::Integer => write!(f, "integer"),
+            ColumnType::Float => write!(f, "float"),
+            ColumnType::Text => write!(f, "text"),
+        }
+    }
+}
+
+impl ColumnType {
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, ColumnType::Integer | ColumnType::Float)
+    }
+
+    fn widen(self, other: ColumnType) -> ColumnType {
+        match (self, other) {
+            (ColumnType::Empty, other) | (other, ColumnType::Empty) => other,
+            (a, b) if a == b => a,
+            (ColumnType::Integer, ColumnType::Float) | (ColumnType::Float, ColumnType::Integer) => {
+                ColumnType::Float
+            }
+            _ => ColumnType::Text,
+        }
+    }
+}
+
+pub fn normalize_value(value: &str) -> String {
+    strip_ansi_sequences(value).trim().to_string()
+}
+
+pub fn is_null_value(value: &str) -> bool {
+    normalize_value(value).is_empty()
+}
+
+pub fn infer_value_type(value: &str) -> ColumnType {
+    let value = normalize_value(value);
+
+    if value.is_empty() {
+        ColumnType::Empty
+    } else if value.parse::<i64>().is_ok() {
+        ColumnType::Integer
+    } else if value.parse::<f64>().is_ok_and(|v| v.is_finite()) {
+        ColumnType::Float
+    } else if matches!(value.to_lowercase().as_str(), "true" | "false") {
+        ColumnType::Boolean
+    } else {
+        ColumnType::Text
+    }
+}
+
+pub fn infer_column_type<'a>(values: impl IntoIterator<Item = &'a str>) -> ColumnType {
+    values
+        .into_iter()
+        .map(infer_value_type)
+        .fold(ColumnType::Empty, ColumnType::widen)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    pub name: String,
+    pub column_type: ColumnType,
+    pub min: Option<String>,
+    pub max: Option<String>,
+    pub mean: Option<f64>,
+    pub distinct_count: usize,
+    pub null_count: usize,
+}
+
+impl ColumnStats {
+    /// One-line description such as `integer, 3 distinct, min 25, max 35`.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![
+            self.column_type.to_string(),
+            format!("{} distinct", self.distinct_count),
+        ];
+        if let Some(min) = &self.min {
+            parts.push(format!("min {min}"));
+        }
+        if let Some(max) = &self.max {
+            parts.push(format!("max {max}"));
+        }
+        if self.null_count > 0 {
+            parts.push(format!("{} empty", self.null_count));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Guesses whether the first row is a header: every first-row cell is
+/// non-empty text, and at least one column holds numbers or booleans below it.
+/// All-text data is assumed to have no header.
+pub fn detect_header(rows: &[Row]) -> bool {
+    let [first_row, body @ ..] = rows else {
+        return false;
+    };
+    if body.is_empty() {
+        return false;
+    }
+
+    let header_like = first_row
+        .iter()
+        .all(|cell| infer_value_type(cell) == ColumnType::Text);
+
+    header_like
+        && (0..first_row.len()).any(|col_idx| {
+            let body_type = infer_column_type(
+                body.iter()
+                    .map(|row| row.get(col_idx).map(String::as_str).unwrap_or("")),
+            );
+            !matches!(body_type, ColumnType::Text | ColumnType::Empty)
+        })
+}
+
+/// How to treat the first row of imported data. In configs it is `true`,
+/// `false` or `"auto"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HeaderRow {
+    Yes,
+    #[default]
+    No,
+    Auto,
+}
+
+impl HeaderRow {
+    pub fn resolve(self, rows: &[Row]) -> bool {
+        match self {
+            HeaderRow::Yes => true,
+            HeaderRow::No => false,
+            HeaderRow::Auto => detect_header(rows),
+        }
+    }
+}
+
+impl Serialize for HeaderRow {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            HeaderRow::Yes => serializer.serialize_bool(true),
+            HeaderRow::No => serializer.serialize_bool(false),
+            HeaderRow::Auto => serializer.serialize_str("auto"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HeaderRow {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Flag(bool),
+            Mode(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Flag(true) => Ok(HeaderRow::Yes),
+            Repr::Flag(false) => Ok(HeaderRow::No),
+            Repr::Mode(mode) => mode.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+impl std::str::FromStr for HeaderRow {
+    type Err = crate::types::TableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "yes" | "true" => Ok(HeaderRow::Yes),
+            "no" | "false" => Ok(HeaderRow::No),
+            "auto" => Ok(HeaderRow::Auto),
+            _ => Err(crate::types::TableError::parse(
+                "header row mode",
+                s,
+                "expected yes, no or auto",
+            )),
+        }
+    }
+}
+
+pub fn calculate_column_stats(rows: &[Row], has_header: bool) -> Vec<ColumnStats> {
+    let Some(first_row) = rows.first() else {
+        return Vec::new();
+    };
+
+    let body = if has_header { &rows[1..] } else { rows };
+
+    (0..first_row.len())
+        .map(|col_idx| {
+            let name = if has_header {
+                normalize_value(&first_row[col_idx])
+            } else {
+                format!("Column {}", col_idx + 1)
+            };
+
+            let values: Vec<String> = body
+                .iter()
+                .map(|row| normalize_value(row.get(col_idx).map(String::as_str).unwrap_or("")))
+                .collect();
+
+            calculate_single_column_stats(name, &values)
+        })
+        .collect()
+}
+
+fn calculate_single_column_stats(name: String, values: &[String]) -> ColumnStats {
+    let present: Vec<&str> = values
+        .iter()
+        .map(String::as_str)
+        .filter(|value| !value.is_empty())
+        .collect();
+
+    let column_type = infer_column_type(present.iter().copied());
+    let null_count = values.len() - present.len();
+    let distinct_count = present.iter().collect::<HashSet<_>>().len();
+
+    let (min, max, mean) = if column_type.is_numeric() {
+        let numbers: Vec<(f64, &str)> = present
+            .iter()
+            .filter_map(|value| value.parse::<f64>().ok().map(|number| (number, *value)))
+            .collect();
+
+        let min = numbers
+            .iter()
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, text)| text.to_string());
+        let max = numbers
+            .iter()
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, text)| text.to_string());
+        let mean = if numbers.is_empty() {
+            None
+        } else {
+            Some(numbers.iter().map(|(number, _)| number).sum::<f64>() / numbers.len() as f64)
+        };
+
+        (min, max, mean)
+    } else {
+        (
+            present.iter().min().map(|value| value.to_string()),
+            present.iter().max().map(|value| value.to_string()),
+            None,
+        )
+    };
+
+    ColumnStats {
+        name,
+        column_type,
+        min,
+        max,
+        mean,
+        distinct_count,
+        null_count,
+    }
+}
+
+/// Why `suggest_redundant_columns` flags a column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnRedundancy {
+    /// Every body cell holds this value, ignoring ANSI styling and surrounding
+    /// spaces; empty for a column with nothing in it.
+    Constant(String),
+    /// Every body cell equals the one in this earlier column.
+    DuplicateOf(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSuggestion {
+    pub column: usize,
+    pub redundancy: ColumnRedundancy,
+}
+
+/// Columns a wide, machine-generated table could do without: constant ones,
+/// and copies of an earlier column. Cells are compared like
+/// `calculate_column_stats` compares them. Needs at least two body rows, since
+/// with fewer every column looks constant.
+pub fn suggest_redundant_columns(rows: &[Row], has_header: bool) -> Vec<ColumnSuggestion> {
+    let Some(first_row) = rows.first() else {
+        return Vec::new();
+    };
+
+    let body = if has_header { &rows[1..] } else { rows };
+    if body.len() < 2 {
+        return Vec::new();
+    }
+
+    let columns: Vec<Vec<String>> = (0..first_row.len())
+        .map(|col_idx| {
+            body.iter()
+                .map(|row| normalize_value(row.get(col_idx).map(String::as_str).unwrap_or("")))
+                .collect()
+        })
+        .collect();
+
+    let mut suggestions = Vec::new();
+    for (column, values) in columns.iter().enumerate() {
+        let redundancy = if values.iter().all(|value| *value == values[0]) {
+            ColumnRedundancy::Constant(values[0].clone())
+        } else if let Some(original) = (0..column).find(|&other| columns[other] == *values) {
+            ColumnRedundancy::DuplicateOf(original)
+        } else {
+            continue;
+        };
+        suggestions.push(ColumnSuggestion { column, redundancy });
+    }
+
+    suggestions
+}
+
+pub fn column_stats_to_rows(stats: &[ColumnStats]) -> Vec<Row> {
+    let mut rows = vec![vec![
+        "Column".to_string(),
+        "Type".to_string(),
+        "Min".to_string(),
+        "Max".to_string(),
+        "Mean".to_string(),
+        "Distinct".to_string(),
+        "Nulls".to_string(),
+    ]];
+
+    for column in stats {
+        rows.push(vec![
+            column.name.clone(),
+            column.column_type.to_string(),
+            column.min.clone().unwrap_or_default(),
+            column.max.clone().unwrap_or_default(),
+            column
+                .mean
+                .map(|mean| format!("{mean:.2}"))
+                .unwrap_or_default(),
+            column.distinct_count.to_string(),
+            column.null_count.to_string(),
+        ]);
+    }
+
+    rows
+}
+
+/// Column statistics kept up to date one value at a time, for streams that
+/// never hold all their rows. Values are read like `calculate_column_stats`
+/// reads them; distinct values are not counted, since that needs every value.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RunningColumnStats {
+    /// Empty until `set_name`.
+    pub name: String,
+    pub column_type: ColumnType,
+    /// The widest line of any value or of the name, ANSI styling excluded.
+    pub max_width: usize,
+    pub count: usize,
+    pub null_count: usize,
+    /// How many values are numbers; `sum`, `min` and `max` are over these.
+    pub numeric_count: usize,
+    pub sum: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl RunningColumnStats {
+    /// Names the column, e.g. from a stream's header row, widening
+    /// `max_width` to fit the name.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = normalize_value(name);
+        self.max_width = self.max_width.max(widest_line(name));
+    }
+
+    pub fn push(&mut self, value: &str) {
+        self.count += 1;
+        self.max_width = self.max_width.max(widest_line(value));
+        self.column_type = self.column_type.widen(infer_value_type(value));
+
+        let value = normalize_value(value);
+        if value.is_empty() {
+            self.null_count += 1;
+        } else if let Ok(number) = value.parse::<f64>()
+            && number.is_finite()
+        {
+            self.numeric_count += 1;
+            self.sum += number;
+            self.min = Some(self.min.map_or(number, |min| min.min(number)));
+            self.max = Some(self.max.map_or(number, |max| max.max(number)));
+        }
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (self.numeric_count > 0).then(|| self.sum / self.numeric_count as f64)
+    }
+
+    /// One-line description such as `integer, 4 wide, min 25, max 35, mean 30`.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![
+            self.column_type.to_string(),
+            format!("{} wide", self.max_width),
+        ];
+        if let (Some(min), Some(max), Some(mean)) = (self.min, self.max, self.mean()) {
+            parts.push(format!("min {min}"));
+            parts.push(format!("max {max}"));
+            parts.push(format!("mean {}", (mean * 100.0).round() / 100.0));
+        }
+        if self.null_count > 0 {
+            parts.push(format!("{} empty", self.null_count));
+        }
+        parts.join(", ")
+    }
+}
+
+fn widest_line(value: &str) -> usize {
+    value
+        .lines()
+        .map(calculate_display_width)
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_rows() -> Vec<Row> {
+        vec![
+            vec!["Name".to_string(), "Age".to_string(), "Score".to_string()],
+            vec!["John".to_string(), "30".to_string(), "1.5".to_string()],
+            vec!["Jane".to_string(), "25".to_string(), "".to_string()],
+            vec!["John".to_string(), "35".to_string(), "2".to_string()],
+        ]
+    }
+
+    #[test]
+    fn test_infer_value_type() {
+        assert_eq!(infer_value_type("42"), ColumnType::Integer);
+        assert_eq!(infer_value_type("-4.2"), ColumnType::Float);
+        assert_eq!(infer_value_type("TRUE"), ColumnType::Boolean);
+        assert_eq!(infer_value_type("  "), ColumnType::Empty);
+        assert_eq!(
+            infer_value_type("\u{1b}[31m7\u{1b}[0m"),
+            ColumnType::Integer
+        );
+        assert_eq!(infer_value_type("hello"), ColumnType::Text);
+    }
+
+    #[test]
+    fn test_infer_column_type_widening() {
+        assert_eq!(infer_column_type(["1", "2.5", ""]), ColumnType::Float);
+        assert_eq!(infer_column_type(["1", "x"]), ColumnType::Text);
+        assert_eq!(infer_column_type(["", ""]), ColumnType::Empty);
+    }
+
+    #[test]
+    fn test_calculate_column_stats() {
+        let stats = calculate_column_stats(&sample_rows(), true);
+        assert_eq!(stats.len(), 3);
+
+        assert_eq!(stats[0].name, "Name");
+        assert_eq!(stats[0].column_type, ColumnType::Text);
+        assert_eq!(stats[0].distinct_count, 2);
+        assert_eq!(stats[0].min.as_deref(), Some("Jane"));
+
+        assert_eq!(stats[1].column_type, ColumnType::Integer);
+        assert_eq!(stats[1].min.as_deref(), Some("25"));
+        assert_eq!(stats[1].max.as_deref(), Some("35"));
+        assert_eq!(stats[1].mean, Some(30.0));
+
+        assert_eq!(stats[2].column_type, ColumnType::Float);
+        assert_eq!(stats[2].null_count, 1);
+        assert_eq!(stats[2].mean, Some(1.75));
+    }
+
+    #[test]
+    fn test_suggest_redundant_columns() {
+        let rows: Vec<Row> = [
+            ["host", "region", "id", "hostname", "note"],
+            ["web-01", "eu", "1", "web-01", ""],
+            ["db-01", "\u{1b}[1meu\u{1b}[0m", "2", "db-01", " "],
+        ]
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect();
+
+        assert_eq!(
+            suggest_redundant_columns(&rows, true),
+            vec![
+                ColumnSuggestion {
+                    column: 1,
+                    redundancy: ColumnRedundancy::Constant("eu".to_string()),
+                },
+                ColumnSuggestion {
+                    column: 3,
+                    redundancy: ColumnRedundancy::DuplicateOf(0),
+                },
+                ColumnSuggestion {
+                    column: 4,
+                    redundancy: ColumnRedundancy::Constant(String::new()),
+                },
+            ]
+        );
+        assert!(suggest_redundant_columns(&rows[..2], true).is_empty());
+    }
+
+    #[test]
+    fn test_detect_header() {
+        assert!(detect_header(&sample_rows()));
+        assert!(!detect_header(&sample_rows()[1..]));
+
+        let all_text = vec![vec!["apple".to_string()], vec!["banana".to_string()]];
+        assert!(!detect_header(&all_text));
+        assert!(!detect_header(&sample_rows()[..1]));
+
+        assert!(HeaderRow::Auto.resolve(&sample_rows()));
+        assert!(!HeaderRow::No.resolve(&sample_rows()));
+        assert_eq!("AUTO".parse::<HeaderRow>().unwrap(), HeaderRow::Auto);
+        assert!("maybe".parse::<HeaderRow>().is_err());
+    }
+
+    #[test]
+    fn test_column_stats_summary() {
+        let stats = calculate_column_stats(&sample_rows(), true);
+        assert_eq!(stats[1].summary(), "integer, 3 distinct, min 25, max 35");
+        assert_eq!(
+            stats[2].summary(),
+            "float, 2 distinct, min 1.5, max 2, 1 empty"
+        );
+    }
+
+    #[test]
+    fn test_running_column_stats() {
+        let mut stats = RunningColumnStats::default();
+        stats.set_name("Age");
+        for value in ["30", "", "\u{1b}[31m25\u{1b}[0m", "35"] {
+            stats.push(value);
+        }
+
+        assert_eq!(stats.name, "Age");
+        assert_eq!(stats.column_type, ColumnType::Integer);
+        assert_eq!(stats.max_width, 3);
+        assert_eq!((stats.count, stats.null_count), (4, 1));
+        assert_eq!(
+            (stats.min, stats.max, stats.mean()),
+            (Some(25.0), Some(35.0), Some(30.0))
+        );
+        assert_eq!(
+            stats.summary(),
+            "integer, 3 wide, min 25, max 35, mean 30, 1 empty"
+        );
+
+        stats.push("n/a");
+        assert_eq!(stats.column_type, ColumnType::Text);
+        assert_eq!(stats.numeric_count, 3);
+    }
+
+    #[test]
+    fn test_column_stats_to_rows() {
+        let stats = calculate_column_stats(&sample_rows(), false);
+        let rows = column_stats_to_rows(&stats);
+
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[1][0], "Column 1");
+        assert!(rows.iter().all(|row| row.len() == 7));
+    }
+}
@@ -1,16 +1,29 @@
 use crate::core::calculator::{
-    calculate_output_column_widths, calculate_row_heights, map_data_using_row_heights,
+    apply_percent_widths, calculate_output_column_widths, calculate_row_heights,
+    map_data_using_row_heights,
 };
+use crate::core::processor::truncate_table_data;
 use crate::core::renderer::BorderType;
-use crate::core::renderer::{draw_border_line, draw_row};
-use crate::types::{ColumnConfig, StreamConfig, StreamUserConfig, TableError, TableResult};
+use crate::core::renderer::{draw_border_line, write_row};
+use crate::features::stats::RunningColumnStats;
+use crate::types::{
+    ColumnConfig, StreamConfig, StreamUserConfig, TableConfig, TableError, TableResult,
+};
+use crate::utils::formatting::normalize_string;
+use crate::utils::terminal::LineEraser;
 use std::io::Write;
 
+/// The width of every column when the config sets neither `columns` nor a
+/// `column_default` width, since a stream cannot measure rows it has not seen.
+pub const DEFAULT_COLUMN_WIDTH: usize = 10;
+
 pub struct TableStream {
     config: StreamConfig,
     column_widths: Vec<usize>,
     first_row: bool,
     row_count: usize,
+    suspended_border: LineEraser,
+    stats: Option<Vec<RunningColumnStats>>,
 }
 
 impl TableStream {
@@ -26,24 +39,52 @@ impl TableStream {
             column_widths: Vec::new(),
             first_row: true,
             row_count: 0,
+            suspended_border: LineEraser::new(),
+            stats: None,
         }
     }
 
+    /// Keeps `stats` for every column as rows are written.
+    pub fn with_stats(mut self) -> Self {
+        self.stats = Some(Vec::new());
+        self
+    }
+
     pub fn initialize_columns(&mut self, column_count: usize) {
         if self.config.columns.is_empty() {
             let default_column = ColumnConfig {
-                width: 10,
-                ..Default::default()
+                width: match self.config.column_default.width {
+                    0 => DEFAULT_COLUMN_WIDTH,
+                    width => width,
+                },
+                ..self.config.column_default.clone()
             };
             self.config.columns = vec![default_column; column_count];
         }
 
         let dummy_data = vec![vec![String::new(); column_count]];
-        self.column_widths =
-            calculate_output_column_widths(&dummy_data, &self.config.clone().into());
+        let table_config = apply_percent_widths(&dummy_data, &self.config.clone().into());
+        self.column_widths = calculate_output_column_widths(&dummy_data, &table_config);
+
+        // Pin every column to its width so each row wraps to the same widths.
+        let column_default = self.config.column_default.clone();
+        self.config.columns.resize(column_count, column_default);
+        for (column, &width) in self.config.columns.iter_mut().zip(&self.column_widths) {
+            column.width = width;
+        }
     }
 
     pub fn write_row(&mut self, row: &[String]) -> TableResult<String> {
+        self.write(row, false)
+    }
+
+    /// Writes `row` like `write_row`, but as column names: in `stats` it names
+    /// the columns instead of counting as values.
+    pub fn write_header(&mut self, row: &[String]) -> TableResult<String> {
+        self.write(row, true)
+    }
+
+    fn write(&mut self, row: &[String], header: bool) -> TableResult<String> {
         if self.column_widths.is_empty() {
             self.initialize_columns(row.len());
         }
@@ -51,8 +92,22 @@ impl TableStream {
         if row.len() != self.column_widths.len() {
             return Err(TableError::InconsistentRowLength);
         }
+        for cell in row {
+            normalize_string(cell)?;
+        }
 
-        let mut result = String::new();
+        if let Some(stats) = &mut self.stats {
+            stats.resize_with(row.len(), RunningColumnStats::default);
+            for (column, cell) in stats.iter_mut().zip(row) {
+                if header {
+                    column.set_name(cell);
+                } else {
+                    column.push(cell);
+                }
+            }
+        }
+
+        let mut result = self.resume();
 
         if self.first_row {
             if (self.config.draw_horizontal_line)(0, 1) {
@@ -64,7 +119,9 @@ impl TableStream {
                 result.push('\n');
             }
             self.first_row = false;
-        } else if (self.config.draw_horizontal_line)(self.row_count, self.row_count + 1) {
+        } else if self.config.border.body_row_separators
+            && (self.config.draw_horizontal_line)(self.row_count, self.row_count + 1)
+        {
             result.push_str(&draw_border_line(
                 &self.column_widths,
                 &self.config.border,
@@ -73,21 +130,15 @@ impl TableStream {
             result.push('\n');
         }
 
-        // Handle multiline cells by calculating row height and mapping data
-        let rows_data = vec![row.to_vec()];
-        let table_config = self.config.clone().into();
+        // The same truncate, wrap and lay out steps as `table`, one row at a time.
+        let table_config: TableConfig = self.config.clone().into();
+        let rows_data = truncate_table_data(&[row.to_vec()], &table_config);
         let row_heights = calculate_row_heights(&rows_data, &table_config);
         let mapped_rows = map_data_using_row_heights(&rows_data, &row_heights, &table_config);
 
-        // Render all sub-rows for this multiline row
         if let Some(sub_rows) = mapped_rows.first() {
             for sub_row in sub_rows {
-                let processed_row = self.process_row_data(sub_row);
-                result.push_str(&draw_row(
-                    &processed_row,
-                    &self.column_widths,
-                    &self.config.border,
-                ));
+                write_row(&mut result, sub_row, &self.column_widths, &table_config);
                 result.push('\n');
             }
         }
@@ -97,9 +148,51 @@ impl TableStream {
         Ok(result)
     }
 
+    /// Emits a temporary bottom border so the table looks closed while the
+    /// producer waits for more rows. The next `write_row` (or an explicit
+    /// `resume`) erases it again.
+    pub fn suspend(&mut self) -> String {
+        if self.first_row || self.is_suspended() {
+            return String::new();
+        }
+
+        let output = format!(
+            "{}\n",
+            draw_border_line(&self.column_widths, &self.config.border, BorderType::Bottom)
+        );
+        self.suspended_border.record(&output);
+        output
+    }
+
+    /// Returns the cursor movement needed to erase the temporary bottom border
+    /// written by `suspend`, or an empty string when the stream is not suspended.
+    pub fn resume(&mut self) -> String {
+        self.suspended_border.erase()
+    }
+
+    /// The width of each column, padding included; empty until the first row.
+    pub fn column_widths(&self) -> &[usize] {
+        &self.column_widths
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        self.suspended_border.lines() > 0
+    }
+
+    /// Statistics for each column over the rows written so far, or `None`
+    /// unless the stream was created `with_stats`. Empty until the first row.
+    pub fn stats(&self) -> Option<&[RunningColumnStats]> {
+        self.stats.as_deref()
+    }
+
     pub fn finalize(&mut self) -> String {
         let mut result = String::new();
 
+        if self.is_suspended() {
+            self.suspended_border = LineEraser::new();
+            return result;
+        }
+
         if !self.first_row && (self.config.draw_horizontal_line)(self.row_count, self.row_count) {
             result.push_str(&draw_border_line(
                 &self.column_widths,
@@ -110,62 +203,6 @@ impl TableStream {
 
         result
     }
-
-    fn process_row_data(&self, row: &[String]) -> Vec<String> {
-        let mut processed = Vec::new();
-
-        for (col_idx, cell) in row.iter().enumerate() {
-            let column_config = self
-                .config
-                .columns
-                .get(col_idx)
-                .unwrap_or(&self.config.column_default);
-            let target_width = self.column_widths.get(col_idx).unwrap_or(&0);
-
-            let mut cell_content = cell.clone();
-
-            if column_config.truncate > 0 {
-                cell_content = self.truncate_cell_content(&cell_content, column_config.truncate);
-            }
-
-            let content_width = target_width
-                .saturating_sub(column_config.padding_left + column_config.padding_right);
-            let aligned =
-                self.align_cell_content(&cell_content, content_width, column_config.alignment);
-            let padded = self.pad_cell_content(
-                &aligned,
-                column_config.padding_left,
-                column_config.padding_right,
-            );
-
-            processed.push(padded);
-        }
-
-        processed
-    }
-
-    fn align_cell_content(
-        &self,
-        content: &str,
-        width: usize,
-        alignment: crate::types::Alignment,
-    ) -> String {
-        use crate::utils::ansi::pad_ansi_string;
-        let padded = pad_ansi_string(content, width, alignment);
-        padded.content
-    }
-
-    fn pad_cell_content(&self, content: &str, left_padding: usize, right_padding: usize) -> String {
-        let left_pad = " ".repeat(left_padding);
-        let right_pad = " ".repeat(right_padding);
-        format!("{left_pad}{content}{right_pad}")
-    }
-
-    fn truncate_cell_content(&self, content: &str, max_width: usize) -> String {
-        use crate::utils::ansi::truncate_ansi_string;
-        let truncated = truncate_ansi_string(content, max_width);
-        truncated.content
-    }
 }
 
 impl StreamUserConfig {
@@ -204,41 +241,147 @@ pub trait WritableStream {
     fn write(&mut self, chunk: &str) -> TableResult<()>;
 }
 
-pub struct StreamWriter<W: Write> {
+/// When `TableStreamWriter` flushes the underlying writer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlushPolicy {
+    /// Flush after every row, so a viewer sees each row as soon as it is written.
+    #[default]
+    EveryRow,
+    /// Flush after every `n` rows, trading latency for fewer syscalls.
+    EveryRows(usize),
+    /// Only flush on `flush`, `suspend` and `finalize`.
+    Manual,
+}
+
+/// Writes a table row by row to any `io::Write` (stdout, a file, a socket).
+/// Only the current row is held in memory, and a slow writer naturally
+/// applies backpressure because each row is written before the next is drawn.
+pub struct TableStreamWriter<W: Write> {
     writer: W,
     stream: TableStream,
+    flush_policy: FlushPolicy,
+    unflushed_rows: usize,
 }
 
-impl<W: Write> StreamWriter<W> {
+/// Former name of `TableStreamWriter`.
+pub type StreamWriter<W> = TableStreamWriter<W>;
+
+impl<W: Write> TableStreamWriter<W> {
     pub fn new(writer: W, config: Option<StreamUserConfig>) -> Self {
         Self {
             writer,
             stream: TableStream::new(config),
+            flush_policy: FlushPolicy::default(),
+            unflushed_rows: 0,
         }
     }
 
+    pub fn with_flush_policy(mut self, flush_policy: FlushPolicy) -> Self {
+        self.flush_policy = flush_policy;
+        self
+    }
+
+    /// Keeps `stats` for every column as rows are written.
+    pub fn with_stats(mut self) -> Self {
+        self.stream = self.stream.with_stats();
+        self
+    }
+
     pub fn write_row(&mut self, row: &[String]) -> TableResult<()> {
         let output = self.stream.write_row(row)?;
+        self.write_output(&output)?;
+        self.row_written()
+    }
+
+    /// Writes `row` as column names; see `TableStream::write_header`.
+    pub fn write_header(&mut self, row: &[String]) -> TableResult<()> {
+        let output = self.stream.write_header(row)?;
+        self.write_output(&output)?;
+        self.row_written()
+    }
+
+    /// Writes rows from an iterator, e.g. a CSV reader or database cursor,
+    /// without collecting them first. Returns the number of rows written.
+    pub fn write_rows<I, R>(&mut self, rows: I) -> TableResult<usize>
+    where
+        I: IntoIterator<Item = R>,
+        R: AsRef<[String]>,
+    {
+        let mut count = 0;
+        for row in rows {
+            self.write_row(row.as_ref())?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    pub fn flush(&mut self) -> TableResult<()> {
+        self.unflushed_rows = 0;
         self.writer
-            .write_all(output.as_bytes())
-            .map_err(|_| TableError::InvalidConfig("Failed to write to stream".to_string()))?;
-        Ok(())
+            .flush()
+            .map_err(TableError::io("Failed to flush stream"))
     }
 
-    pub fn finalize(mut self) -> TableResult<()> {
+    pub fn suspend(&mut self) -> TableResult<()> {
+        let output = self.stream.suspend();
+        self.write_output(&output)?;
+        self.flush()
+    }
+
+    pub fn resume(&mut self) -> TableResult<()> {
+        let output = self.stream.resume();
+        self.write_output(&output)
+    }
+
+    pub fn finalize(self) -> TableResult<()> {
+        self.into_inner().map(|_| ())
+    }
+
+    /// Writes the bottom border, flushes, and hands back the writer.
+    pub fn into_inner(mut self) -> TableResult<W> {
         let output = self.stream.finalize();
+        self.write_output(&output)?;
+        self.flush()?;
+        Ok(self.writer)
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn rows_written(&self) -> usize {
+        self.stream.row_count
+    }
+
+    pub fn stats(&self) -> Option<&[RunningColumnStats]> {
+        self.stream.stats()
+    }
+
+    fn row_written(&mut self) -> TableResult<()> {
+        self.unflushed_rows += 1;
+        let due = match self.flush_policy {
+            FlushPolicy::EveryRow => true,
+            FlushPolicy::EveryRows(n) => self.unflushed_rows >= n.max(1),
+            FlushPolicy::Manual => false,
+        };
+        if due {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn write_output(&mut self, output: &str) -> TableResult<()> {
         self.writer
             .write_all(output.as_bytes())
-            .map_err(|_| TableError::InvalidConfig("Failed to write to stream".to_string()))?;
-        self.writer
-            .flush()
-            .map_err(|_| TableError::InvalidConfig("Failed to flush stream".to_string()))?;
-        Ok(())
+            .map_err(TableError::io("Failed to write to stream"))
     }
 }
 
-pub fn create_stream<W: Write>(writer: W, config: Option<StreamUserConfig>) -> StreamWriter<W> {
-    StreamWriter::new(writer, config)
+pub fn create_stream<W: Write>(
+    writer: W,
+    config: Option<StreamUserConfig>,
+) -> TableStreamWriter<W> {
+    TableStreamWriter::new(writer, config)
 }
 
 pub fn create_string_stream(config: Option<StreamUserConfig>) -> TableStream {
@@ -285,6 +428,33 @@ mod tests {
         assert!(finalized.contains("└"));
     }
 
+    #[test]
+    fn test_stream_suspend_and_resume() {
+        let mut stream = TableStream::new(None);
+        assert_eq!(stream.suspend(), "");
+
+        let _ = stream.write_row(&["a".to_string()]).unwrap();
+        let suspended = stream.suspend();
+        assert!(suspended.starts_with("└"));
+        assert!(suspended.ends_with('\n'));
+        assert!(stream.is_suspended());
+        assert_eq!(stream.suspend(), "");
+
+        let next = stream.write_row(&["b".to_string()]).unwrap();
+        assert!(next.starts_with("\u{1b}[1A\u{1b}[2K├"));
+        assert!(!stream.is_suspended());
+
+        let _ = stream.suspend();
+        assert_eq!(stream.finalize(), "");
+    }
+
+    #[test]
+    fn test_stream_resume_without_suspend() {
+        let mut stream = TableStream::new(None);
+        let _ = stream.write_row(&["a".to_string()]).unwrap();
+        assert_eq!(stream.resume(), "");
+    }
+
     #[test]
     fn test_stream_writer() {
         let mut cursor = Cursor::new(Vec::new());
@@ -305,6 +475,72 @@ mod tests {
         assert!(output.contains("30"));
     }
 
+    struct CountingWriter {
+        bytes: Vec<u8>,
+        flushes: usize,
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.bytes.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.flushes += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_table_stream_writer_flush_policy() {
+        let rows = (0..5).map(|i| vec![i.to_string(), "x".to_string()]);
+        let counting = CountingWriter {
+            bytes: Vec::new(),
+            flushes: 0,
+        };
+        let mut writer =
+            TableStreamWriter::new(counting, None).with_flush_policy(FlushPolicy::EveryRows(2));
+
+        assert_eq!(writer.write_rows(rows).unwrap(), 5);
+        assert_eq!(writer.rows_written(), 5);
+        assert_eq!(writer.get_ref().flushes, 2);
+
+        let counting = writer.into_inner().unwrap();
+        assert_eq!(counting.flushes, 3);
+        let output = String::from_utf8(counting.bytes).unwrap();
+        assert!(output.ends_with("┘"));
+        assert_eq!(output.matches("│ 4").count(), 1);
+    }
+
+    #[test]
+    fn test_stream_stats() {
+        let mut stream = TableStream::new(None);
+        let _ = stream.write_row(&["a".to_string()]).unwrap();
+        assert_eq!(stream.stats(), None);
+
+        let mut writer = TableStreamWriter::new(Vec::new(), None).with_stats();
+        assert_eq!(writer.stats(), Some(&[][..]));
+        writer
+            .write_header(&["Name".to_string(), "Age".to_string()])
+            .unwrap();
+        writer
+            .write_rows(
+                [["Alexandra", "30"], ["Bo", ""], ["Cy", "41"]].map(|row| row.map(String::from)),
+            )
+            .unwrap();
+
+        let stats = writer.stats().unwrap();
+        assert_eq!(stats[0].name, "Name");
+        assert_eq!(stats[0].max_width, 9);
+        assert_eq!(stats[0].count, 3);
+        assert_eq!(
+            stats[1].summary(),
+            "integer, 3 wide, min 30, max 41, mean 35.5, 1 empty"
+        );
+        assert_eq!(writer.rows_written(), 4);
+    }
+
     #[test]
     fn test_stream_inconsistent_row_length() {
         let mut stream = TableStream::new(None);
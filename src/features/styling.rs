@@ -0,0 +1,447 @@
+use crate::features::stats::normalize_value;
+use crate::types::{Row, TableConfig, TableError, TableResult};
+use crate::utils::ansi::split_ansi_string;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Which cell values a `StyleRule` matches. Values are compared with ANSI
+/// styling and surrounding whitespace removed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StyleCondition {
+    /// Every value.
+    #[default]
+    Any,
+    Equals(String),
+    Contains(String),
+    Regex(String),
+    /// Numbers within the bounds, both inclusive; an unset bound is open.
+    Range {
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+}
+
+// Bounds compare bit for bit so conditions can key caches.
+impl PartialEq for StyleCondition {
+    fn eq(&self, other: &Self) -> bool {
+        let bits = |bound: &Option<f64>| bound.map(f64::to_bits);
+        match (self, other) {
+            (StyleCondition::Any, StyleCondition::Any) => true,
+            (StyleCondition::Equals(a), StyleCondition::Equals(b))
+            | (StyleCondition::Contains(a), StyleCondition::Contains(b))
+            | (StyleCondition::Regex(a), StyleCondition::Regex(b)) => a == b,
+            (
+                StyleCondition::Range { min, max },
+                StyleCondition::Range {
+                    min: other_min,
+                    max: other_max,
+                },
+            ) => bits(min) == bits(other_min) && bits(max) == bits(other_max),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for StyleCondition {}
+
+impl std::hash::Hash for StyleCondition {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            StyleCondition::Any => {}
+            StyleCondition::Equals(value)
+            | StyleCondition::Contains(value)
+            | StyleCondition::Regex(value) => value.hash(state),
+            StyleCondition::Range { min, max } => {
+                (min.map(f64::to_bits), max.map(f64::to_bits)).hash(state)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CellStyle {
+    /// Foreground SGR parameters, e.g. `"32"` for green or `"38;5;208"` for
+    /// 256-color orange.
+    pub color: Option<String>,
+    /// Background SGR parameters, e.g. `"41"` for red.
+    pub background: Option<String>,
+    #[serde(default)]
+    pub bold: bool,
+    #[serde(default)]
+    pub underline: bool,
+}
+
+impl CellStyle {
+    pub fn color(color: impl Into<String>) -> Self {
+        CellStyle {
+            color: Some(color.into()),
+            ..Default::default()
+        }
+    }
+
+    /// `self` with `over` layered on top: the colors `over` sets replace
+    /// these, and bold or underline stay on when either turns them on.
+    pub fn layered(&self, over: &CellStyle) -> CellStyle {
+        CellStyle {
+            color: over.color.clone().or_else(|| self.color.clone()),
+            background: over.background.clone().or_else(|| self.background.clone()),
+            bold: self.bold || over.bold,
+            underline: self.underline || over.underline,
+        }
+    }
+
+    fn sgr(&self) -> Option<String> {
+        let params: Vec<&str> = [
+            self.bold.then_some("1"),
+            self.underline.then_some("4"),
+            self.color.as_deref(),
+            self.background.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        (!params.is_empty()).then(|| params.join(";"))
+    }
+}
+
+/// Styles the cells it matches while the table is rendered, so cells need no
+/// escape codes of their own. Rules without a `row` apply to body rows only;
+/// a rule naming a row, counted after filtering and sorting, can style the
+/// header or footer too.
+///
+/// Every matching rule applies, layered from the broadest to the most
+/// specific: rules for the whole table, then for a column, then for a row,
+/// then for one cell, and escape codes in the cell itself last. A more
+/// specific rule's colors win and bold and underline add up, so a red row
+/// with a bold column draws bold red cells. Rules naming as much apply in
+/// order, later colors winning.
+///
+/// Under `symbols_only`, as for `FormatRule`s, a rule with a condition
+/// prefixes the cells it matches with its `symbol` instead of styling them,
+/// the most specific rule's symbol winning; rules for every value still
+/// style.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StyleRule {
+    /// The column to check; every column when unset.
+    pub column: Option<usize>,
+    /// The row to check; every body row when unset.
+    pub row: Option<usize>,
+    #[serde(default)]
+    pub condition: StyleCondition,
+    pub style: CellStyle,
+    /// Prefix used instead of the style under `symbols_only`; `*` when
+    /// unset.
+    pub symbol: Option<String>,
+}
+
+impl StyleRule {
+    // Where the rule sits in the cascade: table, column, row, then cell.
+    fn specificity(&self) -> u8 {
+        u8::from(self.column.is_some()) + 2 * u8::from(self.row.is_some())
+    }
+}
+
+enum CompiledCondition<'a> {
+    Any,
+    Equals(&'a str),
+    Contains(&'a str),
+    Regex(Regex),
+    Range(Option<f64>, Option<f64>),
+}
+
+impl CompiledCondition<'_> {
+    fn matches(&self, value: &str) -> bool {
+        match self {
+            CompiledCondition::Any => true,
+            CompiledCondition::Equals(expected) => value == *expected,
+            CompiledCondition::Contains(needle) => value.contains(needle),
+            CompiledCondition::Regex(regex) => regex.is_match(value),
+            CompiledCondition::Range(min, max) => value.parse::<f64>().is_ok_and(|number| {
+                min.is_none_or(|min| number >= min) && max.is_none_or(|max| number <= max)
+            }),
+        }
+    }
+}
+
+fn compile(condition: &StyleCondition) -> TableResult<CompiledCondition<'_>> {
+    Ok(match condition {
+        StyleCondition::Any => CompiledCondition::Any,
+        StyleCondition::Equals(value) => CompiledCondition::Equals(value),
+        StyleCondition::Contains(value) => CompiledCondition::Contains(value),
+        StyleCondition::Regex(pattern) => {
+            CompiledCondition::Regex(Regex::new(pattern).map_err(|source| {
+                TableError::InvalidPattern {
+                    kind: "style",
+                    pattern: pattern.clone(),
+                    source,
+                }
+            })?)
+        }
+        StyleCondition::Range { min, max } => CompiledCondition::Range(*min, *max),
+    })
+}
+
+// Wraps the cell in the style, re-applying it after any reset inside the cell
+// so existing styling does not cut it short.
+pub(crate) fn style_cell(cell: &str, sgr: &str) -> String {
+    let open = format!("\u{1b}[{sgr}m");
+    let body = cell
+        .replace("\u{1b}[0m", &format!("\u{1b}[0m{open}"))
+        .replace("\u{1b}[m", &format!("\u{1b}[m{open}"));
+    format!("{open}{body}\u{1b}[0m")
+}
+
+/// Writes the header row in capitals when `config.uppercase_header` is set,
+/// leaving color and hyperlink sequences as they are.
+pub fn uppercase_header(rows: &mut [Row], config: &TableConfig) {
+    if !config.uppercase_header || config.header.is_none() {
+        return;
+    }
+    if let Some(header) = rows.first_mut() {
+        for cell in header {
+            *cell = split_ansi_string(cell)
+                .into_iter()
+                .map(|part| {
+                    if part.starts_with('\u{1b}') {
+                        part
+                    } else {
+                        part.to_uppercase()
+                    }
+                })
+                .collect();
+        }
+    }
+}
+
+/// Applies `config.style_rules` to the rows about to be rendered.
+pub fn apply_style_rules(rows: &mut [Row], config: &TableConfig) -> TableResult<()> {
+    if config.style_rules.is_empty() {
+        return Ok(());
+    }
+
+    let mut rules = config
+        .style_rules
+        .iter()
+        .map(|rule| Ok((rule, compile(&rule.condition)?)))
+        .collect::<TableResult<Vec<_>>>()?;
+    rules.sort_by_key(|(rule, _)| rule.specificity());
+    let body_rows = config.body_rows(rows.len());
+
+    for (row_idx, row) in rows.iter_mut().enumerate() {
+        for (column, cell) in row.iter_mut().enumerate() {
+            let value = normalize_value(cell);
+            let mut style = CellStyle::default();
+            let mut symbol = None;
+
+            for (rule, condition) in &rules {
+                let row_matches = match rule.row {
+                    Some(row) => row == row_idx,
+                    None => body_rows.contains(&row_idx),
+                };
+                if !row_matches
+                    || rule.column.is_some_and(|col| col != column)
+                    || !condition.matches(&value)
+                {
+                    continue;
+                }
+                if config.symbols_only && rule.condition != StyleCondition::Any {
+                    symbol = Some(rule.symbol.as_deref().unwrap_or("*"));
+                } else {
+                    style = style.layered(&rule.style);
+                }
+            }
+
+            if let Some(sgr) = style.sgr() {
+                *cell = style_cell(cell, &sgr);
+            }
+            if let Some(symbol) = symbol {
+                *cell = format!("{symbol} {cell}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_rules() -> Vec<StyleRule> {
+        vec![
+            StyleRule {
+                column: Some(1),
+                condition: StyleCondition::Equals("Active".to_string()),
+                style: CellStyle::color("32"),
+                ..Default::default()
+            },
+            StyleRule {
+                column: Some(1),
+                condition: StyleCondition::Regex("^Fail".to_string()),
+                style: CellStyle::color("31"),
+                ..Default::default()
+            },
+            StyleRule {
+                row: Some(0),
+                style: CellStyle {
+                    bold: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn styled(rows: &[[&str; 2]], rules: Vec<StyleRule>) -> TableResult<Vec<Row>> {
+        let mut rows: Vec<Row> = rows
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        let config = TableConfig {
+            header: Some(Box::default()),
+            style_rules: rules,
+            ..Default::default()
+        };
+        apply_style_rules(&mut rows, &config)?;
+        Ok(rows)
+    }
+
+    #[test]
+    fn test_apply_style_rules_by_value_and_row() {
+        let rows = styled(
+            &[["Name", "Status"], ["web", "Active"], ["db", "Failed"]],
+            status_rules(),
+        )
+        .unwrap();
+
+        assert_eq!(rows[0][1], "\u{1b}[1mStatus\u{1b}[0m");
+        assert_eq!(rows[1][0], "web");
+        assert_eq!(rows[1][1], "\u{1b}[32mActive\u{1b}[0m");
+        assert_eq!(rows[2][1], "\u{1b}[31mFailed\u{1b}[0m");
+    }
+
+    #[test]
+    fn test_symbols_only_marks_matches_instead_of_styling() {
+        let mut rows: Vec<Row> = [["Name", "Status"], ["web", "Active"], ["db", "Failed"]]
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        let mut rules = status_rules();
+        rules[1].symbol = Some("!".to_string());
+        let config = TableConfig {
+            header: Some(Box::default()),
+            style_rules: rules,
+            symbols_only: true,
+            ..Default::default()
+        };
+        apply_style_rules(&mut rows, &config).unwrap();
+
+        assert_eq!(rows[0][1], "\u{1b}[1mStatus\u{1b}[0m");
+        assert_eq!(rows[1][1], "* Active");
+        assert_eq!(rows[2][1], "! Failed");
+
+        // Stripping colors implies `symbols_only`.
+        let user_config = crate::types::TableUserConfig {
+            header: Some(Box::default()),
+            style_rules: Some(status_rules()),
+            color_mode: Some(crate::types::ColorMode::Strip),
+            ..Default::default()
+        };
+        let data = [["Name", "Status"], ["web", "Active"]]
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect::<Vec<Row>>();
+        let output = crate::table(&data, Some(&user_config)).unwrap();
+        assert!(output.contains("│ web  │ * Active │"), "{output}");
+    }
+
+    #[test]
+    fn test_specific_rules_layer_over_broad_ones() {
+        let rules = vec![
+            StyleRule {
+                row: Some(1),
+                style: CellStyle::color("31"),
+                ..Default::default()
+            },
+            StyleRule {
+                column: Some(1),
+                style: CellStyle {
+                    color: Some("32".to_string()),
+                    bold: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            // Listed last, but the broadest: the row's red still wins.
+            StyleRule {
+                style: CellStyle {
+                    color: Some("37".to_string()),
+                    background: Some("40".to_string()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ];
+        let rows = styled(
+            &[
+                ["Name", "Status"],
+                ["web", "\u{1b}[4mup\u{1b}[m"],
+                ["db", "down"],
+            ],
+            rules,
+        )
+        .unwrap();
+
+        assert_eq!(rows[1][0], "\u{1b}[31;40mweb\u{1b}[0m");
+        // The cell's own underline comes last, and its reset does not end the
+        // row's style.
+        assert_eq!(
+            rows[1][1],
+            "\u{1b}[1;31;40m\u{1b}[4mup\u{1b}[m\u{1b}[1;31;40m\u{1b}[0m"
+        );
+        assert_eq!(rows[2][1], "\u{1b}[1;32;40mdown\u{1b}[0m");
+    }
+
+    #[test]
+    fn test_apply_style_rules_numeric_range_and_existing_styles() {
+        let rules = vec![
+            StyleRule {
+                condition: StyleCondition::Range {
+                    min: Some(90.0),
+                    max: None,
+                },
+                style: CellStyle {
+                    color: Some("33".to_string()),
+                    underline: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            StyleRule {
+                column: Some(0),
+                style: CellStyle {
+                    background: Some("44".to_string()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ];
+        let rows = styled(&[["cpu", "load"], ["95.5", "\u{1b}[2m12\u{1b}[0m"]], rules).unwrap();
+
+        assert_eq!(rows[0], vec!["cpu", "load"]);
+        assert_eq!(rows[1][0], "\u{1b}[4;33;44m95.5\u{1b}[0m");
+        assert_eq!(rows[1][1], "\u{1b}[2m12\u{1b}[0m");
+
+        let invalid = vec![StyleRule {
+            condition: StyleCondition::Regex("(".to_string()),
+            ..Default::default()
+        }];
+        assert!(styled(&[["a", "b"]], invalid).is_err());
+        assert_eq!(
+            style_cell("a\u{1b}[0mb", "1"),
+            "\u{1b}[1ma\u{1b}[0m\u{1b}[1mb\u{1b}[0m"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use crate::types::{Row, TableConfig, TableError, TableResult};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+/// What `fill_placeholders` does with a `{name}` that has no value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MissingPlaceholder {
+    /// Leave `{name}` in the text, so the gap shows.
+    #[default]
+    Keep,
+    /// Replace it with nothing.
+    Empty,
+    /// Fail with `TableError::MissingPlaceholder`.
+    Error,
+}
+
+impl std::str::FromStr for MissingPlaceholder {
+    type Err = TableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "keep" => Ok(MissingPlaceholder::Keep),
+            "empty" => Ok(MissingPlaceholder::Empty),
+            "error" => Ok(MissingPlaceholder::Error),
+            _ => Err(TableError::parse(
+                "missing placeholder policy",
+                s,
+                "expected keep, empty or error",
+            )),
+        }
+    }
+}
+
+/// `text` with every `{name}` replaced by its value in `values`. Names are
+/// letters, digits, `_`, `-` and `.`; other braces are left as they are, and
+/// `{{` and `}}` stand for literal braces.
+pub fn fill_placeholders<'a>(
+    text: &'a str,
+    values: &BTreeMap<String, String>,
+    missing: MissingPlaceholder,
+) -> TableResult<Cow<'a, str>> {
+    if !text.contains(['{', '}']) {
+        return Ok(Cow::Borrowed(text));
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(['{', '}']) {
+        result.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            result.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+
+        let name_len = tail[1..]
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.')))
+            .unwrap_or(tail.len() - 1);
+        let name = &tail[1..1 + name_len];
+        if !tail.starts_with('{') || name.is_empty() || !tail[1 + name_len..].starts_with('}') {
+            result.push_str(&tail[..1]);
+            rest = &tail[1..];
+            continue;
+        }
+
+        match (values.get(name), missing) {
+            (Some(value), _) => result.push_str(value),
+            (None, MissingPlaceholder::Keep) => result.push_str(&tail[..name_len + 2]),
+            (None, MissingPlaceholder::Empty) => {}
+            (None, MissingPlaceholder::Error) => {
+                return Err(TableError::MissingPlaceholder(name.to_string()));
+            }
+        }
+        rest = &tail[name_len + 2..];
+    }
+    result.push_str(rest);
+
+    Ok(Cow::Owned(result))
+}
+
+/// Fills the placeholders of every cell and of the title and caption from
+/// `config.placeholders`. Without a `placeholders` map, braces are plain text
+/// and nothing changes.
+pub fn fill_table_placeholders(rows: &mut [Row], config: &mut TableConfig) -> TableResult<()> {
+    let Some(values) = &config.placeholders else {
+        return Ok(());
+    };
+    let missing = config.missing_placeholder;
+
+    for cell in rows.iter_mut().flatten() {
+        if let Cow::Owned(filled) = fill_placeholders(cell, values, missing)? {
+            *cell = filled;
+        }
+    }
+    for label in [&mut config.title, &mut config.caption]
+        .into_iter()
+        .flatten()
+    {
+        if let Cow::Owned(filled) = fill_placeholders(&label.text, values, missing)? {
+            label.text = filled;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{TableLabel, TableUserConfig};
+
+    fn values() -> BTreeMap<String, String> {
+        [("env", "prod"), ("api.status", "up")]
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_fill_placeholders() {
+        let fill = |text, missing| fill_placeholders(text, &values(), missing);
+
+        assert!(matches!(
+            fill("plain", MissingPlaceholder::Keep),
+            Ok(Cow::Borrowed("plain"))
+        ));
+        assert_eq!(
+            fill("{env}: api {api.status}", MissingPlaceholder::Keep).unwrap(),
+            "prod: api up"
+        );
+        assert_eq!(
+            fill("{{env}} {} { env } {db}", MissingPlaceholder::Keep).unwrap(),
+            "{env} {} { env } {db}"
+        );
+        assert_eq!(fill("db {db}", MissingPlaceholder::Empty).unwrap(), "db ");
+        assert!(matches!(
+            fill("db {db}", MissingPlaceholder::Error),
+            Err(TableError::MissingPlaceholder(name)) if name == "db"
+        ));
+    }
+
+    #[test]
+    fn test_tables_fill_cells_and_labels() {
+        let rows = vec![
+            vec!["Service".to_string(), "Status".to_string()],
+            vec!["api".to_string(), "{api.status}".to_string()],
+        ];
+        let config = TableUserConfig {
+            placeholders: Some(values()),
+            title: Some(TableLabel {
+                text: "{env}".to_string(),
+                alignment: None,
+                placement: None,
+            }),
+            ..Default::default()
+        };
+
+        let output = crate::table(&rows, Some(&config)).unwrap();
+        assert!(output.starts_with("┌────── prod ──────┐"), "{output}");
+        assert!(output.contains("│ api     │ up     │"), "{output}");
+        // Without a map, braces are left alone.
+        assert!(crate::table(&rows, None).unwrap().contains("{api.status}"));
+
+        // Filled cells are what validation checks.
+        let mut values = values();
+        values.insert("api.status".to_string(), "up  ".to_string());
+        let rejecting = TableUserConfig {
+            placeholders: Some(values),
+            data_hygiene: Some(crate::types::DataHygiene::Reject),
+            ..config
+        };
+        assert!(matches!(
+            crate::table(&rows, Some(&rejecting)),
+            Err(TableError::UncleanData(_))
+        ));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::types::{TableError, TableResult};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The names `TerminalProfile::preset` knows, in the order `--terminal` lists
+/// them.
+pub const TERMINAL_PROFILES: &[&str] = &["generic", "iterm2", "windows-terminal", "kitty", "tmux"];
+
+/// How a terminal draws the characters whose width terminals disagree on, and
+/// whether it shows hyperlinks. One profile is active for the whole process,
+/// set with `set_terminal_profile`; every width the crate measures follows it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TerminalProfile {
+    /// East Asian Ambiguous characters, such as `±`, `→` and the box drawing
+    /// characters of most border styles, take two columns. Borders then need
+    /// an ASCII style, e.g. `ramac`, to line up.
+    pub ambiguous_wide: bool,
+    /// A character followed by the emoji variation selector U+FE0F, such as
+    /// `❤️`, takes two columns. Terminals that ignore the selector draw it
+    /// one column wide.
+    pub emoji_variation_wide: bool,
+    /// OSC 8 hyperlinks are kept; otherwise rendered tables keep only the
+    /// link text.
+    pub hyperlinks: bool,
+}
+
+impl Default for TerminalProfile {
+    fn default() -> Self {
+        Self::GENERIC
+    }
+}
+
+impl TerminalProfile {
+    /// Unicode's own widths, with hyperlinks passed through.
+    pub const GENERIC: Self = Self {
+        ambiguous_wide: false,
+        emoji_variation_wide: true,
+        hyperlinks: true,
+    };
+
+    /// tmux draws `❤️` and the like one column wide unless
+    /// `variation-selector-always-wide` is on, and drops hyperlinks unless its
+    /// `terminal-features` include them.
+    pub const TMUX: Self = Self {
+        ambiguous_wide: false,
+        emoji_variation_wide: false,
+        hyperlinks: false,
+    };
+
+    /// The profile named `name`, one of `TERMINAL_PROFILES`. iTerm2, Windows
+    /// Terminal and kitty draw as `GENERIC`; set `ambiguous_wide` for an
+    /// iTerm2 profile with "Ambiguous characters are double-width" checked.
+    pub fn preset(name: &str) -> TableResult<Self> {
+        match name.to_lowercase().as_str() {
+            "generic" | "iterm2" | "windows-terminal" | "kitty" => Ok(Self::GENERIC),
+            "tmux" => Ok(Self::TMUX),
+            _ => Err(TableError::parse(
+                "terminal profile",
+                name,
+                "expected generic, iterm2, windows-terminal, kitty or tmux",
+            )),
+        }
+    }
+
+    /// The name of the preset for the terminal the process runs in, judged by
+    /// `TMUX`, `TERM_PROGRAM`, `WT_SESSION`, `TERM` and `KITTY_WINDOW_ID`.
+    /// Inside tmux, tmux decides what the outer terminal gets to draw.
+    pub fn detect_name() -> &'static str {
+        let var = |name| std::env::var_os(name).is_some();
+        if var("TMUX") {
+            "tmux"
+        } else if std::env::var("TERM_PROGRAM").is_ok_and(|program| program == "iTerm.app") {
+            "iterm2"
+        } else if var("WT_SESSION") {
+            "windows-terminal"
+        } else if var("KITTY_WINDOW_ID")
+            || std::env::var("TERM").is_ok_and(|term| term == "xterm-kitty")
+        {
+            "kitty"
+        } else {
+            "generic"
+        }
+    }
+
+    /// The preset `detect_name` picks.
+    pub fn detect() -> Self {
+        Self::preset(Self::detect_name()).expect("detected profiles are presets")
+    }
+
+    const fn to_bits(self) -> u8 {
+        self.ambiguous_wide as u8
+            | (self.emoji_variation_wide as u8) << 1
+            | (self.hyperlinks as u8) << 2
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        Self {
+            ambiguous_wide: bits & 1 != 0,
+            emoji_variation_wide: bits & 2 != 0,
+            hyperlinks: bits & 4 != 0,
+        }
+    }
+}
+
+/// A preset name, or `auto` for the one `TerminalProfile::detect` picks.
+impl std::str::FromStr for TerminalProfile {
+    type Err = TableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(Self::detect());
+        }
+        Self::preset(s)
+    }
+}
+
+// Widths are measured on every cell, so the active profile is read from one
+// atomic rather than through a lock.
+static ACTIVE_PROFILE: AtomicU8 = AtomicU8::new(TerminalProfile::GENERIC.to_bits());
+
+/// Makes `profile` the one every table drawn from now on is measured and
+/// drawn for. `RenderCache` keys include the profile, so tables cached under
+/// another one are drawn again.
+pub fn set_terminal_profile(profile: TerminalProfile) {
+    ACTIVE_PROFILE.store(profile.to_bits(), Ordering::Relaxed);
+}
+
+/// The profile set with `set_terminal_profile`, `TerminalProfile::GENERIC`
+/// until then.
+pub fn terminal_profile() -> TerminalProfile {
+    TerminalProfile::from_bits(ACTIVE_PROFILE.load(Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::unicode::string_width_for;
+
+    #[test]
+    fn test_presets_and_bits() {
+        for name in TERMINAL_PROFILES {
+            let profile: TerminalProfile = name.parse().unwrap();
+            assert_eq!(TerminalProfile::from_bits(profile.to_bits()), profile);
+        }
+        assert_eq!(
+            "tmux".parse::<TerminalProfile>().unwrap(),
+            TerminalProfile::TMUX
+        );
+        assert!("xterm".parse::<TerminalProfile>().is_err());
+        assert_eq!(terminal_profile(), TerminalProfile::GENERIC);
+    }
+
+    #[test]
+    fn test_profiles_measure_disputed_characters() {
+        let cjk = TerminalProfile {
+            ambiguous_wide: true,
+            ..TerminalProfile::GENERIC
+        };
+        let text = "❤\u{fe0f} ±1 a\u{fe0f}";
+
+        assert_eq!(string_width_for(text, TerminalProfile::GENERIC), 7);
+        assert_eq!(string_width_for(text, TerminalProfile::TMUX), 6);
+        assert_eq!(string_width_for(text, cjk), 8);
+        assert_eq!(string_width_for("👍🏽", TerminalProfile::TMUX), 2);
+    }
+}
@@ -0,0 +1,288 @@
+use crate::features::styling::{CellStyle, StyleCondition, StyleRule};
+use crate::types::{
+    BorderConfig, DrawHorizontalLine, TableConfig, TableError, TableResult, TableUserConfig,
+    default_draw_horizontal_line, get_border_characters,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, OnceLock, PoisonError};
+
+/// The names `get_theme` always knows, in the order `theme_names` lists them.
+pub const BUILTIN_THEMES: &[&str] = &["compact", "double", "dotted", "fancy", "markdown"];
+
+/// A look for the whole table, selected by name with `TableUserConfig::theme`.
+/// Its settings are defaults: whatever the user config sets wins, and the
+/// `header_style` rule comes before the config's own `style_rules`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Theme {
+    pub border: BorderConfig,
+    pub padding_left: usize,
+    pub padding_right: usize,
+    /// Draws the top and bottom borders.
+    pub outer_lines: bool,
+    /// Draws the lines between body rows. The lines below a header and above
+    /// a footer are drawn either way.
+    pub row_lines: bool,
+    /// Applied to the first row when the table has a `header`.
+    pub header_style: Option<CellStyle>,
+    /// Used as `row_striping`.
+    pub row_striping: Option<String>,
+}
+
+impl Theme {
+    /// `default` with the theme's settings in place of its own, for a table
+    /// that has a header when `has_header`.
+    pub fn apply(&self, default: &TableConfig, has_header: bool) -> TableConfig {
+        let mut config = default.clone();
+        config.border = self.border.clone();
+        config.column_default.padding_left = self.padding_left;
+        config.column_default.padding_right = self.padding_right;
+        config.draw_horizontal_line = self.draw_horizontal_line();
+        if let Some(style) = self.header_style.clone().filter(|_| has_header) {
+            config.style_rules.insert(
+                0,
+                StyleRule {
+                    column: None,
+                    row: Some(0),
+                    condition: StyleCondition::Any,
+                    style,
+                    symbol: None,
+                },
+            );
+        }
+        if self.row_striping.is_some() {
+            config.row_striping = self.row_striping.clone();
+        }
+        config
+    }
+
+    fn draw_horizontal_line(&self) -> DrawHorizontalLine {
+        match (self.outer_lines, self.row_lines) {
+            (true, true) => default_draw_horizontal_line,
+            (true, false) => |line, row_count| line == 0 || line == row_count,
+            (false, true) => |line, row_count| line != 0 && line != row_count,
+            (false, false) => |_, _| false,
+        }
+    }
+}
+
+// Themes added with `register_theme`. A panic elsewhere cannot leave the map
+// half-updated, so a poisoned lock is still safe to use.
+fn custom_themes() -> MutexGuard<'static, BTreeMap<String, Theme>> {
+    static THEMES: OnceLock<Mutex<BTreeMap<String, Theme>>> = OnceLock::new();
+    THEMES
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+// Counts `register_theme` calls, so `RenderCache` can tell a registered
+// theme name may now draw differently.
+static THEME_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(feature = "memo")]
+pub(crate) fn theme_generation() -> u64 {
+    THEME_GENERATION.load(Ordering::Relaxed)
+}
+
+/// Makes `theme` available by `name` to `get_theme` for the rest of the
+/// process, replacing an earlier theme of that name. Built-in names cannot be
+/// replaced.
+pub fn register_theme(name: impl Into<String>, theme: Theme) -> TableResult<()> {
+    let name = name.into();
+    if BUILTIN_THEMES.contains(&name.as_str()) {
+        return Err(TableError::parse(
+            "theme",
+            name,
+            "built-in themes cannot be replaced",
+        ));
+    }
+    custom_themes().insert(name, theme);
+    THEME_GENERATION.fetch_add(1, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Every name `get_theme` accepts: the built-in themes, then the registered
+/// ones in name order.
+pub fn theme_names() -> Vec<String> {
+    BUILTIN_THEMES
+        .iter()
+        .map(|name| name.to_string())
+        .chain(custom_themes().keys().cloned())
+        .collect()
+}
+
+pub fn get_theme(name: &str) -> TableResult<Theme> {
+    let border = |name| get_border_characters(name).expect("built-in border style");
+    let theme = match name {
+        "compact" => Theme {
+            border: BorderConfig {
+                header_join: "─".to_string(),
+                join_join: " ".to_string(),
+                ..border("void")
+            },
+            padding_left: 0,
+            padding_right: 0,
+            outer_lines: false,
+            row_lines: false,
+            header_style: Some(CellStyle {
+                bold: true,
+                ..Default::default()
+            }),
+            row_striping: None,
+        },
+        "double" | "dotted" => Theme {
+            border: border(name),
+            padding_left: 1,
+            padding_right: 1,
+            outer_lines: true,
+            row_lines: true,
+            header_style: None,
+            row_striping: None,
+        },
+        "fancy" => Theme {
+            border: BorderConfig {
+                header_join: "━".to_string(),
+                join_left: "┝".to_string(),
+                join_join: "┿".to_string(),
+                join_right: "┥".to_string(),
+                ..border("rounded")
+            },
+            padding_left: 1,
+            padding_right: 1,
+            outer_lines: true,
+            row_lines: false,
+            header_style: Some(CellStyle {
+                color: Some("36".to_string()),
+                bold: true,
+                ..Default::default()
+            }),
+            row_striping: Some("48;5;236".to_string()),
+        },
+        "markdown" => Theme {
+            border: border("markdown"),
+            padding_left: 1,
+            padding_right: 1,
+            outer_lines: false,
+            row_lines: false,
+            header_style: None,
+            row_striping: None,
+        },
+        _ => {
+            return custom_themes().get(name).cloned().ok_or_else(|| {
+                TableError::parse(
+                    "theme",
+                    name,
+                    "expected compact, double, dotted, fancy, markdown or a registered theme",
+                )
+            });
+        }
+    };
+    Ok(theme)
+}
+
+/// Fails for a `theme` that `get_theme` does not know, which
+/// `TableUserConfig::merge_with_default` would otherwise ignore.
+pub fn check_theme(config: &TableUserConfig) -> TableResult<()> {
+    match &config.theme {
+        Some(name) => get_theme(name).map(drop),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BorderUserConfig;
+
+    fn rows() -> Vec<Vec<String>> {
+        vec![
+            vec!["Name".to_string(), "Age".to_string()],
+            vec!["John".to_string(), "30".to_string()],
+            vec!["Ann".to_string(), "4".to_string()],
+        ]
+    }
+
+    #[test]
+    fn test_markdown_theme_draws_only_the_header_line() {
+        let config = TableUserConfig {
+            theme: Some("markdown".to_string()),
+            header: Some(Box::default()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            crate::table(&rows(), Some(&config)).unwrap(),
+            "| Name | Age |\n|------|-----|\n| John | 30  |\n| Ann  | 4   |\n"
+        );
+    }
+
+    #[test]
+    fn test_user_settings_override_the_theme() {
+        let config = TableUserConfig {
+            theme: Some("double".to_string()),
+            border: Some(BorderUserConfig {
+                top_left: Some("*".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let output = crate::table(&rows(), Some(&config)).unwrap();
+        assert!(output.starts_with("*══════╦"), "{output}");
+        assert!(output.ends_with("╚══════╩═════╝"), "{output}");
+    }
+
+    #[test]
+    fn test_header_style_comes_before_user_style_rules() {
+        let config = TableUserConfig {
+            theme: Some("fancy".to_string()),
+            header: Some(Box::default()),
+            style_rules: Some(vec![StyleRule {
+                column: Some(0),
+                row: Some(0),
+                condition: StyleCondition::Any,
+                style: CellStyle::color("31"),
+                symbol: None,
+            }]),
+            ..Default::default()
+        };
+
+        let output = crate::table(&rows(), Some(&config)).unwrap();
+        assert!(output.contains("\u{1b}[1;31mName"), "{output}");
+        assert!(output.contains("\u{1b}[1;36mAge"), "{output}");
+        // Without a header, no row is styled as one.
+        let plain = crate::table(
+            &rows(),
+            Some(&TableUserConfig {
+                theme: Some("fancy".to_string()),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        assert!(!plain.contains("\u{1b}[1;36m"), "{plain}");
+    }
+
+    #[test]
+    fn test_registered_themes_and_unknown_names() {
+        let mut theme = get_theme("compact").unwrap();
+        theme.row_striping = Some("7".to_string());
+        register_theme("test-inverse", theme.clone()).unwrap();
+        assert!(register_theme("markdown", theme.clone()).is_err());
+
+        let names = theme_names();
+        assert_eq!(names[..BUILTIN_THEMES.len()], *BUILTIN_THEMES);
+        assert!(names.contains(&"test-inverse".to_string()));
+        assert_eq!(get_theme("test-inverse").unwrap(), theme);
+
+        let config = TableUserConfig {
+            theme: Some("nonexistent".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            crate::table(&rows(), Some(&config)),
+            Err(TableError::Parse { kind: "theme", .. })
+        ));
+    }
+}
@@ -0,0 +1,171 @@
+use crate::core::renderer::outer_borders;
+use crate::types::{Alignment, LabelPlacement, TableConfig, TableLabel, TruncatePosition};
+use crate::utils::ansi::{calculate_display_width, pad_ansi_string, truncate_ansi_string_at};
+
+// Border characters kept on each side of a label set into a border line: the
+// corner and one horizontal.
+const BORDER_MARGIN: usize = 2;
+
+/// Adds `config.title` and `config.caption` to `output`, a table drawn for
+/// `row_count` rows and not yet positioned. Labels are fitted to the widest
+/// line of `output`.
+pub fn draw_title_and_caption(output: String, config: &TableConfig, row_count: usize) -> String {
+    if config.title.is_none() && config.caption.is_none() {
+        return output;
+    }
+
+    let (has_top, has_bottom) = outer_borders(config, row_count);
+    let width = output
+        .lines()
+        .map(calculate_display_width)
+        .max()
+        .unwrap_or(0);
+    let ellipsis = &config.column_default.ellipsis;
+    let mut lines: Vec<String> = output.lines().map(str::to_string).collect();
+
+    if let Some(title) = &config.title {
+        let in_border = has_top
+            .then(|| {
+                lines
+                    .first()
+                    .and_then(|line| set_into_border(line, title, ellipsis))
+            })
+            .flatten();
+        match in_border {
+            Some(line) => lines[0] = line,
+            None => lines.insert(0, label_line(title, width, ellipsis)),
+        }
+    }
+
+    if let Some(caption) = &config.caption {
+        let in_border = has_bottom
+            .then(|| {
+                lines
+                    .last()
+                    .and_then(|line| set_into_border(line, caption, ellipsis))
+            })
+            .flatten();
+        match in_border {
+            Some(line) => *lines.last_mut().expect("checked above") = line,
+            None => lines.push(label_line(caption, width, ellipsis)),
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// How many lines `draw_title_and_caption` adds above a table drawn for
+/// `row_count` rows.
+pub fn title_lines(config: &TableConfig, row_count: usize) -> usize {
+    let Some(title) = &config.title else {
+        return 0;
+    };
+    let (has_top, _) = outer_borders(config, row_count);
+    usize::from(!has_top || title.placement == Some(LabelPlacement::Outside))
+}
+
+fn alignment(label: &TableLabel) -> Alignment {
+    match label.alignment.unwrap_or(Alignment::Center) {
+        Alignment::Justify | Alignment::Char(_) => Alignment::Left,
+        alignment => alignment,
+    }
+}
+
+// `line` with ` text ` written over its horizontals, or `None` when the label
+// goes outside: by request, or because the line is too narrow or is not one
+// column per character (e.g. a colored border).
+fn set_into_border(line: &str, label: &TableLabel, ellipsis: &str) -> Option<String> {
+    if label.placement == Some(LabelPlacement::Outside) {
+        return None;
+    }
+
+    let chars: Vec<char> = line.chars().collect();
+    let room = chars.len().checked_sub(2 * BORDER_MARGIN + 2)?;
+    if room == 0 || calculate_display_width(line) != chars.len() {
+        return None;
+    }
+
+    let text = truncate_ansi_string_at(&label.text, room, TruncatePosition::End, ellipsis);
+    let text_width = text.display_width + 2;
+    let start = match alignment(label) {
+        Alignment::Right => chars.len() - BORDER_MARGIN - text_width,
+        Alignment::Center => (chars.len() - text_width) / 2,
+        _ => BORDER_MARGIN,
+    };
+
+    let mut result: String = chars[..start].iter().collect();
+    result.push(' ');
+    result.push_str(&text.content);
+    result.push(' ');
+    result.extend(&chars[start + text_width..]);
+    Some(result)
+}
+
+fn label_line(label: &TableLabel, width: usize, ellipsis: &str) -> String {
+    let text = truncate_ansi_string_at(&label.text, width, TruncatePosition::End, ellipsis);
+    pad_ansi_string(&text.content, width, alignment(label))
+        .content
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::renderer::draw_table;
+    use crate::types::Row;
+
+    fn rows() -> Vec<Row> {
+        vec![vec!["Region".to_string(), "Revenue".to_string()]]
+    }
+
+    fn label(text: &str) -> Option<TableLabel> {
+        Some(TableLabel {
+            text: text.to_string(),
+            alignment: None,
+            placement: None,
+        })
+    }
+
+    #[test]
+    fn test_labels_set_into_borders() {
+        let config = TableConfig {
+            title: label("Sales Q3"),
+            caption: Some(TableLabel {
+                alignment: Some(Alignment::Right),
+                ..label("EUR").unwrap()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            draw_table(&rows(), &config),
+            [
+                "┌──── Sales Q3 ────┐",
+                "│ Region │ Revenue │",
+                "└────────┴─── EUR ─┘",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_labels_outside_and_cut_to_width() {
+        let config = TableConfig {
+            title: Some(TableLabel {
+                placement: Some(LabelPlacement::Outside),
+                alignment: Some(Alignment::Left),
+                ..label("Quarterly sales by region").unwrap()
+            }),
+            caption: label("The longest caption that fits nowhere"),
+            ..Default::default()
+        };
+
+        let output = draw_table(&rows(), &config);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "Quarterly sales b...");
+        assert_eq!(lines[1], "┌────────┬─────────┐");
+        assert_eq!(lines[3], "└─ The longest... ─┘");
+        assert_eq!(title_lines(&config, 1), 1);
+    }
+}
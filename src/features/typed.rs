@@ -0,0 +1,187 @@
+use crate::features::computed::civil_from_days;
+use crate::features::number_format::Formatter;
+use crate::types::{Alignment, ColumnUserConfig, Row, TableUserConfig, TypedCell};
+
+// What a column's body cells hold, nulls aside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Numbers,
+    DateTimes,
+    Mixed,
+}
+
+/// The text of typed `data` and the config that draws it: `user_config`
+/// layered over the defaults the types imply. Columns whose body cells are
+/// all numbers are right-aligned and columns of dates get `Formatter::Date`,
+/// unless the column or `column_default` sets its own alignment or
+/// formatter. Nulls are written as the `null_placeholder`, and dates with
+/// their time when any date in the column has one.
+pub fn typed_table_data(
+    data: &[Vec<TypedCell>],
+    user_config: Option<&TableUserConfig>,
+) -> (Vec<Row>, TableUserConfig) {
+    let user_config = user_config.cloned().unwrap_or_default();
+    let column_count = data.iter().map(Vec::len).max().unwrap_or(0);
+    let with_time: Vec<bool> = (0..column_count)
+        .map(|col_idx| {
+            data.iter().any(|row| {
+                matches!(row.get(col_idx), Some(TypedCell::DateTime(seconds)) if seconds.rem_euclid(86_400) != 0)
+            })
+        })
+        .collect();
+
+    let null_placeholder = user_config.null_placeholder.clone().unwrap_or_default();
+    let rows: Vec<Row> = data
+        .iter()
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .map(|(col_idx, cell)| match cell {
+                    TypedCell::Str(text) => text.clone(),
+                    TypedCell::Int(value) => value.to_string(),
+                    TypedCell::Float(value) => value.to_string(),
+                    TypedCell::Bool(value) => value.to_string(),
+                    TypedCell::DateTime(seconds) => format_date_time(*seconds, with_time[col_idx]),
+                    TypedCell::Null => null_placeholder.clone(),
+                })
+                .collect()
+        })
+        .collect();
+
+    // Header and footer rows are usually text, so they do not decide types.
+    // `first_row_is_header` is settled against the text as `table` settles it.
+    let has_header = user_config.header.is_some()
+        || user_config
+            .first_row_is_header
+            .is_some_and(|header_row| header_row.resolve(&rows));
+    let body_start = usize::from(has_header).min(data.len());
+    let body_end = data
+        .len()
+        .saturating_sub(usize::from(user_config.footer.is_some()))
+        .max(body_start);
+    let body = &data[body_start..body_end];
+
+    let kinds: Vec<Option<ColumnKind>> = (0..column_count)
+        .map(|col_idx| {
+            body.iter()
+                .filter_map(|row| row.get(col_idx))
+                .filter(|cell| **cell != TypedCell::Null)
+                .map(|cell| match cell {
+                    TypedCell::Int(_) | TypedCell::Float(_) => ColumnKind::Numbers,
+                    TypedCell::DateTime(_) => ColumnKind::DateTimes,
+                    _ => ColumnKind::Mixed,
+                })
+                .reduce(|kind, other| {
+                    if kind == other {
+                        kind
+                    } else {
+                        ColumnKind::Mixed
+                    }
+                })
+        })
+        .collect();
+
+    let column_default = user_config.column_default.clone().unwrap_or_default();
+    let typed_columns = kinds
+        .iter()
+        .map(|kind| ColumnUserConfig {
+            alignment: (*kind == Some(ColumnKind::Numbers) && column_default.alignment.is_none())
+                .then_some(Alignment::Right),
+            formatter: (*kind == Some(ColumnKind::DateTimes) && column_default.formatter.is_none())
+                .then_some(Formatter::Date),
+            ..Default::default()
+        })
+        .collect();
+    let config = TableUserConfig {
+        columns: Some(typed_columns),
+        ..Default::default()
+    }
+    .merge(user_config);
+    (rows, config)
+}
+
+// `YYYY-MM-DD`, followed by ` HH:MM:SS` when `with_time`.
+fn format_date_time(seconds: i64, with_time: bool) -> String {
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let date = format!("{year:04}-{month:02}-{day:02}");
+    if !with_time {
+        return date;
+    }
+    let time = seconds.rem_euclid(86_400);
+    format!(
+        "{date} {:02}:{:02}:{:02}",
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::stats::HeaderRow;
+
+    #[test]
+    fn test_typed_table_data() {
+        let data = vec![
+            vec!["name".into(), "count".into(), "seen".into(), "ok".into()],
+            vec!["a".into(), 3.into(), TypedCell::DateTime(0), true.into()],
+            vec![
+                "b".into(),
+                TypedCell::Float(2.5),
+                TypedCell::DateTime(1_735_633_800),
+                TypedCell::Null,
+            ],
+            vec![
+                TypedCell::Null,
+                None::<i64>.into(),
+                TypedCell::Null,
+                false.into(),
+            ],
+        ];
+        let user_config = TableUserConfig {
+            header: Some(Box::default()),
+            null_placeholder: Some("-".to_string()),
+            columns: Some(vec![ColumnUserConfig::default(); 2]),
+            ..Default::default()
+        };
+
+        let (rows, config) = typed_table_data(&data, Some(&user_config));
+        assert_eq!(
+            rows,
+            [
+                ["name", "count", "seen", "ok"],
+                ["a", "3", "1970-01-01 00:00:00", "true"],
+                ["b", "2.5", "2024-12-31 08:30:00", "-"],
+                ["-", "-", "-", "false"],
+            ]
+        );
+        let columns = config.columns.unwrap();
+        assert_eq!(columns[0].alignment, None);
+        assert_eq!(columns[1].alignment, Some(Alignment::Right));
+        assert_eq!(columns[2].formatter, Some(Formatter::Date));
+        assert_eq!(columns[3], ColumnUserConfig::default());
+
+        let left = TableUserConfig {
+            column_default: Some(ColumnUserConfig {
+                alignment: Some(Alignment::Left),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let (_, config) = typed_table_data(&data[1..], Some(&left));
+        assert_eq!(config.columns.unwrap()[1].alignment, None);
+
+        // A header found with `first_row_is_header` does not decide types either.
+        for header_row in [HeaderRow::Yes, HeaderRow::Auto] {
+            let first_row = TableUserConfig {
+                first_row_is_header: Some(header_row),
+                ..Default::default()
+            };
+            let (_, config) = typed_table_data(&data, Some(&first_row));
+            let columns = config.columns.unwrap();
+            assert_eq!(columns[1].alignment, Some(Alignment::Right));
+            assert_eq!(columns[2].formatter, Some(Formatter::Date));
+        }
+    }
+}
@@ -0,0 +1,569 @@
+use crate::core::calculator::{calculate_row_heights, column_width, map_data_using_row_heights};
+use crate::core::processor::truncate_cell;
+use crate::core::renderer::{
+    BorderType, draw_border_line, draw_row, draw_row_annotation, process_mapped_data,
+};
+use crate::types::{Row, TableConfig};
+use crate::utils::ansi::calculate_display_width;
+
+/// The visible area, in terminal cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub width: usize,
+    pub height: usize,
+}
+
+/// A table laid out once and drawn a window at a time, so TUI hosts can scroll
+/// very large tables: column widths are measured up front in a single pass,
+/// and each `view` wraps and draws only the rows and columns it shows.
+pub struct TableView<'a> {
+    rows: &'a [Row],
+    body: TableConfig,
+    header: Option<TableConfig>,
+    footer: Option<TableConfig>,
+    body_rows: std::ops::Range<usize>,
+    column_widths: Vec<usize>,
+}
+
+impl<'a> TableView<'a> {
+    pub fn new(rows: &'a [Row], config: &TableConfig) -> Self {
+        Self::with_column_widths(rows, config, measure_column_widths(rows, config))
+    }
+
+    /// A view drawn at `column_widths` instead of the measured widths, e.g.
+    /// ones taken from an earlier view and resized, without measuring `rows`
+    /// again.
+    pub fn with_column_widths(
+        rows: &'a [Row],
+        config: &TableConfig,
+        column_widths: Vec<usize>,
+    ) -> Self {
+        let fixed = |section: &TableConfig| with_fixed_widths(section, &column_widths);
+        let header = config.header.as_deref().map(fixed);
+        let footer = config.footer.as_deref().map(fixed);
+        let body = with_fixed_widths(config, &column_widths);
+
+        Self {
+            rows,
+            body,
+            header,
+            footer,
+            body_rows: config.body_rows(rows.len()),
+            column_widths,
+        }
+    }
+
+    pub fn column_widths(&self) -> &[usize] {
+        &self.column_widths
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Draws the rows from `top_row` and the whole columns from `left_col` that
+    /// fit in `viewport` (always at least one column). The top border is only
+    /// drawn when `top_row` is 0 and the bottom border only once the last row is
+    /// in view, so consecutive windows line up like one scrolled table.
+    pub fn view(&self, top_row: usize, left_col: usize, viewport: Viewport) -> String {
+        let Some(window) = self.window(left_col, viewport) else {
+            return String::new();
+        };
+        if top_row >= self.rows.len() {
+            return String::new();
+        }
+
+        let mut lines = Vec::new();
+        if top_row == 0 {
+            lines.extend(window.top_border());
+        }
+        for row_idx in top_row..self.rows.len() {
+            if lines.len() >= viewport.height {
+                break;
+            }
+            lines.extend(window.row_lines(row_idx));
+            lines.extend(window.line_after(row_idx));
+        }
+
+        lines.truncate(viewport.height);
+        lines.join("\n")
+    }
+
+    /// Like `view`, but the header rows stay at the top and the footer rows,
+    /// such as a totals row, at the bottom, with the body rows from
+    /// `body_top` (counted from the first body row) scrolling between them.
+    /// When `viewport` is too short for both, the header is kept first.
+    pub fn pinned_view(&self, body_top: usize, left_col: usize, viewport: Viewport) -> String {
+        let Some(window) = self.window(left_col, viewport) else {
+            return String::new();
+        };
+
+        let (head, tail) = self.pinned_lines(&window);
+        let room = viewport.height.saturating_sub(head.len() + tail.len());
+        let mut lines = head;
+        let body_start = lines.len();
+        let mut last_separator = None;
+        for row_idx in (self.body_rows.start + body_top)..self.body_rows.end {
+            if lines.len() - body_start >= room {
+                break;
+            }
+            lines.extend(window.row_lines(row_idx));
+            if (row_idx + 1 < self.body_rows.end || tail.is_empty())
+                && let Some(line) = window.line_after(row_idx)
+            {
+                lines.push(line);
+                last_separator = Some(lines.len());
+            }
+        }
+        lines.truncate(body_start + room);
+        // A separator with no row below it would double the one above the
+        // footer.
+        if last_separator == Some(lines.len()) && !tail.is_empty() {
+            lines.pop();
+        }
+        lines.extend(tail);
+
+        lines.truncate(viewport.height);
+        lines.join("\n")
+    }
+
+    /// The `body_top` for `pinned_view` that shows the last body rows, as many
+    /// as fit, e.g. to follow rows as they are appended.
+    pub fn last_body_top(&self, left_col: usize, viewport: Viewport) -> usize {
+        let Some(window) = self.window(left_col, viewport) else {
+            return 0;
+        };
+
+        let (head, tail) = self.pinned_lines(&window);
+        let room = viewport.height.saturating_sub(head.len() + tail.len());
+        let mut used = 0;
+        let mut top = self.body_rows.end;
+        while top > self.body_rows.start {
+            let row_idx = top - 1;
+            let mut height = window.row_lines(row_idx).len();
+            if row_idx + 1 < self.body_rows.end || self.footer_rows().is_empty() {
+                height += usize::from(window.line_after(row_idx).is_some());
+            }
+            if used + height > room && top < self.body_rows.end {
+                break;
+            }
+            used += height;
+            top = row_idx;
+        }
+        top - self.body_rows.start
+    }
+
+    /// How many body rows from `body_top` `pinned_view` shows whole, at least
+    /// one, e.g. to page through them.
+    pub fn page_rows(&self, body_top: usize, left_col: usize, viewport: Viewport) -> usize {
+        let Some(window) = self.window(left_col, viewport) else {
+            return 1;
+        };
+
+        let (head, tail) = self.pinned_lines(&window);
+        let room = viewport.height.saturating_sub(head.len() + tail.len());
+        let mut used = 0;
+        let mut shown = 0;
+        for row_idx in (self.body_rows.start + body_top)..self.body_rows.end {
+            used += window.row_lines(row_idx).len();
+            if used > room {
+                break;
+            }
+            shown += 1;
+            used += usize::from(window.line_after(row_idx).is_some());
+        }
+        shown.max(1)
+    }
+
+    // The lines `pinned_view` keeps: the top border and header rows, and the
+    // line above the footer rows with those rows and the bottom border.
+    fn pinned_lines(&self, window: &Window) -> (Vec<String>, Vec<String>) {
+        let mut head = window.top_border();
+        for row_idx in 0..self.body_rows.start {
+            head.extend(window.row_lines(row_idx));
+            head.extend(window.line_after(row_idx));
+        }
+
+        let mut tail = Vec::new();
+        if !self.footer_rows().is_empty() && !self.body_rows.is_empty() {
+            tail.extend(window.line_after(self.body_rows.end - 1));
+        }
+        for row_idx in self.footer_rows() {
+            tail.extend(window.row_lines(row_idx));
+            tail.extend(window.line_after(row_idx));
+        }
+        (head, tail)
+    }
+
+    fn footer_rows(&self) -> std::ops::Range<usize> {
+        self.body_rows.end.max(self.body_rows.start)..self.rows.len()
+    }
+
+    // The columns from `left_col` that fit in `viewport`, or `None` when
+    // nothing would be drawn.
+    fn window(&self, left_col: usize, viewport: Viewport) -> Option<Window<'_, 'a>> {
+        if self.rows.is_empty() || left_col >= self.column_widths.len() || viewport.height == 0 {
+            return None;
+        }
+
+        let columns = left_col..self.visible_column_end(left_col, viewport.width);
+        let visible = |section: &Option<TableConfig>| {
+            section
+                .as_ref()
+                .map(|section| slice_columns(section, columns.clone()))
+        };
+        Some(Window {
+            view: self,
+            body: slice_columns(&self.body, columns.clone()),
+            header: visible(&self.header),
+            footer: visible(&self.footer),
+            columns,
+        })
+    }
+
+    fn visible_column_end(&self, left_col: usize, max_width: usize) -> usize {
+        let border = &self.body.border;
+        let join_width = calculate_display_width(&border.body_join);
+        let mut width = calculate_display_width(&border.body_left)
+            + calculate_display_width(&border.body_right)
+            + self.column_widths[left_col];
+
+        let mut end = left_col + 1;
+        while end < self.column_widths.len()
+            && width + join_width + self.column_widths[end] <= max_width
+        {
+            width += join_width + self.column_widths[end];
+            end += 1;
+        }
+        end
+    }
+
+    // Wraps the whole row so its height does not change while scrolling
+    // sideways, then aligns and pads just the visible columns.
+    fn draw_row_lines(
+        &self,
+        row_idx: usize,
+        full_config: &TableConfig,
+        visible_config: &TableConfig,
+        columns: &std::ops::Range<usize>,
+    ) -> Vec<Row> {
+        let row: Row = self.rows[row_idx]
+            .iter()
+            .enumerate()
+            .map(|(col_idx, cell)| truncate_cell(cell, column_config(&self.body, col_idx)))
+            .collect();
+        let rows = std::slice::from_ref(&row);
+
+        let heights = calculate_row_heights(rows, full_config);
+        let mapped: Vec<Vec<Row>> = map_data_using_row_heights(rows, &heights, full_config)
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|line| line[columns.clone()].to_vec())
+                    .collect()
+            })
+            .collect();
+
+        let widths = &self.column_widths[columns.clone()];
+        process_mapped_data(&mapped, visible_config, widths)
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+// The columns of a `TableView` a view shows, with each section's config cut
+// down to them.
+struct Window<'v, 'a> {
+    view: &'v TableView<'a>,
+    body: TableConfig,
+    header: Option<TableConfig>,
+    footer: Option<TableConfig>,
+    columns: std::ops::Range<usize>,
+}
+
+impl Window<'_, '_> {
+    fn widths(&self) -> &[usize] {
+        &self.view.column_widths[self.columns.clone()]
+    }
+
+    fn top_border(&self) -> Vec<String> {
+        let row_count = self.view.rows.len();
+        if !self.view.body.draws_outer_line(0, row_count) {
+            return Vec::new();
+        }
+        let border = &self.header.as_ref().unwrap_or(&self.body).border;
+        vec![draw_border_line(self.widths(), border, BorderType::Top)]
+    }
+
+    // The lines of row `row_idx` and of its annotations.
+    fn row_lines(&self, row_idx: usize) -> Vec<String> {
+        let view = self.view;
+        let body_rows = &view.body_rows;
+        let (full_config, visible_config) = if row_idx < body_rows.start {
+            (view.header.as_ref(), self.header.as_ref())
+        } else if row_idx >= body_rows.end {
+            (view.footer.as_ref(), self.footer.as_ref())
+        } else {
+            (None, None)
+        };
+        let full_config = full_config.unwrap_or(&view.body);
+        let visible_config = visible_config.unwrap_or(&self.body);
+
+        let widths = self.widths();
+        let mut lines: Vec<String> = view
+            .draw_row_lines(row_idx, full_config, visible_config, &self.columns)
+            .iter()
+            .map(|sub_row| draw_row(sub_row, widths, &visible_config.border))
+            .collect();
+        for annotation in view
+            .body
+            .row_annotations
+            .iter()
+            .filter(|a| a.row == row_idx)
+        {
+            lines.extend(draw_row_annotation(
+                annotation,
+                widths,
+                &visible_config.border,
+            ));
+        }
+        lines
+    }
+
+    // The border line below row `row_idx`, if one is drawn there.
+    fn line_after(&self, row_idx: usize) -> Option<String> {
+        let view = self.view;
+        let row_count = view.rows.len();
+        let body_rows = &view.body_rows;
+        let row_separators = view.body.border_visibility.row_separators;
+        let widths = self.widths();
+        let footer_border = &self.footer.as_ref().unwrap_or(&self.body).border;
+
+        if row_idx + 1 >= row_count {
+            return view
+                .body
+                .draws_outer_line(row_count, row_count)
+                .then(|| draw_border_line(widths, footer_border, BorderType::Bottom));
+        }
+        if row_idx < body_rows.start {
+            let border = &self.header.as_ref().unwrap_or(&self.body).border;
+            return row_separators.then(|| draw_border_line(widths, border, BorderType::Header));
+        }
+        if row_idx + 1 >= body_rows.end {
+            return row_separators
+                .then(|| draw_border_line(widths, footer_border, BorderType::Footer));
+        }
+        view.body
+            .draws_body_row_line(row_idx + 1, row_count)
+            .then(|| draw_border_line(widths, &self.body.border, BorderType::Join))
+    }
+}
+
+fn column_config(config: &TableConfig, col_idx: usize) -> &crate::types::ColumnConfig {
+    config
+        .columns
+        .get(col_idx)
+        .unwrap_or(&config.column_default)
+}
+
+// Same widths as `calculate_output_column_widths` on the truncated rows, but
+// measured cell by cell instead of materializing a truncated copy.
+fn measure_column_widths(rows: &[Row], config: &TableConfig) -> Vec<usize> {
+    let column_count = rows.first().map_or(0, Vec::len);
+    let mut content_widths = vec![0; column_count];
+
+    for row in rows {
+        for (col_idx, cell) in row.iter().enumerate().take(column_count) {
+            let truncated = truncate_cell(cell, column_config(config, col_idx));
+            let width = truncated
+                .lines()
+                .map(calculate_display_width)
+                .max()
+                .unwrap_or(0);
+            content_widths[col_idx] = content_widths[col_idx].max(width);
+        }
+    }
+
+    content_widths
+        .into_iter()
+        .enumerate()
+        .map(|(col_idx, content_width)| column_width(column_config(config, col_idx), content_width))
+        .collect()
+}
+
+// Pins every column to its measured width so single rows lay out like the
+// whole table. The copy never has a header or footer of its own.
+fn with_fixed_widths(config: &TableConfig, column_widths: &[usize]) -> TableConfig {
+    let mut fixed = TableConfig {
+        header: None,
+        footer: None,
+        ..config.clone()
+    };
+    fixed
+        .columns
+        .resize(column_widths.len(), config.column_default.clone());
+    for (column, &width) in fixed.columns.iter_mut().zip(column_widths) {
+        column.width = width;
+    }
+    fixed
+}
+
+fn slice_columns(config: &TableConfig, columns: std::ops::Range<usize>) -> TableConfig {
+    TableConfig {
+        columns: config.columns[columns].to_vec(),
+        ..config.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::renderer::draw_table;
+
+    fn sample_rows() -> Vec<Row> {
+        (0..6)
+            .map(|i| vec![format!("r{i}"), format!("b{i}"), format!("c{i}")])
+            .collect()
+    }
+
+    #[test]
+    fn test_view_matches_full_table_window() {
+        let rows = sample_rows();
+        let config = TableConfig::default();
+        let full = draw_table(&rows, &config);
+        let full_lines: Vec<&str> = full.lines().collect();
+        let view = TableView::new(&rows, &config);
+
+        let viewport = Viewport {
+            width: 80,
+            height: 4,
+        };
+        let top = view.view(0, 0, viewport);
+        assert_eq!(top, full_lines[..4].join("\n"));
+
+        let middle = view.view(2, 0, viewport);
+        assert_eq!(middle, full_lines[5..9].join("\n"));
+
+        let bottom = view.view(5, 0, viewport);
+        assert_eq!(bottom, full_lines[11..].join("\n"));
+    }
+
+    #[test]
+    fn test_view_with_header_matches_full_table() {
+        let rows = sample_rows();
+        let mut header = TableConfig::default();
+        header.column_default.alignment = crate::types::Alignment::Right;
+        let config = TableConfig {
+            header: Some(Box::new(header)),
+            ..Default::default()
+        };
+
+        let full = draw_table(&rows, &config);
+        let view = TableView::new(&rows, &config).view(
+            0,
+            0,
+            Viewport {
+                width: 80,
+                height: 100,
+            },
+        );
+        assert_eq!(view, full);
+    }
+
+    #[test]
+    fn test_view_scrolls_columns() {
+        let rows = sample_rows();
+        let view = TableView::new(&rows, &TableConfig::default());
+        assert_eq!(view.column_widths(), &[4, 4, 4]);
+        assert_eq!(view.row_count(), 6);
+
+        let window = view.view(
+            0,
+            1,
+            Viewport {
+                width: 10,
+                height: 3,
+            },
+        );
+        assert_eq!(window, "┌────┐\n│ b0 │\n├────┤");
+
+        assert_eq!(
+            view.view(
+                9,
+                0,
+                Viewport {
+                    width: 80,
+                    height: 3
+                }
+            ),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_view_keeps_wrapped_row_height() {
+        let rows = vec![
+            vec!["a".to_string(), "one\ntwo".to_string()],
+            vec!["b".to_string(), "x".to_string()],
+        ];
+        let view = TableView::new(&rows, &TableConfig::default());
+
+        let window = view.view(
+            0,
+            0,
+            Viewport {
+                width: 5,
+                height: 4,
+            },
+        );
+        assert_eq!(window, "┌───┐\n│ a │\n│   │\n├───┤");
+    }
+
+    #[test]
+    fn test_pinned_view_keeps_header_and_footer() {
+        let rows = sample_rows();
+        let config = TableConfig {
+            header: Some(Box::default()),
+            footer: Some(Box::default()),
+            ..Default::default()
+        };
+        let view = TableView::new(&rows, &config);
+        let viewport = Viewport {
+            width: 80,
+            height: 9,
+        };
+
+        let line = "├────┼────┼────┤";
+        let row = |i: usize| format!("│ r{i} │ b{i} │ c{i} │");
+        let head = ["┌────┬────┬────┐".to_string(), row(0), line.to_string()];
+        let tail = [line.to_string(), row(5), "└────┴────┴────┘".to_string()];
+        let expected = |body: &[String]| [&head[..], body, &tail[..]].concat().join("\n");
+
+        assert_eq!(
+            view.pinned_view(1, 0, viewport),
+            expected(&[row(2), line.to_string(), row(3)])
+        );
+        assert_eq!(view.last_body_top(0, viewport), 2);
+        assert_eq!(view.page_rows(1, 0, viewport), 2);
+        assert_eq!(
+            view.pinned_view(2, 0, viewport),
+            expected(&[row(3), line.to_string(), row(4)])
+        );
+        // No separator is left dangling above the footer's.
+        let short = Viewport {
+            height: 8,
+            ..viewport
+        };
+        assert_eq!(view.pinned_view(1, 0, short), expected(&[row(2)]));
+        assert_eq!(view.page_rows(1, 0, short), 1);
+
+        let widened = TableView::with_column_widths(&rows, &config, vec![5, 4, 4]);
+        assert!(
+            widened
+                .pinned_view(1, 0, viewport)
+                .starts_with("┌─────┬────┬")
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Tricky layout inputs and the geometry invariants every rendered table must
+//! satisfy. Shared by the unit tests and the `fuzz/` harness so a case found by
+//! fuzzing can be pinned here as a regression fixture.
+
+use crate::features::aggregation::{Aggregation, ColumnAggregation};
+use crate::features::spanning::{HeaderGroup, HeaderSpec};
+use crate::features::styling::CellStyle;
+use crate::types::{Alignment, ColumnUserConfig, Row, TableUserConfig, Width};
+use crate::utils::ansi::calculate_display_width;
+use regex::Regex;
+use std::sync::OnceLock;
+
+static SGR_REGEX: OnceLock<Regex> = OnceLock::new();
+
+fn get_sgr_regex() -> &'static Regex {
+    SGR_REGEX.get_or_init(|| Regex::new(r"\x1b\[([0-9;]*)m").unwrap())
+}
+
+#[derive(Debug, Clone)]
+pub struct LayoutFixture {
+    pub name: &'static str,
+    pub rows: Vec<Row>,
+    pub config: Option<TableUserConfig>,
+}
+
+impl LayoutFixture {
+    fn new(name: &'static str, rows: &[&[&str]]) -> Self {
+        Self {
+            name,
+            rows: rows
+                .iter()
+                .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+                .collect(),
+            config: None,
+        }
+    }
+
+    fn with_config(mut self, config: TableUserConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+}
+
+fn fixed_columns(widths: &[usize], wrap_word: bool, alignment: Alignment) -> TableUserConfig {
+    TableUserConfig {
+        columns: Some(
+            widths
+                .iter()
+                .map(|&width| ColumnUserConfig {
+                    width: Some(Width::Chars(width)),
+                    wrap_word: Some(wrap_word),
+                    alignment: Some(alignment),
+                    ..Default::default()
+                })
+                .collect(),
+        ),
+        ..Default::default()
+    }
+}
+
+/// Inputs that have broken the layout engine before, or are likely to.
+pub fn layout_corpus() -> Vec<LayoutFixture> {
+    vec![
+        LayoutFixture::new("ascii", &[&["Name", "Age"], &["John", "30"]]),
+        LayoutFixture::new(
+            "ansi_colors",
+            &[&["\u{1b}[31mred\u{1b}[0m", "\u{1b}[1;32mbold green\u{1b}[0m"]],
+        ),
+        LayoutFixture::new("emoji", &[&["🚚 shipped", "📦"], &["✓", "👍🏽 ok"]]),
+        LayoutFixture::new("cjk", &[&["漢字", "テーブル"], &["한국어", "x"]]),
+        LayoutFixture::new("empty_cells", &[&["", ""], &["", "x"]]),
+        LayoutFixture::new("newlines", &[&["a\nb\n\nc", "single"]]),
+        LayoutFixture::new("wrapped_cjk", &[&["漢字漢字漢字漢字", "テーブルテーブル"]])
+            .with_config(fixed_columns(&[5, 6], false, Alignment::Left)),
+        LayoutFixture::new(
+            "wrapped_words_right",
+            &[&["the quick brown fox", "jumps over the lazy dog"]],
+        )
+        .with_config(fixed_columns(&[8, 10], true, Alignment::Right)),
+        LayoutFixture::new(
+            "wrapped_ansi",
+            &[&["\u{1b}[33myellow text that wraps\u{1b}[0m", "b"]],
+        )
+        .with_config(fixed_columns(&[8, 3], false, Alignment::Center)),
+        LayoutFixture::new(
+            "mixed_everything",
+            &[&["\u{1b}[35m漢字🚚\u{1b}[0m", "e\u{301}", "tab-free"]],
+        ),
+        LayoutFixture::new("degraded_bold_header", &[&["", "", ""], &["", "", ""]]).with_config(
+            TableUserConfig {
+                auto_layout: Some(true),
+                terminal_width: Some(10),
+                header: Some(Box::default()),
+                header_style: Some(CellStyle {
+                    bold: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        ),
+        LayoutFixture::new("degraded_footer", &[&["1", "2", "3"], &["3", "4", "5"]]).with_config(
+            TableUserConfig {
+                max_table_width: Some(10),
+                aggregations: Some(vec![ColumnAggregation {
+                    column: 0,
+                    aggregation: Aggregation::Sum,
+                }]),
+                ..Default::default()
+            },
+        ),
+        LayoutFixture::new("degraded_beside_fixed", &[&["", "", "", ""]]).with_config(
+            TableUserConfig {
+                columns: Some(vec![
+                    ColumnUserConfig {
+                        width: Some(Width::Chars(11)),
+                        ..Default::default()
+                    },
+                    ColumnUserConfig {
+                        width: Some(Width::Chars(8)),
+                        ..Default::default()
+                    },
+                    ColumnUserConfig::default(),
+                    ColumnUserConfig::default(),
+                ]),
+                max_table_width: Some(28),
+                ..Default::default()
+            },
+        ),
+        LayoutFixture::new("markdown_header_group", &[&["", ""]]).with_config(TableUserConfig {
+            header_groups: Some(HeaderSpec {
+                rows: vec![vec![HeaderGroup::new("Group", 2)]],
+            }),
+            theme: Some("markdown".to_string()),
+            ..Default::default()
+        }),
+    ]
+}
+
+/// Checks that every line has the same display width and that no SGR style is
+/// left open at the end of a line (it would bleed into borders or the prompt).
+pub fn check_layout_invariants(output: &str) -> Result<(), String> {
+    let mut expected_width = None;
+
+    for (line_idx, line) in output.lines().enumerate() {
+        let width = calculate_display_width(line);
+        match expected_width {
+            None => expected_width = Some(width),
+            Some(expected) if expected != width => {
+                return Err(format!(
+                    "line {line_idx} has width {width}, expected {expected}: {line:?}"
+                ));
+            }
+            _ => {}
+        }
+
+        if has_open_style(line) {
+            return Err(format!("line {line_idx} leaves a style open: {line:?}"));
+        }
+    }
+
+    Ok(())
+}
+
+fn has_open_style(line: &str) -> bool {
+    get_sgr_regex()
+        .captures_iter(line)
+        .fold(false, |_, captures| !matches!(&captures[1], "" | "0"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_corpus_satisfies_invariants() {
+        for fixture in layout_corpus() {
+            let output = crate::table(&fixture.rows, fixture.config.as_ref())
+                .unwrap_or_else(|e| panic!("{} failed to render: {e}", fixture.name));
+            if let Err(message) = check_layout_invariants(&output) {
+                panic!("{}: {message}\n{output}", fixture.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_layout_invariants_detects_problems() {
+        assert!(check_layout_invariants("ab\nabc").is_err());
+        assert!(check_layout_invariants("\u{1b}[31mab").is_err());
+        assert!(check_layout_invariants("\u{1b}[31mab\u{1b}[0m\nab").is_ok());
+    }
+}
@@ -1,54 +1,602 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub mod bench;
 pub mod bindings;
+mod compat;
 pub mod core;
 pub mod features;
+pub mod fixtures;
+pub mod prelude;
+pub mod testing;
 pub mod types;
 pub mod utils;
+#[cfg(feature = "tui")]
+pub mod viewer;
 
 #[cfg(feature = "cli")]
 pub mod cli;
 
 pub use types::{
-    Alignment, BorderConfig, BorderUserConfig, CellConfig, CellCoordinates, CellUserConfig,
-    ColumnConfig, ColumnUserConfig, RangeConfig, RangeCoordinate, Row, SpanningCellConfig,
-    StreamConfig, StreamUserConfig, TableConfig, TableError, TableResult, TableUserConfig,
-    VerticalAlignment,
+    Alignment, BUILTIN_BORDER_STYLES, BorderConfig, BorderLine, BorderLineUserConfig,
+    BorderUserConfig, BorderVisibility, CellConfig, CellCoordinates, CellUserConfig, ColorMode,
+    ColumnConfig, ColumnUserConfig, DataHygiene, FormatCondition, FormatRule, LabelPlacement,
+    Overflow, OverflowPolicy, RangeConfig, RangeCoordinate, RedundantColumns, Row, RowAnnotation,
+    RowOverride, Severity, SortKey, SortKind, SortOrder, SpanningCellConfig, StreamConfig,
+    StreamUserConfig, TableAlignment, TableConfig, TableError, TableLabel, TableResult,
+    TableUserConfig, TextDirection, TruncatePosition, TypedCell, VerticalAlignment, Width,
+    WordBreakPolicy, border_style_names, register_border_style,
 };
 
-pub use core::renderer::draw_table;
-pub use core::validator::{validate_config, validate_table_data_with_config};
-
-pub use features::alignment_processor::*;
-pub use features::borders::*;
-pub use features::spanning::*;
-pub use features::streaming::*;
+pub use core::layout_report::{
+    ColumnTrace, LayoutReport, LayoutStep, WidthChange, trace_table_layout,
+};
+pub use core::renderer::{IoWriter, draw_table, draw_table_to, paginate_table};
+pub use core::session::{RenderSession, SessionStats};
+#[cfg(feature = "config-files")]
+pub use core::validator::validate_user_config_verbose;
+pub use core::validator::{
+    ValidationIssue, ValidationResult, validate_config, validate_table,
+    validate_table_data_with_config,
+};
 
-pub use utils::ansi::*;
+pub use features::aggregation::{AggregateFn, Aggregation, ColumnAggregation};
+pub use features::borders::{BorderStyle, preview_border_style, preview_border_styles};
+pub use features::column_order::{drop_redundant_columns, select_columns, visible_columns};
+pub use features::computed::{Computation, ComputedColumn};
+pub use features::diff::{RowChange, diff_table_data, diff_tables};
+pub use features::estimate::{SizeEstimate, estimate_table_size};
+pub use features::filtering::{RowFilter, RowPredicate};
+pub use features::harmonize::{TableSection, harmonize_widths};
+pub use features::hash_color::{DEFAULT_HASH_PALETTE, HashColorizer};
+pub use features::hygiene::{
+    HygieneIssue, HygieneIssueKind, clean_cell, clean_rows, is_invisible_character, lint_rows,
+};
+#[cfg(feature = "json")]
+pub use features::json::{
+    JsonColumns, json_to_rows, table_from_json, table_from_json_with, value_to_cell,
+};
+pub use features::live::LiveTable;
+#[cfg(feature = "memo")]
+pub use features::memo::{GLOBAL_CACHE_CAPACITY, RenderCache, RenderedTable, render_key};
+pub use features::number_format::{Formatter, Locale, LocaleUserConfig};
+pub use features::render_target::{
+    FileTarget, RenderTarget, StringTarget, TestTarget, TtyTarget, fit_config_to_target,
+};
+#[cfg(feature = "serde-rows")]
+pub use features::serde_rows::{serializable_to_rows, table_from_serializable};
+pub use features::sorting::{TextCollator, sort_rows, sort_rows_by};
+pub use features::spanning::{HeaderGroup, HeaderSpec};
+pub use features::stats::{
+    ColumnRedundancy, ColumnStats, ColumnSuggestion, ColumnType, HeaderRow, RunningColumnStats,
+    calculate_column_stats, column_stats_to_rows, detect_header, suggest_redundant_columns,
+};
+pub use features::streaming::{
+    FlushPolicy, StreamWriter, TableStream, TableStreamWriter, WritableStream, create_stream,
+    create_string_stream,
+};
+pub use features::styling::{CellStyle, StyleCondition, StyleRule};
+pub use features::template::{MissingPlaceholder, fill_placeholders, fill_table_placeholders};
+pub use features::terminal_profile::{
+    TERMINAL_PROFILES, TerminalProfile, set_terminal_profile, terminal_profile,
+};
+pub use features::themes::{BUILTIN_THEMES, Theme, get_theme, register_theme, theme_names};
+pub use features::title::{draw_title_and_caption, title_lines};
+pub use features::typed::typed_table_data;
+pub use features::viewport::{TableView, Viewport};
+
+pub use utils::ansi::{
+    AnsiString, apply_color_mode, calculate_display_width, contains_ansi_sequences,
+    pad_ansi_string, strip_ansi_sequences, strip_hyperlinks, truncate_ansi_string,
+    truncate_ansi_string_at, truncate_ansi_string_at_word, truncate_ansi_string_at_word_with,
+};
 pub use utils::formatting::{calculate_maximum_column_widths, stringify_table_data};
+pub use utils::line::LineBuilder;
+pub use utils::terminal::stdout_supports_ansi;
 pub use utils::unicode::*;
-pub use utils::wrapping::{calculate_cell_height, wrap_text};
+pub use utils::wrapping::{calculate_cell_height, wrap_text, wrap_text_with};
+
+// Pipeline internals that used to be re-exported here; see `compat`.
+pub use compat::*;
 
 pub fn table(data: &[Row], user_config: Option<&TableUserConfig>) -> TableResult<String> {
+    #[cfg(feature = "memo")]
+    if user_config.is_some_and(|config| config.memoize == Some(true)) {
+        return features::memo::RenderCache::global()
+            .render(data, user_config)
+            .map(|table| table.to_string());
+    }
+
+    render_uncached(data, user_config)
+}
+
+/// `table` for cells that keep their types: numbers are right-aligned, dates
+/// written in the locale's order and nulls as the `null_placeholder`, as
+/// `typed_table_data` sets up.
+pub fn table_typed(
+    data: &[Vec<TypedCell>],
+    user_config: Option<&TableUserConfig>,
+) -> TableResult<String> {
+    let (rows, config) = typed_table_data(data, user_config);
+    table(&rows, Some(&config))
+}
+
+// `table` without the `memoize` lookup, which `RenderCache` calls on a miss.
+pub(crate) fn render_uncached(
+    data: &[Row],
+    user_config: Option<&TableUserConfig>,
+) -> TableResult<String> {
     if data.is_empty() {
         return Ok(String::new());
     }
 
     let string_data = stringify_table_data(data)?;
 
-    let config = if let Some(user_config) = user_config {
-        user_config
-            .clone()
-            .merge_with_default(&TableConfig::default())
+    let mut config = resolve_config(&string_data, user_config, stdout_supports_ansi)?;
+
+    let string_data = prepare_table_data(string_data, &mut config)?;
+
+    let mut output = String::new();
+    write_table(&string_data, &config, &mut output).expect("writing to a String cannot fail");
+
+    Ok(output)
+}
+
+/// Writes the table `table` returns for `data` into `writer`, such as a file
+/// or a socket, a line at a time where `draw_table_to` can, without holding
+/// the whole table in memory.
+pub fn table_to_writer<W: std::io::Write>(
+    data: &[Row],
+    user_config: Option<&TableUserConfig>,
+    writer: W,
+) -> TableResult<()> {
+    if data.is_empty() {
+        return Ok(());
+    }
+
+    let string_data = stringify_table_data(data)?;
+
+    let mut config = resolve_config(&string_data, user_config, stdout_supports_ansi)?;
+
+    let string_data = prepare_table_data(string_data, &mut config)?;
+
+    let mut writer = IoWriter::new(writer);
+    write_table(&string_data, &config, &mut writer).map_err(|_| {
+        let error = writer
+            .take_error()
+            .unwrap_or_else(|| std::io::Error::other("formatting failed"));
+        TableError::io("Failed to write table")(error)
+    })
+}
+
+/// `table` for the rows `rows` yields, such as a generator or a database
+/// cursor. When every column sets `width` and the config needs nothing from
+/// the whole table first (sorting, filters, aggregations, formatting and
+/// styling rules, fitted widths, a title or caption, positioning...), rows
+/// are drawn as they come, a few at a time; otherwise they are collected and
+/// drawn as `table` draws them.
+pub fn table_from_iter(
+    rows: impl IntoIterator<Item = Row>,
+    user_config: Option<&TableUserConfig>,
+) -> TableResult<String> {
+    let mut output = String::new();
+    write_table_from_iter(rows.into_iter(), user_config, &mut output, |_| {
+        unreachable!("writing to a String cannot fail")
+    })?;
+    Ok(output)
+}
+
+/// Writes the table `table_from_iter` returns for `rows` into `writer`. Drawn
+/// in one pass, the rows before one that fails, e.g. with
+/// `TableError::InconsistentRowLength`, are already written.
+pub fn table_from_iter_to_writer<W: std::io::Write>(
+    rows: impl IntoIterator<Item = Row>,
+    user_config: Option<&TableUserConfig>,
+    writer: W,
+) -> TableResult<()> {
+    let mut writer = IoWriter::new(writer);
+    write_table_from_iter(rows.into_iter(), user_config, &mut writer, |writer| {
+        let error = writer
+            .take_error()
+            .unwrap_or_else(|| std::io::Error::other("formatting failed"));
+        TableError::io("Failed to write table")(error)
+    })
+}
+
+// Draws `rows` a row at a time where `draws_in_one_pass` allows, checking
+// each row against the first, and otherwise collects them for `write_table`.
+// `write_error` turns a failed write into the error returned.
+fn write_table_from_iter<W: std::fmt::Write>(
+    rows: impl Iterator<Item = Row>,
+    user_config: Option<&TableUserConfig>,
+    out: &mut W,
+    write_error: impl FnOnce(&mut W) -> TableError,
+) -> TableResult<()> {
+    let mut rows = rows.peekable();
+    let Some(first) = rows.peek() else {
+        return Ok(());
+    };
+    let first = stringify_table_data(std::slice::from_ref(first))?;
+    let config = resolve_config(&first, user_config, stdout_supports_ansi)?;
+
+    if !draws_in_one_pass(&config, first[0].len()) {
+        let string_data = stringify_table_data(&rows.collect::<Vec<_>>())?;
+        let mut config = config;
+        let string_data = prepare_table_data(string_data, &mut config)?;
+        return write_table(&string_data, &config, out).map_err(|_| write_error(out));
+    }
+
+    // Rows are counted once drawn, so only then are the row settings
+    // checked against them.
+    validate(
+        &first,
+        &TableConfig {
+            row_annotations: Vec::new(),
+            row_overrides: Default::default(),
+            ..config.clone()
+        },
+    )?;
+    let column_count = first[0].len();
+    let mut row_error = None;
+    let checked_rows = rows.map_while(|row| {
+        let checked = stringify_table_data(&[row]).and_then(|mut rows| match rows.pop() {
+            Some(row) if row.len() == column_count => Ok(row),
+            _ => Err(TableError::InconsistentRowLength),
+        });
+        checked.map_err(|error| row_error = Some(error)).ok()
+    });
+
+    let finish = config.color_mode == ColorMode::Strip || !terminal_profile().hyperlinks;
+    let mut finished = FinishedOutput {
+        out: &mut *out,
+        config: &config,
+    };
+    let written = if finish {
+        core::renderer::draw_rows_in_one_pass(checked_rows, &config, &mut finished)
     } else {
-        create_default_config(&string_data)
+        core::renderer::draw_rows_in_one_pass(checked_rows, &config, finished.out)
+    };
+    let Ok(row_count) = written else {
+        return Err(write_error(out));
     };
+    match row_error {
+        Some(error) => Err(error),
+        None => core::validator::check_row_indices(&config, row_count),
+    }
+}
 
-    validate_config(&config)?;
+// Whether `config` can be drawn as rows come: every column has a fixed width
+// and none is aligned on a character, and nothing picks, orders, changes or
+// lints rows, or fits or frames the table, from all of them.
+fn draws_in_one_pass(config: &TableConfig, column_count: usize) -> bool {
+    let fixed_widths = (0..column_count).all(|col_idx| {
+        let column = config
+            .columns
+            .get(col_idx)
+            .unwrap_or(&config.column_default);
+        column.width > 0
+            && column.width_percent == 0
+            && !column.hidden
+            && !matches!(column.alignment, Alignment::Char(_))
+    });
+    fixed_widths
+        && config.placeholders.is_none()
+        && config.data_hygiene == DataHygiene::Allow
+        && config.filters.is_empty()
+        && config.sort.is_empty()
+        && config.aggregations.is_empty()
+        && config.computed_columns.is_empty()
+        && config.column_default.formatter.is_none()
+        && config
+            .columns
+            .iter()
+            .all(|column| column.formatter.is_none())
+        && config.format_rules.is_empty()
+        && config.style_rules.is_empty()
+        && !config.uppercase_header
+        && config.header_groups.is_none()
+        && config.redundant_columns == RedundantColumns::Keep
+        && config.column_order.is_none()
+        && config.fail_on.is_none()
+        && config.spanning_cells.is_empty()
+        && !config.column_summaries
+        && config.max_table_width.is_none()
+        && config.exact_table_width.is_none()
+        && !config.auto_layout
+        && config.title.is_none()
+        && config.caption.is_none()
+        && config.table_indent == 0
+        && config.table_alignment == TableAlignment::Left
+}
 
-    validate_table_data_with_config(&string_data, &config)?;
+// Passes each piece written through `finish_output`. The one-pass drawer
+// writes whole rows and border lines, so no escape sequence is split.
+struct FinishedOutput<'a, W: ?Sized> {
+    out: &'a mut W,
+    config: &'a TableConfig,
+}
 
-    let result = draw_table(&string_data, &config);
+impl<W: std::fmt::Write + ?Sized> std::fmt::Write for FinishedOutput<'_, W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.out
+            .write_str(&finish_output(s.to_string(), self.config))
+    }
+}
 
-    Ok(result)
+// The rows and resolved config `table` draws `data` with, for views that
+// draw the table their own way.
+pub(crate) fn prepare_table(
+    data: &[Row],
+    user_config: Option<&TableUserConfig>,
+) -> TableResult<(Vec<Row>, TableConfig)> {
+    let string_data = stringify_table_data(data)?;
+
+    let mut config = resolve_config(&string_data, user_config, stdout_supports_ansi)?;
+
+    let string_data = prepare_table_data(string_data, &mut config)?;
+
+    Ok((string_data, config))
+}
+
+/// The config `table` resolves `user_config` to for `data`, for functions
+/// that take a `TableConfig` such as `paginate_table`: the theme is applied,
+/// `first_row_is_header` is settled against `data` into `header`, and
+/// `ColorMode::Auto` is settled for stdout.
+pub fn resolve_table_config(
+    data: &[Row],
+    user_config: Option<&TableUserConfig>,
+) -> TableResult<TableConfig> {
+    resolve_config(data, user_config, stdout_supports_ansi)
+}
+
+/// Explains the column widths `table` would draw `data` with. Rows in the
+/// report are counted after filtering and sorting, and columns after
+/// `column_order`.
+pub fn layout_report(
+    data: &[Row],
+    user_config: Option<&TableUserConfig>,
+) -> TableResult<LayoutReport> {
+    let string_data = stringify_table_data(data)?;
+
+    let mut config = resolve_config(&string_data, user_config, stdout_supports_ansi)?;
+
+    let string_data = prepare_table_data(string_data, &mut config)?;
+
+    Ok(trace_table_layout(&string_data, &config))
+}
+
+/// The size `table` output would have for `data`, worked out from the layout
+/// without drawing it, so callers can reserve a buffer, paginate or refuse
+/// output that is too large. See `SizeEstimate` for when it is exact.
+pub fn estimate_render_size(
+    data: &[Row],
+    user_config: Option<&TableUserConfig>,
+) -> TableResult<SizeEstimate> {
+    if data.is_empty() {
+        return Ok(estimate_table_size(data, &TableConfig::default()));
+    }
+
+    let string_data = stringify_table_data(data)?;
+
+    let mut config = resolve_config(&string_data, user_config, stdout_supports_ansi)?;
+
+    let string_data = prepare_table_data(string_data, &mut config)?;
+
+    let mut estimate = estimate_table_size(&string_data, &config);
+    // Whatever stripping removes only makes the output smaller.
+    estimate.exact &= config.color_mode == ColorMode::Preserve;
+    Ok(estimate)
+}
+
+/// Renders `data` as an HTML `<pre>` block; with `column_summaries` enabled the
+/// header cells carry the column summaries as tooltips.
+#[cfg(feature = "html")]
+pub fn table_to_html(data: &[Row], user_config: Option<&TableUserConfig>) -> TableResult<String> {
+    if data.is_empty() {
+        return Ok(String::new());
+    }
+
+    let string_data = stringify_table_data(data)?;
+
+    // Colors become HTML styles, so only `ColorMode::Strip` removes them.
+    let mut config = resolve_config(&string_data, user_config, || true)?;
+
+    let string_data = prepare_table_data(string_data, &mut config)?;
+
+    Ok(features::column_summary::draw_table_html(
+        &string_data,
+        &config,
+    ))
+}
+
+/// Renders `data` into `target`, letting the target's capabilities decide whether
+/// ANSI sequences are kept and how wide the table may be.
+pub fn table_to_target(
+    data: &[Row],
+    user_config: Option<&TableUserConfig>,
+    target: &mut dyn RenderTarget,
+) -> TableResult<()> {
+    if data.is_empty() {
+        return Ok(());
+    }
+
+    let string_data = stringify_table_data(data)?;
+
+    let mut config = resolve_config(&string_data, user_config, || target.supports_ansi())?;
+    if !target.supports_ansi() {
+        config.color_mode = ColorMode::Strip;
+        config.symbols_only = true;
+    }
+
+    let string_data = prepare_table_data(string_data, &mut config)?;
+
+    fit_config_to_target(&string_data, &mut config, target);
+
+    let result = render_table(&string_data, &config);
+
+    target.write(&finish_output(result, &config))
+}
+
+// Writes the table `render_table` draws, passed through `finish_output`. Only
+// output that needs nothing added or removed afterwards goes to `out` line by
+// line.
+fn write_table<W: std::fmt::Write + ?Sized>(
+    data: &[Row],
+    config: &TableConfig,
+    out: &mut W,
+) -> std::fmt::Result {
+    if config.column_summaries
+        || config.color_mode == ColorMode::Strip
+        || !terminal_profile().hyperlinks
+    {
+        return out.write_str(&finish_output(render_table(data, config), config));
+    }
+    draw_table_to(data, config, out)
+}
+
+// Strips the colors `color_mode` drops, and the hyperlinks the terminal
+// profile cannot show.
+pub(crate) fn finish_output(output: String, config: &TableConfig) -> String {
+    let output = apply_color_mode(output, config.color_mode);
+    if terminal_profile().hyperlinks || !output.contains("\u{1b}]8;") {
+        output
+    } else {
+        strip_hyperlinks(&output)
+    }
+}
+
+// Fails with the first error, or, when `fail_on` is set, with everything
+// `validate_table` finds once an issue reaches it.
+fn validate(data: &[Row], config: &TableConfig) -> TableResult<()> {
+    let Some(threshold) = config.fail_on else {
+        validate_config(config)?;
+        return validate_table_data_with_config(data, config);
+    };
+
+    let result = validate_table(data, config);
+    if result.fails(threshold) {
+        return Err(TableError::Validation(result));
+    }
+    Ok(())
+}
+
+// Draws the table plus, when enabled, the column summary legend beneath it, and
+// positions both as one block.
+pub(crate) fn render_table(data: &[Row], config: &TableConfig) -> String {
+    if !config.column_summaries {
+        return draw_table(data, config);
+    }
+
+    let output = core::renderer::draw_table_body(data, config);
+    let mut output = features::title::draw_title_and_caption(output, config, data.len());
+    let table_width = output
+        .lines()
+        .map(calculate_display_width)
+        .max()
+        .unwrap_or(0);
+    for line in features::column_summary::draw_column_legend(data, config, table_width) {
+        if !output.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str(&line);
+    }
+
+    core::renderer::position_table_block(output, config)
+}
+
+// Fills `{placeholders}` and validates the filled cells, cleans cells under
+// `DataHygiene::Clean`, applies `config.filters`, `config.sort`, the totals
+// row, `config.computed_columns`, `config.format_rules`, then
+// `config.style_rules`, and finally drops redundant columns, keeps the drawn
+// columns in their order and pads cells aligned on a character, before the
+// table is laid out.
+pub(crate) fn prepare_table_data(
+    data: Vec<Row>,
+    config: &mut TableConfig,
+) -> TableResult<Vec<Row>> {
+    prepare_table_columns(data, config).map(|(data, _)| data)
+}
+
+// `prepare_table_data`, also returning the column each drawn column shows,
+// counting computed columns on from the last data column, as `config.columns`
+// is indexed.
+pub(crate) fn prepare_table_columns(
+    mut data: Vec<Row>,
+    config: &mut TableConfig,
+) -> TableResult<(Vec<Row>, Vec<usize>)> {
+    features::template::fill_table_placeholders(&mut data, config)?;
+    validate(&data, config)?;
+    if config.data_hygiene == DataHygiene::Clean {
+        features::hygiene::clean_rows(&mut data);
+    }
+    features::styling::uppercase_header(&mut data, config);
+    features::aggregation::reserve_aggregate_row(&mut data, config);
+    let mut data = features::filtering::filter_rows(data, config)?;
+    if !config.sort.is_empty() {
+        sort_rows_by(&mut data, &config.sort, config)?;
+    }
+    features::aggregation::fill_aggregate_row(&mut data, config);
+    features::computed::append_computed_columns(&mut data, config);
+    features::conditional_format::apply_format_rules(&mut data, config);
+    features::styling::apply_style_rules(&mut data, config)?;
+    core::processor::apply_formatters(&mut data, config);
+    features::column_order::drop_redundant_columns(&mut data, config);
+    let drawn = visible_columns(data.first().map_or(0, Vec::len), config);
+    let mut data = features::column_order::select_columns(data, config);
+    core::calculator::align_on_char(&mut data, config);
+    features::spanning::insert_header_groups(&mut data, config)?;
+    Ok((data, drawn))
+}
+
+// Settles `ColorMode::Auto` with `supports_ansi`, which says whether the
+// output's destination takes colors. Where colors are stripped, formatting
+// rules must mark cells with symbols. `first_row_is_header` is settled
+// against `data` here too.
+fn resolve_config(
+    data: &[Row],
+    user_config: Option<&TableUserConfig>,
+    supports_ansi: impl FnOnce() -> bool,
+) -> TableResult<TableConfig> {
+    let Some(user_config) = user_config else {
+        return Ok(create_default_config(data));
+    };
+
+    features::themes::check_theme(user_config)?;
+    core::validator::validate_overflow_modes(user_config)?;
+    let mut user_config = user_config.clone();
+    if user_config.header.is_none()
+        && user_config
+            .first_row_is_header
+            .is_some_and(|header_row| header_row.resolve(data))
+    {
+        user_config.header = Some(Box::default());
+        user_config.header_style.get_or_insert(CellStyle {
+            bold: true,
+            ..Default::default()
+        });
+    }
+    let mut config = user_config.merge_with_default(&TableConfig::default());
+    if let Some(style) = config.header_style.clone()
+        && config.header.is_some()
+    {
+        config.style_rules.insert(
+            0,
+            StyleRule {
+                column: None,
+                row: Some(0),
+                condition: StyleCondition::Any,
+                style,
+                symbol: None,
+            },
+        );
+    }
+    if config.color_mode == ColorMode::Auto {
+        config.color_mode = if supports_ansi() {
+            ColorMode::Preserve
+        } else {
+            ColorMode::Strip
+        };
+    }
+    config.symbols_only |= config.color_mode == ColorMode::Strip;
+    Ok(config)
 }
 
 fn create_default_config(data: &[Row]) -> TableConfig {
@@ -66,15 +614,6 @@ pub fn get_border_characters(name: &str) -> TableResult<BorderConfig> {
     types::borders::get_border_characters(name)
 }
 
-#[cfg(test)]
-pub fn benchmark_wrap_text(text: &str, width: usize, iterations: usize) -> u128 {
-    let start = std::time::Instant::now();
-    for _ in 0..iterations {
-        let _result = wrap_text(text, width, false);
-    }
-    start.elapsed().as_millis()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,12 +654,8 @@ mod tests {
         ];
 
         let config = TableUserConfig {
-            border: None,
-            columns: None,
-            column_default: None,
             single_line: Some(false),
-            spanning_cells: None,
-            header: None,
+            ..Default::default()
         };
 
         let result = table(&data, Some(&config)).unwrap();
@@ -144,6 +679,26 @@ mod tests {
         assert_eq!(void_border.top_left, "");
         assert_eq!(void_border.body_join, " ");
 
+        let rounded = get_border_characters("rounded").unwrap();
+        assert_eq!(
+            [rounded.top_left, rounded.top_right],
+            ["╭".to_string(), "╮".to_string()]
+        );
+
+        let data: Vec<Row> = [["a", "b"], ["c", "d"], ["e", "f"], ["g", "h"]]
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        let markdown = TableUserConfig {
+            border: Some(get_border_characters("markdown").unwrap().into()),
+            header: Some(Box::default()),
+            ..Default::default()
+        };
+        assert_eq!(
+            table(&data, Some(&markdown)).unwrap(),
+            "| a | b |\n|---|---|\n| c | d |\n| e | f |\n| g | h |\n"
+        );
+
         assert!(get_border_characters("invalid").is_err());
     }
 
@@ -156,7 +711,7 @@ mod tests {
 
         let config = TableUserConfig {
             columns: Some(vec![ColumnUserConfig {
-                width: Some(10),
+                width: Some(Width::Chars(10)),
                 wrap_word: Some(false),
                 ..Default::default()
             }]),
@@ -231,7 +786,7 @@ mod tests {
 
         let config = TableUserConfig {
             columns: Some(vec![ColumnUserConfig {
-                width: Some(5),
+                width: Some(Width::Chars(5)),
                 wrap_word: Some(false),
                 ..Default::default()
             }]),
@@ -317,22 +872,22 @@ mod tests {
         let config = TableUserConfig {
             columns: Some(vec![
                 ColumnUserConfig {
-                    width: Some(12),
+                    width: Some(Width::Chars(12)),
                     wrap_word: Some(true),
                     ..Default::default()
                 },
                 ColumnUserConfig {
-                    width: Some(25),
+                    width: Some(Width::Chars(25)),
                     wrap_word: Some(true),
                     ..Default::default()
                 },
                 ColumnUserConfig {
-                    width: Some(15),
+                    width: Some(Width::Chars(15)),
                     wrap_word: Some(false),
                     ..Default::default()
                 },
                 ColumnUserConfig {
-                    width: Some(8),
+                    width: Some(Width::Chars(8)),
                     wrap_word: Some(true),
                     ..Default::default()
                 },
@@ -466,12 +1021,12 @@ mod tests {
         let config = TableUserConfig {
             columns: Some(vec![
                 ColumnUserConfig {
-                    width: Some(10),
+                    width: Some(Width::Chars(10)),
                     vertical_alignment: Some(VerticalAlignment::Bottom),
                     ..Default::default()
                 },
                 ColumnUserConfig {
-                    width: Some(15),
+                    width: Some(Width::Chars(15)),
                     vertical_alignment: Some(VerticalAlignment::Top),
                     ..Default::default()
                 },
@@ -511,6 +1066,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_bottom_padding_adds_newlines() {
         use crate::core::processor::apply_vertical_alignment;
         use crate::types::{ColumnConfig, TableConfig, VerticalAlignment};
@@ -544,6 +1100,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_middle_padding_adds_newlines() {
         use crate::core::processor::apply_vertical_alignment;
         use crate::types::{ColumnConfig, TableConfig, VerticalAlignment};
@@ -591,14 +1148,21 @@ mod tests {
     }
 
     #[test]
-    fn test_textwrap_performance() {
+    fn test_wrap_text_performance() {
         let long_text = "a".repeat(1000);
-        let time_ms = benchmark_wrap_text(&long_text, 3, 100);
+        let options = bench::BenchOptions {
+            warmup_iterations: 0,
+            iterations: 100,
+            ..Default::default()
+        };
+        let time_ms = bench::bench_wrap_text(&long_text, 3, &options)
+            .total()
+            .as_millis();
         println!("100 wrap operations took {time_ms}ms");
 
         assert!(
             time_ms < 1000,
-            "Textwrap should be reasonably fast, took {time_ms}ms"
+            "wrap_text should be reasonably fast, took {time_ms}ms"
         );
     }
 
@@ -633,44 +1197,912 @@ mod tests {
                     join_left: Some("╠".to_string()),
                     join_right: Some("╣".to_string()),
                     join_join: Some("╬".to_string()),
+                    ..Default::default()
                 }),
-                columns: None,
-                column_default: None,
-                single_line: None,
-                spanning_cells: None,
-                header: None,
+                ..Default::default()
             })),
             ..Default::default()
         };
 
-        let result = table(&data, Some(&config)).unwrap();
-        println!("Table with header result:");
-        println!("{result}");
+        let result = table(&data, Some(&config)).unwrap();
+        println!("Table with header result:");
+        println!("{result}");
+
+        assert!(result.contains("Name"));
+        assert!(result.contains("Age"));
+        assert!(result.contains("City"));
+        assert!(result.contains("Alice"));
+        assert!(result.contains("Bob"));
+        assert!(result.contains("New York"));
+        assert!(result.contains("London"));
+
+        println!("Looking for header separator character '═' in output...");
+        let lines: Vec<&str> = result.lines().collect();
+        for (i, line) in lines.iter().enumerate() {
+            println!("Line {i}: {line}");
+            if line.contains("═") {
+                println!("Found header separator at line {i}");
+            }
+        }
+        assert!(
+            result.contains("═"),
+            "Header separator '═' not found in output"
+        );
+
+        assert!(result.contains("║"));
+        assert!(result.contains("╔"));
+        assert!(result.contains("╗"));
+    }
+
+    #[test]
+    fn test_table_sorts_body_below_header() {
+        let data = vec![
+            vec!["Item".to_string(), "Qty".to_string()],
+            vec!["item10".to_string(), "1".to_string()],
+            vec!["item2".to_string(), "5".to_string()],
+        ];
+
+        let config = TableUserConfig {
+            header: Some(Box::default()),
+            sort: Some(vec!["0:natural".parse().unwrap()]),
+            ..Default::default()
+        };
+        let result = table(&data, Some(&config)).unwrap();
+        let lines: Vec<&str> = result.lines().collect();
+        assert!(lines[1].contains("Item"));
+        assert!(lines[3].contains("item2"));
+        assert!(lines[5].contains("item10"));
+
+        let out_of_range = TableUserConfig {
+            sort: Some(vec!["2".parse().unwrap()]),
+            ..Default::default()
+        };
+        assert!(table(&data, Some(&out_of_range)).is_err());
+    }
+
+    #[test]
+    fn test_format_rules_symbols_in_ansi_html_and_plain_targets() {
+        let data = vec![
+            vec!["Host".to_string(), "Change".to_string()],
+            vec!["web".to_string(), "-4".to_string()],
+        ];
+        let rule = FormatRule {
+            column: Some(1),
+            condition: FormatCondition::Negative,
+            color: "31".to_string(),
+            symbol: None,
+        };
+        let config = TableUserConfig {
+            header: Some(Box::default()),
+            format_rules: Some(vec![rule]),
+            ..Default::default()
+        };
+
+        let colored = table(&data, Some(&config)).unwrap();
+        assert!(colored.contains("\u{1b}[31m-4\u{1b}[0m"));
+
+        let mut plain = StringTarget::new();
+        table_to_target(&data, Some(&config), &mut plain).unwrap();
+        assert!(plain.as_str().contains("↓ -4"));
+
+        let symbols = TableUserConfig {
+            symbols_only: Some(true),
+            ..config
+        };
+        assert!(table(&data, Some(&symbols)).unwrap().contains("↓ -4"));
+        #[cfg(feature = "html")]
+        {
+            let html = table_to_html(&data, Some(&symbols)).unwrap();
+            assert!(html.contains("↓ -4") && !html.contains("color"));
+        }
+    }
+
+    #[test]
+    fn test_multi_line_header_wraps_and_aligns() {
+        let data = vec![
+            vec!["A very long header label".to_string(), "Short".to_string()],
+            vec!["x".to_string(), "y".to_string()],
+        ];
+
+        let config = TableUserConfig {
+            columns: Some(vec![ColumnUserConfig {
+                width: Some(Width::Chars(10)),
+                wrap_word: Some(true),
+                ..Default::default()
+            }]),
+            border: Some(get_border_characters("ramac").unwrap().into()),
+            header: Some(Box::new(TableUserConfig {
+                column_default: Some(ColumnUserConfig {
+                    alignment: Some(Alignment::Center),
+                    vertical_alignment: Some(VerticalAlignment::Bottom),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        let result = table(&data, Some(&config)).unwrap();
+        assert_eq!(
+            result,
+            [
+                "+----------+-------+",
+                "|  A very  |       |",
+                "|   long   |       |",
+                "|  header  |       |",
+                "|  label   | Short |",
+                "+----------+-------+",
+                "| x        | y     |",
+                "+----------+-------+",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_border_sections() {
+        let data: Vec<Row> = [["Item", "Cost"], ["a", "3"], ["b", "12"], ["Total", "15"]]
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        let double = BorderLineUserConfig {
+            left: Some("╞".to_string()),
+            body: Some("═".to_string()),
+            join: Some("╪".to_string()),
+            right: Some("╡".to_string()),
+        };
+        let config = TableUserConfig {
+            border: Some(BorderUserConfig {
+                header_separator: Some(double),
+                body_row_separators: Some(false),
+                footer_separator: Some(BorderLineUserConfig {
+                    body: Some("-".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            header: Some(Box::default()),
+            footer: Some(Box::default()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            table(&data, Some(&config)).unwrap(),
+            [
+                "┌───────┬──────┐",
+                "│ Item  │ Cost │",
+                "╞═══════╪══════╡",
+                "│ a     │ 3    │",
+                "│ b     │ 12   │",
+                "╞-------╪------╡",
+                "│ Total │ 15   │",
+                "└───────┴──────┘",
+            ]
+            .join("\n")
+        );
+
+        // `body_row_separator` leaves the header line, which shares its
+        // `join_*` characters, as it was.
+        let config = TableUserConfig {
+            border: Some(BorderUserConfig {
+                join_body: Some("=".to_string()),
+                body_row_separator: Some(BorderLineUserConfig {
+                    body: Some(".".to_string()),
+                    join: Some(".".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            header: Some(Box::default()),
+            ..Default::default()
+        };
+        let lines: Vec<String> = table(&data, Some(&config))
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(lines[2], "├───────┼──────┤");
+        assert_eq!(lines[4], "├..............┤");
+    }
+
+    #[test]
+    fn test_border_visibility() {
+        let data: Vec<Row> = [["Name", "Age"], ["John", "30"], ["Ann", "4"]]
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        let config = TableUserConfig {
+            border_visibility: Some(BorderVisibility {
+                column_separators: false,
+                row_separators: false,
+                ..Default::default()
+            }),
+            header: Some(Box::default()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            table(&data, Some(&config)).unwrap(),
+            [
+                "┌────────────┐",
+                "│ Name   Age │",
+                "│ John   30  │",
+                "│ Ann    4   │",
+                "└────────────┘",
+            ]
+            .join("\n")
+        );
+
+        // Only the lines between rows, like a spreadsheet without a frame.
+        let config = TableUserConfig {
+            border_visibility: Some(BorderVisibility {
+                outer: false,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            table(&data, Some(&config)).unwrap(),
+            [
+                " Name │ Age ",
+                "──────┼─────",
+                " John │ 30  ",
+                "──────┼─────",
+                " Ann  │ 4   ",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_footer_row_stays_last_with_its_own_border() {
+        let data = vec![
+            vec!["Item".to_string(), "Cost".to_string()],
+            vec!["a".to_string(), "3".to_string()],
+            vec!["b".to_string(), "12".to_string()],
+            vec!["Total".to_string(), "15".to_string()],
+        ];
+
+        let config = TableUserConfig {
+            border: Some(get_border_characters("ramac").unwrap().into()),
+            header: Some(Box::default()),
+            footer: Some(Box::new(TableUserConfig {
+                border: Some(get_border_characters("norc").unwrap().into()),
+                column_default: Some(ColumnUserConfig {
+                    alignment: Some(Alignment::Right),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })),
+            sort: Some(vec!["1:desc".parse().unwrap()]),
+            ..Default::default()
+        };
+
+        let result = table(&data, Some(&config)).unwrap();
+        assert_eq!(
+            result,
+            [
+                "+-------+------+",
+                "| Item  | Cost |",
+                "+-------+------+",
+                "| b     | 12   |",
+                "+-------+------+",
+                "| a     | 3    |",
+                "├───────┼──────┤",
+                "│ Total │   15 │",
+                "└───────┴──────┘",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_resolve_table_config_settles_first_row_is_header() {
+        let rows: Vec<Row> = [["Host", "Load"], ["web", "0.5"], ["db", "2.25"]]
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        let user_config = TableUserConfig {
+            first_row_is_header: Some(features::stats::HeaderRow::Auto),
+            color_mode: Some(ColorMode::Strip),
+            ..Default::default()
+        };
+
+        let config = resolve_table_config(&rows, Some(&user_config)).unwrap();
+        assert!(config.header.is_some());
+        let pages = paginate_table(&rows, &config, 1).unwrap();
+        assert_eq!(pages.len(), 2);
+        for (page, body) in pages.iter().zip(["│ web  │ 0.5  │", "│ db   │ 2.25 │"]) {
+            assert_eq!(page.lines().nth(1), Some("│ Host │ Load │"), "{page}");
+            assert_eq!(page.lines().nth(3), Some(body), "{page}");
+        }
+    }
+
+    #[test]
+    fn test_table_from_iter_matches_table() {
+        let rows = |count: usize| -> Vec<Row> {
+            (0..count)
+                .map(|idx| {
+                    vec![
+                        format!("\u{1b}[31mrow {idx}\u{1b}[0m"),
+                        "a long cell".repeat(idx),
+                    ]
+                })
+                .collect()
+        };
+        let fixed = TableUserConfig {
+            columns: Some(vec![
+                ColumnUserConfig {
+                    width: Some(Width::Chars(8)),
+                    ..Default::default()
+                },
+                ColumnUserConfig {
+                    width: Some(Width::Chars(14)),
+                    ..Default::default()
+                },
+            ]),
+            header: Some(Box::default()),
+            footer: Some(Box::default()),
+            row_striping: Some("48;5;236".to_string()),
+            row_annotations: Some(vec![RowAnnotation {
+                row: 1,
+                text: "first body row".to_string(),
+                indent: None,
+                dim: None,
+            }]),
+            color_mode: Some(ColorMode::Strip),
+            ..Default::default()
+        };
+        let sorted = TableUserConfig {
+            sort: Some(vec!["0:desc".parse().unwrap()]),
+            ..Default::default()
+        };
+        let decimal = TableUserConfig {
+            column_default: Some(ColumnUserConfig {
+                width: Some(Width::Chars(12)),
+                alignment: Some(Alignment::Char('.')),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let numbers = |count: usize| -> Vec<Row> {
+            (0..count)
+                .map(|idx| vec![format!("{}.5", 10_usize.pow(idx as u32))])
+                .collect()
+        };
+        for count in 0..6 {
+            assert_eq!(
+                format!("{:?}", table_from_iter(numbers(count), Some(&decimal))),
+                format!("{:?}", table(&numbers(count), Some(&decimal)))
+            );
+        }
+
+        for config in [&fixed, &sorted] {
+            for count in 0..6 {
+                // With one row, both fail on the annotation of row 1.
+                assert_eq!(
+                    format!("{:?}", table_from_iter(rows(count), Some(config))),
+                    format!("{:?}", table(&rows(count), Some(config)))
+                );
+            }
+        }
+
+        let mut ragged = rows(4);
+        ragged[3].pop();
+        assert!(matches!(
+            table_from_iter(ragged, Some(&fixed)),
+            Err(TableError::InconsistentRowLength)
+        ));
+
+        // A later row is checked for unclean data as `table` checks it.
+        let rejecting = TableUserConfig {
+            data_hygiene: Some(DataHygiene::Reject),
+            ..fixed.clone()
+        };
+        let unclean = vec![
+            vec!["lead".to_string(), "x".to_string()],
+            vec!["trail  ".to_string(), "x".to_string()],
+        ];
+        assert!(matches!(
+            table(&unclean, Some(&rejecting)),
+            Err(TableError::UncleanData(_))
+        ));
+        assert!(matches!(
+            table_from_iter(unclean, Some(&rejecting)),
+            Err(TableError::UncleanData(_))
+        ));
+
+        // Drawn in one pass, an endless iterator is read only as far as the
+        // writer takes rows.
+        struct Full(usize);
+        impl std::io::Write for Full {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0 = self
+                    .0
+                    .checked_sub(buf.len())
+                    .ok_or(std::io::ErrorKind::WriteZero)?;
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        let endless = (0..).map(|idx: u64| vec![idx.to_string(), "x".to_string()]);
+        assert!(matches!(
+            table_from_iter_to_writer(endless, Some(&fixed), Full(4096)),
+            Err(TableError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_aggregations_append_totals_of_filtered_rows() {
+        let data = vec![
+            vec!["Item".to_string(), "Cost".to_string()],
+            vec!["a".to_string(), "3".to_string()],
+            vec!["b".to_string(), "12.5".to_string()],
+            vec!["c".to_string(), "4".to_string()],
+        ];
+
+        let config = TableUserConfig {
+            header: Some(Box::default()),
+            filters: Some(vec!["0=~^[ab]$".parse().unwrap()]),
+            sort: Some(vec!["1:desc".parse().unwrap()]),
+            aggregations: Some(vec!["0:Total".parse().unwrap(), "1:sum".parse().unwrap()]),
+            ..Default::default()
+        };
+
+        let result = table(&data, Some(&config)).unwrap();
+        assert_eq!(
+            result,
+            [
+                "┌───────┬──────┐",
+                "│ Item  │ Cost │",
+                "├───────┼──────┤",
+                "│ b     │ 12.5 │",
+                "├───────┼──────┤",
+                "│ a     │ 3    │",
+                "├───────┼──────┤",
+                "│ Total │ 15.5 │",
+                "└───────┴──────┘",
+            ]
+            .join("\n")
+        );
+
+        let config = TableUserConfig {
+            aggregations: Some(vec!["2:sum".parse().unwrap()]),
+            ..Default::default()
+        };
+        assert!(table(&data, Some(&config)).is_err());
+    }
+
+    #[test]
+    fn test_style_rules_color_matching_cells() {
+        let data = vec![
+            vec!["Host".to_string(), "Status".to_string()],
+            vec!["web".to_string(), "Active".to_string()],
+            vec!["db".to_string(), "Failed".to_string()],
+        ];
+        let status = |value: &str, color: &str| StyleRule {
+            column: Some(1),
+            condition: StyleCondition::Equals(value.to_string()),
+            style: CellStyle::color(color),
+            ..Default::default()
+        };
+
+        let config = TableUserConfig {
+            style_rules: Some(vec![status("Active", "32"), status("Failed", "31")]),
+            ..Default::default()
+        };
+        let result = table(&data, Some(&config)).unwrap();
+        let lines: Vec<&str> = result.lines().collect();
+        assert_eq!(lines[1], "│ Host │ Status │");
+        assert_eq!(lines[3], "│ web  │ \u{1b}[32mActive\u{1b}[0m │");
+        assert_eq!(lines[5], "│ db   │ \u{1b}[31mFailed\u{1b}[0m │");
+
+        let config = TableUserConfig {
+            style_rules: Some(vec![StyleRule {
+                column: Some(2),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+        assert!(table(&data, Some(&config)).is_err());
+    }
+
+    #[test]
+    fn test_prelude_covers_common_use() {
+        use crate::prelude::*;
+
+        let data: Vec<Row> = vec![vec!["a".to_string(), "b".to_string()]];
+        let config = TableUserConfig {
+            border: Some(get_border_characters("ramac").unwrap().into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            table(&data, Some(&config)).unwrap(),
+            "+---+---+\n| a | b |\n+---+---+"
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_root_names_still_work() {
+        assert_eq!(crate::normalize_value(" \u{1b}[1mx\u{1b}[0m "), "x");
+        let layout = crate::AlignmentProcessor::layout("ab", 4, Alignment::Right);
+        assert_eq!(layout.to_string(), "  ab");
+    }
+
+    #[test]
+    fn test_clean_data_before_filtering() {
+        let data = vec![
+            vec!["\u{FEFF}Name".to_string(), "Role".to_string()],
+            vec!["Ada".to_string(), "admin  ".to_string()],
+            vec!["Bob".to_string(), "user".to_string()],
+        ];
+        let config = TableUserConfig {
+            data_hygiene: Some(DataHygiene::Clean),
+            header: Some(Box::default()),
+            filters: Some(vec!["1=admin".parse().unwrap()]),
+            ..Default::default()
+        };
+
+        let output = table(&data, Some(&config)).unwrap();
+        assert_eq!(
+            output,
+            [
+                "┌──────┬───────┐",
+                "│ Name │ Role  │",
+                "├──────┼───────┤",
+                "│ Ada  │ admin │",
+                "└──────┴───────┘",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_word_break_policy_overrides_wrap_word() {
+        let data = vec![vec!["abcdefgh".to_string(), "x".to_string()]];
+        let config = TableUserConfig {
+            column_default: Some(ColumnUserConfig {
+                width: Some(Width::Chars(5)),
+                word_break: Some(WordBreakPolicy::BreakAtHyphen),
+                ..Default::default()
+            }),
+            columns: Some(vec![
+                ColumnUserConfig::default(),
+                ColumnUserConfig {
+                    wrap_word: Some(true),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+        let resolved = config.clone().merge_with_default(&TableConfig::default());
+        assert_eq!(
+            resolved.columns[1].word_break_policy(),
+            WordBreakPolicy::NoBreakOverflow
+        );
+
+        let output = table(&data, Some(&config)).unwrap();
+        assert_eq!(
+            output,
+            [
+                "┌─────┬─────┐",
+                "│ ab- │ x   │",
+                "│ cd- │     │",
+                "│ ef- │     │",
+                "│ gh  │     │",
+                "└─────┴─────┘",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_fail_on_gates_rendering_by_severity() {
+        let data = vec![
+            vec!["name".to_string(), "note".to_string()],
+            vec!["web ".to_string(), "unbreakable".to_string()],
+        ];
+        let mut config = TableUserConfig {
+            columns: Some(vec![
+                ColumnUserConfig::default(),
+                ColumnUserConfig {
+                    width: Some(Width::Chars(6)),
+                    wrap_word: Some(true),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+        assert!(table(&data, Some(&config)).is_ok());
+
+        config.fail_on = Some(Severity::Error);
+        assert!(table(&data, Some(&config)).is_ok());
+
+        config.fail_on = Some(Severity::Warning);
+        match table(&data, Some(&config)) {
+            Err(error @ TableError::Validation(_)) => assert_eq!(
+                error.to_string(),
+                "Validation failed: warning at row 1, column 0: trailing whitespace on line 0; \
+                 warning at row 1, column 1: a word is wider than the column and overflows its border"
+            ),
+            other => panic!("unexpected result: {other:?}"),
+        }
+
+        config.fail_on = Some(Severity::Error);
+        config.flex_column = Some(5);
+        match table(&data, Some(&config)) {
+            Err(TableError::Validation(result)) => {
+                assert_eq!(result.max_severity(), Some(Severity::Error));
+                assert_eq!(result.issues.len(), 1);
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_title_and_caption_move_with_the_table() {
+        let data = vec![vec!["a".to_string(), "b".to_string()]];
+        let config = TableUserConfig {
+            title: Some(TableLabel {
+                text: "Totals".to_string(),
+                alignment: None,
+                placement: Some(LabelPlacement::Outside),
+            }),
+            caption: Some(TableLabel {
+                text: "v2".to_string(),
+                alignment: Some(Alignment::Left),
+                placement: None,
+            }),
+            table_indent: Some(2),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            table(&data, Some(&config)).unwrap(),
+            ["   Totals", "  ┌───┬───┐", "  │ a │ b │", "  └─ v2 ──┘",].join("\n")
+        );
+    }
+
+    #[test]
+    fn test_layout_report_follows_the_drawn_columns() {
+        let data = vec![
+            vec!["host".to_string(), "notes".to_string()],
+            vec!["db".to_string(), "a long note".to_string()],
+        ];
+        let config = TableUserConfig {
+            column_order: Some(vec![1]),
+            ..Default::default()
+        };
+
+        let report = layout_report(&data, Some(&config)).unwrap();
+        assert_eq!(
+            report.to_string(),
+            "column 0: 13 wide, content 11 from row 1; padding 11 -> 13"
+        );
+        assert!(
+            layout_report(
+                &data,
+                Some(&TableUserConfig {
+                    flex_column: Some(4),
+                    ..Default::default()
+                })
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_percent_column_widths() {
+        assert_eq!("30%".parse::<Width>().unwrap(), Width::Percent(30));
+        assert_eq!(" 12 ".parse::<Width>().unwrap(), Width::Chars(12));
+        assert!(matches!(
+            "wide".parse::<Width>(),
+            Err(TableError::Parse { kind: "width", .. })
+        ));
+
+        let data = vec![vec!["a".to_string(), "b".to_string()]];
+        let config = TableUserConfig {
+            column_default: Some(ColumnUserConfig {
+                width: Some(Width::Percent(50)),
+                ..Default::default()
+            }),
+            max_table_width: Some(23),
+            ..Default::default()
+        };
+        let output = table(&data, Some(&config)).unwrap();
+        assert_eq!(output.lines().next().unwrap(), "┌──────────┬──────────┐");
+
+        let too_wide = TableUserConfig {
+            columns: Some(vec![ColumnUserConfig {
+                width: Some(Width::Percent(120)),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+        assert!(table(&data, Some(&too_wide)).is_err());
+    }
+
+    #[test]
+    fn test_column_order_and_hidden_columns() {
+        let data = vec![
+            vec!["Name".to_string(), "Port".to_string(), "Host".to_string()],
+            vec![
+                "web".to_string(),
+                "443".to_string(),
+                "a.example".to_string(),
+            ],
+        ];
+        let config = TableUserConfig {
+            columns: Some(vec![
+                ColumnUserConfig::default(),
+                ColumnUserConfig {
+                    alignment: Some(Alignment::Right),
+                    ..Default::default()
+                },
+                ColumnUserConfig {
+                    hidden: Some(true),
+                    ..Default::default()
+                },
+            ]),
+            column_order: Some(vec![2, 1, 0]),
+            sort: Some(vec!["0".parse().unwrap()]),
+            ..Default::default()
+        };
+
+        let output = table(&data, Some(&config)).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[1], "│ Port │ Name │");
+        assert_eq!(lines[3], "│  443 │ web  │");
+
+        let out_of_bounds = TableUserConfig {
+            column_order: Some(vec![3]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            table(&data, Some(&out_of_bounds)),
+            Err(TableError::IndexOutOfBounds {
+                setting: "Column order",
+                ..
+            })
+        ));
+
+        let nothing = TableUserConfig {
+            column_order: Some(vec![]),
+            ..Default::default()
+        };
+        assert!(table(&data, Some(&nothing)).is_err());
+    }
+
+    #[test]
+    fn test_errors_are_matchable() {
+        assert!(matches!(
+            get_border_characters("sketchy"),
+            Err(TableError::Parse { kind: "border style", ref input, .. }) if input == "sketchy"
+        ));
+        assert!(matches!(
+            "x:desc".parse::<SortKey>(),
+            Err(TableError::Parse {
+                kind: "sort key",
+                ..
+            })
+        ));
+
+        let data: Vec<Row> = vec![vec!["a".to_string()]];
+        let config = TableUserConfig {
+            filters: Some(vec![RowFilter::Regex {
+                column: 0,
+                pattern: "(".to_string(),
+            }]),
+            ..Default::default()
+        };
+        let error = table(&data, Some(&config)).unwrap_err();
+        assert!(matches!(
+            error,
+            TableError::InvalidPattern { kind: "filter", .. }
+        ));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn test_spanning_cells_cover_rows_and_columns() {
+        let data: Vec<Row> = [
+            ["Region", "Q1", "Q2", "Q3"],
+            ["North", "10", "20", "30"],
+            ["", "11", "21", "31"],
+            ["South", "Total", "", "33"],
+            ["West", "1", "2", ""],
+        ]
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect();
+        let span = |col, row, col_span, row_span, vertical_alignment| SpanningCellConfig {
+            col,
+            row,
+            col_span: Some(col_span),
+            row_span: Some(row_span),
+            alignment: Some(Alignment::Center),
+            vertical_alignment: Some(vertical_alignment),
+            padding_left: None,
+            padding_right: None,
+            truncate: None,
+            wrap_word: None,
+        };
+        let config = TableUserConfig {
+            spanning_cells: Some(vec![
+                span(0, 1, 1, 2, VerticalAlignment::Middle),
+                span(1, 3, 2, 1, VerticalAlignment::Top),
+                span(3, 3, 1, 2, VerticalAlignment::Bottom),
+            ]),
+            ..Default::default()
+        };
+
+        let output = table(&data, Some(&config)).unwrap();
+        assert_eq!(
+            output,
+            [
+                "┌────────┬────┬────┬────┐",
+                "│ Region │ Q1 │ Q2 │ Q3 │",
+                "├────────┼────┼────┼────┤",
+                "│        │ 10 │ 20 │ 30 │",
+                "│ North  ├────┼────┼────┤",
+                "│        │ 11 │ 21 │ 31 │",
+                "├────────┼────┴────┼────┤",
+                "│ South  │  Total  │    │",
+                "├────────┼────┬────┤    │",
+                "│ West   │ 1  │ 2  │ 33 │",
+                "└────────┴────┴────┴────┘",
+            ]
+            .join("\n")
+        );
+        crate::fixtures::check_layout_invariants(&output).unwrap();
+    }
 
-        assert!(result.contains("Name"));
-        assert!(result.contains("Age"));
-        assert!(result.contains("City"));
-        assert!(result.contains("Alice"));
-        assert!(result.contains("Bob"));
-        assert!(result.contains("New York"));
-        assert!(result.contains("London"));
+    #[test]
+    fn test_span_across_an_overridden_row() {
+        let data: Vec<Row> = [["two\nlines", "a"], ["", "b"], ["", "c"]]
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        let config = TableUserConfig {
+            spanning_cells: Some(vec![SpanningCellConfig {
+                col: 0,
+                row: 0,
+                col_span: None,
+                row_span: Some(3),
+                alignment: None,
+                vertical_alignment: Some(VerticalAlignment::Top),
+                padding_left: None,
+                padding_right: None,
+                truncate: None,
+                wrap_word: None,
+            }]),
+            row_overrides: Some(std::collections::BTreeMap::from([(
+                0,
+                RowOverride {
+                    separator_after: Some(false),
+                    ..Default::default()
+                },
+            )])),
+            ..Default::default()
+        };
 
-        println!("Looking for header separator character '═' in output...");
-        let lines: Vec<&str> = result.lines().collect();
-        for (i, line) in lines.iter().enumerate() {
-            println!("Line {i}: {line}");
-            if line.contains("═") {
-                println!("Found header separator at line {i}");
-            }
-        }
-        assert!(
-            result.contains("═"),
-            "Header separator '═' not found in output"
+        let output = table(&data, Some(&config)).unwrap();
+        assert_eq!(
+            output,
+            [
+                "┌───────┬───┐",
+                "│ two   │ a │",
+                "│ lines │ b │",
+                "│       ├───┤",
+                "│       │ c │",
+                "└───────┴───┘",
+            ]
+            .join("\n")
         );
-
-        assert!(result.contains("║"));
-        assert!(result.contains("╔"));
-        assert!(result.contains("╗"));
     }
 
     #[test]
@@ -701,12 +2133,9 @@ mod tests {
                     join_left: Some("+".to_string()),
                     join_right: Some("+".to_string()),
                     join_join: Some("+".to_string()),
+                    ..Default::default()
                 }),
-                columns: None,
-                column_default: None,
-                single_line: None,
-                spanning_cells: None,
-                header: None,
+                ..Default::default()
             })),
             ..Default::default()
         };
@@ -775,23 +2204,21 @@ mod tests {
                     join_left: Some("╞".to_string()),
                     join_right: Some("╡".to_string()),
                     join_join: Some("╪".to_string()),
+                    ..Default::default()
                 }),
                 columns: Some(vec![
                     ColumnUserConfig {
-                        width: Some(8),
+                        width: Some(Width::Chars(8)),
                         alignment: Some(crate::types::Alignment::Center),
                         ..Default::default()
                     },
                     ColumnUserConfig {
-                        width: Some(20),
+                        width: Some(Width::Chars(20)),
                         alignment: Some(crate::types::Alignment::Left),
                         ..Default::default()
                     },
                 ]),
-                column_default: None,
-                single_line: None,
-                spanning_cells: None,
-                header: None,
+                ..Default::default()
             })),
             ..Default::default()
         };
@@ -858,12 +2285,9 @@ mod tests {
                     join_left: Some("╠".to_string()),
                     join_right: Some("╣".to_string()),
                     join_join: Some("╬".to_string()),
+                    ..Default::default()
                 }),
-                columns: None,
-                column_default: None,
-                single_line: None,
-                spanning_cells: None,
-                header: None,
+                ..Default::default()
             })),
             ..Default::default()
         };
@@ -984,14 +2408,14 @@ mod tests {
         let config = TableUserConfig {
             columns: Some(vec![
                 ColumnUserConfig {
-                    width: Some(5),
+                    width: Some(Width::Chars(5)),
                     alignment: Some(crate::types::Alignment::Left),
                     padding_left: Some(1),
                     padding_right: Some(1),
                     ..Default::default()
                 },
                 ColumnUserConfig {
-                    width: Some(5),
+                    width: Some(Width::Chars(5)),
                     alignment: Some(crate::types::Alignment::Right),
                     padding_left: Some(1),
                     padding_right: Some(1),
@@ -1054,6 +2478,7 @@ mod tests {
                     join_left: Some("╠".to_string()),
                     join_right: Some("╣".to_string()),
                     join_join: Some("╬".to_string()),
+                    ..Default::default()
                 }),
                 column_default: Some(ColumnUserConfig {
                     padding_left: Some(1),
@@ -1154,7 +2579,7 @@ mod tests {
         let config = TableUserConfig {
             columns: Some(vec![
                 ColumnUserConfig {
-                    width: Some(8),
+                    width: Some(Width::Chars(8)),
                     alignment: Some(crate::types::Alignment::Left),
                     padding_left: Some(2),
                     padding_right: Some(1),
@@ -1162,7 +2587,7 @@ mod tests {
                     ..Default::default()
                 },
                 ColumnUserConfig {
-                    width: Some(10),
+                    width: Some(Width::Chars(10)),
                     alignment: Some(crate::types::Alignment::Center),
                     padding_left: Some(1),
                     padding_right: Some(1),
@@ -1170,7 +2595,7 @@ mod tests {
                     ..Default::default()
                 },
                 ColumnUserConfig {
-                    width: Some(8),
+                    width: Some(Width::Chars(8)),
                     alignment: Some(crate::types::Alignment::Right),
                     padding_left: Some(1),
                     padding_right: Some(2),
@@ -1178,7 +2603,7 @@ mod tests {
                     ..Default::default()
                 },
                 ColumnUserConfig {
-                    width: Some(12),
+                    width: Some(Width::Chars(12)),
                     alignment: Some(crate::types::Alignment::Left),
                     padding_left: Some(1),
                     padding_right: Some(1),
@@ -1301,17 +2726,17 @@ mod tests {
         let config = crate::types::StreamUserConfig {
             columns: Some(vec![
                 ColumnUserConfig {
-                    width: Some(4),
+                    width: Some(Width::Chars(4)),
                     alignment: Some(crate::types::Alignment::Center),
                     ..Default::default()
                 },
                 ColumnUserConfig {
-                    width: Some(12),
+                    width: Some(Width::Chars(12)),
                     alignment: Some(crate::types::Alignment::Left),
                     ..Default::default()
                 },
                 ColumnUserConfig {
-                    width: Some(8),
+                    width: Some(Width::Chars(8)),
                     alignment: Some(crate::types::Alignment::Right),
                     ..Default::default()
                 },
@@ -1346,4 +2771,494 @@ mod tests {
 
         println!("✅ Streaming with configuration complete!");
     }
+
+    #[test]
+    fn test_table_to_target_strips_ansi_and_fits_width() {
+        let data = vec![vec![
+            "\u{1b}[31mred\u{1b}[0m".to_string(),
+            "a fairly long description".to_string(),
+        ]];
+
+        let mut target = TestTarget::new(false, Some(20));
+        table_to_target(&data, None, &mut target).unwrap();
+
+        assert_eq!(target.writes.len(), 1);
+        let output = &target.writes[0];
+        assert!(!output.contains('\u{1b}'));
+        assert!(
+            output
+                .lines()
+                .all(|line| calculate_display_width(line) == 20)
+        );
+    }
+
+    #[test]
+    fn test_table_to_target_matches_table_when_unconstrained() {
+        let data = vec![vec!["\u{1b}[32mok\u{1b}[0m".to_string(), "b".to_string()]];
+
+        let mut target = StringTarget::new().with_ansi(true);
+        table_to_target(&data, None, &mut target).unwrap();
+
+        assert_eq!(target.into_string(), table(&data, None).unwrap());
+    }
+
+    #[test]
+    fn test_color_mode_strips_data_and_styling() {
+        let data = vec![
+            vec!["\u{1b}[32mok\u{1b}[0m".to_string(), "-4".to_string()],
+            vec!["down".to_string(), "7".to_string()],
+        ];
+        let config = |color_mode| TableUserConfig {
+            color_mode: Some(color_mode),
+            format_rules: Some(vec![FormatRule {
+                column: Some(1),
+                condition: FormatCondition::Negative,
+                color: "31".to_string(),
+                symbol: None,
+            }]),
+            ..Default::default()
+        };
+
+        let colored = table(&data, Some(&config(ColorMode::Preserve))).unwrap();
+        assert!(colored.contains("\u{1b}[32mok") && colored.contains("\u{1b}[31m-4"));
+
+        let plain = table(&data, Some(&config(ColorMode::Strip))).unwrap();
+        assert!(!plain.contains('\u{1b}'), "{plain}");
+        assert!(plain.contains("│ ok   │ ↓ -4 │"), "{plain}");
+
+        // Auto follows the target rather than standard output.
+        let mut target = StringTarget::new().with_ansi(true);
+        table_to_target(&data, Some(&config(ColorMode::Auto)), &mut target).unwrap();
+        assert_eq!(target.as_str(), colored);
+        let mut target = StringTarget::new().with_ansi(true);
+        table_to_target(&data, Some(&config(ColorMode::Strip)), &mut target).unwrap();
+        assert_eq!(target.as_str(), plain);
+
+        assert_eq!("never".parse::<ColorMode>().unwrap(), ColorMode::Strip);
+        assert!("sometimes".parse::<ColorMode>().is_err());
+    }
+
+    #[test]
+    fn test_table_to_writer_matches_table() {
+        let data = vec![
+            vec!["Name".to_string(), "\u{1b}[31mAge\u{1b}[0m".to_string()],
+            vec!["John".to_string(), "30".to_string()],
+        ];
+        let summarized = TableUserConfig {
+            column_summaries: Some(true),
+            ..Default::default()
+        };
+
+        for config in [None, Some(&summarized)] {
+            let mut output = Vec::new();
+            table_to_writer(&data, config, &mut output).unwrap();
+            assert_eq!(
+                String::from_utf8(output).unwrap(),
+                table(&data, config).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_table_with_column_summaries_legend() {
+        let data = vec![
+            vec!["Host".to_string(), "Load".to_string()],
+            vec!["web-01".to_string(), "0.5".to_string()],
+        ];
+        let config = TableUserConfig {
+            column_summaries: Some(true),
+            ..Default::default()
+        };
+
+        let result = table(&data, Some(&config)).unwrap();
+        let lines: Vec<&str> = result.lines().collect();
+
+        assert!(lines[4].starts_with('└'));
+        assert!(lines[5].contains("Column 1: text,"));
+        assert!(result.contains("Column 2:"));
+        let table_width = calculate_display_width(lines[0]);
+        assert!(
+            lines[5..]
+                .iter()
+                .all(|line| calculate_display_width(line) <= table_width)
+        );
+    }
+
+    #[test]
+    fn test_user_config_merge_precedence() {
+        let file = TableUserConfig {
+            single_line: Some(true),
+            table_indent: Some(2),
+            border: Some(BorderUserConfig {
+                body_left: Some("|".to_string()),
+                body_right: Some("|".to_string()),
+                ..Default::default()
+            }),
+            columns: Some(vec![
+                ColumnUserConfig {
+                    width: Some(Width::Chars(10)),
+                    alignment: Some(Alignment::Left),
+                    ..Default::default()
+                },
+                ColumnUserConfig {
+                    width: Some(Width::Chars(5)),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+        let flags = TableUserConfig {
+            single_line: Some(false),
+            border: Some(BorderUserConfig {
+                body_left: Some("#".to_string()),
+                ..Default::default()
+            }),
+            columns: Some(vec![ColumnUserConfig {
+                alignment: Some(Alignment::Right),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        let merged = file.merge(flags);
+        assert_eq!(merged.single_line, Some(false));
+        assert_eq!(merged.table_indent, Some(2));
+
+        let border = merged.border.unwrap();
+        assert_eq!(border.body_left.as_deref(), Some("#"));
+        assert_eq!(border.body_right.as_deref(), Some("|"));
+
+        let columns = merged.columns.unwrap();
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0].width, Some(Width::Chars(10)));
+        assert_eq!(columns[0].alignment, Some(Alignment::Right));
+        assert_eq!(columns[1].width, Some(Width::Chars(5)));
+    }
+
+    #[test]
+    fn test_table_typed_aligns_numbers_and_localizes_dates() {
+        let data = vec![
+            vec![TypedCell::from("item"), "price".into(), "due".into()],
+            vec!["pen".into(), 1.5.into(), TypedCell::DateTime(1_735_603_200)],
+            vec!["notebook".into(), 12.into(), TypedCell::Null],
+        ];
+        let config = TableUserConfig {
+            header: Some(Box::default()),
+            locale: Some("de".to_string()),
+            ..Default::default()
+        };
+
+        let output = table_typed(&data, Some(&config)).unwrap();
+        assert!(output.contains("│ pen      │   1.5 │ 31.12.2024 │"));
+        assert!(output.contains("│ notebook │    12 │            │"));
+    }
+
+    #[test]
+    fn test_first_row_is_header() {
+        let data = vec![
+            vec!["name".to_string(), "qty".to_string()],
+            vec!["pen".to_string(), "3".to_string()],
+            vec!["ink".to_string(), "1".to_string()],
+        ];
+        let config = |first_row_is_header: &str| TableUserConfig {
+            first_row_is_header: Some(first_row_is_header.parse().unwrap()),
+            uppercase_header: Some(true),
+            sort: Some(vec!["1:numeric".parse().unwrap()]),
+            ..Default::default()
+        };
+
+        let output = table(&data, Some(&config("auto"))).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines[1],
+            "│ \u{1b}[1mNAME\u{1b}[0m │ \u{1b}[1mQTY\u{1b}[0m │"
+        );
+        assert!(lines[3].contains("ink") && lines[5].contains("pen"));
+
+        let text_only = vec![data[0].clone(), data[0].clone()];
+        let output = table(&text_only, Some(&config("auto"))).unwrap();
+        assert!(output.contains("│ name │ qty │"));
+
+        let plain = TableUserConfig {
+            header_style: Some(CellStyle::default()),
+            ..config("yes")
+        };
+        let output = table(&data, Some(&plain)).unwrap();
+        assert!(output.lines().nth(1).unwrap().contains("│ NAME │ QTY │"));
+    }
+
+    #[test]
+    fn test_header_groups_span_their_columns() {
+        let data = vec![
+            vec!["Region", "Revenue", "Cost", "Revenue", "Cost"],
+            vec!["North", "10", "4", "12", "5"],
+        ]
+        .into_iter()
+        .map(|row| row.into_iter().map(str::to_string).collect())
+        .collect::<Vec<Row>>();
+        let config = TableUserConfig {
+            header: Some(Box::default()),
+            header_groups: Some(HeaderSpec {
+                rows: vec![vec![
+                    HeaderGroup::new("", 1),
+                    HeaderGroup::new("Q1", 2),
+                    HeaderGroup::new("Q2", 2),
+                ]],
+            }),
+            ..Default::default()
+        };
+
+        let output = table(&data, Some(&config)).unwrap();
+        assert_eq!(
+            output.lines().take(4).collect::<Vec<_>>(),
+            [
+                "┌────────┬────────────────┬────────────────┐",
+                "│        │       Q1       │       Q2       │",
+                "├────────┼─────────┬──────┼─────────┬──────┤",
+                "│ Region │ Revenue │ Cost │ Revenue │ Cost │",
+            ]
+        );
+
+        let fancy = TableUserConfig {
+            theme: Some("fancy".to_string()),
+            ..config.clone()
+        };
+        let output = table(&data, Some(&fancy)).unwrap();
+        assert_eq!(
+            output.lines().nth(2),
+            Some("┝━━━━━━━━┿━━━━━━━━━┯━━━━━━┿━━━━━━━━━┯━━━━━━┥")
+        );
+        let too_wide = TableUserConfig {
+            header_groups: Some(HeaderSpec {
+                rows: vec![vec![HeaderGroup::new("All", 6)]],
+            }),
+            ..Default::default()
+        };
+        assert!(matches!(
+            table(&data, Some(&too_wide)),
+            Err(TableError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_header_groups_without_box_drawing_tees() {
+        let data = vec![vec!["Revenue", "Cost", "Margin"], vec!["10", "4", "6"]]
+            .into_iter()
+            .map(|row| row.into_iter().map(str::to_string).collect())
+            .collect::<Vec<Row>>();
+        let config = TableUserConfig {
+            header: Some(Box::default()),
+            header_groups: Some(HeaderSpec {
+                rows: vec![vec![HeaderGroup::new("Q1", 2), HeaderGroup::new("", 1)]],
+            }),
+            theme: Some("markdown".to_string()),
+            ..Default::default()
+        };
+
+        let output = table(&data, Some(&config)).unwrap();
+        assert_eq!(
+            output.lines().take(3).collect::<Vec<_>>(),
+            [
+                "|       Q1       |        |",
+                "|---------|------|--------|",
+                "| Revenue | Cost | Margin |",
+            ]
+        );
+
+        let ramac = TableUserConfig {
+            theme: None,
+            border: Some(get_border_characters("ramac").unwrap().into()),
+            ..config
+        };
+        let output = table(&data, Some(&ramac)).unwrap();
+        assert_eq!(
+            output.lines().take(4).collect::<Vec<_>>(),
+            [
+                "+----------------+--------+",
+                "|       Q1       |        |",
+                "+---------+------+--------+",
+                "| Revenue | Cost | Margin |",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overflow_modes() {
+        let data = vec![vec![
+            "/home/me/src/app/file.rs".to_string(),
+            "a long description".to_string(),
+            "kept whole".to_string(),
+        ]];
+        let column = |width: usize, overflow_mode: Overflow| ColumnUserConfig {
+            width: Some(Width::Chars(width)),
+            overflow_mode: Some(overflow_mode),
+            ..Default::default()
+        };
+        let config = TableUserConfig {
+            columns: Some(vec![
+                column(
+                    9,
+                    Overflow::Truncate {
+                        position: TruncatePosition::Middle,
+                        ellipsis: Some("…".to_string()),
+                    },
+                ),
+                column(6, Overflow::Clip),
+                ColumnUserConfig {
+                    overflow_mode: Some(Overflow::Expand),
+                    ..Default::default()
+                },
+            ]),
+            max_table_width: Some(30),
+            ..Default::default()
+        };
+
+        let output = table(&data, Some(&config)).unwrap();
+        assert_eq!(
+            output.lines().nth(1),
+            Some("│ /ho….rs │ a lo │ kept whole │")
+        );
+
+        let conflicting = TableUserConfig {
+            columns: Some(vec![ColumnUserConfig {
+                truncate: Some(4),
+                ..column(6, Overflow::Wrap)
+            }]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            table(&data, Some(&conflicting)),
+            Err(TableError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_inherited_overflow_mode_gives_way_to_column_settings() {
+        let data = vec![
+            vec!["cut here".to_string(), "cut here".to_string()],
+            vec!["wrap these".to_string(), "wrap these".to_string()],
+        ];
+        let width = |width: usize| ColumnUserConfig {
+            width: Some(Width::Chars(width)),
+            ..Default::default()
+        };
+        let config = TableUserConfig {
+            column_default: Some(ColumnUserConfig {
+                overflow_mode: Some(Overflow::Clip),
+                ..Default::default()
+            }),
+            columns: Some(vec![
+                ColumnUserConfig {
+                    wrap_word: Some(true),
+                    ..width(8)
+                },
+                width(8),
+            ]),
+            ..Default::default()
+        };
+
+        let output = table(&data, Some(&config)).unwrap();
+        assert_eq!(
+            output.lines().skip(1).take(5).collect::<Vec<_>>(),
+            [
+                "│ cut    │ cut he │",
+                "│ here   │        │",
+                "├────────┼────────┤",
+                "│ wrap   │ wrap t │",
+                "│ these  │        │",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_justified_paragraphs() {
+        let data = vec![vec![
+            "the quick brown fox jumps over the lazy dog\nshort line".to_string(),
+        ]];
+        let config = TableUserConfig {
+            columns: Some(vec![ColumnUserConfig {
+                width: Some(Width::Chars(18)),
+                alignment: Some(Alignment::Justify),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        let output = table(&data, Some(&config)).unwrap();
+        assert_eq!(
+            output.lines().skip(1).take(4).collect::<Vec<_>>(),
+            [
+                "│ the  quick brown │",
+                "│ fox  jumps  over │",
+                "│ the lazy dog     │",
+                "│ short line       │",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_alignment_on_decimal_point() {
+        let data = vec![
+            vec!["Price", "Qty"],
+            vec!["3.5", "12"],
+            vec!["1024.25", "n/a"],
+            vec!["17", "1.125"],
+        ]
+        .into_iter()
+        .map(|row| row.into_iter().map(str::to_string).collect())
+        .collect::<Vec<Row>>();
+        let decimal = |width: Option<usize>| ColumnUserConfig {
+            alignment: Some(Alignment::Char('.')),
+            width: width.map(Width::Chars),
+            ..Default::default()
+        };
+        let config = TableUserConfig {
+            header: Some(Box::default()),
+            columns: Some(vec![decimal(None), decimal(Some(10))]),
+            ..Default::default()
+        };
+
+        let output = table(&data, Some(&config)).unwrap();
+        assert_eq!(
+            output
+                .lines()
+                .skip(1)
+                .step_by(2)
+                .take(4)
+                .collect::<Vec<_>>(),
+            [
+                "│ Price   │ Qty      │",
+                "│    3.5  │   12     │",
+                "│ 1024.25 │  n/a     │",
+                "│   17    │    1.125 │",
+            ]
+        );
+
+        // The fill goes before the integer part, after any sign.
+        let zeros = TableUserConfig {
+            column_default: Some(ColumnUserConfig {
+                pad_char: Some('0'),
+                ..decimal(None)
+            }),
+            ..Default::default()
+        };
+        let signed: Vec<Row> = [["3.5"], ["-12.25"], ["+7"]]
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        let output = table(&signed, Some(&zeros)).unwrap();
+        assert_eq!(
+            output.lines().skip(1).step_by(2).collect::<Vec<_>>(),
+            ["│ 003.5  │", "│ -12.25 │", "│ +07    │"]
+        );
+        assert_eq!(
+            "char:,".parse::<Alignment>().ok(),
+            Some(Alignment::Char(','))
+        );
+        assert_eq!(
+            "decimal".parse::<Alignment>().ok(),
+            Some(Alignment::Char('.'))
+        );
+        assert!("char:ab".parse::<Alignment>().is_err());
+    }
 }
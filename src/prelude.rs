@@ -0,0 +1,59 @@
+//! The stable API in one import: `use ascii_ansi_table::prelude::*;`.
+//!
+//! Everything here follows semver. Module paths such as `core::renderer` or
+//! `features::alignment_processor` are implementation detail and may change
+//! between minor releases.
+
+pub use crate::types::{
+    Alignment, BorderConfig, BorderLine, BorderLineUserConfig, BorderUserConfig, BorderVisibility,
+    CellConfig, CellCoordinates, CellUserConfig, ColorMode, ColumnConfig, ColumnUserConfig,
+    DataHygiene, FormatCondition, FormatRule, LabelPlacement, Overflow, OverflowPolicy,
+    RedundantColumns, Row, RowAnnotation, RowOverride, Severity, SortKey, SortKind, SortOrder,
+    SpanningCellConfig, StreamConfig, StreamUserConfig, TableAlignment, TableConfig, TableError,
+    TableLabel, TableResult, TableUserConfig, TextDirection, TruncatePosition, TypedCell,
+    VerticalAlignment, Width, WordBreakPolicy,
+};
+
+#[cfg(feature = "html")]
+pub use crate::table_to_html;
+pub use crate::types::register_border_style;
+pub use crate::{
+    estimate_render_size, get_border_characters, layout_report, paginate_table,
+    resolve_table_config, table, table_from_iter, table_from_iter_to_writer, table_to_target,
+    table_to_writer, table_typed,
+};
+
+pub use crate::core::layout_report::LayoutReport;
+pub use crate::core::session::{RenderSession, SessionStats};
+pub use crate::core::validator::{ValidationIssue, ValidationResult};
+pub use crate::features::aggregation::{Aggregation, ColumnAggregation};
+pub use crate::features::borders::{BorderStyle, preview_border_style};
+pub use crate::features::computed::{Computation, ComputedColumn};
+pub use crate::features::diff::{RowChange, diff_tables};
+pub use crate::features::estimate::SizeEstimate;
+pub use crate::features::filtering::RowFilter;
+pub use crate::features::harmonize::{TableSection, harmonize_widths};
+pub use crate::features::hygiene::{HygieneIssue, HygieneIssueKind, clean_cell, lint_rows};
+#[cfg(feature = "json")]
+pub use crate::features::json::{JsonColumns, table_from_json, table_from_json_with};
+pub use crate::features::live::LiveTable;
+#[cfg(feature = "memo")]
+pub use crate::features::memo::{RenderCache, RenderedTable};
+pub use crate::features::number_format::{Formatter, Locale, LocaleUserConfig};
+pub use crate::features::render_target::{
+    FileTarget, RenderTarget, StringTarget, TestTarget, TtyTarget,
+};
+#[cfg(feature = "serde-rows")]
+pub use crate::features::serde_rows::table_from_serializable;
+pub use crate::features::spanning::{HeaderGroup, HeaderSpec};
+pub use crate::features::streaming::{FlushPolicy, TableStreamWriter, create_stream};
+pub use crate::features::styling::{CellStyle, StyleCondition, StyleRule};
+pub use crate::features::terminal_profile::{TerminalProfile, set_terminal_profile};
+pub use crate::features::themes::{Theme, get_theme, register_theme};
+pub use crate::features::viewport::{TableView, Viewport};
+
+pub use crate::utils::ansi::{apply_color_mode, calculate_display_width, strip_ansi_sequences};
+#[cfg(feature = "formats-csv")]
+pub use crate::utils::csv::{QuotePolicy, table_to_csv};
+#[cfg(feature = "formats-markdown")]
+pub use crate::utils::markdown::table_to_markdown;
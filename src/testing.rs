@@ -0,0 +1,316 @@
+//! Deterministic sample data for demos, benchmarks, fuzzers and downstream
+//! tests. The same arguments always produce the same rows, on every platform
+//! and release, so snapshots and benchmark baselines stay comparable.
+
+use crate::features::streaming::TableStream;
+use crate::types::{ColumnUserConfig, Row, StreamUserConfig, TableResult, TableUserConfig, Width};
+
+const HEADERS: &[&str] = &[
+    "ID", "Product", "Price", "Status", "Quantity", "Region", "Updated", "Notes",
+];
+
+const PRODUCTS: &[&str] = &[
+    "Gaming\nLaptop",
+    "Wireless\nMouse",
+    "Mechanical\nKeyboard",
+    "4K\nMonitor",
+    "Bluetooth\nSpeakers",
+    "HD\nWebcam",
+    "Noise-Cancelling\nHeadphones",
+    "Android\nTablet",
+    "Smartphone\nPro",
+    "Fast\nCharger",
+    "USB-C\nCable",
+    "Power\nAdapter",
+    "WiFi\nRouter",
+    "Network\nSwitch",
+    "Laser\nPrinter",
+];
+
+const UNICODE_PRODUCTS: &[&str] = &[
+    "Café\nMachine",
+    "機械式\nキーボード",
+    "무선\n마우스",
+    "Écran\n4K",
+    "Kopfhörer\nPro",
+];
+
+// Status, symbol and SGR color, as drawn by the CLI demos.
+const STATUSES: &[(&str, &str, &str)] = &[
+    ("Active", "✓", "32"),
+    ("Sold", "✗", "31"),
+    ("Pending", "⚠", "33"),
+    ("Shipped", "🚚", "36"),
+    ("Delivered", "📦", "35"),
+];
+
+const REGIONS: &[&str] = &["North", "South", "East", "West", "Central"];
+const UNICODE_REGIONS: &[&str] = &["Zürich", "São Paulo", "東京", "서울", "Kraków"];
+
+const WORDS: &[&str] = &[
+    "restock",
+    "fragile",
+    "priority",
+    "backorder",
+    "gift",
+    "wrapped",
+    "returned",
+    "express",
+    "bulk",
+    "discount",
+];
+
+const HEADER_COLORS: &[&str] = &["36", "32", "33", "35", "34", "37"];
+
+/// SplitMix64: tiny, fast and well distributed, and fixed here so the rows
+/// never change with a dependency upgrade.
+#[derive(Debug, Clone)]
+pub struct SampleRng(u64);
+
+impl SampleRng {
+    pub fn new(seed: u64) -> Self {
+        SampleRng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`; `bound` must not be zero.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+fn paint(text: &str, sgr: &str, ansi: bool) -> String {
+    if ansi {
+        format!("\u{1b}[{sgr}m{text}\u{1b}[0m")
+    } else {
+        text.to_string()
+    }
+}
+
+fn header(column: usize, ansi: bool) -> String {
+    let name = HEADERS
+        .get(column)
+        .map_or_else(|| format!("Column {}", column + 1), |name| name.to_string());
+    let color = HEADER_COLORS[column % HEADER_COLORS.len()];
+    paint(&name, &format!("1;{color}"), ansi)
+}
+
+fn cell(rng: &mut SampleRng, row: usize, column: usize, ansi: bool, unicode: bool) -> String {
+    match column {
+        0 => paint(&(row + 1).to_string(), "37", ansi),
+        1 => {
+            let product = if unicode && rng.below(4) == 0 {
+                rng.choose(UNICODE_PRODUCTS)
+            } else {
+                rng.choose(PRODUCTS)
+            };
+            paint(product, "34", ansi)
+        }
+        2 => {
+            let price = format!("${}", rng.below(1_000) + 99);
+            paint(&price, "32", ansi)
+        }
+        3 => {
+            let (status, symbol, color) = rng.choose(STATUSES);
+            let text = if unicode {
+                format!("{symbol} {status}")
+            } else {
+                status.to_string()
+            };
+            paint(&text, color, ansi)
+        }
+        4 => rng.below(500).to_string(),
+        5 => {
+            let regions = if unicode { UNICODE_REGIONS } else { REGIONS };
+            rng.choose(regions).to_string()
+        }
+        6 => format!("2024-{:02}-{:02}", rng.below(12) + 1, rng.below(28) + 1),
+        _ => {
+            let words: Vec<&str> = (0..rng.below(6) + 1).map(|_| *rng.choose(WORDS)).collect();
+            words.join(" ")
+        }
+    }
+}
+
+/// A header row followed by `rows` rows of `cols` columns of product-inventory
+/// data, generated from `seed`. Columns past the eighth hold free text.
+///
+/// `ansi` colors every cell the way the CLI demos do; `unicode` mixes in
+/// accented, CJK and Hangul text plus symbol and emoji status markers. With
+/// both off every cell is printable ASCII, though products still span two
+/// lines.
+pub fn sample_rows(rows: usize, cols: usize, seed: u64, ansi: bool, unicode: bool) -> Vec<Row> {
+    let mut rng = SampleRng::new(seed);
+    let mut data = Vec::with_capacity(rows + 1);
+
+    data.push((0..cols).map(|column| header(column, ansi)).collect());
+    for row in 0..rows {
+        data.push(
+            (0..cols)
+                .map(|column| cell(&mut rng, row, column, ansi, unicode))
+                .collect(),
+        );
+    }
+
+    data
+}
+
+/// Writes every row of `data` to a `TableStream` and finalizes it, returning
+/// the whole output and the stream's column widths.
+pub fn render_streamed(
+    data: &[Row],
+    config: Option<&StreamUserConfig>,
+) -> TableResult<(String, Vec<usize>)> {
+    let mut stream = TableStream::new(config.cloned());
+    let mut output = String::new();
+    for row in data {
+        output.push_str(&stream.write_row(row)?);
+    }
+    output.push_str(&stream.finalize());
+    Ok((output, stream.column_widths().to_vec()))
+}
+
+/// Panics, printing both tables, unless streaming `data` with `config` draws
+/// exactly what `table` draws for the same rows and settings, given the column
+/// widths the stream chose. Both rejecting the data also counts as a match.
+pub fn assert_stream_matches_batch(data: &[Row], config: Option<&StreamUserConfig>) {
+    let streamed = render_streamed(data, config);
+    let widths = streamed
+        .as_ref()
+        .map(|(_, widths)| widths.clone())
+        .unwrap_or_default();
+
+    let user = config.cloned().unwrap_or_default();
+    let columns = widths
+        .iter()
+        .enumerate()
+        .map(|(col_idx, &width)| ColumnUserConfig {
+            width: Some(Width::Chars(width)),
+            ..user
+                .columns
+                .as_ref()
+                .and_then(|columns| columns.get(col_idx))
+                .cloned()
+                .unwrap_or_default()
+        })
+        .collect();
+    let batch_config = TableUserConfig {
+        border: user.border,
+        columns: Some(columns),
+        column_default: user.column_default,
+        single_line: user.single_line,
+        ..Default::default()
+    };
+    let batch = crate::table(data, Some(&batch_config));
+
+    match (streamed, batch) {
+        (Ok((streamed, _)), Ok(batch)) => assert!(
+            streamed == batch,
+            "streamed output differs from table output\n--- streamed ---\n{streamed}\n--- table ---\n{batch}"
+        ),
+        (Err(_), Err(_)) => {}
+        (streamed, batch) => panic!(
+            "streaming and table disagree on the data: streamed {:?}, table {:?}",
+            streamed.map(|(output, _)| output),
+            batch
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::check_layout_invariants;
+    use crate::table;
+    use crate::utils::ansi::strip_ansi_sequences;
+
+    #[test]
+    fn test_sample_rows_is_deterministic() {
+        let rows = sample_rows(20, 10, 7, true, true);
+        assert_eq!(rows.len(), 21);
+        assert!(rows.iter().all(|row| row.len() == 10));
+        assert_eq!(rows, sample_rows(20, 10, 7, true, true));
+        assert_ne!(rows, sample_rows(20, 10, 8, true, true));
+        assert_eq!(strip_ansi_sequences(&rows[0][9]), "Column 10");
+        assert_eq!(strip_ansi_sequences(&rows[3][0]), "3");
+    }
+
+    #[test]
+    fn test_sample_rows_plain_and_renderable() {
+        let plain = sample_rows(50, 9, 1, false, false);
+        assert!(plain.iter().flatten().all(|cell| {
+            cell.chars()
+                .all(|c| c == '\n' || c.is_ascii_graphic() || c == ' ')
+        }));
+
+        for seed in 0..4 {
+            let rows = sample_rows(10, 8, seed, true, true);
+            let output = table(&rows, None).unwrap();
+            check_layout_invariants(&output).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_stream_matches_batch() {
+        use crate::types::{Alignment, VerticalAlignment};
+
+        let configs = [
+            None,
+            Some(StreamUserConfig {
+                column_default: Some(ColumnUserConfig {
+                    alignment: Some(Alignment::Right),
+                    padding_left: Some(2),
+                    width: Some(Width::Chars(9)),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Some(StreamUserConfig {
+                column_default: Some(ColumnUserConfig {
+                    truncate: Some(6),
+                    wrap_word: Some(true),
+                    vertical_alignment: Some(VerticalAlignment::Bottom),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Some(StreamUserConfig {
+                border: Some(crate::get_border_characters("ramac").unwrap().into()),
+                columns: Some(vec![
+                    ColumnUserConfig {
+                        width: Some(Width::Chars(4)),
+                        ..Default::default()
+                    },
+                    ColumnUserConfig {
+                        alignment: Some(Alignment::Center),
+                        ..Default::default()
+                    },
+                ]),
+                ..Default::default()
+            }),
+        ];
+
+        for seed in 0..3 {
+            for (ansi, unicode) in [(false, false), (true, true)] {
+                let rows = sample_rows(6, 4, seed, ansi, unicode);
+                for config in &configs {
+                    assert_stream_matches_batch(&rows, config.as_ref());
+                }
+            }
+        }
+
+        assert_stream_matches_batch(&[vec!["bell\u{7}".to_string()]], None);
+        assert_stream_matches_batch(&[], None);
+    }
+}
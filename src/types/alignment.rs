@@ -1,13 +1,49 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
-#[serde(rename_all = "lowercase")]
+/// Written as its lowercase name, e.g. `"center"`, in config files and on the
+/// command line alike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Alignment {
     #[default]
     Left,
     Right,
     Center,
     Justify,
+    /// Lines the column's body and footer cells up on the first occurrence of
+    /// the character, typically `.` so numbers align on their decimal point.
+    /// Cells without it line up as if it followed their text; header cells
+    /// stay flush left. Written `char:.`, or `decimal` for `.`.
+    Char(char),
+}
+
+// The names serde suggests from when an alignment is misspelled.
+const ALIGNMENT_NAMES: &[&str] = &["left", "right", "center", "justify", "decimal", "char:"];
+
+impl Serialize for Alignment {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Alignment {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AlignmentVisitor;
+
+        impl serde::de::Visitor<'_> for AlignmentVisitor {
+            type Value = Alignment;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("an alignment name")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, name: &str) -> Result<Alignment, E> {
+                name.parse()
+                    .map_err(|_| E::unknown_variant(name, ALIGNMENT_NAMES))
+            }
+        }
+
+        deserializer.deserialize_str(AlignmentVisitor)
+    }
 }
 
 impl std::fmt::Display for Alignment {
@@ -17,6 +53,7 @@ impl std::fmt::Display for Alignment {
             Alignment::Right => write!(f, "right"),
             Alignment::Center => write!(f, "center"),
             Alignment::Justify => write!(f, "justify"),
+            Alignment::Char(c) => write!(f, "char:{c}"),
         }
     }
 }
@@ -30,12 +67,19 @@ impl std::str::FromStr for Alignment {
             "right" => Ok(Alignment::Right),
             "center" => Ok(Alignment::Center),
             "justify" => Ok(Alignment::Justify),
-            _ => Err(crate::types::TableError::InvalidAlignment),
+            "decimal" => Ok(Alignment::Char('.')),
+            _ => {
+                let mut chars = s.strip_prefix("char:").unwrap_or_default().chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(Alignment::Char(c)),
+                    _ => Err(crate::types::TableError::InvalidAlignment),
+                }
+            }
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum VerticalAlignment {
     #[default]
@@ -66,3 +110,76 @@ impl std::str::FromStr for VerticalAlignment {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TableAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl std::fmt::Display for TableAlignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableAlignment::Left => write!(f, "left"),
+            TableAlignment::Center => write!(f, "center"),
+            TableAlignment::Right => write!(f, "right"),
+        }
+    }
+}
+
+impl std::str::FromStr for TableAlignment {
+    type Err = crate::types::TableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "left" => Ok(TableAlignment::Left),
+            "center" => Ok(TableAlignment::Center),
+            "right" => Ok(TableAlignment::Right),
+            _ => Err(crate::types::TableError::InvalidAlignment),
+        }
+    }
+}
+
+/// The writing direction of a column's text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TextDirection {
+    #[default]
+    Ltr,
+    /// Mirrors the alignment and padding: `Left` aligns text to the right
+    /// edge, the side right-to-left text starts from.
+    Rtl,
+    /// `Rtl` for lines whose first letter is Arabic, Hebrew or another
+    /// right-to-left script, `Ltr` otherwise.
+    Auto,
+}
+
+impl std::fmt::Display for TextDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextDirection::Ltr => write!(f, "ltr"),
+            TextDirection::Rtl => write!(f, "rtl"),
+            TextDirection::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+impl std::str::FromStr for TextDirection {
+    type Err = crate::types::TableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ltr" => Ok(TextDirection::Ltr),
+            "rtl" => Ok(TextDirection::Rtl),
+            "auto" => Ok(TextDirection::Auto),
+            _ => Err(crate::types::TableError::parse(
+                "direction",
+                s,
+                "expected ltr, rtl or auto",
+            )),
+        }
+    }
+}
@@ -1,6 +1,23 @@
+use crate::utils::ansi::calculate_display_width;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard, OnceLock, PoisonError};
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// The names `get_border_characters` always knows, in the order
+/// `border_style_names` lists them.
+pub const BUILTIN_BORDER_STYLES: &[&str] = &[
+    "honeywell",
+    "norc",
+    "ramac",
+    "void",
+    "rounded",
+    "double",
+    "heavy",
+    "dotted",
+    "markdown",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct BorderConfig {
     pub top_body: String,
     pub top_join: String,
@@ -18,6 +35,189 @@ pub struct BorderConfig {
     pub join_left: String,
     pub join_right: String,
     pub join_join: String,
+    /// The line below the header; the `join_*` characters with `header_join`
+    /// as its horizontal when unset.
+    #[serde(default)]
+    pub header_separator: Option<BorderLine>,
+    /// The line above the footer; drawn like the header separator when unset.
+    #[serde(default)]
+    pub footer_separator: Option<BorderLine>,
+    /// Draws the lines between body rows where `draw_horizontal_line` does;
+    /// `row_overrides` can still force one.
+    #[serde(default = "draws_body_row_separators")]
+    pub body_row_separators: bool,
+}
+
+fn draws_body_row_separators() -> bool {
+    true
+}
+
+impl BorderConfig {
+    pub fn top_line(&self) -> BorderLine {
+        BorderLine::new(
+            &self.top_left,
+            &self.top_body,
+            &self.top_join,
+            &self.top_right,
+        )
+    }
+
+    pub fn header_line(&self) -> BorderLine {
+        self.header_separator.clone().unwrap_or_else(|| {
+            BorderLine::new(
+                &self.join_left,
+                &self.header_join,
+                &self.join_join,
+                &self.join_right,
+            )
+        })
+    }
+
+    pub fn body_row_line(&self) -> BorderLine {
+        BorderLine::new(
+            &self.join_left,
+            &self.join_body,
+            &self.join_join,
+            &self.join_right,
+        )
+    }
+
+    pub fn footer_line(&self) -> BorderLine {
+        self.footer_separator
+            .clone()
+            .unwrap_or_else(|| self.header_line())
+    }
+
+    pub fn bottom_line(&self) -> BorderLine {
+        BorderLine::new(
+            &self.bottom_left,
+            &self.bottom_body,
+            &self.bottom_join,
+            &self.bottom_right,
+        )
+    }
+}
+
+/// The characters of one horizontal border line.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BorderLine {
+    pub left: String,
+    /// Repeated across each column.
+    pub body: String,
+    /// Where a column divider meets the line.
+    pub join: String,
+    pub right: String,
+}
+
+impl BorderLine {
+    pub fn new(left: &str, body: &str, join: &str, right: &str) -> Self {
+        BorderLine {
+            left: left.to_string(),
+            body: body.to_string(),
+            join: join.to_string(),
+            right: right.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub struct BorderLineUserConfig {
+    pub left: Option<String>,
+    pub body: Option<String>,
+    pub join: Option<String>,
+    pub right: Option<String>,
+}
+
+impl BorderLineUserConfig {
+    /// Layers `other` over `self`: every character set in `other` wins.
+    pub fn merge(self, other: BorderLineUserConfig) -> BorderLineUserConfig {
+        BorderLineUserConfig {
+            left: other.left.or(self.left),
+            body: other.body.or(self.body),
+            join: other.join.or(self.join),
+            right: other.right.or(self.right),
+        }
+    }
+
+    pub fn merge_with_default(self, default: &BorderLine) -> BorderLine {
+        BorderLine {
+            left: self.left.unwrap_or_else(|| default.left.clone()),
+            body: self.body.unwrap_or_else(|| default.body.clone()),
+            join: self.join.unwrap_or_else(|| default.join.clone()),
+            right: self.right.unwrap_or_else(|| default.right.clone()),
+        }
+    }
+}
+
+impl From<BorderLine> for BorderLineUserConfig {
+    fn from(line: BorderLine) -> Self {
+        Self {
+            left: Some(line.left),
+            body: Some(line.body),
+            join: Some(line.join),
+            right: Some(line.right),
+        }
+    }
+}
+
+/// Which parts of the border are drawn. Hidden parts keep their width, so
+/// hiding the column separators leaves spaces between the columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BorderVisibility {
+    /// The top and bottom lines and the left and right edges.
+    pub outer: bool,
+    /// The vertical lines between columns.
+    pub column_separators: bool,
+    /// The lines between rows, including those below the header and above
+    /// the footer. `row_overrides` can still force one.
+    pub row_separators: bool,
+}
+
+impl Default for BorderVisibility {
+    fn default() -> Self {
+        Self {
+            outer: true,
+            column_separators: true,
+            row_separators: true,
+        }
+    }
+}
+
+impl BorderVisibility {
+    /// `border` with the characters of hidden edges and column separators
+    /// removed. Hidden lines are left out by the renderer instead.
+    pub fn apply(&self, mut border: BorderConfig) -> BorderConfig {
+        if !self.column_separators {
+            let (header, footer) = (border.header_line(), border.footer_line());
+            border.header_separator = Some(BorderLine {
+                join: header.body.clone(),
+                ..header
+            });
+            border.footer_separator = Some(BorderLine {
+                join: footer.body.clone(),
+                ..footer
+            });
+            border.top_join = border.top_body.clone();
+            border.join_join = border.join_body.clone();
+            border.bottom_join = border.bottom_body.clone();
+            border.body_join = " ".repeat(calculate_display_width(&border.body_join));
+        }
+        if !self.outer {
+            for line in [&mut border.header_separator, &mut border.footer_separator]
+                .into_iter()
+                .flatten()
+            {
+                line.left.clear();
+                line.right.clear();
+            }
+            border.body_left.clear();
+            border.body_right.clear();
+            border.join_left.clear();
+            border.join_right.clear();
+        }
+        border
+    }
 }
 
 impl Default for BorderConfig {
@@ -26,7 +226,7 @@ impl Default for BorderConfig {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub struct BorderUserConfig {
     pub top_body: Option<String>,
     pub top_join: Option<String>,
@@ -44,11 +244,56 @@ pub struct BorderUserConfig {
     pub join_left: Option<String>,
     pub join_right: Option<String>,
     pub join_join: Option<String>,
+    /// The border line by line. Each line is layered over the flat
+    /// characters above that draw it, so `top` overrides `top_*` and
+    /// `body_row_separator` overrides `join_*` and `join_body`, without
+    /// changing the header and footer separators that share `join_*`.
+    pub top: Option<BorderLineUserConfig>,
+    pub header_separator: Option<BorderLineUserConfig>,
+    pub body_row_separator: Option<BorderLineUserConfig>,
+    pub footer_separator: Option<BorderLineUserConfig>,
+    pub bottom: Option<BorderLineUserConfig>,
+    pub body_row_separators: Option<bool>,
 }
 
 impl BorderUserConfig {
+    /// Layers `other` over `self`: every character set in `other` wins.
+    pub fn merge(self, other: BorderUserConfig) -> BorderUserConfig {
+        BorderUserConfig {
+            top_body: other.top_body.or(self.top_body),
+            top_join: other.top_join.or(self.top_join),
+            top_left: other.top_left.or(self.top_left),
+            top_right: other.top_right.or(self.top_right),
+            bottom_body: other.bottom_body.or(self.bottom_body),
+            bottom_join: other.bottom_join.or(self.bottom_join),
+            bottom_left: other.bottom_left.or(self.bottom_left),
+            bottom_right: other.bottom_right.or(self.bottom_right),
+            body_left: other.body_left.or(self.body_left),
+            body_right: other.body_right.or(self.body_right),
+            body_join: other.body_join.or(self.body_join),
+            header_join: other.header_join.or(self.header_join),
+            join_body: other.join_body.or(self.join_body),
+            join_left: other.join_left.or(self.join_left),
+            join_right: other.join_right.or(self.join_right),
+            join_join: other.join_join.or(self.join_join),
+            top: merge_line(self.top, other.top),
+            header_separator: merge_line(self.header_separator, other.header_separator),
+            body_row_separator: merge_line(self.body_row_separator, other.body_row_separator),
+            footer_separator: merge_line(self.footer_separator, other.footer_separator),
+            bottom: merge_line(self.bottom, other.bottom),
+            body_row_separators: other.body_row_separators.or(self.body_row_separators),
+        }
+    }
+
     pub fn merge_with_default(self, default: &BorderConfig) -> BorderConfig {
-        BorderConfig {
+        let sections = BorderSections {
+            top: self.top.clone(),
+            header_separator: self.header_separator.clone(),
+            body_row_separator: self.body_row_separator.clone(),
+            footer_separator: self.footer_separator.clone(),
+            bottom: self.bottom.clone(),
+        };
+        let flat = BorderConfig {
             top_body: self.top_body.unwrap_or_else(|| default.top_body.clone()),
             top_join: self.top_join.unwrap_or_else(|| default.top_join.clone()),
             top_left: self.top_left.unwrap_or_else(|| default.top_left.clone()),
@@ -79,8 +324,142 @@ impl BorderUserConfig {
                 .join_right
                 .unwrap_or_else(|| default.join_right.clone()),
             join_join: self.join_join.unwrap_or_else(|| default.join_join.clone()),
+            header_separator: default.header_separator.clone(),
+            footer_separator: default.footer_separator.clone(),
+            body_row_separators: self
+                .body_row_separators
+                .unwrap_or(default.body_row_separators),
+        };
+        sections.apply(flat)
+    }
+}
+
+fn merge_line(
+    base: Option<BorderLineUserConfig>,
+    over: Option<BorderLineUserConfig>,
+) -> Option<BorderLineUserConfig> {
+    match (base, over) {
+        (Some(base), Some(over)) => Some(base.merge(over)),
+        (base, over) => over.or(base),
+    }
+}
+
+// The sectioned settings of a `BorderUserConfig`, applied over its resolved
+// flat characters.
+struct BorderSections {
+    top: Option<BorderLineUserConfig>,
+    header_separator: Option<BorderLineUserConfig>,
+    body_row_separator: Option<BorderLineUserConfig>,
+    footer_separator: Option<BorderLineUserConfig>,
+    bottom: Option<BorderLineUserConfig>,
+}
+
+impl BorderSections {
+    fn apply(self, mut border: BorderConfig) -> BorderConfig {
+        if let Some(top) = self.top {
+            let line = top.merge_with_default(&border.top_line());
+            border.top_left = line.left;
+            border.top_body = line.body;
+            border.top_join = line.join;
+            border.top_right = line.right;
         }
+        if let Some(bottom) = self.bottom {
+            let line = bottom.merge_with_default(&border.bottom_line());
+            border.bottom_left = line.left;
+            border.bottom_body = line.body;
+            border.bottom_join = line.join;
+            border.bottom_right = line.right;
+        }
+
+        // Resolved before `body_row_separator` replaces the `join_*`
+        // characters they fall back to. The footer separator is layered over
+        // the header one, as an unset footer separator draws the header one.
+        border.header_separator = match self.header_separator {
+            Some(header) => Some(header.merge_with_default(&border.header_line())),
+            None if self.body_row_separator.is_some() => Some(border.header_line()),
+            None => border.header_separator.take(),
+        };
+        if let Some(footer) = self.footer_separator {
+            border.footer_separator = Some(footer.merge_with_default(&border.footer_line()));
+        }
+
+        if let Some(body_row) = self.body_row_separator {
+            let line = body_row.merge_with_default(&border.body_row_line());
+            border.join_left = line.left;
+            border.join_body = line.body;
+            border.join_join = line.join;
+            border.join_right = line.right;
+        }
+        border
+    }
+}
+
+impl From<BorderConfig> for BorderUserConfig {
+    fn from(border: BorderConfig) -> Self {
+        Self {
+            top_body: Some(border.top_body),
+            top_join: Some(border.top_join),
+            top_left: Some(border.top_left),
+            top_right: Some(border.top_right),
+            bottom_body: Some(border.bottom_body),
+            bottom_join: Some(border.bottom_join),
+            bottom_left: Some(border.bottom_left),
+            bottom_right: Some(border.bottom_right),
+            body_left: Some(border.body_left),
+            body_right: Some(border.body_right),
+            body_join: Some(border.body_join),
+            header_join: Some(border.header_join),
+            join_body: Some(border.join_body),
+            join_left: Some(border.join_left),
+            join_right: Some(border.join_right),
+            join_join: Some(border.join_join),
+            top: None,
+            header_separator: border.header_separator.map(BorderLineUserConfig::from),
+            body_row_separator: None,
+            footer_separator: border.footer_separator.map(BorderLineUserConfig::from),
+            bottom: None,
+            body_row_separators: Some(border.body_row_separators),
+        }
+    }
+}
+
+// Styles added with `register_border_style`. A panic elsewhere cannot leave
+// the map half-updated, so a poisoned lock is still safe to use.
+fn custom_border_styles() -> MutexGuard<'static, BTreeMap<String, BorderConfig>> {
+    static STYLES: OnceLock<Mutex<BTreeMap<String, BorderConfig>>> = OnceLock::new();
+    STYLES
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Makes `border` available by `name` to `get_border_characters` for the rest
+/// of the process, replacing an earlier style of that name. Built-in names
+/// cannot be replaced.
+pub fn register_border_style(
+    name: impl Into<String>,
+    border: BorderConfig,
+) -> Result<(), crate::types::TableError> {
+    let name = name.into();
+    if BUILTIN_BORDER_STYLES.contains(&name.as_str()) {
+        return Err(crate::types::TableError::parse(
+            "border style",
+            name,
+            "built-in styles cannot be replaced",
+        ));
     }
+    custom_border_styles().insert(name, border);
+    Ok(())
+}
+
+/// Every name `get_border_characters` accepts: the built-in styles, then the
+/// registered ones in name order.
+pub fn border_style_names() -> Vec<String> {
+    BUILTIN_BORDER_STYLES
+        .iter()
+        .map(|name| name.to_string())
+        .chain(custom_border_styles().keys().cloned())
+        .collect()
 }
 
 pub fn get_border_characters(name: &str) -> Result<BorderConfig, crate::types::TableError> {
@@ -102,6 +481,9 @@ pub fn get_border_characters(name: &str) -> Result<BorderConfig, crate::types::T
             join_left: "├".to_string(),
             join_right: "┤".to_string(),
             join_join: "┼".to_string(),
+            header_separator: None,
+            footer_separator: None,
+            body_row_separators: true,
         }),
         "norc" => Ok(BorderConfig {
             top_body: "─".to_string(),
@@ -120,6 +502,9 @@ pub fn get_border_characters(name: &str) -> Result<BorderConfig, crate::types::T
             join_left: "├".to_string(),
             join_right: "┤".to_string(),
             join_join: "┼".to_string(),
+            header_separator: None,
+            footer_separator: None,
+            body_row_separators: true,
         }),
         "ramac" => Ok(BorderConfig {
             top_body: "-".to_string(),
@@ -138,6 +523,9 @@ pub fn get_border_characters(name: &str) -> Result<BorderConfig, crate::types::T
             join_left: "+".to_string(),
             join_right: "+".to_string(),
             join_join: "+".to_string(),
+            header_separator: None,
+            footer_separator: None,
+            body_row_separators: true,
         }),
         "void" => Ok(BorderConfig {
             top_body: "".to_string(),
@@ -156,9 +544,123 @@ pub fn get_border_characters(name: &str) -> Result<BorderConfig, crate::types::T
             join_left: "".to_string(),
             join_right: "".to_string(),
             join_join: "".to_string(),
+            header_separator: None,
+            footer_separator: None,
+            body_row_separators: true,
+        }),
+        "rounded" => Ok(BorderConfig {
+            top_body: "─".to_string(),
+            top_join: "┬".to_string(),
+            top_left: "╭".to_string(),
+            top_right: "╮".to_string(),
+            bottom_body: "─".to_string(),
+            bottom_join: "┴".to_string(),
+            bottom_left: "╰".to_string(),
+            bottom_right: "╯".to_string(),
+            body_left: "│".to_string(),
+            body_right: "│".to_string(),
+            body_join: "│".to_string(),
+            header_join: "─".to_string(),
+            join_body: "─".to_string(),
+            join_left: "├".to_string(),
+            join_right: "┤".to_string(),
+            join_join: "┼".to_string(),
+            header_separator: None,
+            footer_separator: None,
+            body_row_separators: true,
+        }),
+        "double" => Ok(BorderConfig {
+            top_body: "═".to_string(),
+            top_join: "╦".to_string(),
+            top_left: "╔".to_string(),
+            top_right: "╗".to_string(),
+            bottom_body: "═".to_string(),
+            bottom_join: "╩".to_string(),
+            bottom_left: "╚".to_string(),
+            bottom_right: "╝".to_string(),
+            body_left: "║".to_string(),
+            body_right: "║".to_string(),
+            body_join: "║".to_string(),
+            header_join: "═".to_string(),
+            join_body: "═".to_string(),
+            join_left: "╠".to_string(),
+            join_right: "╣".to_string(),
+            join_join: "╬".to_string(),
+            header_separator: None,
+            footer_separator: None,
+            body_row_separators: true,
+        }),
+        "heavy" => Ok(BorderConfig {
+            top_body: "━".to_string(),
+            top_join: "┳".to_string(),
+            top_left: "┏".to_string(),
+            top_right: "┓".to_string(),
+            bottom_body: "━".to_string(),
+            bottom_join: "┻".to_string(),
+            bottom_left: "┗".to_string(),
+            bottom_right: "┛".to_string(),
+            body_left: "┃".to_string(),
+            body_right: "┃".to_string(),
+            body_join: "┃".to_string(),
+            header_join: "━".to_string(),
+            join_body: "━".to_string(),
+            join_left: "┣".to_string(),
+            join_right: "┫".to_string(),
+            join_join: "╋".to_string(),
+            header_separator: None,
+            footer_separator: None,
+            body_row_separators: true,
+        }),
+        "dotted" => Ok(BorderConfig {
+            top_body: "┄".to_string(),
+            top_join: "┬".to_string(),
+            top_left: "┌".to_string(),
+            top_right: "┐".to_string(),
+            bottom_body: "┄".to_string(),
+            bottom_join: "┴".to_string(),
+            bottom_left: "└".to_string(),
+            bottom_right: "┘".to_string(),
+            body_left: "┆".to_string(),
+            body_right: "┆".to_string(),
+            body_join: "┆".to_string(),
+            header_join: "┄".to_string(),
+            join_body: "┄".to_string(),
+            join_left: "├".to_string(),
+            join_right: "┤".to_string(),
+            join_join: "┼".to_string(),
+            header_separator: None,
+            footer_separator: None,
+            body_row_separators: true,
+        }),
+        // Rows and the line below the header parse as a Markdown table; there
+        // are no top and bottom lines, nor lines between body rows.
+        "markdown" => Ok(BorderConfig {
+            top_body: "".to_string(),
+            top_join: "".to_string(),
+            top_left: "".to_string(),
+            top_right: "".to_string(),
+            bottom_body: "".to_string(),
+            bottom_join: "".to_string(),
+            bottom_left: "".to_string(),
+            bottom_right: "".to_string(),
+            body_left: "|".to_string(),
+            body_right: "|".to_string(),
+            body_join: "|".to_string(),
+            header_join: "-".to_string(),
+            join_body: "-".to_string(),
+            join_left: "|".to_string(),
+            join_right: "|".to_string(),
+            join_join: "|".to_string(),
+            header_separator: None,
+            footer_separator: None,
+            body_row_separators: false,
+        }),
+        _ => custom_border_styles().get(name).cloned().ok_or_else(|| {
+            crate::types::TableError::parse(
+                "border style",
+                name,
+                "expected honeywell, norc, ramac, void, rounded, double, heavy, dotted, markdown or a registered style",
+            )
         }),
-        _ => Err(crate::types::TableError::InvalidConfig(format!(
-            "Unknown border style: {name}"
-        ))),
     }
 }
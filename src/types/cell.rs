@@ -0,0 +1,68 @@
+/// A cell that keeps the type of its value, for `table_typed`. Numbers are
+/// right-aligned, dates written in the table's locale and nulls as its
+/// `null_placeholder`, unless the column's config says otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedCell {
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    /// Seconds since 1970-01-01 UTC.
+    DateTime(i64),
+    Null,
+}
+
+impl TypedCell {
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, TypedCell::Int(_) | TypedCell::Float(_))
+    }
+}
+
+impl From<&str> for TypedCell {
+    fn from(value: &str) -> Self {
+        TypedCell::Str(value.to_string())
+    }
+}
+
+impl From<String> for TypedCell {
+    fn from(value: String) -> Self {
+        TypedCell::Str(value)
+    }
+}
+
+impl From<i64> for TypedCell {
+    fn from(value: i64) -> Self {
+        TypedCell::Int(value)
+    }
+}
+
+impl From<i32> for TypedCell {
+    fn from(value: i32) -> Self {
+        TypedCell::Int(value.into())
+    }
+}
+
+impl From<u32> for TypedCell {
+    fn from(value: u32) -> Self {
+        TypedCell::Int(value.into())
+    }
+}
+
+impl From<f64> for TypedCell {
+    fn from(value: f64) -> Self {
+        TypedCell::Float(value)
+    }
+}
+
+impl From<bool> for TypedCell {
+    fn from(value: bool) -> Self {
+        TypedCell::Bool(value)
+    }
+}
+
+/// `None` is `Null`.
+impl<T: Into<TypedCell>> From<Option<T>> for TypedCell {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(TypedCell::Null, Into::into)
+    }
+}
@@ -1,7 +1,19 @@
-use super::{Alignment, BorderConfig, BorderUserConfig, VerticalAlignment};
+use super::{
+    Alignment, BorderConfig, BorderUserConfig, BorderVisibility, TableAlignment, TextDirection,
+    VerticalAlignment,
+};
+use crate::features::aggregation::ColumnAggregation;
+use crate::features::computed::ComputedColumn;
+use crate::features::filtering::RowFilter;
+use crate::features::number_format::{Formatter, Locale, LocaleUserConfig};
+use crate::features::spanning::HeaderSpec;
+use crate::features::stats::HeaderRow;
+use crate::features::styling::{CellStyle, StyleRule};
+use crate::features::template::MissingPlaceholder;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct CellConfig {
     pub alignment: Alignment,
     pub vertical_alignment: VerticalAlignment,
@@ -24,7 +36,7 @@ impl Default for CellConfig {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct CellUserConfig {
     pub alignment: Option<Alignment>,
     pub vertical_alignment: Option<VerticalAlignment>,
@@ -49,15 +61,458 @@ impl CellUserConfig {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// How a column resolves content that does not fit when `width`, `truncate` and
+/// `wrap_word` are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Cut the content to `truncate` columns (with an ellipsis) first, then wrap
+    /// what is left to the column width.
+    #[default]
+    TruncateThenWrap,
+    /// Wrap to the column width first, then keep at most `truncate` wrapped
+    /// lines, ending the last kept line with an ellipsis when lines were dropped.
+    WrapThenTruncateLines,
+    /// Never wrap: the first line is cut to the column width (or `truncate`, if
+    /// smaller) with an ellipsis.
+    TruncateOnly,
+    /// Ignore `truncate` and wrap all content to the column width.
+    WrapOnly,
+}
+
+/// What a column does with content wider than it, in one setting, as
+/// `ColumnUserConfig::overflow_mode`. It takes the place of `truncate`,
+/// `wrap_word` and `overflow`, and setting it with one of them that it
+/// contradicts is an error.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Overflow {
+    /// Wraps the content to the column width; nothing is cut.
+    Wrap,
+    /// Cuts the content to one line at the column width, with `ellipsis`, or
+    /// the column's, where it was cut.
+    Truncate {
+        #[serde(default)]
+        position: TruncatePosition,
+        ellipsis: Option<String>,
+    },
+    /// Cuts the content to one line at the column width, unmarked.
+    Clip,
+    /// Widens the column to its content: nothing is wrapped or cut, and the
+    /// column keeps its width when the table is fitted to `max_table_width`.
+    Expand,
+}
+
+/// What `table` does with cells that `lint_rows` reports: invisible
+/// characters, trailing whitespace and mixed tabs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DataHygiene {
+    /// Draw cells as given.
+    #[default]
+    Allow,
+    /// Fail with `TableError::UncleanData` for the first issue.
+    Reject,
+    /// Draw every cell as `clean_cell` returns it.
+    Clean,
+}
+
+/// Whether rendered tables keep their ANSI sequences, e.g. to draw the same
+/// data plainly when output is piped to a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    /// Keep colors and emphasis as they are.
+    #[default]
+    Preserve,
+    /// Remove every ANSI sequence.
+    Strip,
+    /// Keep them only when standard output is a terminal that takes colors,
+    /// as `stdout_supports_ansi` decides.
+    Auto,
+}
+
+impl ColorMode {
+    /// Whether output drawn in this mode keeps its ANSI sequences.
+    pub fn keeps_ansi(self) -> bool {
+        match self {
+            ColorMode::Preserve => true,
+            ColorMode::Strip => false,
+            ColorMode::Auto => crate::utils::terminal::stdout_supports_ansi(),
+        }
+    }
+}
+
+impl std::fmt::Display for ColorMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ColorMode::Preserve => "preserve",
+            ColorMode::Strip => "strip",
+            ColorMode::Auto => "auto",
+        })
+    }
+}
+
+/// Also accepts the `--color` spellings: `always` for `Preserve` and `never`
+/// for `Strip`.
+impl std::str::FromStr for ColorMode {
+    type Err = crate::types::TableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "preserve" | "always" => Ok(ColorMode::Preserve),
+            "strip" | "never" => Ok(ColorMode::Strip),
+            "auto" => Ok(ColorMode::Auto),
+            _ => Err(crate::types::TableError::parse(
+                "color mode",
+                s,
+                "expected always, never or auto",
+            )),
+        }
+    }
+}
+
+/// How serious a `ValidationIssue` is, from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The table draws as configured, but not every character of the data is
+    /// shown, e.g. a cell is truncated.
+    Info,
+    /// The table draws, but probably not as intended, e.g. a cell has unclean
+    /// data or overflows its column.
+    Warning,
+    /// The table cannot be drawn.
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+impl std::str::FromStr for Severity {
+    type Err = crate::types::TableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "info" => Ok(Severity::Info),
+            "warning" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            _ => Err(crate::types::TableError::parse(
+                "severity",
+                s,
+                "expected info, warning or error",
+            )),
+        }
+    }
+}
+
+/// What `table` does with the columns `suggest_redundant_columns` finds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RedundantColumns {
+    /// Draw every column.
+    #[default]
+    Keep,
+    /// Leave out constant columns and copies of earlier columns.
+    Drop,
+    /// Like `Drop`, but a copy's header cell is appended to its original's,
+    /// as in `host / hostname`.
+    Merge,
+}
+
+impl std::str::FromStr for RedundantColumns {
+    type Err = crate::types::TableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "keep" => Ok(RedundantColumns::Keep),
+            "drop" => Ok(RedundantColumns::Drop),
+            "merge" => Ok(RedundantColumns::Merge),
+            _ => Err(crate::types::TableError::parse(
+                "redundant columns mode",
+                s,
+                "expected keep, drop or merge",
+            )),
+        }
+    }
+}
+
+/// How wrapping handles a word wider than its column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WordBreakPolicy {
+    /// Cut the word between any two grapheme clusters, as `wrap_word: false`
+    /// does.
+    #[default]
+    BreakAnywhere,
+    /// Cut the word like `BreakAnywhere`, ending each cut piece with a hyphen
+    /// counted in its width. A word with a cluster too wide to sit beside the
+    /// hyphen is cut like `BreakAnywhere`.
+    BreakAtHyphen,
+    /// Keep the word whole on a line of its own, wider than the column, as
+    /// `wrap_word: true` does.
+    NoBreakOverflow,
+    /// Keep the word on a line of its own, cut to the column width with the
+    /// column's `ellipsis`.
+    NoBreakTruncate,
+}
+
+impl WordBreakPolicy {
+    /// The policy `wrap_word` stands for.
+    pub fn from_wrap_word(wrap_word: bool) -> Self {
+        if wrap_word {
+            WordBreakPolicy::NoBreakOverflow
+        } else {
+            WordBreakPolicy::BreakAnywhere
+        }
+    }
+}
+
+/// Which part of over-long content `truncate` removes, leaving the ellipsis
+/// in its place: `Middle` shortens `/home/me/src/app/file.rs` to
+/// `/home/…/file.rs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TruncatePosition {
+    Start,
+    Middle,
+    #[default]
+    End,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// How the values of a sort column compare. Empty cells always sort last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKind {
+    /// Numeric when every non-empty value in the column is a number, lexical
+    /// otherwise.
+    #[default]
+    Auto,
+    /// By text, using the `locale` collation when the `icu` feature is enabled.
+    Lexical,
+    /// By value; cells that are not numbers sort after those that are.
+    Numeric,
+    /// Lexical, except runs of digits compare by value: `item2` < `item10`.
+    Natural,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SortKey {
+    pub column: usize,
+    pub order: Option<SortOrder>,
+    pub kind: Option<SortKind>,
+}
+
+/// Parses `COLUMN[:asc|desc][:auto|lexical|numeric|natural]`, e.g. `2:desc:natural`.
+impl std::str::FromStr for SortKey {
+    type Err = crate::types::TableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            crate::types::TableError::parse(
+                "sort key",
+                s,
+                "expected COLUMN[:asc|desc][:auto|lexical|numeric|natural]",
+            )
+        };
+        let mut parts = s.split(':');
+
+        let column = parts
+            .next()
+            .and_then(|column| column.trim().parse().ok())
+            .ok_or_else(invalid)?;
+        let mut key = SortKey {
+            column,
+            order: None,
+            kind: None,
+        };
+
+        for part in parts {
+            match part.trim().to_lowercase().as_str() {
+                "asc" | "ascending" => key.order = Some(SortOrder::Ascending),
+                "desc" | "descending" => key.order = Some(SortOrder::Descending),
+                "auto" => key.kind = Some(SortKind::Auto),
+                "lexical" => key.kind = Some(SortKind::Lexical),
+                "numeric" => key.kind = Some(SortKind::Numeric),
+                "natural" => key.kind = Some(SortKind::Natural),
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(key)
+    }
+}
+
+/// A column width set by the user: a number of characters, or a share of the
+/// table width. Written as `12` or `"30%"` in config files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "WidthValue", into = "WidthValue")]
+pub enum Width {
+    /// Characters, padding included.
+    Chars(usize),
+    /// Percent of the space inside the borders of a table as wide as
+    /// `exact_table_width`, else `max_table_width`, else the terminal.
+    Percent(usize),
+}
+
+impl From<usize> for Width {
+    fn from(chars: usize) -> Self {
+        Width::Chars(chars)
+    }
+}
+
+/// Parses `CHARS` or `PERCENT%`, e.g. `12` or `30%`.
+impl std::str::FromStr for Width {
+    type Err = crate::types::TableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let width = match value.strip_suffix('%') {
+            Some(percent) => percent.trim().parse().map(Width::Percent),
+            None => value.parse().map(Width::Chars),
+        };
+        width.map_err(|_| {
+            crate::types::TableError::parse("width", s, "expected CHARS or PERCENT%, e.g. 30%")
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum WidthValue {
+    Chars(usize),
+    Text(String),
+}
+
+impl TryFrom<WidthValue> for Width {
+    type Error = crate::types::TableError;
+
+    fn try_from(value: WidthValue) -> Result<Self, Self::Error> {
+        match value {
+            WidthValue::Chars(chars) => Ok(Width::Chars(chars)),
+            WidthValue::Text(text) => text.parse(),
+        }
+    }
+}
+
+impl From<Width> for WidthValue {
+    fn from(width: Width) -> Self {
+        match width {
+            Width::Chars(chars) => WidthValue::Chars(chars),
+            Width::Percent(percent) => WidthValue::Text(format!("{percent}%")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ColumnConfig {
     pub alignment: Alignment,
     pub vertical_alignment: VerticalAlignment,
     pub padding_left: usize,
     pub padding_right: usize,
+    /// Fills the space alignment leaves around the content, inside the
+    /// padding: `.` for leader dots, `0` for zero-padded numbers, the zeros
+    /// after any sign. In a column aligned on a character only the space
+    /// before the integer part is filled.
+    pub pad_char: char,
     pub truncate: usize,
+    pub truncate_at_word: bool,
+    pub truncate_position: TruncatePosition,
+    /// Marks where truncated content was cut.
+    pub ellipsis: String,
+    pub overflow: OverflowPolicy,
     pub wrap_word: bool,
+    /// How words wider than the column wrap; `wrap_word` decides when unset.
+    pub word_break: Option<WordBreakPolicy>,
     pub width: usize,
+    /// `Width::Percent`, resolved to `width` by the calculator; 0 means unset.
+    pub width_percent: usize,
+    /// Bounds for the auto-sized width, padding included; 0 means no bound.
+    /// Content wider than `max_width` wraps. Ignored when `width` is set.
+    pub min_width: usize,
+    pub max_width: usize,
+    /// Leaves the column out of the drawn table.
+    pub hidden: bool,
+    pub direction: TextDirection,
+    /// Reorders each line of right-to-left text for display with
+    /// `reorder_bidi_line`, for terminals that draw characters in the order
+    /// they are written.
+    pub bidi: bool,
+    /// Rewrites the column's numbers, outside the header rows, before the
+    /// width is measured.
+    pub formatter: Option<Formatter>,
+    /// The `overflow_mode` the settings above were resolved from, if any.
+    pub overflow_mode: Option<Overflow>,
+}
+
+impl ColumnConfig {
+    pub fn word_break_policy(&self) -> WordBreakPolicy {
+        self.word_break
+            .unwrap_or(WordBreakPolicy::from_wrap_word(self.wrap_word))
+    }
+
+    // `self` with the `truncate`, `overflow` and truncation settings
+    // `overflow_mode` stands for.
+    fn with_overflow_mode(mut self) -> Self {
+        let Some(mode) = &self.overflow_mode else {
+            return self;
+        };
+        self.truncate = 0;
+        match mode {
+            Overflow::Wrap | Overflow::Expand => self.overflow = OverflowPolicy::WrapOnly,
+            Overflow::Truncate { position, ellipsis } => {
+                self.overflow = OverflowPolicy::TruncateOnly;
+                self.truncate_position = *position;
+                if let Some(ellipsis) = ellipsis {
+                    self.ellipsis = ellipsis.clone();
+                }
+            }
+            Overflow::Clip => {
+                self.overflow = OverflowPolicy::TruncateOnly;
+                self.ellipsis = String::new();
+            }
+        }
+        self
+    }
+
+    // `self` with the settings its `overflow_mode` stood for back at their
+    // defaults, for a column that sets its own overflow settings instead. The
+    // settings a mode conflicts with cannot be set along with it, so the
+    // defaults are what they were before the mode was applied.
+    fn without_overflow_mode(mut self) -> Self {
+        let Some(mode) = self.overflow_mode.take() else {
+            return self;
+        };
+        let defaults = ColumnConfig::default();
+        self.truncate = defaults.truncate;
+        self.overflow = defaults.overflow;
+        match mode {
+            Overflow::Wrap | Overflow::Expand => {}
+            Overflow::Truncate { ellipsis, .. } => {
+                self.truncate_position = defaults.truncate_position;
+                if ellipsis.is_some() {
+                    self.ellipsis = defaults.ellipsis;
+                }
+            }
+            Overflow::Clip => self.ellipsis = defaults.ellipsis,
+        }
+        self
+    }
 }
 
 impl Default for ColumnConfig {
@@ -67,47 +522,187 @@ impl Default for ColumnConfig {
             vertical_alignment: VerticalAlignment::Top,
             padding_left: 1,
             padding_right: 1,
+            pad_char: ' ',
             truncate: 0,
+            truncate_at_word: false,
+            truncate_position: TruncatePosition::End,
+            ellipsis: "...".to_string(),
+            overflow: OverflowPolicy::TruncateThenWrap,
             wrap_word: false,
+            word_break: None,
             width: 0,
+            width_percent: 0,
+            min_width: 0,
+            max_width: 0,
+            hidden: false,
+            direction: TextDirection::Ltr,
+            bidi: false,
+            formatter: None,
+            overflow_mode: None,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub struct ColumnUserConfig {
     pub alignment: Option<Alignment>,
     pub vertical_alignment: Option<VerticalAlignment>,
     pub padding_left: Option<usize>,
     pub padding_right: Option<usize>,
+    pub pad_char: Option<char>,
     pub truncate: Option<usize>,
+    pub truncate_at_word: Option<bool>,
+    pub truncate_position: Option<TruncatePosition>,
+    pub ellipsis: Option<String>,
+    pub overflow: Option<OverflowPolicy>,
     pub wrap_word: Option<bool>,
-    pub width: Option<usize>,
+    /// Takes precedence over `wrap_word`, which is shorthand for
+    /// `BreakAnywhere` (false) and `NoBreakOverflow` (true).
+    pub word_break: Option<WordBreakPolicy>,
+    pub width: Option<Width>,
+    pub min_width: Option<usize>,
+    pub max_width: Option<usize>,
+    pub hidden: Option<bool>,
+    pub direction: Option<TextDirection>,
+    pub bidi: Option<bool>,
+    pub formatter: Option<Formatter>,
+    /// Replaces `truncate`, `wrap_word` and `overflow`, and the settings a
+    /// column inherits for them, with one explicit choice.
+    pub overflow_mode: Option<Overflow>,
 }
 
 impl ColumnUserConfig {
+    /// Layers `other` over `self`: every setting present in `other` wins.
+    pub fn merge(self, other: ColumnUserConfig) -> ColumnUserConfig {
+        ColumnUserConfig {
+            alignment: other.alignment.or(self.alignment),
+            vertical_alignment: other.vertical_alignment.or(self.vertical_alignment),
+            padding_left: other.padding_left.or(self.padding_left),
+            padding_right: other.padding_right.or(self.padding_right),
+            pad_char: other.pad_char.or(self.pad_char),
+            truncate: other.truncate.or(self.truncate),
+            truncate_at_word: other.truncate_at_word.or(self.truncate_at_word),
+            truncate_position: other.truncate_position.or(self.truncate_position),
+            ellipsis: other.ellipsis.or(self.ellipsis),
+            overflow: other.overflow.or(self.overflow),
+            wrap_word: other.wrap_word.or(self.wrap_word),
+            word_break: other.word_break.or(if other.wrap_word.is_some() {
+                None
+            } else {
+                self.word_break
+            }),
+            width: other.width.or(self.width),
+            min_width: other.min_width.or(self.min_width),
+            max_width: other.max_width.or(self.max_width),
+            hidden: other.hidden.or(self.hidden),
+            direction: other.direction.or(self.direction),
+            bidi: other.bidi.or(self.bidi),
+            formatter: other.formatter.or(self.formatter),
+            overflow_mode: other.overflow_mode.or(self.overflow_mode),
+        }
+    }
+
     pub fn merge_with_default(self, default: &ColumnConfig) -> ColumnConfig {
-        ColumnConfig {
+        // An inherited mode gives way to the column's own settings that
+        // contradict it, which then apply over the settings the mode did not
+        // decide.
+        let gives_way = self.overflow_mode.is_none()
+            && ColumnUserConfig {
+                overflow_mode: default.overflow_mode.clone(),
+                ..self.clone()
+            }
+            .overflow_mode_conflict()
+            .is_some();
+        let without_mode;
+        let default = if gives_way {
+            without_mode = default.clone().without_overflow_mode();
+            &without_mode
+        } else {
+            default
+        };
+        let overflow_mode = self
+            .overflow_mode
+            .clone()
+            .or_else(|| default.overflow_mode.clone());
+        let own_mode = self.overflow_mode.is_some();
+        let column = ColumnConfig {
             alignment: self.alignment.unwrap_or(default.alignment),
             vertical_alignment: self
                 .vertical_alignment
                 .unwrap_or(default.vertical_alignment),
             padding_left: self.padding_left.unwrap_or(default.padding_left),
             padding_right: self.padding_right.unwrap_or(default.padding_right),
+            pad_char: self.pad_char.unwrap_or(default.pad_char),
             truncate: self.truncate.unwrap_or(default.truncate),
+            truncate_at_word: self.truncate_at_word.unwrap_or(default.truncate_at_word),
+            truncate_position: self.truncate_position.unwrap_or(default.truncate_position),
+            ellipsis: self.ellipsis.unwrap_or_else(|| default.ellipsis.clone()),
+            overflow: self.overflow.unwrap_or(default.overflow),
             wrap_word: self.wrap_word.unwrap_or(default.wrap_word),
-            width: self.width.unwrap_or(default.width),
+            // A `wrap_word` set here overrides a policy inherited from `default`.
+            word_break: self.word_break.or(if self.wrap_word.is_some() {
+                None
+            } else {
+                default.word_break
+            }),
+            width: match self.width {
+                Some(Width::Chars(chars)) => chars,
+                Some(Width::Percent(_)) => 0,
+                None => default.width,
+            },
+            width_percent: match self.width {
+                Some(Width::Percent(percent)) => percent,
+                Some(Width::Chars(_)) => 0,
+                None => default.width_percent,
+            },
+            min_width: self.min_width.unwrap_or(default.min_width),
+            max_width: self.max_width.unwrap_or(default.max_width),
+            hidden: self.hidden.unwrap_or(default.hidden),
+            direction: self.direction.unwrap_or(default.direction),
+            bidi: self.bidi.unwrap_or(default.bidi),
+            formatter: self.formatter.or_else(|| default.formatter.clone()),
+            overflow_mode,
+        };
+        if own_mode {
+            column.with_overflow_mode()
+        } else {
+            column
+        }
+    }
+
+    /// The setting of `overflow_mode` that conflicts with it, if any.
+    pub fn overflow_mode_conflict(&self) -> Option<&'static str> {
+        let mode = self.overflow_mode.as_ref()?;
+        let wraps = self.wrap_word.is_some() || self.word_break.is_some();
+        match mode {
+            _ if self.overflow.is_some() => Some("overflow"),
+            _ if self.truncate.is_some() => Some("truncate"),
+            Overflow::Truncate { .. } | Overflow::Clip if wraps => Some("wrap_word"),
+            Overflow::Truncate { .. } if self.truncate_position.is_some() => {
+                Some("truncate_position")
+            }
+            Overflow::Truncate {
+                ellipsis: Some(_), ..
+            }
+            | Overflow::Clip
+                if self.ellipsis.is_some() =>
+            {
+                Some("ellipsis")
+            }
+            Overflow::Expand if self.width.is_some() => Some("width"),
+            Overflow::Expand if self.max_width.is_some() => Some("max_width"),
+            _ => None,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct CellCoordinates {
     pub col: usize,
     pub row: usize,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SpanningCellConfig {
     pub col: usize,
     pub row: usize,
@@ -121,19 +716,102 @@ pub struct SpanningCellConfig {
     pub wrap_word: Option<bool>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RangeCoordinate {
     pub top_left: CellCoordinates,
     pub bottom_right: CellCoordinates,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RangeConfig {
     pub top_left: CellCoordinates,
     pub bottom_right: CellCoordinates,
     pub config: CellConfig,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RowAnnotation {
+    pub row: usize,
+    pub text: String,
+    pub indent: Option<usize>,
+    pub dim: Option<bool>,
+}
+
+/// Where a `TableLabel` is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelPlacement {
+    /// Set into the top (title) or bottom (caption) border, as in
+    /// `┌── Sales Q3 ──┐`. Drawn `Outside` instead when there is no such
+    /// border or it is too narrow.
+    #[default]
+    Border,
+    /// On a line of its own above or below the table.
+    Outside,
+}
+
+/// A one-line title or caption, fitted to the width of the drawn table and cut
+/// with the default column's `ellipsis` when longer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TableLabel {
+    pub text: String,
+    /// Defaults to `Alignment::Center`; `Justify` and `Char` align left.
+    pub alignment: Option<Alignment>,
+    pub placement: Option<LabelPlacement>,
+}
+
+/// Border settings for the lines around one row, keyed by row index in
+/// `row_overrides`. Where two rows set the line between them, the lower row's
+/// `_before` settings win over the upper row's `_after` ones.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub struct RowOverride {
+    /// Draws (`true`) or leaves out (`false`) the line above the row, whatever
+    /// `draw_horizontal_line` decides.
+    pub separator_before: Option<bool>,
+    pub separator_after: Option<bool>,
+    /// Replaces the horizontal character of the line above the row, e.g. `═`
+    /// for a double line above a totals row.
+    pub horizontal_before: Option<String>,
+    pub horizontal_after: Option<String>,
+}
+
+/// Which cells a `FormatRule` applies to. Cells are compared with ANSI styling
+/// and surrounding whitespace removed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FormatCondition {
+    Equals(String),
+    Contains(String),
+    /// Numbers above zero.
+    Positive,
+    /// Numbers below zero.
+    Negative,
+}
+
+impl FormatCondition {
+    /// The symbol used in `symbols_only` mode when the rule does not set one.
+    pub fn default_symbol(&self) -> &'static str {
+        match self {
+            FormatCondition::Positive => "↑",
+            FormatCondition::Negative => "↓",
+            FormatCondition::Equals(_) | FormatCondition::Contains(_) => "*",
+        }
+    }
+}
+
+/// Conditional formatting: matching cells are colored, or prefixed with a
+/// symbol when `symbols_only` is set so the meaning never rests on color alone.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FormatRule {
+    /// The column to check; every column when unset.
+    pub column: Option<usize>,
+    pub condition: FormatCondition,
+    /// SGR parameters, e.g. `"31"` for red or `"1;33"` for bold yellow.
+    pub color: String,
+    /// Prefix used instead of the color, e.g. `"!"`.
+    pub symbol: Option<String>,
+}
+
 pub type DrawVerticalLine = fn(line_index: usize, column_count: usize) -> bool;
 pub type DrawHorizontalLine = fn(line_index: usize, row_count: usize) -> bool;
 
@@ -145,16 +823,162 @@ pub fn default_draw_horizontal_line(_line_index: usize, _row_count: usize) -> bo
     true
 }
 
-#[derive(Debug, Clone)]
+// The line predicates compare by address, as closures in the config do by
+// identity: equal configs draw the same, and configs that merely behave the
+// same may compare unequal.
+#[allow(unpredictable_function_pointer_comparisons)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TableConfig {
     pub border: BorderConfig,
     pub columns: Vec<ColumnConfig>,
     pub column_default: ColumnConfig,
     pub draw_vertical_line: DrawVerticalLine,
     pub draw_horizontal_line: DrawHorizontalLine,
+    pub border_visibility: BorderVisibility,
     pub single_line: bool,
     pub spanning_cells: Vec<SpanningCellConfig>,
     pub header: Option<Box<TableConfig>>,
+    /// Layered over the header row's cells when there is a `header`.
+    pub header_style: Option<CellStyle>,
+    /// Writes the header row in capitals when there is a `header`.
+    pub uppercase_header: bool,
+    /// Rows of column groups above the header row.
+    pub header_groups: Option<HeaderSpec>,
+    /// How many `header_groups` rows the prepared rows start with; they are
+    /// drawn as header rows. Set by `insert_header_groups`.
+    pub header_group_rows: usize,
+    /// Styles the last row, e.g. totals, which is drawn after a separator line
+    /// that is always shown.
+    pub footer: Option<Box<TableConfig>>,
+    pub row_annotations: Vec<RowAnnotation>,
+    pub row_overrides: BTreeMap<usize, RowOverride>,
+    pub table_alignment: TableAlignment,
+    pub table_indent: usize,
+    pub terminal_width: Option<usize>,
+    /// Draws the table exactly this wide, so stacked tables line up. Extra
+    /// width goes to `flex_column`, else is shared among the columns; a table
+    /// too wide gives it up as under `max_table_width`.
+    pub exact_table_width: Option<usize>,
+    /// The column that grows and shrinks first to meet `exact_table_width`.
+    pub flex_column: Option<usize>,
+    /// Upper bound on the table width; long auto-sized columns shrink and wrap
+    /// to stay within it.
+    pub max_table_width: Option<usize>,
+    /// Caps the table at the terminal width when `max_table_width` is unset.
+    pub auto_layout: bool,
+    pub column_summaries: bool,
+    /// BCP 47 tag, e.g. `"de"` or `"zh-u-co-pinyin"`, used by `sort_rows` to
+    /// collate text columns, which needs the `icu` feature, and by
+    /// `computed_columns` to write percentages, ordinals and weeks.
+    pub locale: Option<String>,
+    /// The separators and date format column `formatter`s write with;
+    /// `locale`'s conventions when unset.
+    pub format_locale: Option<Locale>,
+    /// Sort keys applied before rendering, the first key taking precedence. The
+    /// header row, when there is one, stays first.
+    pub sort: Vec<SortKey>,
+    pub format_rules: Vec<FormatRule>,
+    /// Marks `format_rules` matches with symbols instead of colors, for
+    /// color-blind readers and monochrome output.
+    pub symbols_only: bool,
+    /// Whether ANSI sequences, from the data and from styling, are kept in
+    /// the output. Where they are not, `symbols_only` is implied.
+    pub color_mode: ColorMode,
+    /// Rows must match every filter to be rendered. The header row, when there
+    /// is one, is always kept.
+    pub filters: Vec<RowFilter>,
+    /// Per-column aggregations computed over the body rows after filtering and
+    /// sorting, and appended as the footer row.
+    pub aggregations: Vec<ColumnAggregation>,
+    /// Shown by numeric aggregations over cells that are not numbers.
+    pub aggregate_placeholder: String,
+    /// Written by `table_typed` for `TypedCell::Null`.
+    pub null_placeholder: String,
+    /// Columns appended after the data columns, such as each row's share of
+    /// a column's total or its rank, computed after aggregation.
+    pub computed_columns: Vec<ComputedColumn>,
+    /// Ends each page drawn by `paginate_table` with a "Page X of Y" line.
+    pub page_captions: bool,
+    /// Caps each page drawn by `paginate_table` at this many lines, caption
+    /// included. A row taller than the space left on a page is split there,
+    /// each cut cell ending with its `ellipsis`, and continues on the next.
+    pub page_height: Option<usize>,
+    /// Colors and emphasis applied to matching cells as the table is rendered.
+    pub style_rules: Vec<StyleRule>,
+    /// SGR parameters, e.g. `"48;5;236"`, applied to every line of every other
+    /// body row, starting with the second.
+    pub row_striping: Option<String>,
+    /// The data columns to draw, in this order; columns left out, like
+    /// `hidden` ones, are not drawn. Every other column setting keeps
+    /// referring to data columns.
+    pub column_order: Option<Vec<usize>>,
+    pub data_hygiene: DataHygiene,
+    /// Constant and duplicate columns, judged on the header and body rows,
+    /// are dropped or merged before drawing. At least one column is kept.
+    pub redundant_columns: RedundantColumns,
+    /// Refuses to draw, failing with `TableError::Validation`, when
+    /// `validate_table` finds an issue at or above this severity. Unset, only
+    /// errors fail, with the error the first failing check returns.
+    pub fail_on: Option<Severity>,
+    /// Drawn above the table, or into its top border.
+    pub title: Option<TableLabel>,
+    /// Drawn below the table, or into its bottom border.
+    pub caption: Option<TableLabel>,
+    /// Values for `{name}` placeholders in cells, the title and the caption.
+    /// Without a map, braces are plain text.
+    pub placeholders: Option<BTreeMap<String, String>>,
+    pub missing_placeholder: MissingPlaceholder,
+}
+
+impl TableConfig {
+    /// The rows drawn as body rows: all but the header group rows, the first
+    /// when there is a header and the last when there is a footer.
+    pub fn body_rows(&self, row_count: usize) -> std::ops::Range<usize> {
+        let start = (self.header_group_rows + usize::from(self.header.is_some())).min(row_count);
+        let end = if self.footer.is_some() {
+            row_count.saturating_sub(1).max(start)
+        } else {
+            row_count
+        };
+        start..end
+    }
+
+    /// Whether the line above row `line_index`, between two body rows, is
+    /// drawn, unless `row_overrides` say otherwise.
+    pub fn draws_body_row_line(&self, line_index: usize, row_count: usize) -> bool {
+        self.border_visibility.row_separators
+            && self.border.body_row_separators
+            && (self.draw_horizontal_line)(line_index, row_count)
+    }
+
+    /// Whether the top (`line_index` 0) or bottom (`row_count`) border is
+    /// drawn, unless `row_overrides` say otherwise. A border whose line has
+    /// no characters, as the `markdown` style's, draws none.
+    pub fn draws_outer_line(&self, line_index: usize, row_count: usize) -> bool {
+        let border = if line_index == 0 {
+            &self.header.as_deref().unwrap_or(self).border
+        } else {
+            &self.footer.as_deref().unwrap_or(self).border
+        };
+        let line = if line_index == 0 {
+            [
+                &border.top_left,
+                &border.top_body,
+                &border.top_join,
+                &border.top_right,
+            ]
+        } else {
+            [
+                &border.bottom_left,
+                &border.bottom_body,
+                &border.bottom_join,
+                &border.bottom_right,
+            ]
+        };
+        self.border_visibility.outer
+            && (self.draw_horizontal_line)(line_index, row_count)
+            && line.iter().any(|part| !part.is_empty())
+    }
 }
 
 impl Default for TableConfig {
@@ -165,29 +989,238 @@ impl Default for TableConfig {
             column_default: ColumnConfig::default(),
             draw_vertical_line: default_draw_vertical_line,
             draw_horizontal_line: default_draw_horizontal_line,
+            border_visibility: BorderVisibility::default(),
             single_line: false,
             spanning_cells: Vec::new(),
             header: None,
+            header_style: None,
+            uppercase_header: false,
+            header_groups: None,
+            header_group_rows: 0,
+            footer: None,
+            row_annotations: Vec::new(),
+            row_overrides: BTreeMap::new(),
+            table_alignment: TableAlignment::Left,
+            table_indent: 0,
+            terminal_width: None,
+            exact_table_width: None,
+            flex_column: None,
+            max_table_width: None,
+            auto_layout: false,
+            column_summaries: false,
+            locale: None,
+            format_locale: None,
+            sort: Vec::new(),
+            format_rules: Vec::new(),
+            symbols_only: false,
+            color_mode: ColorMode::Preserve,
+            filters: Vec::new(),
+            aggregations: Vec::new(),
+            aggregate_placeholder: "n/a".to_string(),
+            null_placeholder: String::new(),
+            computed_columns: Vec::new(),
+            page_captions: false,
+            page_height: None,
+            style_rules: Vec::new(),
+            row_striping: None,
+            column_order: None,
+            data_hygiene: DataHygiene::Allow,
+            redundant_columns: RedundantColumns::Keep,
+            fail_on: None,
+            title: None,
+            caption: None,
+            placeholders: None,
+            missing_placeholder: MissingPlaceholder::Keep,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub struct TableUserConfig {
     pub border: Option<BorderUserConfig>,
     pub columns: Option<Vec<ColumnUserConfig>>,
     pub column_default: Option<ColumnUserConfig>,
+    pub border_visibility: Option<BorderVisibility>,
     pub single_line: Option<bool>,
     pub spanning_cells: Option<Vec<SpanningCellConfig>>,
     pub header: Option<Box<TableUserConfig>>,
+    /// Treats the first row as the header, as an empty `header` would, when
+    /// `header` is unset: `true`, `false`, or `"auto"` to guess from the
+    /// data with `detect_header`. A header found this way is bold unless
+    /// `header_style` says otherwise.
+    pub first_row_is_header: Option<HeaderRow>,
+    pub header_style: Option<CellStyle>,
+    pub uppercase_header: Option<bool>,
+    pub header_groups: Option<HeaderSpec>,
+    pub footer: Option<Box<TableUserConfig>>,
+    pub row_annotations: Option<Vec<RowAnnotation>>,
+    pub row_overrides: Option<BTreeMap<usize, RowOverride>>,
+    pub table_alignment: Option<TableAlignment>,
+    pub table_indent: Option<usize>,
+    pub terminal_width: Option<usize>,
+    pub exact_table_width: Option<usize>,
+    pub flex_column: Option<usize>,
+    pub max_table_width: Option<usize>,
+    pub auto_layout: Option<bool>,
+    pub column_summaries: Option<bool>,
+    pub locale: Option<String>,
+    pub format_locale: Option<LocaleUserConfig>,
+    pub sort: Option<Vec<SortKey>>,
+    pub format_rules: Option<Vec<FormatRule>>,
+    pub symbols_only: Option<bool>,
+    pub color_mode: Option<ColorMode>,
+    pub filters: Option<Vec<RowFilter>>,
+    pub aggregations: Option<Vec<ColumnAggregation>>,
+    pub aggregate_placeholder: Option<String>,
+    pub null_placeholder: Option<String>,
+    pub computed_columns: Option<Vec<ComputedColumn>>,
+    pub page_captions: Option<bool>,
+    pub page_height: Option<usize>,
+    pub style_rules: Option<Vec<StyleRule>>,
+    pub row_striping: Option<String>,
+    pub column_order: Option<Vec<usize>>,
+    pub data_hygiene: Option<DataHygiene>,
+    pub redundant_columns: Option<RedundantColumns>,
+    pub fail_on: Option<Severity>,
+    pub title: Option<TableLabel>,
+    pub caption: Option<TableLabel>,
+    pub placeholders: Option<BTreeMap<String, String>>,
+    pub missing_placeholder: Option<MissingPlaceholder>,
+    /// Renders through the shared `RenderCache::global()`, so redrawing the
+    /// same rows with the same config is a lookup. Needs the `memo` feature;
+    /// ignored without it.
+    pub memoize: Option<bool>,
+    /// A theme from `get_theme`, e.g. `"fancy"`, whose border, padding, header
+    /// style and striping every other setting here is layered over.
+    pub theme: Option<String>,
 }
 
 impl TableUserConfig {
-    pub fn merge_with_default(self, default: &TableConfig) -> TableConfig {
-        let border = self
-            .border
-            .map(|b| b.merge_with_default(&default.border))
-            .unwrap_or_else(|| default.border.clone());
+    /// Layers `other` over `self`, so applications can stack e.g. CLI flags over
+    /// a config file over built-in defaults:
+    ///
+    /// - scalar settings: `other` wins when it is `Some`;
+    /// - `border`, `column_default`, `format_locale`, `header` and `footer`:
+    ///   merged setting by setting;
+    /// - `columns`: merged index by index, the longer list's extra columns kept;
+    /// - `placeholders`: merged value by value;
+    /// - lists of items (`spanning_cells`, `row_annotations`, `row_overrides`,
+    ///   `sort`, `format_rules`, `filters`, `aggregations`, `computed_columns`,
+    ///   `style_rules`): `other` replaces.
+    pub fn merge(self, other: TableUserConfig) -> TableUserConfig {
+        TableUserConfig {
+            border: merge_nested(self.border, other.border, BorderUserConfig::merge),
+            columns: merge_nested(self.columns, other.columns, merge_columns),
+            column_default: merge_nested(
+                self.column_default,
+                other.column_default,
+                ColumnUserConfig::merge,
+            ),
+            border_visibility: other.border_visibility.or(self.border_visibility),
+            single_line: other.single_line.or(self.single_line),
+            spanning_cells: other.spanning_cells.or(self.spanning_cells),
+            first_row_is_header: other.first_row_is_header.or(self.first_row_is_header),
+            header_style: other.header_style.or(self.header_style),
+            uppercase_header: other.uppercase_header.or(self.uppercase_header),
+            header_groups: other.header_groups.or(self.header_groups),
+            header: merge_nested(self.header, other.header, |base, over| {
+                Box::new(base.merge(*over))
+            }),
+            footer: merge_nested(self.footer, other.footer, |base, over| {
+                Box::new(base.merge(*over))
+            }),
+            row_annotations: other.row_annotations.or(self.row_annotations),
+            row_overrides: other.row_overrides.or(self.row_overrides),
+            table_alignment: other.table_alignment.or(self.table_alignment),
+            table_indent: other.table_indent.or(self.table_indent),
+            terminal_width: other.terminal_width.or(self.terminal_width),
+            exact_table_width: other.exact_table_width.or(self.exact_table_width),
+            flex_column: other.flex_column.or(self.flex_column),
+            max_table_width: other.max_table_width.or(self.max_table_width),
+            auto_layout: other.auto_layout.or(self.auto_layout),
+            column_summaries: other.column_summaries.or(self.column_summaries),
+            locale: other.locale.or(self.locale),
+            format_locale: merge_nested(
+                self.format_locale,
+                other.format_locale,
+                LocaleUserConfig::merge,
+            ),
+            sort: other.sort.or(self.sort),
+            format_rules: other.format_rules.or(self.format_rules),
+            symbols_only: other.symbols_only.or(self.symbols_only),
+            color_mode: other.color_mode.or(self.color_mode),
+            filters: other.filters.or(self.filters),
+            aggregations: other.aggregations.or(self.aggregations),
+            aggregate_placeholder: other.aggregate_placeholder.or(self.aggregate_placeholder),
+            null_placeholder: other.null_placeholder.or(self.null_placeholder),
+            computed_columns: other.computed_columns.or(self.computed_columns),
+            page_captions: other.page_captions.or(self.page_captions),
+            page_height: other.page_height.or(self.page_height),
+            style_rules: other.style_rules.or(self.style_rules),
+            row_striping: other.row_striping.or(self.row_striping),
+            column_order: other.column_order.or(self.column_order),
+            data_hygiene: other.data_hygiene.or(self.data_hygiene),
+            redundant_columns: other.redundant_columns.or(self.redundant_columns),
+            fail_on: other.fail_on.or(self.fail_on),
+            title: other.title.or(self.title),
+            caption: other.caption.or(self.caption),
+            placeholders: merge_nested(self.placeholders, other.placeholders, |mut base, over| {
+                base.extend(over);
+                base
+            }),
+            missing_placeholder: other.missing_placeholder.or(self.missing_placeholder),
+            memoize: other.memoize.or(self.memoize),
+            theme: other.theme.or(self.theme),
+        }
+    }
+
+    /// Settings left unset take `default`'s, or the `theme`'s when one is set.
+    /// A theme `get_theme` does not know is ignored.
+    pub fn merge_with_default(mut self, default: &TableConfig) -> TableConfig {
+        if let Some(theme) = self
+            .theme
+            .take()
+            .and_then(|name| crate::features::themes::get_theme(&name).ok())
+        {
+            let mut themed = theme.apply(default, self.header.is_some());
+            // The theme's header style goes first so the config's rules win.
+            if let Some(style_rules) = self.style_rules.take() {
+                themed.style_rules.extend(style_rules);
+            }
+            return self.merge_with_default(&themed);
+        }
+
+        // Header and footer settings left unset fall back to the body's, column
+        // by column.
+        let body = TableUserConfig {
+            header: None,
+            footer: None,
+            aggregations: None,
+            ..self.clone()
+        };
+        let section = |section: &TableUserConfig| {
+            Box::new(
+                body.clone()
+                    .merge(section.clone())
+                    .merge_with_default(default),
+            )
+        };
+        let header = self.header.as_deref().map(section);
+        // Aggregations are drawn as the footer, styled like the body unless
+        // configured.
+        let footer = self.footer.as_deref().map(section).or_else(|| {
+            self.aggregations
+                .as_ref()
+                .is_some_and(|aggregations| !aggregations.is_empty())
+                .then(|| section(&TableUserConfig::default()))
+        });
+
+        let border_visibility = self.border_visibility.unwrap_or(default.border_visibility);
+        let border = border_visibility.apply(
+            self.border
+                .map(|b| b.merge_with_default(&default.border))
+                .unwrap_or_else(|| default.border.clone()),
+        );
 
         let column_default = self
             .column_default
@@ -203,21 +1236,109 @@ impl TableUserConfig {
             })
             .unwrap_or_else(|| default.columns.clone());
 
-        let header = self
-            .header
-            .map(|h| Box::new(h.merge_with_default(&TableConfig::default())));
-
+        let locale = self.locale.or_else(|| default.locale.clone());
         TableConfig {
             border,
             columns,
             column_default,
             draw_vertical_line: default.draw_vertical_line,
             draw_horizontal_line: default.draw_horizontal_line,
+            border_visibility,
             single_line: self.single_line.unwrap_or(default.single_line),
             spanning_cells: self
                 .spanning_cells
                 .unwrap_or_else(|| default.spanning_cells.clone()),
             header,
+            header_style: self.header_style.or_else(|| default.header_style.clone()),
+            uppercase_header: self.uppercase_header.unwrap_or(default.uppercase_header),
+            header_groups: self.header_groups.or_else(|| default.header_groups.clone()),
+            header_group_rows: default.header_group_rows,
+            footer,
+            row_annotations: self
+                .row_annotations
+                .unwrap_or_else(|| default.row_annotations.clone()),
+            row_overrides: self
+                .row_overrides
+                .unwrap_or_else(|| default.row_overrides.clone()),
+            table_alignment: self.table_alignment.unwrap_or(default.table_alignment),
+            table_indent: self.table_indent.unwrap_or(default.table_indent),
+            terminal_width: self.terminal_width.or(default.terminal_width),
+            exact_table_width: self.exact_table_width.or(default.exact_table_width),
+            flex_column: self.flex_column.or(default.flex_column),
+            max_table_width: self.max_table_width.or(default.max_table_width),
+            auto_layout: self.auto_layout.unwrap_or(default.auto_layout),
+            column_summaries: self.column_summaries.unwrap_or(default.column_summaries),
+            format_locale: self
+                .format_locale
+                .map(|format_locale| {
+                    format_locale.merge_with_default(
+                        &default
+                            .format_locale
+                            .clone()
+                            .unwrap_or_else(|| Locale::from_tag(locale.as_deref())),
+                    )
+                })
+                .or_else(|| default.format_locale.clone()),
+            locale,
+            sort: self.sort.unwrap_or_else(|| default.sort.clone()),
+            format_rules: self
+                .format_rules
+                .unwrap_or_else(|| default.format_rules.clone()),
+            symbols_only: self.symbols_only.unwrap_or(default.symbols_only),
+            color_mode: self.color_mode.unwrap_or(default.color_mode),
+            filters: self.filters.unwrap_or_else(|| default.filters.clone()),
+            aggregations: self
+                .aggregations
+                .unwrap_or_else(|| default.aggregations.clone()),
+            aggregate_placeholder: self
+                .aggregate_placeholder
+                .unwrap_or_else(|| default.aggregate_placeholder.clone()),
+            null_placeholder: self
+                .null_placeholder
+                .unwrap_or_else(|| default.null_placeholder.clone()),
+            computed_columns: self
+                .computed_columns
+                .unwrap_or_else(|| default.computed_columns.clone()),
+            page_captions: self.page_captions.unwrap_or(default.page_captions),
+            page_height: self.page_height.or(default.page_height),
+            style_rules: self
+                .style_rules
+                .unwrap_or_else(|| default.style_rules.clone()),
+            row_striping: self.row_striping.or_else(|| default.row_striping.clone()),
+            column_order: self.column_order.or_else(|| default.column_order.clone()),
+            data_hygiene: self.data_hygiene.unwrap_or(default.data_hygiene),
+            redundant_columns: self.redundant_columns.unwrap_or(default.redundant_columns),
+            fail_on: self.fail_on.or(default.fail_on),
+            title: self.title.or_else(|| default.title.clone()),
+            caption: self.caption.or_else(|| default.caption.clone()),
+            placeholders: self.placeholders.or_else(|| default.placeholders.clone()),
+            missing_placeholder: self
+                .missing_placeholder
+                .unwrap_or(default.missing_placeholder),
+        }
+    }
+}
+
+fn merge_nested<T>(base: Option<T>, over: Option<T>, merge: impl FnOnce(T, T) -> T) -> Option<T> {
+    match (base, over) {
+        (Some(base), Some(over)) => Some(merge(base, over)),
+        (base, over) => over.or(base),
+    }
+}
+
+fn merge_columns(
+    base: Vec<ColumnUserConfig>,
+    over: Vec<ColumnUserConfig>,
+) -> Vec<ColumnUserConfig> {
+    let mut merged = Vec::with_capacity(base.len().max(over.len()));
+    let mut base = base.into_iter();
+    let mut over = over.into_iter();
+
+    loop {
+        match (base.next(), over.next()) {
+            (Some(b), Some(o)) => merged.push(b.merge(o)),
+            (Some(column), None) | (None, Some(column)) => merged.push(column),
+            (None, None) => return merged,
         }
     }
 }
@@ -253,14 +1374,53 @@ impl From<StreamConfig> for TableConfig {
             column_default: stream_config.column_default,
             draw_vertical_line: stream_config.draw_vertical_line,
             draw_horizontal_line: stream_config.draw_horizontal_line,
+            border_visibility: BorderVisibility::default(),
             single_line: stream_config.single_line,
             spanning_cells: Vec::new(),
             header: None,
+            header_style: None,
+            uppercase_header: false,
+            header_groups: None,
+            header_group_rows: 0,
+            footer: None,
+            row_annotations: Vec::new(),
+            row_overrides: BTreeMap::new(),
+            table_alignment: TableAlignment::Left,
+            table_indent: 0,
+            terminal_width: None,
+            exact_table_width: None,
+            flex_column: None,
+            max_table_width: None,
+            auto_layout: false,
+            column_summaries: false,
+            locale: None,
+            format_locale: None,
+            sort: Vec::new(),
+            format_rules: Vec::new(),
+            symbols_only: false,
+            color_mode: ColorMode::Preserve,
+            filters: Vec::new(),
+            aggregations: Vec::new(),
+            aggregate_placeholder: "n/a".to_string(),
+            null_placeholder: String::new(),
+            computed_columns: Vec::new(),
+            page_captions: false,
+            page_height: None,
+            style_rules: Vec::new(),
+            row_striping: None,
+            column_order: None,
+            data_hygiene: DataHygiene::Allow,
+            redundant_columns: RedundantColumns::Keep,
+            fail_on: None,
+            title: None,
+            caption: None,
+            placeholders: None,
+            missing_placeholder: MissingPlaceholder::Keep,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub struct StreamUserConfig {
     pub border: Option<BorderUserConfig>,
     pub columns: Option<Vec<ColumnUserConfig>>,
@@ -1,9 +1,11 @@
 pub mod alignment;
 pub mod borders;
+pub mod cell;
 pub mod config;
 
 pub use alignment::*;
 pub use borders::*;
+pub use cell::*;
 pub use config::*;
 
 use thiserror::Error;
@@ -29,6 +31,103 @@ pub enum TableError {
     InvalidAlignment,
     #[error("Border characters cannot be empty")]
     EmptyBorderCharacters,
+    /// A setting names a column or row the table does not have.
+    #[error("{setting} index {index} out of bounds (must be less than {bound})")]
+    IndexOutOfBounds {
+        setting: &'static str,
+        index: usize,
+        bound: usize,
+    },
+    /// Text given for a setting, such as a sort key or a border style name,
+    /// could not be parsed.
+    #[error("Invalid {kind} `{input}`: {reason}")]
+    Parse {
+        kind: &'static str,
+        input: String,
+        reason: String,
+    },
+    /// A filter or style rule pattern is not a valid regular expression.
+    #[error("Invalid {kind} pattern `{pattern}`: {source}")]
+    InvalidPattern {
+        kind: &'static str,
+        pattern: String,
+        #[source]
+        source: regex::Error,
+    },
+    /// Reading or writing failed; `context` says what was being done.
+    #[error("{context}: {source}")]
+    Io {
+        context: String,
+        #[source]
+        source: std::io::Error,
+    },
+    /// A cell has an issue that `DataHygiene::Reject` refuses.
+    #[error("Unclean cell data at {0}")]
+    UncleanData(crate::features::hygiene::HygieneIssue),
+    /// `validate_table` found an issue at or above `fail_on`; every issue it
+    /// found is included.
+    #[error("Validation failed: {0}")]
+    Validation(crate::core::validator::ValidationResult),
+    /// A `{placeholder}` has no value and `MissingPlaceholder::Error` is set.
+    #[error("No value for placeholder `{{{0}}}`")]
+    MissingPlaceholder(String),
+    #[cfg(feature = "json")]
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl TableError {
+    /// A stable kebab-case name for the variant, such as `index-out-of-bounds`,
+    /// for reports that tools parse.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TableError::InvalidData => "invalid-data",
+            TableError::InvalidRowData => "invalid-row-data",
+            TableError::InconsistentRowLength => "inconsistent-row-length",
+            TableError::ControlCharacters => "control-characters",
+            TableError::InvalidConfig(_) => "invalid-config",
+            TableError::InvalidWidth => "invalid-width",
+            TableError::InvalidAlignment => "invalid-alignment",
+            TableError::EmptyBorderCharacters => "empty-border-characters",
+            TableError::IndexOutOfBounds { .. } => "index-out-of-bounds",
+            TableError::Parse { .. } => "parse",
+            TableError::InvalidPattern { .. } => "invalid-pattern",
+            TableError::Io { .. } => "io",
+            TableError::UncleanData(_) => "unclean-data",
+            TableError::Validation(_) => "validation",
+            TableError::MissingPlaceholder(_) => "missing-placeholder",
+            #[cfg(feature = "json")]
+            TableError::Json(_) => "json",
+        }
+    }
+
+    /// Wraps an I/O error with what was being done, for `map_err`:
+    /// `file.write_all(bytes).map_err(TableError::io("Failed to write output"))`.
+    pub fn io(context: impl Into<String>) -> impl FnOnce(std::io::Error) -> TableError {
+        let context = context.into();
+        move |source| TableError::Io { context, source }
+    }
+
+    pub(crate) fn parse(
+        kind: &'static str,
+        input: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> TableError {
+        TableError::Parse {
+            kind,
+            input: input.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl From<std::io::Error> for TableError {
+    fn from(source: std::io::Error) -> Self {
+        TableError::Io {
+            context: "I/O error".to_string(),
+            source,
+        }
+    }
 }
 
 pub type TableResult<T> = Result<T, TableError>;
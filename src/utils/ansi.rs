@@ -1,10 +1,19 @@
+use crate::utils::slab::spaces;
+use crate::utils::unicode::{calculate_string_width, char_width};
 use regex::Regex;
 use std::sync::OnceLock;
 
 static ANSI_REGEX: OnceLock<Regex> = OnceLock::new();
+static HYPERLINK_REGEX: OnceLock<Regex> = OnceLock::new();
 
+// SGR sequences, and the OSC 8 sequences that open and close hyperlinks.
 fn get_ansi_regex() -> &'static Regex {
-    ANSI_REGEX.get_or_init(|| Regex::new(r"\x1b\[[0-9;]*m").unwrap())
+    ANSI_REGEX
+        .get_or_init(|| Regex::new(r"\x1b\[[0-9;]*m|\x1b\]8;[^\x07\x1b]*(?:\x07|\x1b\\)").unwrap())
+}
+
+fn get_hyperlink_regex() -> &'static Regex {
+    HYPERLINK_REGEX.get_or_init(|| Regex::new(r"\x1b\]8;[^\x07\x1b]*(?:\x07|\x1b\\)").unwrap())
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -48,14 +57,61 @@ impl From<&str> for AnsiString {
 }
 
 pub fn calculate_display_width(text: &str) -> usize {
+    if !text.contains('\u{1b}') {
+        return calculate_string_width(text);
+    }
     let clean_text = strip_ansi_sequences(text);
-    unicode_width::UnicodeWidthStr::width(clean_text.as_str())
+    calculate_string_width(&clean_text)
 }
 
 pub fn strip_ansi_sequences(text: &str) -> String {
     get_ansi_regex().replace_all(text, "").to_string()
 }
 
+/// `text` without its OSC 8 hyperlinks, keeping the link text and any colors.
+pub fn strip_hyperlinks(text: &str) -> String {
+    get_hyperlink_regex().replace_all(text, "").to_string()
+}
+
+pub fn contains_ansi_sequences(text: &str) -> bool {
+    text.contains('\u{1b}') && get_ansi_regex().is_match(text)
+}
+
+/// The sequences that close what `text` leaves open at its end: a hyperlink
+/// opened and not ended, then an SGR style set after the last reset. Empty
+/// when nothing is left open.
+pub fn closing_sequences(text: &str) -> &'static str {
+    let (mut styled, mut linked) = (false, false);
+    if text.contains('\u{1b}') {
+        for sequence in get_ansi_regex().find_iter(text).map(|m| m.as_str()) {
+            if let Some(params) = sequence.strip_prefix("\u{1b}[") {
+                styled = !matches!(params, "m" | "0m");
+            } else {
+                // `ESC ] 8 ; params ; uri`, ended by BEL or ST; no URI ends the link.
+                let uri = sequence[4..].split_once(';').map_or("", |(_, rest)| {
+                    rest.trim_end_matches(['\u{7}', '\u{1b}', '\\'])
+                });
+                linked = !uri.is_empty();
+            }
+        }
+    }
+    match (linked, styled) {
+        (false, false) => "",
+        (false, true) => "\u{1b}[0m",
+        (true, false) => "\u{1b}]8;;\u{1b}\\",
+        (true, true) => "\u{1b}]8;;\u{1b}\\\u{1b}[0m",
+    }
+}
+
+/// `text` with its ANSI sequences removed unless `mode` keeps them.
+pub fn apply_color_mode(text: String, mode: crate::types::ColorMode) -> String {
+    if mode.keeps_ansi() || !text.contains('\u{1b}') {
+        text
+    } else {
+        strip_ansi_sequences(&text)
+    }
+}
+
 pub fn split_ansi_string(text: &str) -> Vec<String> {
     let chars: Vec<char> = text.chars().collect();
     let mut result = Vec::new();
@@ -96,7 +152,18 @@ pub fn skip_ansi_sequence(chars: &[char], mut pos: usize) -> usize {
         return pos;
     }
 
-    if chars[pos] == '[' {
+    if chars[pos] == ']' {
+        // OSC, up to BEL or ST.
+        pos += 1;
+        while pos < chars.len() && chars[pos] != '\u{7}' && chars[pos] != '\u{1b}' {
+            pos += 1;
+        }
+        if chars.get(pos) == Some(&'\u{7}') {
+            pos += 1;
+        } else if chars.get(pos + 1) == Some(&'\\') {
+            pos += 2;
+        }
+    } else if chars[pos] == '[' {
         pos += 1;
         while pos < chars.len() && chars[pos] >= '\u{30}' && chars[pos] <= '\u{3F}' {
             pos += 1;
@@ -138,7 +205,7 @@ pub fn slice_ansi_string(text: &str, start: usize, end: usize) -> AnsiString {
             if display_pos >= start && display_pos < end {
                 result.push(chars[i]);
             }
-            display_pos += unicode_width::UnicodeWidthChar::width(chars[i]).unwrap_or(0);
+            display_pos += char_width(chars[i]);
             i += 1;
         }
     }
@@ -155,17 +222,14 @@ pub fn pad_ansi_string(text: &str, width: usize, alignment: crate::types::Alignm
 
     let padding = width - display_width;
     let result = match alignment {
-        crate::types::Alignment::Left => format!("{}{}", text, " ".repeat(padding)),
-        crate::types::Alignment::Right => format!("{}{}", " ".repeat(padding), text),
+        crate::types::Alignment::Left | crate::types::Alignment::Char(_) => {
+            format!("{}{}", text, spaces(padding))
+        }
+        crate::types::Alignment::Right => format!("{}{}", spaces(padding), text),
         crate::types::Alignment::Center => {
             let left_padding = padding / 2;
             let right_padding = padding - left_padding;
-            format!(
-                "{}{}{}",
-                " ".repeat(left_padding),
-                text,
-                " ".repeat(right_padding)
-            )
+            format!("{}{}{}", spaces(left_padding), text, spaces(right_padding))
         }
         crate::types::Alignment::Justify => justify_text(text, width),
     };
@@ -174,69 +238,150 @@ pub fn pad_ansi_string(text: &str, width: usize, alignment: crate::types::Alignm
 }
 
 pub fn truncate_ansi_string(text: &str, max_width: usize) -> AnsiString {
-    let display_width = calculate_display_width(text);
+    truncate_ansi_string_at(text, max_width, crate::types::TruncatePosition::End, "...")
+}
 
+/// Cuts `text` to at most `max_width` columns, putting `ellipsis` where the
+/// start, middle or end was removed. Every escape sequence is kept, so styles
+/// open and close as they did around the removed text. A limit no wider than
+/// the ellipsis just keeps the first `max_width` columns. Styles still open
+/// where the end is cut are closed before the ellipsis, which is drawn plain
+/// like the padding after it.
+pub fn truncate_ansi_string_at(
+    text: &str,
+    max_width: usize,
+    position: crate::types::TruncatePosition,
+    ellipsis: &str,
+) -> AnsiString {
+    use crate::types::TruncatePosition;
+
+    let display_width = calculate_display_width(text);
     if display_width <= max_width {
         return AnsiString::new(text.to_string());
     }
 
-    if max_width <= 3 {
-        return slice_ansi_string(text, 0, max_width);
+    let ellipsis_width = calculate_display_width(ellipsis);
+    if max_width <= ellipsis_width {
+        return AnsiString::new(keep_columns(text, 0..max_width));
     }
 
-    let truncated = slice_ansi_string(text, 0, max_width - 3);
-    AnsiString::new(format!("{}...", truncated.content))
+    let kept = max_width - ellipsis_width;
+    let result = match position {
+        TruncatePosition::End => {
+            let head = keep_columns(text, 0..kept);
+            let closing = closing_sequences(&head);
+            format!("{head}{closing}{ellipsis}")
+        }
+        TruncatePosition::Start => format!(
+            "{ellipsis}{}",
+            keep_columns(text, display_width - kept..display_width)
+        ),
+        TruncatePosition::Middle => {
+            let head = kept.div_ceil(2);
+            let tail = kept - head;
+            format!(
+                "{}{ellipsis}{}",
+                keep_columns(text, 0..head),
+                keep_columns(text, display_width - tail..display_width)
+            )
+        }
+    };
+
+    AnsiString::new(result)
 }
 
-fn justify_text(text: &str, width: usize) -> String {
-    let clean_text = strip_ansi_sequences(text);
-    let words: Vec<&str> = clean_text.split_whitespace().collect();
+// Keeps the characters that lie wholly within `columns`, and every escape
+// sequence wherever it is.
+fn keep_columns(text: &str, columns: std::ops::Range<usize>) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut display_pos = 0;
+    let mut i = 0;
 
-    if words.len() <= 1 {
-        return text.to_string();
+    while i < chars.len() {
+        if chars[i] == '\u{1b}' {
+            let ansi_start = i;
+            i = skip_ansi_sequence(&chars, i);
+            result.extend(&chars[ansi_start..i]);
+        } else {
+            let width = char_width(chars[i]);
+            if display_pos >= columns.start && display_pos + width <= columns.end {
+                result.push(chars[i]);
+            }
+            display_pos += width;
+            i += 1;
+        }
     }
 
-    let total_chars: usize = words.iter().map(|w| w.len()).sum();
-    let total_spaces = width - total_chars;
-    let gaps = words.len() - 1;
+    result
+}
 
-    if gaps == 0 {
-        return text.to_string();
-    }
+/// Like `truncate_ansi_string`, but when the cut would split a word it backs up
+/// to the previous word boundary. A single word longer than the limit is still
+/// cut mid-word.
+pub fn truncate_ansi_string_at_word(text: &str, max_width: usize) -> AnsiString {
+    truncate_ansi_string_at_word_with(text, max_width, "...")
+}
 
-    let spaces_per_gap = total_spaces / gaps;
-    let extra_spaces = total_spaces % gaps;
+/// `truncate_ansi_string_at_word` with a custom ellipsis.
+pub fn truncate_ansi_string_at_word_with(
+    text: &str,
+    max_width: usize,
+    ellipsis: &str,
+) -> AnsiString {
+    use crate::types::TruncatePosition;
+
+    let ellipsis_width = calculate_display_width(ellipsis);
+    if calculate_display_width(text) <= max_width || max_width <= ellipsis_width {
+        return truncate_ansi_string_at(text, max_width, TruncatePosition::End, ellipsis);
+    }
 
-    let mut result = String::new();
-    let ansi_segments = split_ansi_string(text);
-    let mut segment_idx = 0;
-
-    for (i, _word) in words.iter().enumerate() {
-        while segment_idx < ansi_segments.len() {
-            let segment = &ansi_segments[segment_idx];
-            if segment.starts_with('\u{1b}') {
-                result.push_str(segment);
-                segment_idx += 1;
-            } else {
-                result.push_str(segment);
-                segment_idx += 1;
-                break;
-            }
-        }
+    let plain = strip_ansi_sequences(text);
+    let prefix =
+        strip_ansi_sequences(&slice_ansi_string(text, 0, max_width - ellipsis_width).content);
+    let cuts_word = plain
+        .chars()
+        .nth(prefix.chars().count())
+        .is_some_and(|next| !next.is_whitespace());
+
+    let kept = match prefix.rfind(char::is_whitespace) {
+        Some(boundary) if cuts_word => prefix[..boundary].trim_end(),
+        _ => prefix.trim_end(),
+    };
+    let kept = if kept.is_empty() {
+        prefix.as_str()
+    } else {
+        kept
+    };
 
-        if i < words.len() - 1 {
-            let spaces = spaces_per_gap + if i < extra_spaces { 1 } else { 0 };
-            result.push_str(&" ".repeat(spaces));
-        }
-    }
+    let truncated = keep_columns(text, 0..calculate_display_width(kept));
+    let closing = closing_sequences(&truncated);
+    AnsiString::new(format!("{truncated}{closing}{ellipsis}"))
+}
 
-    result
+fn justify_text(text: &str, width: usize) -> String {
+    crate::features::alignment_processor::AlignmentProcessor::justify_line(text, width).into_owned()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_hyperlinks_take_no_columns() {
+        let link = "\u{1b}]8;;https://example.com\u{1b}\\docs\u{1b}]8;;\u{7}";
+        assert_eq!(calculate_display_width(link), 4);
+        assert_eq!(strip_ansi_sequences(link), "docs");
+        assert_eq!(
+            strip_hyperlinks(&format!("\u{1b}[1m{link}")),
+            "\u{1b}[1mdocs"
+        );
+        assert_eq!(
+            slice_ansi_string(link, 0, 2).content,
+            "\u{1b}]8;;https://example.com\u{1b}\\do"
+        );
+    }
+
     #[test]
     fn test_calculate_display_width() {
         assert_eq!(calculate_display_width("hello"), 5);
@@ -262,6 +407,92 @@ mod tests {
         assert_eq!(calculate_display_width(&sliced.content), 5);
     }
 
+    #[test]
+    fn test_truncate_ansi_string_at_word() {
+        assert_eq!(
+            truncate_ansi_string_at_word("this is a long text", 9).content,
+            "this..."
+        );
+        assert_eq!(
+            truncate_ansi_string_at_word("this is a long text", 11).content,
+            "this is..."
+        );
+        assert_eq!(
+            truncate_ansi_string_at_word("Categoryname", 8).content,
+            "Categ..."
+        );
+        assert_eq!(
+            truncate_ansi_string_at_word("\u{1b}[31mred apple pie\u{1b}[0m", 12).content,
+            "\u{1b}[31mred apple\u{1b}[0m..."
+        );
+        assert_eq!(truncate_ansi_string_at_word("short", 10).content, "short");
+    }
+
+    #[test]
+    fn test_truncation_closes_styles_before_the_ellipsis() {
+        use crate::types::TruncatePosition;
+
+        assert_eq!(
+            truncate_ansi_string_at("\u{1b}[41mabcdefgh", 5, TruncatePosition::End, "..").content,
+            "\u{1b}[41mabc\u{1b}[0m.."
+        );
+        assert_eq!(
+            truncate_ansi_string_at_word("\u{1b}[41mred apple pie", 12).content,
+            "\u{1b}[41mred apple\u{1b}[0m..."
+        );
+
+        // The padding after the ellipsis is drawn plain, as for uncolored
+        // cells.
+        let config = crate::TableUserConfig {
+            column_default: Some(crate::ColumnUserConfig {
+                truncate: Some(12),
+                truncate_at_word: Some(true),
+                padding_right: Some(3),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let rows = vec![vec!["\u{1b}[41mred apple pie\u{1b}[0m".to_string()]];
+        let output = crate::table(&rows, Some(&config)).unwrap();
+        assert_eq!(
+            output.lines().nth(1).unwrap(),
+            "│ \u{1b}[41mred apple\u{1b}[0m...   │"
+        );
+    }
+
+    #[test]
+    fn test_truncate_ansi_string_at_positions() {
+        use crate::types::TruncatePosition;
+
+        let path = "/home/me/src/app/file.rs";
+        assert_eq!(
+            truncate_ansi_string_at(path, 15, TruncatePosition::Middle, "…").content,
+            "/home/m…file.rs"
+        );
+        assert_eq!(
+            truncate_ansi_string_at(path, 10, TruncatePosition::Start, "...").content,
+            "...file.rs"
+        );
+        assert_eq!(
+            truncate_ansi_string_at(path, 10, TruncatePosition::End, "~").content,
+            "/home/me/~"
+        );
+        assert_eq!(
+            truncate_ansi_string_at(
+                "\u{1b}[31mabcdef\u{1b}[0mgh",
+                5,
+                TruncatePosition::Middle,
+                ".."
+            )
+            .content,
+            "\u{1b}[31mab\u{1b}[0m..\u{1b}[31m\u{1b}[0mh"
+        );
+        assert_eq!(
+            truncate_ansi_string_at("数据表格", 5, TruncatePosition::Start, "…").content,
+            "…表格"
+        );
+    }
+
     #[test]
     fn test_pad_ansi_string() {
         let text = "\u{1b}[31mhello\u{1b}[39m";
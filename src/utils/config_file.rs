@@ -0,0 +1,642 @@
+use crate::features::aggregation::ColumnAggregation;
+use crate::features::computed::ComputedColumn;
+use crate::features::number_format::LocaleUserConfig;
+use crate::features::spanning::{HeaderGroup, HeaderSpec};
+use crate::features::styling::{CellStyle, StyleRule};
+use crate::types::{
+    BorderUserConfig, BorderVisibility, ColumnUserConfig, FormatRule, RowAnnotation, RowOverride,
+    SortKey, SpanningCellConfig, TableError, TableLabel, TableResult, TableUserConfig,
+};
+use serde::Deserialize;
+use serde::de::{self, Visitor};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    /// Parsed with the `yaml` feature; without it, YAML configs are rejected.
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// `.toml` files are TOML, `.yaml` and `.yml` files YAML, everything else
+    /// is JSON.
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> Self {
+        let extension = path
+            .as_ref()
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("toml") => ConfigFormat::Toml,
+            Some("yaml" | "yml") => ConfigFormat::Yaml,
+            _ => ConfigFormat::Json,
+        }
+    }
+}
+
+/// A key the config types do not know, reported instead of being silently ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigWarning {
+    pub path: String,
+    pub key: String,
+    pub suggestion: Option<String>,
+}
+
+impl std::fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown key `{}`", self.key)?;
+        if !self.path.is_empty() {
+            write!(f, " in `{}`", self.path)?;
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "; did you mean `{suggestion}`?")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a config, rejecting unknown keys such as a misspelled `"alignmnet"`.
+pub fn parse_config_strict(content: &str, format: ConfigFormat) -> TableResult<TableUserConfig> {
+    let (config, warnings) = parse_config_lenient(content, format)?;
+
+    if warnings.is_empty() {
+        Ok(config)
+    } else {
+        let messages: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+        Err(TableError::InvalidConfig(messages.join("\n")))
+    }
+}
+
+/// Parses a config, ignoring unknown keys but reporting each one as a warning.
+pub fn parse_config_lenient(
+    content: &str,
+    format: ConfigFormat,
+) -> TableResult<(TableUserConfig, Vec<ConfigWarning>)> {
+    parse_config_detailed(content, format).map_err(|e| TableError::InvalidConfig(e.to_string()))
+}
+
+/// Why a config could not be parsed, with where and, for a misspelled value,
+/// the valid name closest to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigParseError {
+    pub message: String,
+    /// The key path of the value that failed, e.g. `columns[0].alignment`.
+    pub path: Option<String>,
+    /// The 1-based line and column of the failure.
+    pub position: Option<(usize, usize)>,
+    pub suggestion: Option<String>,
+}
+
+impl std::fmt::Display for ConfigParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some((line, column)) = self.position {
+            write!(f, "{line}:{column}: ")?;
+        }
+        f.write_str(&self.message)?;
+        if let Some(path) = &self.path {
+            write!(f, " (at `{path}`)")?;
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "; did you mean `{suggestion}`?")?;
+        }
+        Ok(())
+    }
+}
+
+/// `parse_config_lenient` with the parse error kept in its parts.
+pub fn parse_config_detailed(
+    content: &str,
+    format: ConfigFormat,
+) -> Result<(TableUserConfig, Vec<ConfigWarning>), ConfigParseError> {
+    let config = deserialize_config(content, format)?;
+
+    let value: Value = match format {
+        ConfigFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+        ConfigFormat::Toml => {
+            toml::from_str(content).map_err(|e: toml::de::Error| e.message().to_string())
+        }
+        ConfigFormat::Yaml => yaml_value(content),
+    }
+    .map_err(|message| ConfigParseError {
+        message,
+        path: None,
+        position: None,
+        suggestion: None,
+    })?;
+
+    let mut warnings = Vec::new();
+    collect_unknown_keys(&value, Schema::Table, "", &mut warnings);
+
+    Ok((config, warnings))
+}
+
+fn deserialize_config(
+    content: &str,
+    format: ConfigFormat,
+) -> Result<TableUserConfig, ConfigParseError> {
+    let (message, key_path, position) = match format {
+        ConfigFormat::Toml => {
+            match serde_path_to_error::deserialize(toml::Deserializer::new(content)) {
+                Ok(config) => return Ok(config),
+                Err(e) => (
+                    e.inner().message().to_string(),
+                    e.path().to_string(),
+                    e.inner()
+                        .span()
+                        .map(|span| line_and_column(content, span.start)),
+                ),
+            }
+        }
+        ConfigFormat::Yaml => match deserialize_yaml(content) {
+            Ok(config) => return Ok(config),
+            Err(error) => error,
+        },
+        ConfigFormat::Json => {
+            let mut deserializer = serde_json::Deserializer::from_str(content);
+            match serde_path_to_error::deserialize(&mut deserializer) {
+                Ok(config) => return Ok(config),
+                Err(e) => {
+                    let inner = e.inner();
+                    let message = inner.to_string();
+                    let message = message
+                        .rsplit_once(" at line ")
+                        .map_or(message.as_str(), |(message, _)| message)
+                        .to_string();
+                    (
+                        message,
+                        e.path().to_string(),
+                        Some((inner.line(), inner.column())),
+                    )
+                }
+            }
+        }
+    };
+
+    Err(ConfigParseError {
+        suggestion: suggest_from_error_message(&message),
+        message,
+        path: (key_path != ".").then_some(key_path),
+        position,
+    })
+}
+
+// A failed parse as the message, key path and position it is reported with.
+type ParseFailure = (String, String, Option<(usize, usize)>);
+
+#[cfg(feature = "yaml")]
+fn deserialize_yaml(content: &str) -> Result<TableUserConfig, ParseFailure> {
+    serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(content)).map_err(|e| {
+        let inner = e.inner();
+        let path = e.path().to_string();
+        // serde_yaml writes the path before the message and the position after.
+        let message = inner.to_string();
+        let message = message
+            .split_once(" at line ")
+            .map_or(message.as_str(), |(message, _)| message);
+        let message = message
+            .strip_prefix(&format!("{path}: "))
+            .unwrap_or(message)
+            .to_string();
+        let position = inner
+            .location()
+            .map(|location| (location.line(), location.column()));
+        (message, path, position)
+    })
+}
+
+#[cfg(not(feature = "yaml"))]
+fn deserialize_yaml(_content: &str) -> Result<TableUserConfig, ParseFailure> {
+    Err((YAML_DISABLED.to_string(), ".".to_string(), None))
+}
+
+#[cfg(feature = "yaml")]
+fn yaml_value(content: &str) -> Result<Value, String> {
+    serde_yaml::from_str(content).map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "yaml"))]
+fn yaml_value(_content: &str) -> Result<Value, String> {
+    Err(YAML_DISABLED.to_string())
+}
+
+#[cfg(not(feature = "yaml"))]
+const YAML_DISABLED: &str = "YAML configs need the `yaml` feature";
+
+fn line_and_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
+}
+
+// Serde reports misspellings as "unknown field `x`, expected one of `a`, `b`"
+// (or "unknown variant ..."); the first quoted name is the input, the rest the
+// valid names.
+fn suggest_from_error_message(message: &str) -> Option<String> {
+    if !message.starts_with("unknown field") && !message.starts_with("unknown variant") {
+        return None;
+    }
+
+    let mut names = message.split('`').skip(1).step_by(2);
+    let input = names.next()?;
+    closest_match(input, names).map(str::to_string)
+}
+
+/// The candidate within a small edit distance of `input`, if any.
+pub fn closest_match<'a>(
+    input: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (strsim::damerau_levenshtein(input, candidate), candidate))
+        .filter(|&(distance, _)| distance <= (input.chars().count() / 3).max(2))
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Schema {
+    Table,
+    Column,
+    Border,
+    BorderVisibility,
+    SpanningCell,
+    RowAnnotation,
+    // `row_overrides`, keyed by row index.
+    RowOverrides,
+    RowOverride,
+    HeaderSpec,
+    HeaderGroup,
+    CellStyle,
+    StyleRule,
+    FormatRule,
+    SortKey,
+    Aggregation,
+    ComputedColumn,
+    Locale,
+    Label,
+}
+
+impl Schema {
+    fn fields(self) -> &'static [&'static str] {
+        match self {
+            Schema::Table => struct_fields::<TableUserConfig>(),
+            Schema::Column => struct_fields::<ColumnUserConfig>(),
+            Schema::Border => struct_fields::<BorderUserConfig>(),
+            Schema::BorderVisibility => struct_fields::<BorderVisibility>(),
+            Schema::SpanningCell => struct_fields::<SpanningCellConfig>(),
+            Schema::RowAnnotation => struct_fields::<RowAnnotation>(),
+            Schema::RowOverrides => &[],
+            Schema::RowOverride => struct_fields::<RowOverride>(),
+            Schema::HeaderSpec => struct_fields::<HeaderSpec>(),
+            Schema::HeaderGroup => struct_fields::<HeaderGroup>(),
+            Schema::CellStyle => struct_fields::<CellStyle>(),
+            Schema::StyleRule => struct_fields::<StyleRule>(),
+            Schema::FormatRule => struct_fields::<FormatRule>(),
+            Schema::SortKey => struct_fields::<SortKey>(),
+            Schema::Aggregation => struct_fields::<ColumnAggregation>(),
+            Schema::ComputedColumn => struct_fields::<ComputedColumn>(),
+            Schema::Locale => struct_fields::<LocaleUserConfig>(),
+            Schema::Label => struct_fields::<TableLabel>(),
+        }
+    }
+
+    // Nested config objects; anything else is a leaf value.
+    fn child(self, key: &str) -> Option<Schema> {
+        match (self, key) {
+            (Schema::Table, "border") => Some(Schema::Border),
+            (Schema::Table, "columns" | "column_default") => Some(Schema::Column),
+            (Schema::Table, "header" | "footer") => Some(Schema::Table),
+            (Schema::Table, "border_visibility") => Some(Schema::BorderVisibility),
+            (Schema::Table, "spanning_cells") => Some(Schema::SpanningCell),
+            (Schema::Table, "row_annotations") => Some(Schema::RowAnnotation),
+            (Schema::Table, "row_overrides") => Some(Schema::RowOverrides),
+            (Schema::Table, "header_groups") => Some(Schema::HeaderSpec),
+            (Schema::Table, "header_style") => Some(Schema::CellStyle),
+            (Schema::Table, "style_rules") => Some(Schema::StyleRule),
+            (Schema::Table, "format_rules") => Some(Schema::FormatRule),
+            (Schema::Table, "sort") => Some(Schema::SortKey),
+            (Schema::Table, "aggregations") => Some(Schema::Aggregation),
+            (Schema::Table, "computed_columns") => Some(Schema::ComputedColumn),
+            (Schema::Table, "format_locale") => Some(Schema::Locale),
+            (Schema::Table, "title" | "caption") => Some(Schema::Label),
+            (Schema::HeaderSpec, "rows") => Some(Schema::HeaderGroup),
+            (Schema::StyleRule, "style") => Some(Schema::CellStyle),
+            _ => None,
+        }
+    }
+
+    // Maps whose keys are the user's, such as row indices, with the schema
+    // of their values.
+    fn entries(self) -> Option<Schema> {
+        match self {
+            Schema::RowOverrides => Some(Schema::RowOverride),
+            _ => None,
+        }
+    }
+}
+
+fn collect_unknown_keys(
+    value: &Value,
+    schema: Schema,
+    path: &str,
+    warnings: &mut Vec<ConfigWarning>,
+) {
+    match value {
+        Value::Array(items) => {
+            for (idx, item) in items.iter().enumerate() {
+                collect_unknown_keys(item, schema, &format!("{path}[{idx}]"), warnings);
+            }
+        }
+        Value::Object(object) => {
+            if let Some(entry_schema) = schema.entries() {
+                for (key, entry) in object {
+                    let entry_path = format!("{path}.{key}");
+                    collect_unknown_keys(entry, entry_schema, &entry_path, warnings);
+                }
+                return;
+            }
+            let fields = schema.fields();
+            for (key, child) in object {
+                if !fields.contains(&key.as_str()) {
+                    warnings.push(ConfigWarning {
+                        path: path.to_string(),
+                        key: key.clone(),
+                        suggestion: closest_match(key, fields.iter().copied()).map(str::to_string),
+                    });
+                } else if let Some(child_schema) = schema.child(key) {
+                    let child_path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    collect_unknown_keys(child, child_schema, &child_path, warnings);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+// Serde-derived structs hand their field list to `deserialize_struct`; this
+// deserializer records it and bails out, so the known keys never have to be
+// duplicated by hand.
+fn struct_fields<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldProbe(&mut fields));
+    fields
+}
+
+struct FieldProbe<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> de::Deserializer<'de> for FieldProbe<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("field probe"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(de::Error::custom("field probe"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TYPO_CONFIG: &str = r#"{
+        "single_line": true,
+        "columns": [{"alignmnet": "left"}],
+        "border": {"bodyLeft": "|"},
+        "colour": "red"
+    }"#;
+
+    #[test]
+    fn test_parse_config_reports_position_and_path() {
+        let content = "{\n  \"columns\": [\n    {\"alignment\": \"centre\"}\n  ]\n}";
+        let error = parse_config_strict(content, ConfigFormat::Json)
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.contains("3:"), "{error}");
+        assert!(error.contains("`columns[0].alignment`"), "{error}");
+        assert!(error.contains("did you mean `center`?"), "{error}");
+    }
+
+    #[test]
+    fn test_parse_config_lenient_collects_warnings() {
+        let (config, warnings) = parse_config_lenient(TYPO_CONFIG, ConfigFormat::Json).unwrap();
+        assert_eq!(config.single_line, Some(true));
+
+        let messages: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "unknown key `alignmnet` in `columns[0]`; did you mean `alignment`?",
+                "unknown key `bodyLeft` in `border`; did you mean `body_left`?",
+                "unknown key `colour`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_config_strict_rejects_unknown_keys() {
+        let error = parse_config_strict(TYPO_CONFIG, ConfigFormat::Json)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("did you mean `alignment`?"), "{error}");
+
+        for (typo, message) in [
+            (
+                r#"{"footer": {"colums": []}}"#,
+                "unknown key `colums` in `footer`; did you mean `columns`?",
+            ),
+            (
+                r#"{"border_visibility": {"outter": false}}"#,
+                "unknown key `outter` in `border_visibility`; did you mean `outer`?",
+            ),
+            (
+                r#"{"row_overrides": {"0": {"separator_aftr": false}}}"#,
+                "unknown key `separator_aftr` in `row_overrides.0`; did you mean `separator_after`?",
+            ),
+            (
+                r#"{"header_groups": {"rows": [], "spams": 1}}"#,
+                "unknown key `spams` in `header_groups`",
+            ),
+            (
+                r#"{"header_groups": {"rows": [[{"title": "A", "spam": 2}]]}}"#,
+                "unknown key `spam` in `header_groups.rows[0][0]`; did you mean `span`?",
+            ),
+            (
+                r#"{"style_rules": [{"style": {"bolt": true}}]}"#,
+                "unknown key `bolt` in `style_rules[0].style`; did you mean `bold`?",
+            ),
+        ] {
+            let (_, warnings) = parse_config_lenient(typo, ConfigFormat::Json).unwrap();
+            let messages: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+            assert_eq!(messages, [message], "{typo}");
+            assert!(
+                parse_config_strict(typo, ConfigFormat::Json).is_err(),
+                "{typo}"
+            );
+        }
+
+        let config = parse_config_strict(r#"{"table_indent": 2}"#, ConfigFormat::Json).unwrap();
+        assert_eq!(config.table_indent, Some(2));
+    }
+
+    #[test]
+    fn test_parse_config_toml() {
+        use crate::features::stats::HeaderRow;
+
+        let config = parse_config_strict("single_line = true\n", ConfigFormat::Toml).unwrap();
+        assert_eq!(config.single_line, Some(true));
+        let config =
+            parse_config_strict("first_row_is_header = \"auto\"\n", ConfigFormat::Toml).unwrap();
+        assert_eq!(config.first_row_is_header, Some(HeaderRow::Auto));
+        let config =
+            parse_config_strict("first_row_is_header = true\n", ConfigFormat::Toml).unwrap();
+        assert_eq!(config.first_row_is_header, Some(HeaderRow::Yes));
+        assert!(
+            parse_config_strict("first_row_is_header = \"maybe\"\n", ConfigFormat::Toml).is_err()
+        );
+
+        let error = parse_config_strict("single_line = \"yes\"\n", ConfigFormat::Toml)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("1:"), "{error}");
+        assert!(error.contains("`single_line`"), "{error}");
+
+        let (_, warnings) =
+            parse_config_lenient("[[columns]]\nwdth = 3\n", ConfigFormat::Toml).unwrap();
+        assert_eq!(warnings[0].suggestion.as_deref(), Some("width"));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_parse_config_yaml() {
+        let content = "single_line: true\ncolumns:\n  - width: 12\n  - width: 30%\n";
+        let config = parse_config_strict(content, ConfigFormat::Yaml).unwrap();
+        assert_eq!(config.single_line, Some(true));
+        assert_eq!(config.columns.unwrap().len(), 2);
+
+        let error = parse_config_strict("columns:\n  - alignment: centre\n", ConfigFormat::Yaml)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains(": 2:16: unknown variant"), "{error}");
+        assert!(error.contains("`columns[0].alignment`"), "{error}");
+        assert!(error.contains("did you mean `center`?"), "{error}");
+
+        let (_, warnings) =
+            parse_config_lenient("border:\n  bodyLeft: '|'\n", ConfigFormat::Yaml).unwrap();
+        assert_eq!(warnings[0].suggestion.as_deref(), Some("body_left"));
+    }
+
+    #[cfg(not(feature = "yaml"))]
+    #[test]
+    fn test_parse_config_yaml_needs_the_feature() {
+        let error = parse_config_strict("single_line: true\n", ConfigFormat::Yaml).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("Invalid configuration: {YAML_DISABLED}")
+        );
+    }
+
+    #[test]
+    fn test_parse_config_widths() {
+        use crate::types::Width;
+
+        let config = parse_config_strict(
+            r#"{"columns": [{"width": 12}, {"width": "30%"}]}"#,
+            ConfigFormat::Json,
+        )
+        .unwrap();
+        let widths: Vec<_> = config.columns.unwrap().iter().map(|c| c.width).collect();
+        assert_eq!(widths, [Some(Width::Chars(12)), Some(Width::Percent(30))]);
+
+        let config =
+            parse_config_strict("[column_default]\nwidth = \"25%\"\n", ConfigFormat::Toml).unwrap();
+        assert_eq!(
+            config.column_default.unwrap().width,
+            Some(Width::Percent(25))
+        );
+
+        assert!(
+            parse_config_strict(
+                r#"{"column_default": {"width": "wide"}}"#,
+                ConfigFormat::Json
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_config_alignments() {
+        use crate::types::Alignment;
+
+        let config = parse_config_strict(
+            r#"{"columns": [{"alignment": "right"}, {"alignment": "decimal"}, {"alignment": "char:,"}]}"#,
+            ConfigFormat::Json,
+        )
+        .unwrap();
+        let alignments: Vec<_> = config
+            .columns
+            .unwrap()
+            .iter()
+            .map(|c| c.alignment)
+            .collect();
+        assert_eq!(
+            alignments,
+            [
+                Some(Alignment::Right),
+                Some(Alignment::Char('.')),
+                Some(Alignment::Char(','))
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&Alignment::Char('.')).unwrap(),
+            r#""char:.""#
+        );
+
+        let error = parse_config_strict(
+            r#"{"column_default": {"alignment": "rigth"}}"#,
+            ConfigFormat::Json,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("did you mean `right`?"), "{error}");
+    }
+
+    #[test]
+    fn test_config_format_from_path() {
+        assert_eq!(ConfigFormat::from_path("a/table.TOML"), ConfigFormat::Toml);
+        assert_eq!(ConfigFormat::from_path("table.json"), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::from_path("table.yml"), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path("table.Yaml"), ConfigFormat::Yaml);
+    }
+
+    #[test]
+    fn test_closest_match() {
+        assert_eq!(
+            closest_match("alignmnet", ["alignment", "width"]),
+            Some("alignment")
+        );
+        assert_eq!(closest_match("zzz", ["left", "right"]), None);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::types::{Row, TableError, TableResult};
+use crate::utils::ansi::strip_ansi_sequences;
+
+/// When `table_to_csv` wraps fields in double quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuotePolicy {
+    /// Only fields containing the delimiter, a quote, or a line break.
+    #[default]
+    Necessary,
+    /// Every field.
+    Always,
+    /// Every field that does not parse as a number, plus those `Necessary` quotes.
+    NonNumeric,
+}
+
+pub fn parse_csv(input: &str, delimiter: char) -> TableResult<Vec<Row>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut field_started = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if in_quotes {
+            if ch == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(ch);
+            }
+            continue;
+        }
+
+        match ch {
+            '"' if !field_started => {
+                in_quotes = true;
+                field_started = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+                field_started = false;
+            }
+            _ if ch == delimiter => {
+                row.push(std::mem::take(&mut field));
+                field_started = false;
+            }
+            _ => {
+                field.push(ch);
+                field_started = true;
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(TableError::InvalidConfig(
+            "Unterminated quoted field in CSV input".to_string(),
+        ));
+    }
+
+    if field_started || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+/// Writes rows as CSV (or TSV with `'\t'`). ANSI styling is stripped, quotes are
+/// doubled, and rows end with `\n`, so `parse_csv` reads the output back unchanged.
+pub fn table_to_csv(rows: &[Row], delimiter: char, quote_policy: QuotePolicy) -> String {
+    let mut output = String::new();
+
+    for row in rows {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|cell| quote_csv_field(&strip_ansi_sequences(cell), delimiter, quote_policy))
+            .collect();
+        output.push_str(&fields.join(&delimiter.to_string()));
+        output.push('\n');
+    }
+
+    output
+}
+
+fn quote_csv_field(field: &str, delimiter: char, quote_policy: QuotePolicy) -> String {
+    let necessary = field.contains(delimiter)
+        || field.contains('"')
+        || field.contains('\n')
+        || field.contains('\r');
+
+    let quote = match quote_policy {
+        QuotePolicy::Necessary => necessary,
+        QuotePolicy::Always => true,
+        QuotePolicy::NonNumeric => necessary || field.trim().parse::<f64>().is_err(),
+    };
+
+    if quote {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_basic() {
+        let rows = parse_csv("Name,Age\nJohn,30\n", ',').unwrap();
+        assert_eq!(rows, vec![vec!["Name", "Age"], vec!["John", "30"]]);
+    }
+
+    #[test]
+    fn test_parse_csv_quoted_fields() {
+        let rows = parse_csv("\"a,b\",\"say \"\"hi\"\"\",\"multi\nline\"\r\n", ',').unwrap();
+        assert_eq!(rows, vec![vec!["a,b", "say \"hi\"", "multi\nline"]]);
+    }
+
+    #[test]
+    fn test_parse_csv_tab_delimiter() {
+        let rows = parse_csv("a\tb\n1\t", '\t').unwrap();
+        assert_eq!(rows, vec![vec!["a", "b"], vec!["1", ""]]);
+    }
+
+    #[test]
+    fn test_table_to_csv_round_trip() {
+        let rows = vec![
+            vec!["a,b".to_string(), "say \"hi\"".to_string()],
+            vec![
+                "multi\nline".to_string(),
+                "\u{1b}[31mred\u{1b}[0m".to_string(),
+            ],
+        ];
+
+        let csv = table_to_csv(&rows, ',', QuotePolicy::Necessary);
+        assert_eq!(csv, "\"a,b\",\"say \"\"hi\"\"\"\n\"multi\nline\",red\n");
+        assert_eq!(
+            parse_csv(&csv, ',').unwrap(),
+            vec![vec!["a,b", "say \"hi\""], vec!["multi\nline", "red"]]
+        );
+    }
+
+    #[test]
+    fn test_table_to_csv_quote_policies() {
+        let rows = vec![vec!["x".to_string(), "1.5".to_string()]];
+        assert_eq!(
+            table_to_csv(&rows, '\t', QuotePolicy::Always),
+            "\"x\"\t\"1.5\"\n"
+        );
+        assert_eq!(
+            table_to_csv(&rows, ',', QuotePolicy::NonNumeric),
+            "\"x\",1.5\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_unterminated_quote() {
+        assert!(parse_csv("\"open", ',').is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::utils::ansi::{slice_ansi_string, strip_ansi_sequences};
+use std::ops::Range;
 use unicode_width::UnicodeWidthChar;
 
 pub fn convert_ansi_to_html(text: &str) -> String {
@@ -8,6 +10,68 @@ pub fn convert_ansi_to_html(text: &str) -> String {
     )
 }
 
+/// A tooltip covering display columns `start..end` of the titled lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlTitle {
+    pub start: usize,
+    pub end: usize,
+    pub title: String,
+}
+
+/// Like `convert_ansi_to_html`, but wraps the given column ranges of
+/// `title_lines` in `<span title="...">` elements.
+pub fn convert_ansi_to_html_with_titles(
+    text: &str,
+    title_lines: Range<usize>,
+    titles: &[HtmlTitle],
+) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let title_lines = title_lines.start.min(lines.len())..title_lines.end.min(lines.len());
+
+    let mut parts = Vec::new();
+    if title_lines.start > 0 {
+        parts.push(convert_segment(&lines[..title_lines.start].join("\n")));
+    }
+    for line in &lines[title_lines.clone()] {
+        let mut html = String::new();
+        let mut position = 0;
+        for title in titles {
+            html.push_str(&convert_segment(
+                &slice_ansi_string(line, position, title.start).content,
+            ));
+            html.push_str(&format!(
+                "<span title=\"{}\">{}</span>",
+                escape_html_attribute(&title.title),
+                convert_segment(&slice_ansi_string(line, title.start, title.end).content)
+            ));
+            position = title.end;
+        }
+        html.push_str(&convert_segment(
+            &slice_ansi_string(line, position, usize::MAX).content,
+        ));
+        parts.push(html);
+    }
+    if title_lines.end < lines.len() {
+        parts.push(convert_segment(&lines[title_lines.end..].join("\n")));
+    }
+    let body = parts.join("\n");
+
+    format!("<pre style=\"margin: 0; font-family: monospace; white-space: pre;\">{body}</pre>")
+}
+
+fn convert_segment(text: &str) -> String {
+    let html = ansi_to_html::convert(text).unwrap_or(text.to_string());
+    wrap_emojis_with_fixed_width(&html)
+}
+
+fn escape_html_attribute(text: &str) -> String {
+    strip_ansi_sequences(text)
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
 fn wrap_emojis_with_fixed_width(text: &str) -> String {
     let mut result = String::new();
 
@@ -93,6 +157,21 @@ mod tests {
         assert!(result.ends_with("</pre>"));
     }
 
+    #[test]
+    fn test_convert_with_titles() {
+        let input = "+---+---+\n| A | B |\n+---+---+";
+        let titles = vec![HtmlTitle {
+            start: 1,
+            end: 4,
+            title: "text, \"1\" distinct".to_string(),
+        }];
+        let result = convert_ansi_to_html_with_titles(input, 1..2, &titles);
+
+        assert!(result.contains(
+            "+---+---+\n|<span title=\"text, &quot;1&quot; distinct\"> A </span>| B |\n+---+---+"
+        ));
+    }
+
     #[test]
     fn test_emoji_fixed_width() {
         // Test with mix of 1-width symbols and 2-width emojis
@@ -0,0 +1,112 @@
+use crate::types::BorderConfig;
+use crate::utils::ansi::closing_sequences;
+use crate::utils::slab::push_repeated;
+
+/// Assembles one table line at the end of a `String`: the left edge, each
+/// column's segment with the join between them, then the right edge. Row
+/// lines, border lines and Markdown rows are all joined here.
+///
+/// Segments are taken as they are, already padded to their column's width. A
+/// segment that leaves a color or a hyperlink open is closed right after it,
+/// so nothing bleeds into the border or the next column. Segments cannot
+/// contain line breaks.
+#[derive(Debug)]
+pub struct LineBuilder<'a> {
+    out: &'a mut String,
+    join: &'a str,
+    right: &'a str,
+    segments: usize,
+}
+
+impl<'a> LineBuilder<'a> {
+    /// Starts a line at the end of `out` with its `left` edge.
+    pub fn new(out: &'a mut String, left: &str, join: &'a str, right: &'a str) -> Self {
+        out.push_str(left);
+        Self {
+            out,
+            join,
+            right,
+            segments: 0,
+        }
+    }
+
+    /// A line of cells between the body characters of `border`.
+    pub fn body(out: &'a mut String, border: &'a BorderConfig) -> Self {
+        Self::new(
+            out,
+            &border.body_left,
+            &border.body_join,
+            &border.body_right,
+        )
+    }
+
+    /// Appends `segment` as the next column.
+    pub fn segment(&mut self, segment: &str) -> &mut Self {
+        self.segment_with(|out| out.push_str(segment))
+    }
+
+    /// Appends each of `segments` as a column.
+    pub fn segments<S: AsRef<str>>(&mut self, segments: impl IntoIterator<Item = S>) -> &mut Self {
+        for segment in segments {
+            self.segment(segment.as_ref());
+        }
+        self
+    }
+
+    /// Appends `count` copies of `unit` as the next column, as border lines
+    /// run across their columns.
+    pub fn run(&mut self, unit: &str, count: usize) -> &mut Self {
+        self.segment_with(|out| push_repeated(out, unit, count))
+    }
+
+    /// Appends the next column as `write` writes it to the end of the line, so
+    /// a cell can be laid out in place.
+    pub fn segment_with(&mut self, write: impl FnOnce(&mut String)) -> &mut Self {
+        if self.segments > 0 {
+            self.out.push_str(self.join);
+        }
+        let start = self.out.len();
+        write(self.out);
+
+        let segment = &self.out[start..];
+        debug_assert!(
+            !segment.contains('\n'),
+            "line segments cannot break the line"
+        );
+        let closing = closing_sequences(segment);
+        self.out.push_str(closing);
+        self.segments += 1;
+        self
+    }
+
+    /// Ends the line with its right edge.
+    pub fn finish(self) {
+        self.out.push_str(self.right);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments_are_joined_and_closed() {
+        let mut line = String::from("> ");
+        let mut builder = LineBuilder::new(&mut line, "|", "+", "|");
+        builder
+            .segment("\u{1b}[1mbold\u{1b}[0m")
+            .segment("\u{1b}]8;;https://example.com\u{1b}\\link")
+            .run("-", 2)
+            .segments(["\u{1b}[32mopen", "\u{1b}[32mclosed\u{1b}[m"]);
+        builder.finish();
+        assert_eq!(
+            line,
+            "> |\u{1b}[1mbold\u{1b}[0m+\u{1b}]8;;https://example.com\u{1b}\\link\u{1b}]8;;\u{1b}\\\
+             +--+\u{1b}[32mopen\u{1b}[0m+\u{1b}[32mclosed\u{1b}[m|"
+        );
+
+        let mut empty = String::new();
+        LineBuilder::new(&mut empty, "[", ",", "]").finish();
+        assert_eq!(empty, "[]");
+    }
+}
@@ -0,0 +1,308 @@
+use crate::types::{Alignment, Row};
+use crate::utils::ansi::{calculate_display_width, strip_ansi_sequences};
+use crate::utils::line::LineBuilder;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownTable {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub indent: String,
+    pub alignments: Vec<Option<Alignment>>,
+    pub rows: Vec<Row>,
+}
+
+pub fn escape_markdown_cell(cell: &str) -> String {
+    strip_ansi_sequences(cell)
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+pub fn table_to_markdown(rows: &[Row]) -> String {
+    if rows.is_empty() {
+        return String::new();
+    }
+
+    let escaped: Vec<Row> = rows
+        .iter()
+        .map(|row| row.iter().map(|cell| escape_markdown_cell(cell)).collect())
+        .collect();
+
+    render_markdown_table(&escaped, &vec![None; escaped[0].len()])
+}
+
+pub fn render_markdown_table(rows: &[Row], alignments: &[Option<Alignment>]) -> String {
+    if rows.is_empty() {
+        return String::new();
+    }
+
+    let column_count = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut widths = vec![3; column_count];
+    for row in rows {
+        for (col_idx, cell) in row.iter().enumerate() {
+            widths[col_idx] = widths[col_idx].max(calculate_display_width(cell));
+        }
+    }
+
+    let alignments: Vec<Option<Alignment>> = (0..column_count)
+        .map(|col_idx| alignments.get(col_idx).copied().flatten())
+        .collect();
+
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+    lines.push(draw_markdown_row(&rows[0], &widths, &alignments));
+
+    let separator: Vec<String> = widths
+        .iter()
+        .zip(&alignments)
+        .map(|(&width, alignment)| draw_alignment_marker(width, *alignment))
+        .collect();
+    lines.push(draw_markdown_line(&separator));
+
+    for row in rows.iter().skip(1) {
+        lines.push(draw_markdown_row(row, &widths, &alignments));
+    }
+
+    lines.join("\n")
+}
+
+fn draw_markdown_row(row: &[String], widths: &[usize], alignments: &[Option<Alignment>]) -> String {
+    let cells: Vec<String> = widths
+        .iter()
+        .enumerate()
+        .map(|(col_idx, &width)| {
+            let cell = row.get(col_idx).map(String::as_str).unwrap_or("");
+            let padding = width.saturating_sub(calculate_display_width(cell));
+            match alignments[col_idx] {
+                Some(Alignment::Right) => format!("{}{}", " ".repeat(padding), cell),
+                Some(Alignment::Center) => {
+                    let left_padding = padding / 2;
+                    format!(
+                        "{}{}{}",
+                        " ".repeat(left_padding),
+                        cell,
+                        " ".repeat(padding - left_padding)
+                    )
+                }
+                _ => format!("{}{}", cell, " ".repeat(padding)),
+            }
+        })
+        .collect();
+
+    draw_markdown_line(&cells)
+}
+
+fn draw_markdown_line(cells: &[String]) -> String {
+    let mut line = String::new();
+    let mut builder = LineBuilder::new(&mut line, "| ", " | ", " |");
+    builder.segments(cells);
+    builder.finish();
+    line
+}
+
+fn draw_alignment_marker(width: usize, alignment: Option<Alignment>) -> String {
+    match alignment {
+        Some(Alignment::Left) => format!(":{}", "-".repeat(width - 1)),
+        Some(Alignment::Right) => format!("{}:", "-".repeat(width - 1)),
+        Some(Alignment::Center) => format!(":{}:", "-".repeat(width - 2)),
+        _ => "-".repeat(width),
+    }
+}
+
+pub fn split_markdown_row(line: &str) -> Vec<String> {
+    let trimmed = line.trim();
+    let trimmed = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let trimmed = if trimmed.ends_with('|') && !trimmed.ends_with("\\|") {
+        &trimmed[..trimmed.len() - 1]
+    } else {
+        trimmed
+    };
+
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut chars = trimmed.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if chars.peek() == Some(&'|') => {
+                current.push('\\');
+                current.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(ch),
+        }
+    }
+    cells.push(current.trim().to_string());
+
+    cells
+}
+
+pub fn parse_alignment_row(line: &str) -> Option<Vec<Option<Alignment>>> {
+    if !line.contains('-') {
+        return None;
+    }
+
+    split_markdown_row(line)
+        .iter()
+        .map(|cell| {
+            let left = cell.starts_with(':');
+            let right = cell.ends_with(':');
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+
+            if dashes.is_empty() || !dashes.chars().all(|ch| ch == '-') {
+                return None;
+            }
+
+            Some(match (left, right) {
+                (true, true) => Some(Alignment::Center),
+                (true, false) => Some(Alignment::Left),
+                (false, true) => Some(Alignment::Right),
+                (false, false) => None,
+            })
+        })
+        .collect()
+}
+
+pub fn find_markdown_tables(text: &str) -> Vec<MarkdownTable> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut tables = Vec::new();
+    let mut in_code_block = false;
+    let mut line_idx = 0;
+
+    while line_idx < lines.len() {
+        let line = lines[line_idx];
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            line_idx += 1;
+            continue;
+        }
+
+        if in_code_block || !line.contains('|') || line_idx + 1 >= lines.len() {
+            line_idx += 1;
+            continue;
+        }
+
+        let header = split_markdown_row(line);
+        let Some(alignments) = parse_alignment_row(lines[line_idx + 1])
+            .filter(|alignments| alignments.len() == header.len())
+        else {
+            line_idx += 1;
+            continue;
+        };
+
+        let mut rows = vec![header];
+        let mut end_line = line_idx + 2;
+        while end_line < lines.len()
+            && lines[end_line].contains('|')
+            && !lines[end_line].trim().is_empty()
+        {
+            rows.push(split_markdown_row(lines[end_line]));
+            end_line += 1;
+        }
+
+        tables.push(MarkdownTable {
+            start_line: line_idx,
+            end_line,
+            indent: line[..line.len() - trimmed.len()].to_string(),
+            alignments,
+            rows,
+        });
+        line_idx = end_line;
+    }
+
+    tables
+}
+
+pub fn reformat_markdown_tables(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut result = Vec::with_capacity(lines.len());
+    let mut line_idx = 0;
+
+    for markdown_table in find_markdown_tables(text) {
+        result.extend(
+            lines[line_idx..markdown_table.start_line]
+                .iter()
+                .map(|l| l.to_string()),
+        );
+
+        let rendered = render_markdown_table(&markdown_table.rows, &markdown_table.alignments);
+        result.extend(
+            rendered
+                .lines()
+                .map(|rendered_line| format!("{}{}", markdown_table.indent, rendered_line)),
+        );
+
+        line_idx = markdown_table.end_line;
+    }
+    result.extend(lines[line_idx..].iter().map(|l| l.to_string()));
+
+    let mut output = result.join("\n");
+    if text.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_to_markdown() {
+        let rows = vec![
+            vec!["Name".to_string(), "Age".to_string()],
+            vec!["John".to_string(), "30".to_string()],
+        ];
+
+        let result = table_to_markdown(&rows);
+        assert_eq!(result, "| Name | Age |\n| ---- | --- |\n| John | 30  |");
+    }
+
+    #[test]
+    fn test_escape_markdown_cell() {
+        assert_eq!(escape_markdown_cell("a|b"), "a\\|b");
+        assert_eq!(escape_markdown_cell("line1\nline2"), "line1<br>line2");
+        assert_eq!(escape_markdown_cell("\u{1b}[31mred\u{1b}[0m"), "red");
+    }
+
+    #[test]
+    fn test_table_to_markdown_empty() {
+        let rows: Vec<Row> = vec![];
+        assert_eq!(table_to_markdown(&rows), "");
+    }
+
+    #[test]
+    fn test_split_markdown_row() {
+        assert_eq!(split_markdown_row("| a | b |"), vec!["a", "b"]);
+        assert_eq!(split_markdown_row("a|b\\|c"), vec!["a", "b\\|c"]);
+    }
+
+    #[test]
+    fn test_parse_alignment_row() {
+        assert_eq!(
+            parse_alignment_row("|:--|--:|:-:|---|"),
+            Some(vec![
+                Some(Alignment::Left),
+                Some(Alignment::Right),
+                Some(Alignment::Center),
+                None
+            ])
+        );
+        assert_eq!(parse_alignment_row("| a | b |"), None);
+    }
+
+    #[test]
+    fn test_reformat_markdown_tables() {
+        let input = "# Title\n\n|a|bb|\n|-|:-:|\n|ccc|d|\n\ntext\n";
+        let expected = "# Title\n\n| a   | bb  |\n| --- | :-: |\n| ccc |  d  |\n\ntext\n";
+        assert_eq!(reformat_markdown_tables(input), expected);
+    }
+
+    #[test]
+    fn test_reformat_skips_code_blocks() {
+        let input = "```\n|a|b|\n|-|-|\n```\n";
+        assert_eq!(reformat_markdown_tables(input), input);
+    }
+}
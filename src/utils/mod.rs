@@ -1,11 +1,31 @@
 pub mod ansi;
+#[cfg(feature = "config-files")]
+pub mod config_file;
+#[cfg(feature = "formats-csv")]
+pub mod csv;
 pub mod formatting;
+#[cfg(feature = "html")]
 pub mod html;
+pub mod line;
+#[cfg(feature = "formats-markdown")]
+pub mod markdown;
+pub mod slab;
+pub mod terminal;
 pub mod unicode;
 pub mod wrapping;
 
 pub use ansi::*;
+#[cfg(feature = "config-files")]
+pub use config_file::*;
+#[cfg(feature = "formats-csv")]
+pub use csv::*;
 pub use formatting::*;
+#[cfg(feature = "html")]
 pub use html::*;
+pub use line::*;
+#[cfg(feature = "formats-markdown")]
+pub use markdown::*;
+pub use slab::*;
+pub use terminal::*;
 pub use unicode::*;
 pub use wrapping::*;
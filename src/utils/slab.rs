@@ -0,0 +1,90 @@
+use std::borrow::Cow;
+
+/// How many copies of a character each slab holds.
+pub const SLAB_WIDTH: usize = 256;
+
+// `unit` repeated to fill `LEN` bytes, for building slabs at compile time.
+const fn fill<const LEN: usize>(unit: &str) -> [u8; LEN] {
+    let unit = unit.as_bytes();
+    let mut bytes = [0; LEN];
+    let mut i = 0;
+    while i < LEN {
+        bytes[i] = unit[i % unit.len()];
+        i += 1;
+    }
+    bytes
+}
+
+macro_rules! slabs {
+    ($($unit:literal),* $(,)?) => {
+        // Each unit beside `SLAB_WIDTH` copies of it.
+        const SLABS: &[(&str, &str)] = &[$(
+            ($unit, {
+                const BYTES: [u8; $unit.len() * SLAB_WIDTH] = fill($unit);
+                match std::str::from_utf8(&BYTES) {
+                    Ok(slab) => slab,
+                    Err(_) => panic!("slabs repeat whole characters"),
+                }
+            }),
+        )*];
+    };
+}
+
+// Spaces, then the horizontal characters of the built-in border styles.
+slabs!(" ", "─", "═", "━", "┄", "-", "=");
+
+fn slab_for(unit: &str) -> Option<&'static str> {
+    SLABS
+        .iter()
+        .find(|(slab_unit, _)| *slab_unit == unit)
+        .map(|(_, slab)| *slab)
+}
+
+/// Appends `count` copies of `unit` to `out`. Spaces and the built-in border
+/// characters are copied from static slabs, up to `SLAB_WIDTH` at a time.
+pub fn push_repeated(out: &mut String, unit: &str, count: usize) {
+    let Some(slab) = slab_for(unit) else {
+        out.reserve(unit.len() * count);
+        (0..count).for_each(|_| out.push_str(unit));
+        return;
+    };
+
+    let mut left = count;
+    while left > 0 {
+        let run = left.min(SLAB_WIDTH);
+        out.push_str(&slab[..run * unit.len()]);
+        left -= run;
+    }
+}
+
+/// `count` copies of `unit`, borrowed from a slab when one holds that many.
+pub fn repeated(unit: &str, count: usize) -> Cow<'static, str> {
+    match slab_for(unit) {
+        Some(slab) if count <= SLAB_WIDTH => Cow::Borrowed(&slab[..count * unit.len()]),
+        _ => Cow::Owned(unit.repeat(count)),
+    }
+}
+
+/// `count` spaces, borrowed from the space slab up to `SLAB_WIDTH`.
+pub fn spaces(count: usize) -> Cow<'static, str> {
+    repeated(" ", count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slabs_match_repeat() {
+        for unit in [" ", "─", "-", "•"] {
+            for count in [0, 1, 7, SLAB_WIDTH, SLAB_WIDTH + 3, 3 * SLAB_WIDTH] {
+                let mut out = String::from(">");
+                push_repeated(&mut out, unit, count);
+                assert_eq!(out, format!(">{}", unit.repeat(count)));
+                assert_eq!(repeated(unit, count), unit.repeat(count));
+            }
+        }
+        assert!(matches!(spaces(12), Cow::Borrowed("            ")));
+        assert!(matches!(repeated("•", 2), Cow::Owned(_)));
+    }
+}
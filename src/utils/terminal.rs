@@ -0,0 +1,153 @@
+pub const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+const CSI: &str = "\u{1b}[";
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn detect_terminal_width() -> Option<usize> {
+    if let Some((terminal_size::Width(width), _)) = terminal_size::terminal_size() {
+        return Some(width as usize);
+    }
+
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse::<usize>().ok())
+        .filter(|&width| width > 0)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn detect_terminal_width() -> Option<usize> {
+    None
+}
+
+/// Whether standard output is a terminal that should get colors: not one with
+/// `NO_COLOR` set or `TERM=dumb`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn stdout_supports_ansi() -> bool {
+    use std::io::IsTerminal;
+
+    std::io::stdout().is_terminal()
+        && std::env::var_os("NO_COLOR").is_none()
+        && std::env::var("TERM").map_or(true, |term| term != "dumb")
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn stdout_supports_ansi() -> bool {
+    false
+}
+
+pub fn terminal_width() -> usize {
+    detect_terminal_width().unwrap_or(DEFAULT_TERMINAL_WIDTH)
+}
+
+pub fn cursor_up(lines: usize) -> String {
+    if lines == 0 {
+        String::new()
+    } else {
+        format!("{CSI}{lines}A")
+    }
+}
+
+pub fn cursor_down(lines: usize) -> String {
+    if lines == 0 {
+        String::new()
+    } else {
+        format!("{CSI}{lines}B")
+    }
+}
+
+pub fn cursor_to_column(column: usize) -> String {
+    format!("{CSI}{}G", column + 1)
+}
+
+pub fn clear_line() -> String {
+    format!("{CSI}2K")
+}
+
+/// Clears from the cursor to the end of the screen.
+pub fn clear_below() -> String {
+    format!("{CSI}J")
+}
+
+pub fn save_cursor() -> String {
+    "\u{1b}7".to_string()
+}
+
+pub fn restore_cursor() -> String {
+    "\u{1b}8".to_string()
+}
+
+/// Moves the cursor up one line at a time, clearing each line on the way, so the
+/// cursor ends at the start of the first erased line.
+pub fn erase_lines(lines: usize) -> String {
+    format!("{}{}", cursor_up(1), clear_line()).repeat(lines)
+}
+
+/// Tracks how many terminal lines the last render occupied so it can be erased
+/// before drawing the next frame in place.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineEraser {
+    lines: usize,
+}
+
+impl LineEraser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn lines(&self) -> usize {
+        self.lines
+    }
+
+    /// Records `output` as the last render. Output ending in a newline leaves the
+    /// cursor on the following line, which is what `erase` expects.
+    pub fn record(&mut self, output: &str) {
+        self.lines = output.matches('\n').count();
+    }
+
+    /// Returns the sequence that erases the last recorded render and forgets it.
+    pub fn erase(&mut self) -> String {
+        erase_lines(std::mem::take(&mut self.lines))
+    }
+
+    /// Erases the previous render and records `output` as the new one.
+    pub fn redraw(&mut self, output: &str) -> String {
+        let erase = self.erase();
+        self.record(output);
+        format!("{erase}{output}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_builders() {
+        assert_eq!(cursor_up(0), "");
+        assert_eq!(cursor_up(3), "\u{1b}[3A");
+        assert_eq!(cursor_down(2), "\u{1b}[2B");
+        assert_eq!(cursor_to_column(0), "\u{1b}[1G");
+        assert_eq!(clear_line(), "\u{1b}[2K");
+        assert_eq!(clear_below(), "\u{1b}[J");
+        assert_eq!(erase_lines(2), "\u{1b}[1A\u{1b}[2K\u{1b}[1A\u{1b}[2K");
+    }
+
+    #[test]
+    fn test_line_eraser() {
+        let mut eraser = LineEraser::new();
+        assert_eq!(eraser.erase(), "");
+
+        let frame = eraser.redraw("a\nb\n");
+        assert_eq!(frame, "a\nb\n");
+        assert_eq!(eraser.lines(), 2);
+
+        let next = eraser.redraw("c\n");
+        assert_eq!(next, format!("{}c\n", erase_lines(2)));
+        assert_eq!(eraser.lines(), 1);
+    }
+
+    #[test]
+    fn test_terminal_width_is_positive() {
+        assert!(terminal_width() > 0);
+    }
+}
@@ -1,14 +1,84 @@
+use crate::features::terminal_profile::{TerminalProfile, terminal_profile};
+use crate::types::TextDirection;
+use crate::utils::slab::spaces;
+use std::borrow::Cow;
 use unicode_segmentation::UnicodeSegmentation;
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
+/// The columns `text` takes in the terminal `set_terminal_profile` chose.
 pub fn calculate_string_width(text: &str) -> usize {
-    UnicodeWidthStr::width(text)
+    string_width_for(text, terminal_profile())
+}
+
+/// The columns `text` takes in a terminal drawing as `profile` does.
+pub fn string_width_for(text: &str, profile: TerminalProfile) -> usize {
+    let width = if profile.ambiguous_wide {
+        UnicodeWidthStr::width_cjk(text)
+    } else {
+        UnicodeWidthStr::width(text)
+    };
+    if profile.emoji_variation_wide || !text.contains('\u{fe0f}') {
+        return width;
+    }
+
+    // Unicode widens a narrow character followed by U+FE0F to two columns.
+    let mut buffer = [0u8; 8];
+    let narrowed: usize = text
+        .chars()
+        .zip(text.chars().skip(1))
+        .filter(|&(base, selector)| selector == '\u{fe0f}' && base.width() == Some(1))
+        .map(|(base, _)| {
+            let len = base.encode_utf8(&mut buffer).len();
+            '\u{fe0f}'.encode_utf8(&mut buffer[len..]);
+            let pair = std::str::from_utf8(&buffer[..len + 3]).expect("two encoded chars");
+            UnicodeWidthStr::width(pair) - 1
+        })
+        .sum();
+    width - narrowed
+}
+
+/// The columns one character takes in the terminal `set_terminal_profile`
+/// chose, 0 for control characters.
+pub fn char_width(c: char) -> usize {
+    let width = if terminal_profile().ambiguous_wide {
+        c.width_cjk()
+    } else {
+        c.width()
+    };
+    width.unwrap_or(0)
 }
 
 pub fn calculate_grapheme_width(text: &str) -> usize {
     text.graphemes(true).count()
 }
 
+/// The display width of one grapheme cluster, measured as a whole the way
+/// `calculate_display_width` measures strings: a ZWJ emoji family, a flag or
+/// a skin-toned emoji is 2 wide, a letter with combining marks 1.
+pub fn grapheme_cluster_width(grapheme: &str) -> usize {
+    match grapheme.as_bytes() {
+        [byte] if byte.is_ascii_graphic() || *byte == b' ' => 1,
+        _ => calculate_string_width(grapheme),
+    }
+}
+
+/// `text` split into extended grapheme clusters, each with its
+/// `grapheme_cluster_width`. ASCII text skips segmentation, one cluster per
+/// byte.
+pub fn grapheme_clusters(text: &str) -> impl Iterator<Item = (&str, usize)> {
+    let (ascii, unicode) = if text.is_ascii() {
+        (Some(text.split_inclusive(|_| true)), None)
+    } else {
+        (None, Some(text.graphemes(true)))
+    };
+
+    ascii
+        .into_iter()
+        .flatten()
+        .chain(unicode.into_iter().flatten())
+        .map(|grapheme| (grapheme, grapheme_cluster_width(grapheme)))
+}
+
 pub fn truncate_string(text: &str, max_width: usize) -> String {
     let width = calculate_string_width(text);
 
@@ -20,7 +90,7 @@ pub fn truncate_string(text: &str, max_width: usize) -> String {
     let mut current_width = 0;
 
     for grapheme in text.graphemes(true) {
-        let grapheme_width = UnicodeWidthStr::width(grapheme);
+        let grapheme_width = calculate_string_width(grapheme);
         if current_width + grapheme_width > max_width {
             break;
         }
@@ -41,58 +111,167 @@ pub fn pad_string(text: &str, width: usize, alignment: crate::types::Alignment)
     let padding = width - current_width;
 
     match alignment {
-        crate::types::Alignment::Left => format!("{}{}", text, " ".repeat(padding)),
-        crate::types::Alignment::Right => format!("{}{}", " ".repeat(padding), text),
+        crate::types::Alignment::Left | crate::types::Alignment::Char(_) => {
+            format!("{}{}", text, spaces(padding))
+        }
+        crate::types::Alignment::Right => format!("{}{}", spaces(padding), text),
         crate::types::Alignment::Center => {
             let left_padding = padding / 2;
             let right_padding = padding - left_padding;
-            format!(
-                "{}{}{}",
-                " ".repeat(left_padding),
-                text,
-                " ".repeat(right_padding)
-            )
+            format!("{}{}{}", spaces(left_padding), text, spaces(right_padding))
         }
         crate::types::Alignment::Justify => justify_string(text, width),
     }
 }
 
 pub fn justify_string(text: &str, width: usize) -> String {
-    let words: Vec<&str> = text.split_whitespace().collect();
+    crate::features::alignment_processor::AlignmentProcessor::justify_line(text, width).into_owned()
+}
 
-    if words.len() <= 1 {
-        return text.to_string();
-    }
+/// Whether `c` is a strong right-to-left character: Hebrew, Arabic, Syriac,
+/// Thaana, N'Ko and the other scripts of the right-to-left blocks, including
+/// their presentation forms.
+pub fn is_rtl_char(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0590}'..='\u{08FF}'
+            | '\u{200F}'
+            | '\u{FB1D}'..='\u{FDFF}'
+            | '\u{FE70}'..='\u{FEFF}'
+            | '\u{10800}'..='\u{10FFF}'
+            | '\u{1E800}'..='\u{1EFFF}'
+    ) && !matches!(c, '\u{0660}'..='\u{0669}' | '\u{06F0}'..='\u{06F9}' | '\u{FEFF}')
+}
 
-    let total_chars: usize = words.iter().map(|w| calculate_string_width(w)).sum();
-    let total_spaces = width - total_chars;
-    let gaps = words.len() - 1;
+// A strong left-to-right character, e.g. a Latin letter. Digits, spaces and
+// punctuation are neutral here.
+fn is_ltr_char(c: char) -> bool {
+    c.is_alphabetic() && !is_rtl_char(c) || c == '\u{200E}'
+}
 
-    if gaps == 0 {
-        return text.to_string();
-    }
+/// The direction of the first strong character in `text`, or `None` when it
+/// has only digits, spaces and punctuation.
+pub fn detect_direction(text: &str) -> Option<TextDirection> {
+    text.chars().find_map(|c| {
+        if is_rtl_char(c) {
+            Some(TextDirection::Rtl)
+        } else if is_ltr_char(c) {
+            Some(TextDirection::Ltr)
+        } else {
+            None
+        }
+    })
+}
 
-    let spaces_per_gap = total_spaces / gaps;
-    let extra_spaces = total_spaces % gaps;
+/// One line of mixed-direction text in display order, for terminals that
+/// draw characters in the order they are written: a simplified Unicode
+/// bidirectional algorithm with the paragraph direction taken from the first
+/// strong character. Neutrals between two runs of the same direction join
+/// them and otherwise follow the paragraph; digits keep their left-to-right
+/// order; brackets in right-to-left runs are mirrored. Lines without
+/// right-to-left characters, or with ANSI sequences, are returned as they are.
+pub fn reorder_bidi_line(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_rtl_char) || text.contains('\u{1b}') {
+        return Cow::Borrowed(text);
+    }
 
-    let mut result = String::new();
+    let rtl_paragraph = detect_direction(text) == Some(TextDirection::Rtl);
+    let clusters: Vec<&str> = text.graphemes(true).collect();
+    let strong: Vec<Option<bool>> = clusters
+        .iter()
+        .map(|cluster| {
+            let c = cluster.chars().next().unwrap_or(' ');
+            if is_rtl_char(c) {
+                Some(true)
+            } else if is_ltr_char(c) || c.is_numeric() {
+                Some(false)
+            } else {
+                None
+            }
+        })
+        .collect();
 
-    for (i, word) in words.iter().enumerate() {
-        result.push_str(word);
+    // Resolve each neutral from the strong characters around it, then give
+    // right-to-left clusters an odd level and left-to-right ones an even level
+    // above the paragraph's.
+    let mut levels = Vec::with_capacity(clusters.len());
+    for (index, direction) in strong.iter().enumerate() {
+        let rtl = direction.unwrap_or_else(|| {
+            let before = strong[..index].iter().rev().find_map(|d| *d);
+            let after = strong[index + 1..].iter().find_map(|d| *d);
+            match (before, after) {
+                (Some(before), Some(after)) if before == after => before,
+                _ => rtl_paragraph,
+            }
+        });
+        levels.push(match (rtl_paragraph, rtl) {
+            (false, false) => 0u8,
+            (false, true) | (true, true) => 1,
+            (true, false) => 2,
+        });
+    }
 
-        if i < words.len() - 1 {
-            let spaces = spaces_per_gap + if i < extra_spaces { 1 } else { 0 };
-            result.push_str(&" ".repeat(spaces));
+    let mut order: Vec<usize> = (0..clusters.len()).collect();
+    let highest = levels.iter().copied().max().unwrap_or(0);
+    for level in (1..=highest).rev() {
+        let mut start = 0;
+        while start < order.len() {
+            if levels[order[start]] < level {
+                start += 1;
+                continue;
+            }
+            let end = (start..order.len())
+                .find(|&i| levels[order[i]] < level)
+                .unwrap_or(order.len());
+            order[start..end].reverse();
+            start = end;
         }
     }
 
-    result
+    let mut line = String::with_capacity(text.len());
+    for index in order {
+        let cluster = clusters[index];
+        match (levels[index] % 2 == 1, cluster) {
+            (true, "(") => line.push(')'),
+            (true, ")") => line.push('('),
+            (true, "[") => line.push(']'),
+            (true, "]") => line.push('['),
+            (true, "{") => line.push('}'),
+            (true, "}") => line.push('{'),
+            (true, "<") => line.push('>'),
+            (true, ">") => line.push('<'),
+            _ => line.push_str(cluster),
+        }
+    }
+    Cow::Owned(line)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_grapheme_clusters_match_string_width() {
+        for text in [
+            "plain ascii",
+            "👨\u{200D}👩\u{200D}👧 family",
+            "e\u{301}te\u{301}",
+            "\u{1100}\u{1161}\u{11A8}",
+            "🇩🇪👍🏽❤\u{FE0F}",
+        ] {
+            let clusters: Vec<(&str, usize)> = grapheme_clusters(text).collect();
+            assert_eq!(clusters.iter().map(|(g, _)| *g).collect::<String>(), text);
+            assert_eq!(
+                clusters.iter().map(|(_, width)| width).sum::<usize>(),
+                calculate_string_width(text),
+                "{text:?}"
+            );
+        }
+
+        let family: Vec<_> = grapheme_clusters("👨\u{200D}👩\u{200D}👧").collect();
+        assert_eq!(family, vec![("👨\u{200D}👩\u{200D}👧", 2)]);
+    }
+
     #[test]
     fn test_calculate_string_width() {
         assert_eq!(calculate_string_width("hello"), 5);
@@ -179,4 +358,18 @@ mod tests {
         assert_eq!(justify_string("a b c", 7), "a  b  c");
         assert_eq!(justify_string("single", 10), "single");
     }
+
+    #[test]
+    fn test_reorder_bidi_line() {
+        assert_eq!(detect_direction("123 שלום abc"), Some(TextDirection::Rtl));
+        assert_eq!(detect_direction("abc שלום"), Some(TextDirection::Ltr));
+        assert_eq!(detect_direction("12.5 %"), None);
+
+        assert!(matches!(reorder_bidi_line("plain"), Cow::Borrowed("plain")));
+        assert_eq!(reorder_bidi_line("abc אבג"), "abc גבא");
+        assert_eq!(reorder_bidi_line("אבג abc"), "abc גבא");
+        // Numbers read left to right inside right-to-left text, and brackets
+        // are mirrored.
+        assert_eq!(reorder_bidi_line("מחיר 120 (אבג)"), "(גבא) 120 ריחמ");
+    }
 }
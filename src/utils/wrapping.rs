@@ -1,29 +1,353 @@
-use textwrap::{Options, WrapAlgorithm, wrap};
+use crate::types::{TruncatePosition, WordBreakPolicy};
+use crate::utils::ansi::{calculate_display_width, truncate_ansi_string_at};
+use crate::utils::unicode::grapheme_clusters;
+use std::borrow::Cow;
+
+const SOFT_HYPHEN: char = '\u{ad}';
+
+/// A wrappable unit of a line, found in a single scan: `line[start..end]` is
+/// the text (ANSI escapes included, at zero width) and `line[end..space_end]`
+/// the spaces after it. A `hyphenated` piece of a cut word is drawn with a
+/// hyphen after it, counted in `width`.
+#[derive(Debug, Clone, Copy)]
+struct Word {
+    start: usize,
+    end: usize,
+    space_end: usize,
+    width: usize,
+    hyphenated: bool,
+}
+
+impl Word {
+    fn space_width(&self) -> usize {
+        self.space_end - self.end
+    }
+}
 
+/// Wraps `text` to `width` columns, breaking at Unicode line-break
+/// opportunities (spaces, after hyphens between alphanumerics, around CJK) with
+/// first-fit placement. Each line is scanned once into words with their widths,
+/// so wrapping is linear in the text length. Unless `word_wrap` is set, words
+/// wider than `width` are cut to fit, between grapheme clusters, so emoji
+/// sequences, combining marks and Hangul syllables stay whole. Widths are
+/// measured per cluster and add up to `calculate_display_width`. Colors still
+/// open at a line break are closed and reopened on the next line.
 pub fn wrap_text(text: &str, width: usize, word_wrap: bool) -> Vec<String> {
+    wrap_text_with(text, width, WordBreakPolicy::from_wrap_word(word_wrap), "")
+}
+
+/// Like `wrap_text`, with `policy` deciding what happens to words wider than
+/// `width`; `ellipsis` marks the cut under `WordBreakPolicy::NoBreakTruncate`.
+pub fn wrap_text_with(
+    text: &str,
+    width: usize,
+    policy: WordBreakPolicy,
+    ellipsis: &str,
+) -> Vec<String> {
+    wrap_paragraphs(text, width, policy, ellipsis, |_| {})
+}
+
+/// Like `wrap_text_with`, calling `paragraph_end` with the index of the last
+/// line each paragraph (each line of `text`) was wrapped to, so justified
+/// text can leave those lines flush left.
+pub(crate) fn wrap_paragraphs(
+    text: &str,
+    width: usize,
+    policy: WordBreakPolicy,
+    ellipsis: &str,
+    mut paragraph_end: impl FnMut(usize),
+) -> Vec<String> {
     if width == 0 {
         return vec![];
     }
 
-    let options = if word_wrap {
-        Options::new(width)
-            .break_words(false)
-            .wrap_algorithm(WrapAlgorithm::FirstFit)
+    let mut lines = Vec::new();
+    let mut words = Vec::new();
+    for line in text.split('\n') {
+        if line.len() < width {
+            // Fewer bytes than columns, so it fits whatever it contains.
+            lines.push(line.trim_end_matches(' ').to_string());
+            paragraph_end(lines.len() - 1);
+            continue;
+        }
+
+        words.clear();
+        scan_words(line, &mut words);
+        let first = lines.len();
+        wrap_words(line, &words, width, policy, &mut lines);
+
+        // Past words kept whole, only a cluster wider than the whole line can
+        // leave it too wide; it is cut as truncation cuts it.
+        if policy != WordBreakPolicy::NoBreakOverflow {
+            let ellipsis = if policy == WordBreakPolicy::NoBreakTruncate {
+                ellipsis
+            } else {
+                ""
+            };
+            for wrapped in &mut lines[first..] {
+                if calculate_display_width(wrapped) > width {
+                    *wrapped =
+                        truncate_ansi_string_at(wrapped, width, TruncatePosition::End, ellipsis)
+                            .content;
+                }
+            }
+        }
+        if lines.len() > first {
+            paragraph_end(lines.len() - 1);
+        }
+    }
+
+    if text.contains('\u{1b}') {
+        fix_ansi_wrapping(lines)
     } else {
-        Options::new(width)
-            .break_words(true)
-            .wrap_algorithm(WrapAlgorithm::FirstFit)
+        lines
+    }
+}
+
+// The end of the text run starting at `start`: the next escape or `end`.
+fn run_end(line: &str, start: usize, end: usize) -> usize {
+    line[start..end]
+        .find('\u{1b}')
+        .map_or(end, |offset| start + offset)
+}
+
+// Returns the byte index just past the escape sequence starting at `start`:
+// CSI sequences up to their final byte, OSC sequences up to BEL or ST.
+fn escape_end(line: &str, start: usize) -> usize {
+    let bytes = line.as_bytes();
+    let mut end = start + 1;
+    match bytes.get(end) {
+        Some(b'[') => {
+            while end + 1 < bytes.len() && !(0x40..=0x7e).contains(&bytes[end + 1]) {
+                end += 1;
+            }
+            (end + 2).min(bytes.len())
+        }
+        Some(b']') => {
+            end += 1;
+            while end < bytes.len()
+                && bytes[end] != 0x07
+                && !(bytes[end] == b'\\' && bytes[end - 1] == 0x1b)
+            {
+                end += 1;
+            }
+            (end + 1).min(bytes.len())
+        }
+        Some(_) => end + line[end..].chars().next().map_or(0, char::len_utf8),
+        None => end,
+    }
+}
+
+fn strip_escapes(line: &str) -> Cow<'_, str> {
+    if !line.contains('\u{1b}') {
+        return Cow::Borrowed(line);
+    }
+
+    let mut stripped = String::with_capacity(line.len());
+    let mut idx = 0;
+    while let Some(offset) = line[idx..].find('\u{1b}') {
+        stripped.push_str(&line[idx..idx + offset]);
+        idx = escape_end(line, idx + offset);
+    }
+    stripped.push_str(&line[idx..]);
+    Cow::Owned(stripped)
+}
+
+// Splits a line into words at its line-break opportunities, found on the text
+// with escapes removed. Breaks after `-` are left to the hyphen rule in
+// `push_word`, and escapes just before a break start the next word.
+fn scan_words(line: &str, words: &mut Vec<Word>) {
+    let stripped = strip_escapes(line);
+    let breaks = unicode_linebreak::linebreaks(&stripped)
+        .map(|(idx, _)| idx)
+        .filter(|&idx| idx < stripped.len())
+        .filter(|&idx| !stripped[..idx].ends_with(['-', SOFT_HYPHEN]));
+
+    let mut start = 0;
+    let mut orig_idx = 0;
+    let mut stripped_idx = 0;
+    for break_idx in breaks {
+        while stripped_idx < break_idx {
+            if line.as_bytes()[orig_idx] == 0x1b {
+                orig_idx = escape_end(line, orig_idx);
+            } else {
+                let len = line[orig_idx..].chars().next().map_or(1, char::len_utf8);
+                orig_idx += len;
+                stripped_idx += len;
+            }
+        }
+
+        push_word(line, start, orig_idx, words);
+        start = orig_idx;
+    }
+
+    push_word(line, start, line.len(), words);
+}
+
+// Adds `line[start..end]` as one word, or several when it has hyphens between
+// alphanumerics (`well-known` becomes `well-` and `known`), measuring widths on
+// the way.
+fn push_word(line: &str, start: usize, end: usize, words: &mut Vec<Word>) {
+    let text_end = start + line[start..end].trim_end_matches(' ').len();
+    let mut piece_start = start;
+    let mut width = 0;
+    let mut prev: Option<char> = None;
+    let mut after_hyphen = false;
+    let mut idx = start;
+
+    while idx < text_end {
+        if line.as_bytes()[idx] == 0x1b {
+            idx = escape_end(line, idx).min(text_end);
+            prev = line[..idx].chars().next_back();
+            after_hyphen = false;
+            continue;
+        }
+
+        let end = run_end(line, idx, text_end);
+        for (grapheme, grapheme_width) in grapheme_clusters(&line[idx..end]) {
+            let ch = grapheme.chars().next().unwrap_or_default();
+            if after_hyphen && ch.is_alphanumeric() {
+                words.push(Word {
+                    start: piece_start,
+                    end: idx,
+                    space_end: idx,
+                    width,
+                    hyphenated: false,
+                });
+                piece_start = idx;
+                width = 0;
+            }
+
+            after_hyphen = ch == '-' && prev.is_some_and(char::is_alphanumeric);
+            prev = grapheme.chars().next_back();
+            width += grapheme_width;
+            idx += grapheme.len();
+        }
+    }
+
+    words.push(Word {
+        start: piece_start,
+        end: text_end,
+        space_end: end,
+        width,
+        hyphenated: false,
+    });
+}
+
+// First-fit: each word goes on the current line if it fits, else starts the
+// next one. Words wider than `width` are first cut into pieces that fit, unless
+// `policy` keeps them whole, overflowing on a line of their own.
+fn wrap_words(
+    line: &str,
+    words: &[Word],
+    width: usize,
+    policy: WordBreakPolicy,
+    lines: &mut Vec<String>,
+) {
+    let mut line_start = 0;
+    let mut line_end = 0;
+    let mut line_width = 0;
+    let mut line_hyphenated = false;
+    let mut has_words = false;
+
+    let finish = |start: usize, end: usize, hyphenated: bool| {
+        let mut text = line[start..end].to_string();
+        if hyphenated {
+            text.push('-');
+        }
+        text
     };
 
-    let wrapped_lines = wrap(text, options);
-    let mut result: Vec<String> = wrapped_lines
-        .into_iter()
-        .map(|line| line.to_string())
-        .collect();
+    let mut place = |piece: Word| {
+        if has_words && line_width + piece.width > width {
+            lines.push(finish(line_start, line_end, line_hyphenated));
+            has_words = false;
+        }
+        if !has_words {
+            line_start = piece.start;
+            line_width = 0;
+            has_words = true;
+        }
+        line_end = piece.end;
+        line_width += piece.width + piece.space_width();
+        line_hyphenated = piece.hyphenated;
+    };
 
-    result = fix_ansi_wrapping(result);
+    for &word in words {
+        if word.width <= width {
+            place(word);
+            continue;
+        }
 
-    result
+        match policy {
+            WordBreakPolicy::BreakAtHyphen if widest_cluster(line, word) < width => {
+                break_apart(line, word, width, true, &mut place)
+            }
+            WordBreakPolicy::BreakAnywhere | WordBreakPolicy::BreakAtHyphen => {
+                break_apart(line, word, width, false, &mut place)
+            }
+            WordBreakPolicy::NoBreakOverflow | WordBreakPolicy::NoBreakTruncate => place(word),
+        }
+    }
+
+    lines.push(finish(line_start, line_end, line_hyphenated));
+}
+
+// Cuts a word between grapheme clusters into pieces at most `width` wide (a
+// single wide cluster may exceed it); escapes stay with the piece they follow,
+// and the last piece keeps the word's trailing spaces. With `hyphenate`, every
+// piece but the last ends in a hyphen, counted in its width: one the word has
+// there already, else one added to it.
+fn break_apart(line: &str, word: Word, width: usize, hyphenate: bool, mut emit: impl FnMut(Word)) {
+    let mut piece_start = word.start;
+    let mut piece_width = 0;
+    let mut placed_width = 0;
+    let mut idx = word.start;
+
+    while idx < word.end {
+        if line.as_bytes()[idx] == 0x1b {
+            idx = escape_end(line, idx).min(word.end);
+            continue;
+        }
+
+        let end = run_end(line, idx, word.end);
+        for (grapheme, grapheme_width) in grapheme_clusters(&line[idx..end]) {
+            placed_width += grapheme_width;
+            // No hyphen is added after the word's last cluster, nor after a
+            // hyphen of its own.
+            let ends_piece = placed_width == word.width || grapheme == "-";
+            let hyphen_width = usize::from(hyphenate && !ends_piece);
+            if piece_width > 0 && piece_width + grapheme_width + hyphen_width > width {
+                let hyphenated = hyphenate && !line[piece_start..idx].ends_with('-');
+                emit(Word {
+                    start: piece_start,
+                    end: idx,
+                    space_end: idx,
+                    width: piece_width + usize::from(hyphenated),
+                    hyphenated,
+                });
+                piece_start = idx;
+                piece_width = 0;
+            }
+
+            piece_width += grapheme_width;
+            idx += grapheme.len();
+        }
+    }
+
+    emit(Word {
+        start: piece_start,
+        width: piece_width,
+        ..word
+    });
+}
+
+// The widest grapheme cluster of `word`.
+fn widest_cluster(line: &str, word: Word) -> usize {
+    grapheme_clusters(&strip_escapes(&line[word.start..word.end]))
+        .map(|(_, width)| width)
+        .max()
+        .unwrap_or(0)
 }
 
 fn fix_ansi_wrapping(lines: Vec<String>) -> Vec<String> {
@@ -121,6 +445,111 @@ mod tests {
         assert_eq!(result, vec!["verylongwo", "rdthatshou", "ldbewrappe", "d"]);
     }
 
+    #[test]
+    fn test_wrap_text_break_opportunities() {
+        assert_eq!(
+            wrap_text("well-known fact", 6, true),
+            vec!["well-", "known", "fact"]
+        );
+        assert_eq!(wrap_text("--flag value", 6, true), vec!["--flag", "value"]);
+        assert_eq!(
+            wrap_text("数据表格渲染", 5, true),
+            vec!["数据", "表格", "渲染"]
+        );
+        assert_eq!(wrap_text("foo bar-", 4, true), vec!["foo", "bar-"]);
+    }
+
+    #[test]
+    fn test_wrap_text_breaks_long_words_with_ansi() {
+        let text = "ab \u{1b}[32mgreenish\u{1b}[0m";
+        assert_eq!(
+            wrap_text(text, 4, false),
+            vec!["ab", "\u{1b}[32mgree\u{1b}[0m", "\u{1b}[32mnish\u{1b}[0m"]
+        );
+        assert_eq!(wrap_text(text, 4, true), vec!["ab", &text[3..]]);
+    }
+
+    #[test]
+    fn test_wrap_text_keeps_grapheme_clusters_whole() {
+        let family = "👨\u{200D}👩\u{200D}👧";
+        let text = format!("{family}{family}{family}");
+        assert_eq!(
+            wrap_text(&text, 4, false),
+            vec![family.repeat(2), family.to_string()]
+        );
+
+        assert_eq!(
+            wrap_text("e\u{301}e\u{301}e\u{301}", 2, false),
+            vec!["e\u{301}e\u{301}", "e\u{301}"]
+        );
+        assert_eq!(
+            wrap_text("\u{1100}\u{1161}\u{1100}\u{1161}", 2, false),
+            vec!["\u{1100}\u{1161}", "\u{1100}\u{1161}"]
+        );
+
+        let flags = "🇩🇪🇫🇷 \u{1b}[1m👍🏽👍🏽\u{1b}[0m";
+        for line in wrap_text(flags, 3, false) {
+            assert!(
+                crate::utils::ansi::calculate_display_width(&line) <= 3,
+                "{line:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_wrap_text_word_break_policies() {
+        let text = "see abcdefghij ok";
+        let wrap = |policy| wrap_text_with(text, 5, policy, "…");
+
+        assert_eq!(
+            wrap(WordBreakPolicy::BreakAnywhere),
+            vec!["see", "abcde", "fghij", "ok"]
+        );
+        assert_eq!(
+            wrap(WordBreakPolicy::BreakAtHyphen),
+            vec!["see", "abcd-", "efgh-", "ij ok"]
+        );
+        assert_eq!(
+            wrap(WordBreakPolicy::NoBreakOverflow),
+            vec!["see", "abcdefghij", "ok"]
+        );
+        assert_eq!(
+            wrap(WordBreakPolicy::NoBreakTruncate),
+            vec!["see", "abcd…", "ok"]
+        );
+        assert_eq!(
+            wrap_text_with("abc", 1, WordBreakPolicy::BreakAtHyphen, ""),
+            vec!["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_text_break_at_hyphen_counts_the_hyphen() {
+        let wrap = |text, width| wrap_text_with(text, width, WordBreakPolicy::BreakAtHyphen, "");
+
+        // The word's own hyphens are kept as break points, and one never
+        // starts or fills a line on its own.
+        assert_eq!(
+            wrap("long-hyphenated-word", 6),
+            vec!["long-", "hyphe-", "nated-", "word"]
+        );
+        assert_eq!(
+            wrap("long-hyphenated-word", 3),
+            vec!["lo-", "ng-", "hy-", "ph-", "en-", "at-", "ed-", "wo-", "rd"]
+        );
+
+        // A wide cluster leaves room for the hyphen after it, or is cut
+        // without one where it fills the line.
+        assert_eq!(wrap("漢字-漢字", 3), vec!["漢", "字-", "漢", "字"]);
+        assert_eq!(wrap("a🚚b-🚚c", 4), vec!["a🚚", "b-🚚", "c"]);
+        assert_eq!(wrap("a🚚b-🚚c", 2), vec!["a", "🚚", "b-", "🚚", "c"]);
+        for width in 1..=6 {
+            for line in wrap("漢字-漢字 \u{1b}[31ma🚚b🚚c\u{1b}[0m", width) {
+                assert!(calculate_display_width(&line) <= width, "{width}: {line:?}");
+            }
+        }
+    }
+
     #[test]
     fn test_wrap_cell() {
         let text = "hello world";
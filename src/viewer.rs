@@ -0,0 +1,354 @@
+//! `ascii_ansi_table view`: a `less` for tables. The table is drawn a screen
+//! at a time through `TableView`, with its header and footer rows pinned, and
+//! scrolled, searched and resized from the keyboard.
+
+use std::io::{self, Write};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute, queue};
+
+use crate::features::viewport::{TableView, Viewport};
+use crate::types::{Row, TableConfig, TableError, TableResult};
+use crate::utils::ansi::strip_ansi_sequences;
+use crate::utils::unicode::truncate_string;
+
+/// A key the viewer acts on, apart from the terminal it comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewerKey {
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Enter,
+    Backspace,
+    Escape,
+    Char(char),
+}
+
+/// The state of the viewer: which body row and column the screen starts at,
+/// the column widths as resized, and the search. Each frame draws only the
+/// rows and columns that fit on the screen.
+#[derive(Debug)]
+pub struct Viewer {
+    rows: Vec<Row>,
+    config: TableConfig,
+    column_widths: Vec<usize>,
+    body_top: usize,
+    left_col: usize,
+    // The search being typed after `/`.
+    prompt: Option<String>,
+    query: Option<String>,
+    message: Option<String>,
+}
+
+impl Viewer {
+    /// A viewer of `rows`, prepared and drawn with `config` as `table` would.
+    pub fn new(rows: Vec<Row>, config: TableConfig) -> Self {
+        let column_widths = TableView::new(&rows, &config).column_widths().to_vec();
+        Self {
+            rows,
+            config,
+            column_widths,
+            body_top: 0,
+            left_col: 0,
+            prompt: None,
+            query: None,
+            message: None,
+        }
+    }
+
+    /// The first body row on screen, counted from the first body row.
+    pub fn body_top(&self) -> usize {
+        self.body_top
+    }
+
+    /// The leftmost column on screen, which `<` and `>` resize.
+    pub fn left_col(&self) -> usize {
+        self.left_col
+    }
+
+    pub fn column_widths(&self) -> &[usize] {
+        &self.column_widths
+    }
+
+    /// The table as it fits on `screen`, above a status line.
+    pub fn frame(&self, screen: Viewport) -> String {
+        let table = self
+            .view()
+            .pinned_view(self.body_top, self.left_col, table_area(screen));
+        format!("{table}\n{}", self.status_line(screen))
+    }
+
+    /// Acts on `key` for a terminal of `screen`'s size, and returns whether
+    /// the viewer stays open.
+    pub fn handle_key(&mut self, key: ViewerKey, screen: Viewport) -> bool {
+        self.message = None;
+        if let Some(prompt) = &mut self.prompt {
+            match key {
+                ViewerKey::Enter => {
+                    let query = self.prompt.take().unwrap_or_default();
+                    if !query.is_empty() {
+                        self.query = Some(query);
+                    }
+                    self.find_next(screen);
+                }
+                ViewerKey::Escape => self.prompt = None,
+                ViewerKey::Backspace => {
+                    prompt.pop();
+                }
+                ViewerKey::Char(c) => prompt.push(c),
+                _ => {}
+            }
+            return true;
+        }
+
+        let area = table_area(screen);
+        let page = self.view().page_rows(self.body_top, self.left_col, area);
+        match key {
+            ViewerKey::Char('q') | ViewerKey::Escape => return false,
+            ViewerKey::Up | ViewerKey::Char('k') => self.body_top = self.body_top.saturating_sub(1),
+            ViewerKey::Down | ViewerKey::Char('j') | ViewerKey::Enter => self.body_top += 1,
+            ViewerKey::PageUp | ViewerKey::Char('b') => {
+                self.body_top = self.body_top.saturating_sub(page)
+            }
+            ViewerKey::PageDown | ViewerKey::Char(' ' | 'f') => self.body_top += page,
+            ViewerKey::Home | ViewerKey::Char('g') => self.body_top = 0,
+            ViewerKey::End | ViewerKey::Char('G') => self.body_top = usize::MAX,
+            ViewerKey::Left | ViewerKey::Char('h') => {
+                self.left_col = self.left_col.saturating_sub(1)
+            }
+            ViewerKey::Right | ViewerKey::Char('l') => {
+                self.left_col = (self.left_col + 1).min(self.column_widths.len().saturating_sub(1))
+            }
+            ViewerKey::Char('<') => self.resize_left_col(false),
+            ViewerKey::Char('>') => self.resize_left_col(true),
+            ViewerKey::Char('/') => self.prompt = Some(String::new()),
+            ViewerKey::Char('n') => self.find_next(screen),
+            _ => {}
+        }
+        self.clamp(screen);
+        true
+    }
+
+    fn view(&self) -> TableView<'_> {
+        TableView::with_column_widths(&self.rows, &self.config, self.column_widths.clone())
+    }
+
+    // Keeps the last screen full rather than scrolling past the last row.
+    fn clamp(&mut self, screen: Viewport) {
+        let last = self.view().last_body_top(self.left_col, table_area(screen));
+        self.body_top = self.body_top.min(last);
+    }
+
+    // Narrows or widens the leftmost column on screen by one, down to its
+    // padding and one character.
+    fn resize_left_col(&mut self, wider: bool) {
+        let column = self
+            .config
+            .columns
+            .get(self.left_col)
+            .unwrap_or(&self.config.column_default);
+        let narrowest = column.padding_left + column.padding_right + 1;
+        if let Some(width) = self.column_widths.get_mut(self.left_col) {
+            *width = if wider {
+                *width + 1
+            } else {
+                width.saturating_sub(1).max(narrowest)
+            };
+        }
+    }
+
+    // Scrolls to the next body row after the top one with a cell containing
+    // the query, ignoring case and colors, and wrapping around to the first.
+    fn find_next(&mut self, screen: Viewport) {
+        let Some(query) = self.query.as_deref().map(str::to_lowercase) else {
+            return;
+        };
+        let body_rows = self.config.body_rows(self.rows.len());
+        let body_len = body_rows.len();
+        let matches = |row: &Row| {
+            row.iter()
+                .any(|cell| strip_ansi_sequences(cell).to_lowercase().contains(&query))
+        };
+
+        let found = (1..=body_len)
+            .map(|offset| (self.body_top + offset) % body_len.max(1))
+            .find(|&body_idx| matches(&self.rows[body_rows.start + body_idx]));
+        match found {
+            Some(body_idx) => {
+                self.body_top = body_idx;
+                self.clamp(screen);
+            }
+            None => self.message = Some(format!("Pattern not found: {query}")),
+        }
+    }
+
+    fn status_line(&self, screen: Viewport) -> String {
+        let status = if let Some(prompt) = &self.prompt {
+            format!("/{prompt}")
+        } else if let Some(message) = &self.message {
+            message.clone()
+        } else {
+            let body_len = self.config.body_rows(self.rows.len()).len();
+            let page = self
+                .view()
+                .page_rows(self.body_top, self.left_col, table_area(screen));
+            let first = (self.body_top + 1).min(body_len);
+            let last = (self.body_top + page).min(body_len);
+            format!(
+                "rows {first}-{last} of {body_len}, column {} of {}  (q quit, / search, n next, < > resize)",
+                self.left_col + 1,
+                self.column_widths.len()
+            )
+        };
+        truncate_string(&status, screen.width)
+    }
+}
+
+// The screen less the status line.
+fn table_area(screen: Viewport) -> Viewport {
+    Viewport {
+        height: screen.height.saturating_sub(1),
+        ..screen
+    }
+}
+
+/// Shows `rows` in the viewer on this terminal, on the alternate screen,
+/// until `q` is pressed.
+pub fn run_viewer(rows: Vec<Row>, config: TableConfig) -> TableResult<()> {
+    let mut viewer = Viewer::new(rows, config);
+    let mut out = io::stdout();
+
+    terminal::enable_raw_mode().map_err(TableError::io("Failed to start the viewer"))?;
+    let result = (|| -> io::Result<()> {
+        execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        loop {
+            let (width, height) = terminal::size()?;
+            let screen = Viewport {
+                width: width.into(),
+                height: height.into(),
+            };
+            let frame = crate::finish_output(viewer.frame(screen), &viewer.config);
+            queue!(out, cursor::MoveTo(0, 0), terminal::Clear(ClearType::All))?;
+            // Raw mode does not return the cursor to the start of the line.
+            out.write_all(frame.replace('\n', "\r\n").as_bytes())?;
+            out.flush()?;
+
+            if let Event::Key(key) = event::read()?
+                && key.kind != KeyEventKind::Release
+                && let Some(key) = viewer_key(key)
+                && !viewer.handle_key(key, screen)
+            {
+                return Ok(());
+            }
+        }
+    })();
+
+    let restored = execute!(out, cursor::Show, terminal::LeaveAlternateScreen)
+        .and_then(|()| terminal::disable_raw_mode());
+    result
+        .and(restored)
+        .map_err(TableError::io("Failed to draw the viewer"))
+}
+
+fn viewer_key(key: KeyEvent) -> Option<ViewerKey> {
+    Some(match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => ViewerKey::Escape,
+        KeyCode::Char(c) => ViewerKey::Char(c),
+        KeyCode::Up => ViewerKey::Up,
+        KeyCode::Down => ViewerKey::Down,
+        KeyCode::Left => ViewerKey::Left,
+        KeyCode::Right => ViewerKey::Right,
+        KeyCode::PageUp => ViewerKey::PageUp,
+        KeyCode::PageDown => ViewerKey::PageDown,
+        KeyCode::Home => ViewerKey::Home,
+        KeyCode::End => ViewerKey::End,
+        KeyCode::Enter => ViewerKey::Enter,
+        KeyCode::Backspace => ViewerKey::Backspace,
+        KeyCode::Esc => ViewerKey::Escape,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewer() -> Viewer {
+        let mut rows = vec![vec!["Host".to_string(), "Status".to_string()]];
+        rows.extend((1..=20).map(|i| vec![format!("host-{i:02}"), "up".to_string()]));
+        rows[15][1] = "\u{1b}[31mDOWN\u{1b}[0m".to_string();
+        let config = TableConfig {
+            header: Some(Box::default()),
+            ..Default::default()
+        };
+        Viewer::new(rows, config)
+    }
+
+    const SCREEN: Viewport = Viewport {
+        width: 80,
+        height: 10,
+    };
+
+    #[test]
+    fn test_viewer_scrolls_pages_and_searches() {
+        let mut viewer = viewer();
+        let frame = viewer.frame(SCREEN);
+        assert_eq!(frame.lines().count(), 10);
+        assert!(frame.contains("│ Host    │") && frame.contains("│ host-01 │"));
+        assert!(
+            frame
+                .ends_with("rows 1-3 of 20, column 1 of 2  (q quit, / search, n next, < > resize)")
+        );
+
+        viewer.handle_key(ViewerKey::PageDown, SCREEN);
+        assert_eq!(viewer.body_top(), 3);
+        viewer.handle_key(ViewerKey::End, SCREEN);
+        assert_eq!(viewer.body_top(), 17);
+        assert!(viewer.frame(SCREEN).contains("host-20"));
+        viewer.handle_key(ViewerKey::Home, SCREEN);
+
+        for key in [
+            ViewerKey::Char('/'),
+            ViewerKey::Char('d'),
+            ViewerKey::Char('O'),
+        ] {
+            viewer.handle_key(key, SCREEN);
+        }
+        assert!(viewer.frame(SCREEN).ends_with("\n/dO"));
+        viewer.handle_key(ViewerKey::Enter, SCREEN);
+        assert_eq!(viewer.body_top(), 14);
+
+        viewer.handle_key(ViewerKey::Char('/'), SCREEN);
+        viewer.handle_key(ViewerKey::Char('x'), SCREEN);
+        viewer.handle_key(ViewerKey::Enter, SCREEN);
+        assert_eq!(viewer.body_top(), 14);
+        assert!(viewer.frame(SCREEN).ends_with("Pattern not found: x"));
+
+        assert!(!viewer.handle_key(ViewerKey::Char('q'), SCREEN));
+    }
+
+    #[test]
+    fn test_viewer_resizes_and_scrolls_columns() {
+        let mut viewer = viewer();
+        assert_eq!(viewer.column_widths(), [9, 8]);
+
+        viewer.handle_key(ViewerKey::Char('>'), SCREEN);
+        assert_eq!(viewer.column_widths(), [10, 8]);
+        assert!(viewer.frame(SCREEN).contains("│ host-01  │"));
+        for _ in 0..20 {
+            viewer.handle_key(ViewerKey::Char('<'), SCREEN);
+        }
+        assert_eq!(viewer.column_widths(), [3, 8]);
+
+        viewer.handle_key(ViewerKey::Right, SCREEN);
+        viewer.handle_key(ViewerKey::Right, SCREEN);
+        assert_eq!(viewer.left_col(), 1);
+        assert!(viewer.frame(SCREEN).starts_with("┌────────┐\n│ Status │\n"));
+    }
+}